@@ -0,0 +1,13 @@
+-- Soft delete for weight and food logs
+-- Migration: 20241229000012_add_soft_delete
+
+-- Deleted entries keep their row with deleted_at set until purged
+ALTER TABLE weight_logs
+    ADD COLUMN deleted_at TIMESTAMPTZ;
+
+ALTER TABLE food_logs
+    ADD COLUMN deleted_at TIMESTAMPTZ;
+
+-- Indexes for the purge job (only soft-deleted rows are indexed)
+CREATE INDEX idx_weight_logs_deleted_at ON weight_logs(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX idx_food_logs_deleted_at ON food_logs(deleted_at) WHERE deleted_at IS NOT NULL;
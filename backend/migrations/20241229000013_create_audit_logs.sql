@@ -0,0 +1,21 @@
+-- Audit log table
+-- Records create/update/delete actions on user data
+
+CREATE TABLE IF NOT EXISTS audit_logs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    
+    -- What was changed (weight_log, body_composition, profile, settings)
+    entity VARCHAR(50) NOT NULL,
+    entity_id UUID NOT NULL,
+    
+    -- What happened (create, update, delete, restore)
+    action VARCHAR(20) NOT NULL,
+    
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Index for listing a user's recent actions
+CREATE INDEX idx_audit_logs_user_created ON audit_logs(user_id, created_at DESC);
+
+COMMENT ON TABLE audit_logs IS 'History of data mutations made by each user';
@@ -0,0 +1,8 @@
+-- Week start preference
+-- Weekly summaries begin on this day (Monday by default)
+
+ALTER TABLE user_settings
+    ADD COLUMN week_start VARCHAR(10) NOT NULL DEFAULT 'monday'
+    CHECK (week_start IN ('monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday', 'sunday'));
+
+COMMENT ON COLUMN user_settings.week_start IS 'First day of the week for weekly summaries';
@@ -0,0 +1,19 @@
+-- Profile completeness tracking
+-- Activity level and unit preferences have defaults, so record whether the
+-- user has chosen them explicitly
+
+ALTER TABLE user_settings
+    ADD COLUMN activity_level_confirmed BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN units_confirmed BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Existing non-default choices count as confirmed
+UPDATE user_settings SET
+    activity_level_confirmed = activity_level <> 'lightly_active',
+    units_confirmed = weight_unit <> 'kg'
+        OR distance_unit <> 'km'
+        OR energy_unit <> 'kcal'
+        OR height_unit <> 'cm'
+        OR temperature_unit <> 'celsius';
+
+COMMENT ON COLUMN user_settings.activity_level_confirmed IS 'Whether the user has set their activity level';
+COMMENT ON COLUMN user_settings.units_confirmed IS 'Whether the user has set their unit preferences';
@@ -0,0 +1,23 @@
+-- Height history
+-- A height is recorded whenever the profile height changes so historical
+-- BMI can use the height that was effective on a given date
+
+CREATE TABLE IF NOT EXISTS height_logs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    height_cm DECIMAL(5,1) NOT NULL,
+    effective_from DATE NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- One height per user per day; later changes that day replace it
+    CONSTRAINT uq_height_logs_user_date UNIQUE (user_id, effective_from)
+);
+
+-- Seed history with current heights, effective from account creation
+INSERT INTO height_logs (user_id, height_cm, effective_from)
+SELECT s.user_id, s.height_cm, u.created_at::date
+FROM user_settings s
+JOIN users u ON u.id = s.user_id
+WHERE s.height_cm IS NOT NULL;
+
+COMMENT ON TABLE height_logs IS 'History of profile height changes';
@@ -0,0 +1,17 @@
+-- Favorite foods
+-- Foods a user has starred for quick-add when logging
+
+CREATE TABLE IF NOT EXISTS favorite_foods (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    food_item_id UUID NOT NULL REFERENCES food_items(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- A food is either a favorite or not; no duplicates per user
+    PRIMARY KEY (user_id, food_item_id)
+);
+
+-- Recently used foods are read per user, newest first
+CREATE INDEX IF NOT EXISTS idx_food_logs_user_logged ON food_logs(user_id, logged_at DESC)
+    WHERE food_item_id IS NOT NULL AND deleted_at IS NULL;
+
+COMMENT ON TABLE favorite_foods IS 'Foods starred by a user for quick logging';
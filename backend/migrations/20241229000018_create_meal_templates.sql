@@ -0,0 +1,26 @@
+-- Meal templates
+-- Named sets of foods a user eats together, logged in one step
+
+CREATE TABLE IF NOT EXISTS meal_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_meal_templates_user ON meal_templates(user_id);
+
+CREATE TABLE IF NOT EXISTS meal_template_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    template_id UUID NOT NULL REFERENCES meal_templates(id) ON DELETE CASCADE,
+    food_item_id UUID NOT NULL REFERENCES food_items(id) ON DELETE CASCADE,
+    servings DECIMAL(10, 2) NOT NULL DEFAULT 1,
+    sort_order INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_meal_template_items_template ON meal_template_items(template_id);
+
+COMMENT ON TABLE meal_templates IS 'Saved meals for quick logging';
+COMMENT ON TABLE meal_template_items IS 'Foods and servings making up a meal template';
@@ -0,0 +1,8 @@
+-- Configurable meal types
+-- Food logs are validated against this list; users can add their own
+-- (e.g. "pre-workout") alongside the standard four
+
+ALTER TABLE user_settings
+    ADD COLUMN meal_types TEXT[] NOT NULL DEFAULT ARRAY['breakfast', 'lunch', 'dinner', 'snack'];
+
+COMMENT ON COLUMN user_settings.meal_types IS 'Meal types the user can log food under';
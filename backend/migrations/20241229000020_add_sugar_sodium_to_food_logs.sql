@@ -0,0 +1,16 @@
+-- Sugar and sodium tracking
+-- Food logs snapshot sugar and sodium at log time like the other nutrients
+
+ALTER TABLE food_logs
+    ADD COLUMN sugar_g DECIMAL(10, 2) NOT NULL DEFAULT 0,
+    ADD COLUMN sodium_mg DECIMAL(10, 2) NOT NULL DEFAULT 0;
+
+-- Backfill existing logs from their food items
+UPDATE food_logs fl SET
+    sugar_g = fi.sugar_g * fl.servings,
+    sodium_mg = COALESCE(fi.sodium_mg, 0) * fl.servings
+FROM food_items fi
+WHERE fi.id = fl.food_item_id;
+
+COMMENT ON COLUMN food_logs.sugar_g IS 'Sugar consumed, in grams';
+COMMENT ON COLUMN food_logs.sodium_mg IS 'Sodium consumed, in milligrams';
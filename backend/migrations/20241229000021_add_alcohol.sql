@@ -0,0 +1,11 @@
+-- Alcohol tracking
+-- Alcohol provides 7 kcal/g, so calories only reconcile with macros when it is recorded
+
+ALTER TABLE food_items
+    ADD COLUMN alcohol_g DECIMAL(10, 2) NOT NULL DEFAULT 0;
+
+ALTER TABLE food_logs
+    ADD COLUMN alcohol_g DECIMAL(10, 2) NOT NULL DEFAULT 0;
+
+COMMENT ON COLUMN food_items.alcohol_g IS 'Alcohol per serving, in grams';
+COMMENT ON COLUMN food_logs.alcohol_g IS 'Alcohol consumed, in grams';
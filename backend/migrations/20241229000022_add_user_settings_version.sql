@@ -0,0 +1,8 @@
+-- Optimistic concurrency for settings and profile updates
+-- Each update bumps the version; clients send the version they last read
+-- and stale updates are rejected instead of overwriting newer changes
+
+ALTER TABLE user_settings
+    ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+
+COMMENT ON COLUMN user_settings.version IS 'Incremented on every update, for optimistic concurrency';
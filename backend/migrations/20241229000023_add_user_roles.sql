@@ -0,0 +1,8 @@
+-- User roles
+-- Admins are created from the command line with `create-admin`; regular
+-- registration always creates users with the `user` role
+
+ALTER TABLE users
+    ADD COLUMN role TEXT NOT NULL DEFAULT 'user' CHECK (role IN ('user', 'admin'));
+
+COMMENT ON COLUMN users.role IS 'Access level: user or admin';
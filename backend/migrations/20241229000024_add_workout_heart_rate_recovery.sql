@@ -0,0 +1,9 @@
+-- Heart-rate recovery (HRR)
+-- The drop from peak heart rate one minute after a workout is a fitness indicator
+
+ALTER TABLE workouts
+    ADD COLUMN recovery_heart_rate INTEGER CHECK (recovery_heart_rate IS NULL OR (recovery_heart_rate >= 30 AND recovery_heart_rate <= 250)),
+    ADD COLUMN heart_rate_recovery INTEGER CHECK (heart_rate_recovery IS NULL OR heart_rate_recovery >= 0);
+
+COMMENT ON COLUMN workouts.recovery_heart_rate IS 'Heart rate one minute after the workout ended, in bpm';
+COMMENT ON COLUMN workouts.heart_rate_recovery IS 'One-minute drop from max_heart_rate, in bpm';
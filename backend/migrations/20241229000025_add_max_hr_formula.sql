@@ -0,0 +1,8 @@
+-- Max heart rate formula preference
+-- Used for default heart rate zones when the user has not set custom zones
+
+ALTER TABLE user_settings
+    ADD COLUMN max_hr_formula VARCHAR(10) NOT NULL DEFAULT 'fox'
+    CHECK (max_hr_formula IN ('fox', 'tanaka', 'gulati'));
+
+COMMENT ON COLUMN user_settings.max_hr_formula IS 'Age-based max heart rate formula: fox (220 - age), tanaka or gulati';
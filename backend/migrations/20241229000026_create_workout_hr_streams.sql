@@ -0,0 +1,19 @@
+-- Per-second heart rate streams recorded by wearables during a workout
+-- Stored as one compact row per workout instead of one heart_rate_logs row per sample
+
+CREATE TABLE IF NOT EXISTS workout_hr_streams (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workout_id UUID NOT NULL UNIQUE REFERENCES workouts(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- Time of the first sample; offsets are seconds from here
+    started_at TIMESTAMPTZ NOT NULL,
+    offsets_seconds INTEGER[] NOT NULL,
+    bpm INTEGER[] NOT NULL,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT matching_sample_lengths CHECK (cardinality(offsets_seconds) = cardinality(bpm))
+);
+
+CREATE INDEX idx_workout_hr_streams_user ON workout_hr_streams(user_id);
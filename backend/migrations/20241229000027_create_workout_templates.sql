@@ -0,0 +1,9 @@
+-- Workout templates
+-- Reusable workouts from structured programs, instantiated as editable workouts.
+-- The workout_templates and template_exercises tables already exist (see 000005);
+-- templates additionally carry free-form notes.
+
+ALTER TABLE workout_templates ADD COLUMN notes TEXT;
+
+COMMENT ON TABLE workout_templates IS 'Saved workouts for structured programs';
+COMMENT ON TABLE template_exercises IS 'Exercises and prescribed sets/reps making up a workout template';
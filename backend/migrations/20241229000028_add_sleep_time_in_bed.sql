@@ -0,0 +1,12 @@
+-- Time in bed, recorded separately from the sleep period
+-- When present, sleep efficiency is time asleep over time in bed
+
+ALTER TABLE sleep_logs
+    ADD COLUMN time_in_bed_minutes INT,
+    ADD CONSTRAINT valid_time_in_bed CHECK (
+        time_in_bed_minutes IS NULL OR
+        (time_in_bed_minutes <= 1440 AND time_in_bed_minutes >= total_duration_minutes)
+    );
+
+COMMENT ON COLUMN sleep_logs.total_duration_minutes IS 'Minutes from sleep_start to sleep_end (the sleep period)';
+COMMENT ON COLUMN sleep_logs.time_in_bed_minutes IS 'Total minutes in bed, including time spent trying to fall asleep';
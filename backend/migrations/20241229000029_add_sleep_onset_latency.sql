@@ -0,0 +1,10 @@
+-- Sleep onset latency: minutes from lights-out to falling asleep
+
+ALTER TABLE sleep_logs
+    ADD COLUMN sleep_onset_minutes INT,
+    ADD CONSTRAINT valid_sleep_onset CHECK (
+        sleep_onset_minutes IS NULL OR
+        (sleep_onset_minutes >= 0 AND sleep_onset_minutes < total_duration_minutes)
+    );
+
+COMMENT ON COLUMN sleep_logs.sleep_onset_minutes IS 'Minutes from lights-out to sleep onset';
@@ -0,0 +1,23 @@
+-- Email verification
+-- Tokens are signed JWTs; the table records each token's ID so it can only be
+-- used once and expires server-side as well
+
+ALTER TABLE users
+    ADD COLUMN email_verified_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN users.email_verified_at IS 'When the user confirmed their email address (NULL = unverified)';
+
+-- Accounts created before verification existed keep full access
+UPDATE users SET email_verified_at = created_at;
+
+CREATE TABLE email_verification_tokens (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_email_verification_tokens_user ON email_verification_tokens(user_id);
+
+COMMENT ON COLUMN email_verification_tokens.id IS 'The jti claim of the verification token';
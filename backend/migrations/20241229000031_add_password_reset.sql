@@ -0,0 +1,19 @@
+-- Password reset
+-- Reset tokens are signed JWTs recorded by ID so each can only be used once
+
+ALTER TABLE users
+    ADD COLUMN sessions_valid_after TIMESTAMPTZ;
+
+COMMENT ON COLUMN users.sessions_valid_after IS 'Tokens issued before this time are rejected (set on password reset)';
+
+CREATE TABLE password_reset_tokens (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_password_reset_tokens_user ON password_reset_tokens(user_id);
+
+COMMENT ON COLUMN password_reset_tokens.id IS 'The jti claim of the reset token';
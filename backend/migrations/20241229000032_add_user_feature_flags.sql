@@ -0,0 +1,7 @@
+-- Per-user feature flags
+-- Maps flag names to booleans; a missing flag is off
+
+ALTER TABLE users
+    ADD COLUMN feature_flags JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+COMMENT ON COLUMN users.feature_flags IS 'Features rolled out to this user, e.g. {"ai_meal_suggestions": true}';
@@ -0,0 +1,23 @@
+-- Asynchronous data export jobs
+-- Queued by the API and picked up by the export worker, which writes the
+-- finished export to disk
+
+CREATE TABLE export_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'running', 'complete', 'failed')),
+    include TEXT,
+    range_start TIMESTAMPTZ,
+    range_end TIMESTAMPTZ,
+    file_path TEXT,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_export_jobs_user ON export_jobs(user_id, created_at DESC);
+CREATE INDEX idx_export_jobs_pending ON export_jobs(created_at) WHERE status = 'pending';
+
+COMMENT ON COLUMN export_jobs.include IS 'Comma-separated datasets to export (NULL = all)';
+COMMENT ON COLUMN export_jobs.file_path IS 'Where the finished export was written';
@@ -0,0 +1,26 @@
+-- Opt-in menstrual cycle tracking
+-- Users log the day each cycle starts; the current phase is derived from the
+-- most recent start and the configured cycle and period lengths
+
+CREATE TABLE cycle_settings (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    enabled BOOLEAN NOT NULL DEFAULT false,
+    cycle_length_days INTEGER NOT NULL DEFAULT 28 CHECK (cycle_length_days BETWEEN 20 AND 45),
+    period_length_days INTEGER NOT NULL DEFAULT 5 CHECK (period_length_days BETWEEN 1 AND 10),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE cycle_starts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    started_on DATE NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT unique_user_cycle_start UNIQUE (user_id, started_on)
+);
+
+CREATE INDEX idx_cycle_starts_user ON cycle_starts(user_id, started_on DESC);
+
+COMMENT ON TABLE cycle_settings IS 'Per-user opt-in and typical lengths for cycle tracking';
+COMMENT ON COLUMN cycle_starts.started_on IS 'First day of menstruation, in the user''s local calendar';
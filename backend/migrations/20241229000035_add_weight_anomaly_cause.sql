@@ -0,0 +1,7 @@
+-- Likely cause of a flagged weight anomaly
+-- NULL for entries that aren't anomalous or were flagged before causes were
+-- recorded
+
+ALTER TABLE weight_logs
+    ADD COLUMN anomaly_cause TEXT
+        CHECK (anomaly_cause IN ('water_retention', 'fluid_loss', 'time_of_day', 'measurement_error'));
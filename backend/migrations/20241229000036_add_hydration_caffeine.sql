@@ -0,0 +1,6 @@
+-- Caffeine in a hydration log entry, in mg
+-- Estimated from the beverage type when not given; NULL for beverages
+-- without caffeine
+
+ALTER TABLE hydration_logs
+    ADD COLUMN caffeine_mg INTEGER CHECK (caffeine_mg >= 0 AND caffeine_mg <= 2000);
@@ -1,11 +1,14 @@
 //! JWT token generation and validation
 //!
 //! Provides access and refresh token management with pre-computed keys
-//! for optimal performance.
+//! for optimal performance. Tokens are signed with HS256 and a shared secret,
+//! or with RS256 so that other services can verify them with the public key.
 
-use anyhow::Result;
+use crate::config::{self, JwtAlgorithm};
+use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use fitness_assistant_shared::models::Role;
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -24,26 +27,74 @@ pub struct Claims {
     /// JWT ID for token revocation tracking (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub jti: Option<String>,
+    /// Role of the user, set on access tokens only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
 }
 
+/// Token type of email verification tokens
+const EMAIL_VERIFICATION_TOKEN_TYPE: &str = "email_verification";
+
+/// Token type of password reset tokens
+const PASSWORD_RESET_TOKEN_TYPE: &str = "password_reset";
+
 /// Pre-computed JWT keys for efficient token operations
 /// These are expensive to create, so we cache them in AppState
 #[derive(Clone)]
 pub struct JwtKeys {
+    algorithm: Algorithm,
     encoding: Arc<EncodingKey>,
     decoding: Arc<DecodingKey>,
 }
 
 impl JwtKeys {
-    /// Create new JWT keys from secret
+    /// Create new HS256 keys from secret
     /// This should be called once at startup
     pub fn new(secret: &str) -> Self {
         Self {
+            algorithm: Algorithm::HS256,
             encoding: Arc::new(EncodingKey::from_secret(secret.as_bytes())),
             decoding: Arc::new(DecodingKey::from_secret(secret.as_bytes())),
         }
     }
 
+    /// Create RS256 keys from PEM-encoded RSA private and public keys
+    /// This should be called once at startup
+    pub fn from_rsa_pem(private_key: &str, public_key: &str) -> Result<Self> {
+        let encoding = EncodingKey::from_rsa_pem(private_key.as_bytes())
+            .context("Invalid JWT RSA private key")?;
+        let decoding = DecodingKey::from_rsa_pem(public_key.as_bytes())
+            .context("Invalid JWT RSA public key")?;
+
+        Ok(Self {
+            algorithm: Algorithm::RS256,
+            encoding: Arc::new(encoding),
+            decoding: Arc::new(decoding),
+        })
+    }
+
+    /// Create the keys for the configured algorithm
+    pub fn from_config(config: &config::JwtConfig) -> Result<Self> {
+        match config.algorithm {
+            JwtAlgorithm::HS256 => Ok(Self::new(&config.secret)),
+            JwtAlgorithm::RS256 => {
+                let private_key = config
+                    .private_key
+                    .as_deref()
+                    .context("jwt.private_key is required for RS256")?;
+                let public_key = config
+                    .public_key
+                    .as_deref()
+                    .context("jwt.public_key is required for RS256")?;
+                Self::from_rsa_pem(private_key, public_key)
+            }
+        }
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
     pub fn encoding(&self) -> &EncodingKey {
         &self.encoding
     }
@@ -86,15 +137,34 @@ impl JwtService {
         }
     }
 
+    /// Create a JWT service for the configured algorithm
+    ///
+    /// Fails if RS256 is selected without valid PEM keys.
+    pub fn from_config(config: &config::JwtConfig) -> Result<Self> {
+        Ok(Self {
+            keys: JwtKeys::from_config(config)?,
+            config: JwtConfig {
+                access_token_expiry_secs: config.access_token_expiry_secs,
+                refresh_token_expiry_secs: config.refresh_token_expiry_secs,
+            },
+        })
+    }
+
     /// Create from pre-computed keys (for sharing across handlers)
     pub fn from_keys(keys: JwtKeys, config: JwtConfig) -> Self {
         Self { keys, config }
     }
 
-    /// Generate an access token for a user
+    /// Generate an access token for a user with the given role
     #[inline]
-    pub fn generate_access_token(&self, user_id: Uuid) -> Result<String> {
-        self.generate_token(user_id, "access", self.config.access_token_expiry_secs)
+    pub fn generate_access_token(&self, user_id: Uuid, role: Role) -> Result<String> {
+        self.generate_token_with_claims(
+            user_id,
+            "access",
+            self.config.access_token_expiry_secs,
+            Some(role),
+        )
+        .map(|(token, _)| token)
     }
 
     /// Generate a refresh token for a user
@@ -103,8 +173,38 @@ impl JwtService {
         self.generate_token(user_id, "refresh", self.config.refresh_token_expiry_secs)
     }
 
+    /// Generate an email verification token, returned with its claims
+    pub fn generate_email_verification_token(
+        &self,
+        user_id: Uuid,
+        expiry_secs: i64,
+    ) -> Result<(String, Claims)> {
+        self.generate_token_with_claims(user_id, EMAIL_VERIFICATION_TOKEN_TYPE, expiry_secs, None)
+    }
+
+    /// Generate a password reset token, returned with its claims
+    pub fn generate_password_reset_token(
+        &self,
+        user_id: Uuid,
+        expiry_secs: i64,
+    ) -> Result<(String, Claims)> {
+        self.generate_token_with_claims(user_id, PASSWORD_RESET_TOKEN_TYPE, expiry_secs, None)
+    }
+
     /// Generate a token with specified type and expiry
     fn generate_token(&self, user_id: Uuid, token_type: &str, expiry_secs: i64) -> Result<String> {
+        self.generate_token_with_claims(user_id, token_type, expiry_secs, None)
+            .map(|(token, _)| token)
+    }
+
+    /// Generate a token with specified type, expiry and role, returning its claims too
+    fn generate_token_with_claims(
+        &self,
+        user_id: Uuid,
+        token_type: &str,
+        expiry_secs: i64,
+        role: Option<Role>,
+    ) -> Result<(String, Claims)> {
         let now = Utc::now();
         let exp = now + Duration::seconds(expiry_secs);
 
@@ -113,17 +213,20 @@ impl JwtService {
             exp: exp.timestamp(),
             iat: now.timestamp(),
             token_type: token_type.to_string(),
-            jti: None, // Can be used for token revocation
+            jti: Some(Uuid::new_v4().to_string()), // Lets the token be revoked on logout
+            role,
         };
 
-        encode(&Header::default(), &claims, self.keys.encoding())
-            .map_err(|e| anyhow::anyhow!("Failed to generate {} token: {}", token_type, e))
+        let token = encode(&Header::new(self.keys.algorithm()), &claims, self.keys.encoding())
+            .map_err(|e| anyhow::anyhow!("Failed to generate {} token: {}", token_type, e))?;
+        Ok((token, claims))
     }
 
     /// Validate a token and return claims
     #[inline]
     pub fn validate_token(&self, token: &str) -> Result<Claims> {
-        let token_data = decode::<Claims>(token, self.keys.decoding(), &Validation::default())
+        let validation = Validation::new(self.keys.algorithm());
+        let token_data = decode::<Claims>(token, self.keys.decoding(), &validation)
             .map_err(|e| anyhow::anyhow!("Invalid token: {}", e))?;
 
         Ok(token_data.claims)
@@ -149,6 +252,26 @@ impl JwtService {
         Ok(claims)
     }
 
+    /// Validate an email verification token specifically
+    #[inline]
+    pub fn validate_email_verification_token(&self, token: &str) -> Result<Claims> {
+        let claims = self.validate_token(token)?;
+        if claims.token_type != EMAIL_VERIFICATION_TOKEN_TYPE {
+            return Err(anyhow::anyhow!("Not an email verification token"));
+        }
+        Ok(claims)
+    }
+
+    /// Validate a password reset token specifically
+    #[inline]
+    pub fn validate_password_reset_token(&self, token: &str) -> Result<Claims> {
+        let claims = self.validate_token(token)?;
+        if claims.token_type != PASSWORD_RESET_TOKEN_TYPE {
+            return Err(anyhow::anyhow!("Not a password reset token"));
+        }
+        Ok(claims)
+    }
+
     /// Get access token expiry in seconds
     #[inline]
     pub fn access_token_expiry_secs(&self) -> i64 {
@@ -179,7 +302,7 @@ mod tests {
         let service = create_test_service();
         let user_id = Uuid::new_v4();
 
-        let token = service.generate_access_token(user_id).unwrap();
+        let token = service.generate_access_token(user_id, Role::User).unwrap();
         let claims = service.validate_access_token(&token).unwrap();
 
         assert_eq!(claims.sub, user_id.to_string());
@@ -196,6 +319,17 @@ mod tests {
 
         assert_eq!(claims.sub, user_id.to_string());
         assert_eq!(claims.token_type, "refresh");
+        assert_eq!(claims.role, None);
+    }
+
+    #[test]
+    fn test_access_token_carries_role() {
+        let service = create_test_service();
+
+        let token = service.generate_access_token(Uuid::new_v4(), Role::Admin).unwrap();
+        let claims = service.validate_access_token(&token).unwrap();
+
+        assert_eq!(claims.role, Some(Role::Admin));
     }
 
     #[test]
@@ -203,12 +337,49 @@ mod tests {
         let service = create_test_service();
         let user_id = Uuid::new_v4();
 
-        let token = service.generate_access_token(user_id).unwrap();
+        let token = service.generate_access_token(user_id, Role::User).unwrap();
         let result = service.validate_refresh_token(&token);
 
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_email_verification_token_type_is_enforced() {
+        let service = create_test_service();
+        let user_id = Uuid::new_v4();
+
+        let (token, claims) = service.generate_email_verification_token(user_id, 3600).unwrap();
+        assert!(claims.jti.is_some());
+        assert_eq!(service.validate_email_verification_token(&token).unwrap().sub, user_id.to_string());
+        assert!(service.validate_access_token(&token).is_err());
+
+        let access = service.generate_access_token(user_id, Role::User).unwrap();
+        assert!(service.validate_email_verification_token(&access).is_err());
+    }
+
+    #[test]
+    fn test_expired_email_verification_token_rejected() {
+        let service = create_test_service();
+
+        // Past the default 60s leeway
+        let (token, _) = service.generate_email_verification_token(Uuid::new_v4(), -120).unwrap();
+
+        assert!(service.validate_email_verification_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_password_reset_token_type_is_enforced() {
+        let service = create_test_service();
+        let user_id = Uuid::new_v4();
+
+        let (token, _) = service.generate_password_reset_token(user_id, 3600).unwrap();
+        assert!(service.validate_password_reset_token(&token).is_ok());
+        assert!(service.validate_email_verification_token(&token).is_err());
+
+        let (verification, _) = service.generate_email_verification_token(user_id, 3600).unwrap();
+        assert!(service.validate_password_reset_token(&verification).is_err());
+    }
+
     #[test]
     fn test_invalid_token_rejected() {
         let service = create_test_service();
@@ -217,6 +388,71 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn rs256_config(public_key: &str) -> config::JwtConfig {
+        config::JwtConfig {
+            algorithm: JwtAlgorithm::RS256,
+            secret: String::new(),
+            private_key: Some(include_str!("../../tests/fixtures/jwt/rs256_private.pem").to_string()),
+            public_key: Some(public_key.to_string()),
+            access_token_expiry_secs: 3600,
+            refresh_token_expiry_secs: 604800,
+        }
+    }
+
+    #[test]
+    fn test_rs256_token_verifies_with_public_key() {
+        let config = rs256_config(include_str!("../../tests/fixtures/jwt/rs256_public.pem"));
+        let service = JwtService::from_config(&config).unwrap();
+        let user_id = Uuid::new_v4();
+
+        let token = service.generate_access_token(user_id, Role::User).unwrap();
+        let claims = service.validate_access_token(&token).unwrap();
+        assert_eq!(claims.sub, user_id.to_string());
+
+        // Another service holding only the public key can verify it too
+        let verifier = DecodingKey::from_rsa_pem(
+            include_bytes!("../../tests/fixtures/jwt/rs256_public.pem"),
+        )
+        .unwrap();
+        assert!(decode::<Claims>(&token, &verifier, &Validation::new(Algorithm::RS256)).is_ok());
+    }
+
+    #[test]
+    fn test_rs256_token_rejected_with_wrong_public_key() {
+        let signer = JwtService::from_config(&rs256_config(include_str!(
+            "../../tests/fixtures/jwt/rs256_public.pem"
+        )))
+        .unwrap();
+        let verifier = JwtService::from_config(&rs256_config(include_str!(
+            "../../tests/fixtures/jwt/rs256_other_public.pem"
+        )))
+        .unwrap();
+
+        let token = signer.generate_access_token(Uuid::new_v4(), Role::User).unwrap();
+        assert!(verifier.validate_access_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_rs256_requires_keys() {
+        let mut config = rs256_config("not a pem");
+        assert!(JwtService::from_config(&config).is_err());
+
+        config.private_key = None;
+        assert!(JwtService::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_hs256_token_rejected_by_rs256_service() {
+        let hs256 = create_test_service();
+        let rs256 = JwtService::from_config(&rs256_config(include_str!(
+            "../../tests/fixtures/jwt/rs256_public.pem"
+        )))
+        .unwrap();
+
+        let token = hs256.generate_access_token(Uuid::new_v4(), Role::User).unwrap();
+        assert!(rs256.validate_access_token(&token).is_err());
+    }
+
     #[test]
     fn test_service_is_clone_cheap() {
         let service = create_test_service();
@@ -233,7 +469,7 @@ mod tests {
         let user_id = Uuid::new_v4();
         
         // Both services should produce valid tokens
-        let token = service.generate_access_token(user_id).unwrap();
+        let token = service.generate_access_token(user_id, Role::User).unwrap();
         let claims = service2.validate_access_token(&token).unwrap();
         assert_eq!(claims.sub, user_id.to_string());
     }
@@ -7,14 +7,19 @@
 //! Uses pre-computed JWT keys from AppState to avoid expensive
 //! key derivation on every request.
 
+use crate::auth::{revocation, JwtService};
+use crate::cache::CacheStore;
 use crate::error::ApiError;
+use crate::services::UserService;
 use crate::state::AppState;
 use axum::{
-    extract::FromRef,
-    http::{header::AUTHORIZATION, request::Parts},
+    extract::{FromRef, FromRequestParts},
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap},
     middleware::Next,
     response::Response,
 };
+use fitness_assistant_shared::models::Role;
+use tracing::{field, Span};
 use uuid::Uuid;
 
 /// Authenticated user extracted from JWT
@@ -24,10 +29,80 @@ use uuid::Uuid;
 #[derive(Debug, Clone)]
 pub struct AuthUser {
     pub user_id: Uuid,
+    /// ID of the presented token, used to revoke it on logout
+    pub token_id: Option<String>,
+    /// Expiry of the presented token (Unix timestamp)
+    pub token_expires_at: i64,
+    /// Role granted when the token was issued
+    pub role: Role,
+}
+
+impl AuthUser {
+    /// Check the user holds `role`, answering 403 otherwise
+    pub fn require_role(&self, role: Role) -> Result<(), ApiError> {
+        if self.role != role {
+            return Err(ApiError::Forbidden(format!(
+                "Requires the {} role",
+                role.as_str()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Attach the authenticated user to the current request span
+fn record_user(user_id: Uuid) {
+    Span::current().record("user_id", field::display(user_id));
+}
+
+/// Validate the bearer token in `headers` and check it hasn't been revoked
+async fn authenticate(
+    jwt: &JwtService,
+    revocations: &dyn CacheStore,
+    headers: &HeaderMap,
+) -> Result<AuthUser, ApiError> {
+    // Extract Authorization header
+    let auth_header = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| ApiError::Unauthorized("Missing authorization header".to_string()))?;
+
+    // Check Bearer prefix
+    let token = auth_header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| ApiError::Unauthorized("Invalid authorization format".to_string()))?;
+
+    // Use pre-computed JWT service (no allocation!)
+    let claims = jwt
+        .validate_access_token(token)
+        .map_err(|e| ApiError::Unauthorized(format!("Invalid token: {}", e)))?;
+
+    if let Some(jti) = &claims.jti {
+        if revocation::is_revoked(revocations, jti).await {
+            return Err(ApiError::Unauthorized("Token has been revoked".to_string()));
+        }
+    }
+
+    // Parse user ID from claims
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| ApiError::Unauthorized("Invalid user ID in token".to_string()))?;
+
+    if revocation::is_revoked_for_user(revocations, user_id, claims.iat).await {
+        return Err(ApiError::Unauthorized("Token has been revoked".to_string()));
+    }
+
+    record_user(user_id);
+
+    Ok(AuthUser {
+        user_id,
+        token_id: claims.jti,
+        token_expires_at: claims.exp,
+        role: claims.role.unwrap_or_default(),
+    })
 }
 
 #[axum::async_trait]
-impl<S> axum::extract::FromRequestParts<S> for AuthUser
+impl<S> FromRequestParts<S> for AuthUser
 where
     AppState: FromRef<S>,
     S: Send + Sync,
@@ -37,29 +112,63 @@ where
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         let app_state = AppState::from_ref(state);
 
-        // Extract Authorization header
-        let auth_header = parts
-            .headers
-            .get(AUTHORIZATION)
-            .and_then(|value| value.to_str().ok())
-            .ok_or_else(|| ApiError::Unauthorized("Missing authorization header".to_string()))?;
-
-        // Check Bearer prefix
-        let token = auth_header
-            .strip_prefix("Bearer ")
-            .ok_or_else(|| ApiError::Unauthorized("Invalid authorization format".to_string()))?;
-
-        // Use pre-computed JWT service from state (no allocation!)
-        let claims = app_state
-            .jwt()
-            .validate_access_token(token)
-            .map_err(|e| ApiError::Unauthorized(format!("Invalid token: {}", e)))?;
-
-        // Parse user ID from claims
-        let user_id = Uuid::parse_str(&claims.sub)
-            .map_err(|_| ApiError::Unauthorized("Invalid user ID in token".to_string()))?;
-
-        Ok(AuthUser { user_id })
+        // Use pre-computed JWT service from state
+        authenticate(app_state.jwt(), app_state.revocations(), &parts.headers).await
+    }
+}
+
+/// Authenticated user whose email address has been verified
+///
+/// Use in place of `AuthUser` on handlers that unverified accounts may not
+/// call; they are answered with 403.
+#[derive(Debug, Clone)]
+pub struct VerifiedUser(pub AuthUser);
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for VerifiedUser
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state).await?;
+        VerifiedUser::check(&AppState::from_ref(state), user).await
+    }
+}
+
+impl VerifiedUser {
+    /// Check an already authenticated user has verified their email address
+    async fn check(state: &AppState, user: AuthUser) -> Result<Self, ApiError> {
+        if !UserService::is_email_verified(&state.db, user.user_id).await? {
+            return Err(ApiError::Forbidden("Email address not verified".to_string()));
+        }
+
+        Ok(VerifiedUser(user))
+    }
+}
+
+/// Authenticated user holding the admin role, with a verified email address
+///
+/// Use in place of `AuthUser` on admin-only handlers; other users, and
+/// admins who haven't verified their address yet, are answered with 403.
+#[derive(Debug, Clone)]
+pub struct AdminUser(pub AuthUser);
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for AdminUser
+where
+    AppState: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state).await?;
+        user.require_role(Role::Admin)?;
+        let VerifiedUser(user) = VerifiedUser::check(&AppState::from_ref(state), user).await?;
+        Ok(AdminUser(user))
     }
 }
 
@@ -72,29 +181,8 @@ pub async fn auth_middleware(
     mut request: axum::http::Request<axum::body::Body>,
     next: Next,
 ) -> Result<Response, ApiError> {
-    // Extract Authorization header
-    let auth_header = request
-        .headers()
-        .get(AUTHORIZATION)
-        .and_then(|value| value.to_str().ok())
-        .ok_or_else(|| ApiError::Unauthorized("Missing authorization header".to_string()))?;
-
-    // Check Bearer prefix
-    let token = auth_header
-        .strip_prefix("Bearer ")
-        .ok_or_else(|| ApiError::Unauthorized("Invalid authorization format".to_string()))?;
-
-    // Use pre-computed JWT service (no allocation!)
-    let claims = state
-        .jwt()
-        .validate_access_token(token)
-        .map_err(|e| ApiError::Unauthorized(format!("Invalid token: {}", e)))?;
-
-    // Parse user ID and add to request extensions
-    let user_id = Uuid::parse_str(&claims.sub)
-        .map_err(|_| ApiError::Unauthorized("Invalid user ID in token".to_string()))?;
-
-    request.extensions_mut().insert(AuthUser { user_id });
+    let user = authenticate(state.jwt(), state.revocations(), request.headers()).await?;
+    request.extensions_mut().insert(user);
 
     Ok(next.run(request).await)
 }
@@ -102,13 +190,60 @@ pub async fn auth_middleware(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::memory::MemoryCache;
 
     #[test]
     fn test_auth_user_debug() {
         let user = AuthUser {
             user_id: Uuid::new_v4(),
+            token_id: None,
+            token_expires_at: 0,
+            role: Role::User,
         };
         let debug_str = format!("{:?}", user);
         assert!(debug_str.contains("AuthUser"));
     }
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, format!("Bearer {}", token).parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_revoked_token_is_rejected() {
+        let jwt = JwtService::new("test-secret", 3600, 604800);
+        let cache = MemoryCache::default();
+        let user_id = Uuid::new_v4();
+        let headers = bearer(&jwt.generate_access_token(user_id, Role::User).unwrap());
+
+        let user = authenticate(&jwt, &cache, &headers).await.unwrap();
+        assert_eq!(user.user_id, user_id);
+
+        revocation::revoke(&cache, user.token_id.as_deref().unwrap(), user.token_expires_at)
+            .await
+            .unwrap();
+
+        let result = authenticate(&jwt, &cache, &headers).await;
+        assert!(matches!(result, Err(ApiError::Unauthorized(_))));
+
+        // Other tokens for the same user are unaffected
+        let other = bearer(&jwt.generate_access_token(user_id, Role::User).unwrap());
+        assert!(authenticate(&jwt, &cache, &other).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_role_comes_from_token() {
+        let jwt = JwtService::new("test-secret", 3600, 604800);
+        let cache = MemoryCache::default();
+        let user_id = Uuid::new_v4();
+
+        let admin = bearer(&jwt.generate_access_token(user_id, Role::Admin).unwrap());
+        let admin = authenticate(&jwt, &cache, &admin).await.unwrap();
+        assert!(admin.require_role(Role::Admin).is_ok());
+
+        let user = bearer(&jwt.generate_access_token(user_id, Role::User).unwrap());
+        let user = authenticate(&jwt, &cache, &user).await.unwrap();
+        assert!(matches!(user.require_role(Role::Admin), Err(ApiError::Forbidden(_))));
+    }
 }
@@ -5,7 +5,8 @@
 mod jwt;
 mod middleware;
 mod password;
+pub mod revocation;
 
 pub use jwt::{Claims, JwtService};
-pub use middleware::{auth_middleware, AuthUser};
+pub use middleware::{auth_middleware, AdminUser, AuthUser, VerifiedUser};
 pub use password::PasswordService;
@@ -1,6 +1,7 @@
 //! Password hashing using argon2
 //!
-//! Provides secure password hashing and verification.
+//! Provides secure password hashing and verification, and the strength
+//! rules new passwords must meet.
 //! 
 //! # Performance Considerations
 //! 
@@ -13,6 +14,33 @@ use argon2::{
     Argon2,
 };
 
+/// Minimum password length, in characters
+pub const MIN_PASSWORD_LENGTH: usize = 12;
+
+/// Maximum password length, in characters (bounds hashing cost)
+pub const MAX_PASSWORD_LENGTH: usize = 128;
+
+/// Commonly used passwords that otherwise meet the rules, compared
+/// case-insensitively
+const COMMON_PASSWORDS: &[&str] = &[
+    "password1234",
+    "password12345",
+    "password123456",
+    "passw0rd1234",
+    "qwerty123456",
+    "qwertyuiop123",
+    "123456789abc",
+    "abc123456789",
+    "iloveyou1234",
+    "letmein12345",
+    "welcome12345",
+    "admin1234567",
+    "changeme1234",
+    "football1234",
+    "baseball1234",
+    "monkey123456",
+];
+
 /// Password hashing service
 /// 
 /// Uses Argon2id which is the recommended variant for password hashing.
@@ -48,6 +76,39 @@ impl PasswordService {
             .map_err(|e| anyhow::anyhow!("Task join error: {}", e))?
     }
 
+    /// Check a new password against the strength rules
+    ///
+    /// Returns every rule the password breaks, not just the first.
+    pub fn validate_strength(password: &str) -> std::result::Result<(), Vec<String>> {
+        let mut failures = Vec::new();
+        let length = password.chars().count();
+
+        if length < MIN_PASSWORD_LENGTH {
+            failures.push(format!("Password must be at least {} characters", MIN_PASSWORD_LENGTH));
+        }
+        if length > MAX_PASSWORD_LENGTH {
+            failures.push(format!("Password must be at most {} characters", MAX_PASSWORD_LENGTH));
+        }
+        if !password.chars().any(char::is_lowercase) {
+            failures.push("Password must contain a lowercase letter".to_string());
+        }
+        if !password.chars().any(char::is_uppercase) {
+            failures.push("Password must contain an uppercase letter".to_string());
+        }
+        if !password.chars().any(|c| c.is_ascii_digit()) {
+            failures.push("Password must contain a digit".to_string());
+        }
+        if COMMON_PASSWORDS.contains(&password.to_lowercase().as_str()) {
+            failures.push("Password is too common".to_string());
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
     /// Verify a password against a hash (blocking operation)
     /// 
     /// # Performance Note
@@ -106,4 +167,36 @@ mod tests {
         assert!(PasswordService::verify_async(password.clone(), hash.clone()).await.unwrap());
         assert!(!PasswordService::verify_async("wrong".to_string(), hash).await.unwrap());
     }
+
+    #[test]
+    fn test_weak_password_reports_every_failed_rule() {
+        let failures = PasswordService::validate_strength("password").unwrap_err();
+
+        assert_eq!(
+            failures,
+            vec![
+                "Password must be at least 12 characters",
+                "Password must contain an uppercase letter",
+                "Password must contain a digit",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_common_password_rejected_regardless_of_case() {
+        let failures = PasswordService::validate_strength("Password1234").unwrap_err();
+        assert_eq!(failures, vec!["Password is too common"]);
+    }
+
+    #[test]
+    fn test_overlong_password_rejected() {
+        let password = format!("Aa1{}", "x".repeat(MAX_PASSWORD_LENGTH));
+        let failures = PasswordService::validate_strength(&password).unwrap_err();
+        assert_eq!(failures, vec!["Password must be at most 128 characters"]);
+    }
+
+    #[test]
+    fn test_strong_password_passes() {
+        assert!(PasswordService::validate_strength("Correct-Horse-9-Battery").is_ok());
+    }
 }
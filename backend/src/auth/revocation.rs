@@ -0,0 +1,200 @@
+//! Access token revocation
+//!
+//! JWTs stay valid until they expire, so logging out records the token's
+//! `jti` in Redis for the rest of its lifetime and authentication rejects
+//! any token on that list.
+//!
+//! Resetting a password revokes all of a user's tokens at once by recording
+//! the reset time; tokens issued before it are rejected.
+//!
+//! Without Redis, revocations are kept in process by `MemoryRevocationStore`
+//! and only apply to the instance that recorded them. A failed Redis lookup
+//! lets the token through rather than locking every user out during an
+//! outage.
+
+use crate::cache::CacheStore;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Key prefix for revoked token IDs
+const REVOKED_TOKEN_PREFIX: &str = "revoked_token:";
+
+/// Key prefix for per-user revocation cutoffs
+const SESSIONS_VALID_AFTER_PREFIX: &str = "sessions_valid_after:";
+
+fn revoked_key(jti: &str) -> String {
+    format!("{}{}", REVOKED_TOKEN_PREFIX, jti)
+}
+
+fn sessions_valid_after_key(user_id: Uuid) -> String {
+    format!("{}{}", SESSIONS_VALID_AFTER_PREFIX, user_id)
+}
+
+/// Seconds until a token expiring at `exp` lapses, or None if it already has
+pub fn remaining_lifetime_secs(exp: i64, now: i64) -> Option<u64> {
+    u64::try_from(exp - now).ok().filter(|secs| *secs > 0)
+}
+
+/// In-process revocation list for deployments without Redis
+///
+/// Entries are dropped once their TTL passes, like Redis keys.
+#[derive(Default)]
+pub struct MemoryRevocationStore {
+    entries: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+#[async_trait]
+impl CacheStore for MemoryRevocationStore {
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries
+            .get(key)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(value, _)| value.clone()))
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl_secs: u64) -> Result<()> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        // Forget lapsed entries so the list doesn't grow forever
+        entries.retain(|_, (_, expires_at)| *expires_at > now);
+        entries.insert(
+            key.to_string(),
+            (value.to_string(), now + Duration::from_secs(ttl_secs)),
+        );
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.entries.lock().unwrap().remove(key);
+        Ok(())
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> Result<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|key, _| !key.starts_with(prefix));
+        Ok(())
+    }
+}
+
+/// Revoke the token `jti` until it expires at `exp` (Unix timestamp)
+pub async fn revoke(cache: &dyn CacheStore, jti: &str, exp: i64) -> Result<()> {
+    // An expired token is rejected anyway, so there is nothing to record
+    let Some(ttl_secs) = remaining_lifetime_secs(exp, Utc::now().timestamp()) else {
+        return Ok(());
+    };
+
+    cache.set(&revoked_key(jti), "1", ttl_secs).await
+}
+
+/// Whether the token `jti` has been revoked
+pub async fn is_revoked(cache: &dyn CacheStore, jti: &str) -> bool {
+    match cache.get(&revoked_key(jti)).await {
+        Ok(entry) => entry.is_some(),
+        Err(e) => {
+            warn!("Token revocation lookup failed for {}: {}", jti, e);
+            false
+        }
+    }
+}
+
+/// Revoke every token issued to `user_id` before `valid_after` (Unix timestamp)
+///
+/// The cutoff is kept for `ttl_secs`, which should be the access token
+/// lifetime; refresh tokens are checked against the database instead.
+pub async fn revoke_all_before(
+    cache: &dyn CacheStore,
+    user_id: Uuid,
+    valid_after: i64,
+    ttl_secs: u64,
+) -> Result<()> {
+    cache
+        .set(&sessions_valid_after_key(user_id), &valid_after.to_string(), ttl_secs)
+        .await
+}
+
+/// Whether a token issued to `user_id` at `iat` was revoked by `revoke_all_before`
+pub async fn is_revoked_for_user(cache: &dyn CacheStore, user_id: Uuid, iat: i64) -> bool {
+    match cache.get(&sessions_valid_after_key(user_id)).await {
+        Ok(entry) => entry
+            .and_then(|valid_after| valid_after.parse::<i64>().ok())
+            .is_some_and(|valid_after| iat < valid_after),
+        Err(e) => {
+            warn!("Token revocation lookup failed for user {}: {}", user_id, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::memory::MemoryCache;
+
+    #[tokio::test]
+    async fn test_revoked_entry_expires_with_token() {
+        let cache = MemoryCache::default();
+        let exp = Utc::now().timestamp() + 900;
+
+        revoke(&cache, "token-1", exp).await.unwrap();
+
+        assert!(is_revoked(&cache, "token-1").await);
+        assert!(!is_revoked(&cache, "token-2").await);
+        let ttl = cache.ttl(&revoked_key("token-1")).unwrap();
+        assert!((899..=900).contains(&ttl));
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_is_not_recorded() {
+        let cache = MemoryCache::default();
+
+        revoke(&cache, "token-1", Utc::now().timestamp() - 1).await.unwrap();
+
+        assert!(!cache.contains(&revoked_key("token-1")));
+    }
+
+    #[test]
+    fn test_remaining_lifetime() {
+        assert_eq!(remaining_lifetime_secs(1_000, 400), Some(600));
+        assert_eq!(remaining_lifetime_secs(1_000, 1_000), None);
+        assert_eq!(remaining_lifetime_secs(1_000, 2_000), None);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_all_before_rejects_only_older_tokens() {
+        let cache = MemoryCache::default();
+        let user_id = Uuid::new_v4();
+
+        revoke_all_before(&cache, user_id, 1_000, 900).await.unwrap();
+
+        assert!(is_revoked_for_user(&cache, user_id, 999).await);
+        assert!(!is_revoked_for_user(&cache, user_id, 1_000).await);
+        assert!(!is_revoked_for_user(&cache, Uuid::new_v4(), 999).await);
+        assert_eq!(cache.ttl(&sessions_valid_after_key(user_id)), Some(900));
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_revokes_until_expiry() {
+        let store = MemoryRevocationStore::default();
+        let user_id = Uuid::new_v4();
+
+        revoke(&store, "token-1", Utc::now().timestamp() + 60).await.unwrap();
+        revoke_all_before(&store, user_id, 1_000, 60).await.unwrap();
+
+        assert!(is_revoked(&store, "token-1").await);
+        assert!(!is_revoked(&store, "token-2").await);
+        assert!(is_revoked_for_user(&store, user_id, 999).await);
+
+        // A lapsed entry no longer revokes anything
+        store.set(&revoked_key("token-3"), "1", 0).await.unwrap();
+        assert!(!is_revoked(&store, "token-3").await);
+    }
+}
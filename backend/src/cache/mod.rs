@@ -0,0 +1,380 @@
+//! Redis-backed caching helpers
+//!
+//! Caching is best-effort: when Redis is not configured or a command
+//! keeps failing after a couple of quick retries, callers fall through
+//! to the underlying data source and the failure is only logged.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use serde::{de::DeserializeOwned, Serialize};
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
+
+/// Retries after a failed cache read or write before giving up
+const CACHE_RETRIES: u32 = 2;
+
+/// Delay before the first retry; doubled after each failed attempt
+const CACHE_RETRY_BACKOFF: Duration = Duration::from_millis(20);
+
+/// Minimal key/value store used for caching serialized values
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    /// Get a value by key
+    async fn get(&self, key: &str) -> Result<Option<String>>;
+
+    /// Set a value with an expiry in seconds
+    async fn set(&self, key: &str, value: &str, ttl_secs: u64) -> Result<()>;
+
+    /// Remove a key
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// Remove every key starting with `prefix`
+    async fn delete_prefix(&self, prefix: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl CacheStore for ConnectionManager {
+    async fn get(&self, key: &str) -> Result<Option<String>> {
+        // ConnectionManager is a cheap Arc clone
+        let mut conn = self.clone();
+        let value: Option<String> = AsyncCommands::get(&mut conn, key).await?;
+        Ok(value)
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl_secs: u64) -> Result<()> {
+        let mut conn = self.clone();
+        conn.set_ex::<_, _, ()>(key, value, ttl_secs).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let mut conn = self.clone();
+        conn.del::<_, ()>(key).await?;
+        Ok(())
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> Result<()> {
+        let mut conn = self.clone();
+        let keys: Vec<String> = {
+            let mut iter = conn.scan_match::<_, String>(format!("{}*", prefix)).await?;
+            let mut keys = Vec::new();
+            while let Some(key) = iter.next_item().await {
+                keys.push(key);
+            }
+            keys
+        };
+
+        if !keys.is_empty() {
+            conn.del::<_, ()>(keys).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Run a cache operation, retrying transient failures with a short backoff
+///
+/// Kept short so a struggling Redis adds little latency before callers
+/// fall back to the uncached path.
+async fn with_retry<T, F, Fut>(mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut backoff = CACHE_RETRY_BACKOFF;
+    let mut retries = 0;
+
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if retries >= CACHE_RETRIES => return Err(e),
+            Err(e) => {
+                warn!(attempt = retries + 1, "Cache operation failed, retrying: {}", e);
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                retries += 1;
+            }
+        }
+    }
+}
+
+/// Return the cached value for `key`, or run `fetch` and cache its result
+///
+/// Reads and writes are retried briefly; cache misses, deserialization
+/// failures and persistent Redis errors all fall through to `fetch`, and
+/// only errors from `fetch` itself are returned.
+pub async fn get_or_fetch<T, F, Fut>(
+    cache: Option<&dyn CacheStore>,
+    key: &str,
+    ttl_secs: u64,
+    fetch: F,
+) -> Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let Some(cache) = cache else {
+        return fetch().await;
+    };
+
+    match with_retry(|| cache.get(key)).await {
+        Ok(Some(cached)) => match serde_json::from_str(&cached) {
+            Ok(value) => return Ok(value),
+            Err(e) => warn!("Discarding unreadable cache entry {}: {}", key, e),
+        },
+        Ok(None) => {}
+        Err(e) => warn!("Cache read failed for {}: {}", key, e),
+    }
+
+    let value = fetch().await?;
+
+    match serde_json::to_string(&value) {
+        Ok(serialized) => {
+            if let Err(e) = with_retry(|| cache.set(key, &serialized, ttl_secs)).await {
+                warn!("Cache write failed for {}: {}", key, e);
+            }
+        }
+        Err(e) => warn!("Failed to serialize cache entry {}: {}", key, e),
+    }
+
+    Ok(value)
+}
+
+/// Remove a cached key, logging rather than returning failures
+pub async fn invalidate(cache: Option<&dyn CacheStore>, key: &str) {
+    if let Some(cache) = cache {
+        if let Err(e) = cache.delete(key).await {
+            warn!("Cache invalidation failed for {}: {}", key, e);
+        }
+    }
+}
+
+/// Remove every cached key starting with `prefix`, logging rather than returning failures
+pub async fn invalidate_prefix(cache: Option<&dyn CacheStore>, prefix: &str) {
+    if let Some(cache) = cache {
+        if let Err(e) = cache.delete_prefix(prefix).await {
+            warn!("Cache invalidation failed for prefix {}: {}", prefix, e);
+        }
+    }
+}
+
+/// In-memory store for exercising cache behaviour in tests
+#[cfg(test)]
+pub(crate) mod memory {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub(crate) struct MemoryCache {
+        entries: Mutex<HashMap<String, String>>,
+        ttls: Mutex<HashMap<String, u64>>,
+    }
+
+    impl MemoryCache {
+        pub(crate) fn contains(&self, key: &str) -> bool {
+            self.entries.lock().unwrap().contains_key(key)
+        }
+
+        /// Expiry in seconds that `key` was last set with
+        pub(crate) fn ttl(&self, key: &str) -> Option<u64> {
+            self.ttls.lock().unwrap().get(key).copied()
+        }
+    }
+
+    #[async_trait]
+    impl CacheStore for MemoryCache {
+        async fn get(&self, key: &str) -> Result<Option<String>> {
+            Ok(self.entries.lock().unwrap().get(key).cloned())
+        }
+
+        async fn set(&self, key: &str, value: &str, ttl_secs: u64) -> Result<()> {
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_string());
+            self.ttls.lock().unwrap().insert(key.to_string(), ttl_secs);
+            Ok(())
+        }
+
+        async fn delete(&self, key: &str) -> Result<()> {
+            self.entries.lock().unwrap().remove(key);
+            self.ttls.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        async fn delete_prefix(&self, prefix: &str) -> Result<()> {
+            self.entries
+                .lock()
+                .unwrap()
+                .retain(|key, _| !key.starts_with(prefix));
+            self.ttls
+                .lock()
+                .unwrap()
+                .retain(|key, _| !key.starts_with(prefix));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::memory::MemoryCache;
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Store whose every operation fails, standing in for a Redis outage
+    struct FailingCache;
+
+    #[async_trait]
+    impl CacheStore for FailingCache {
+        async fn get(&self, _key: &str) -> Result<Option<String>> {
+            anyhow::bail!("connection refused")
+        }
+
+        async fn set(&self, _key: &str, _value: &str, _ttl_secs: u64) -> Result<()> {
+            anyhow::bail!("connection refused")
+        }
+
+        async fn delete(&self, _key: &str) -> Result<()> {
+            anyhow::bail!("connection refused")
+        }
+
+        async fn delete_prefix(&self, _prefix: &str) -> Result<()> {
+            anyhow::bail!("connection refused")
+        }
+    }
+
+    /// Store whose reads fail a set number of times before delegating
+    struct FlakyCache {
+        inner: MemoryCache,
+        failures_left: AtomicUsize,
+    }
+
+    impl FlakyCache {
+        fn new(failures: usize) -> Self {
+            Self {
+                inner: MemoryCache::default(),
+                failures_left: AtomicUsize::new(failures),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CacheStore for FlakyCache {
+        async fn get(&self, key: &str) -> Result<Option<String>> {
+            let failed = self
+                .failures_left
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failed {
+                anyhow::bail!("connection reset");
+            }
+            self.inner.get(key).await
+        }
+
+        async fn set(&self, key: &str, value: &str, ttl_secs: u64) -> Result<()> {
+            self.inner.set(key, value, ttl_secs).await
+        }
+
+        async fn delete(&self, key: &str) -> Result<()> {
+            self.inner.delete(key).await
+        }
+
+        async fn delete_prefix(&self, prefix: &str) -> Result<()> {
+            self.inner.delete_prefix(prefix).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_populates_and_reuses_cache() {
+        let cache = MemoryCache::default();
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            let value: Vec<i32> = get_or_fetch(Some(&cache), "key", 60, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(vec![1, 2, 3])
+            })
+            .await
+            .unwrap();
+            assert_eq!(value, vec![1, 2, 3]);
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_falls_through_on_cache_errors() {
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            let value: String = get_or_fetch(Some(&FailingCache), "key", 60, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok("fresh".to_string())
+            })
+            .await
+            .unwrap();
+            assert_eq!(value, "fresh");
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        invalidate(Some(&FailingCache), "key").await;
+        invalidate_prefix(Some(&FailingCache), "key").await;
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_retries_transient_read_failure() {
+        let cache = FlakyCache::new(1);
+        cache.inner.set("key", "\"cached\"", 60).await.unwrap();
+
+        let value: String = get_or_fetch(Some(&cache), "key", 60, || async {
+            panic!("fetch should not run when the retry succeeds")
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(value, "cached");
+    }
+
+    #[tokio::test]
+    async fn test_get_or_fetch_falls_back_after_retries_exhausted() {
+        let cache = FlakyCache::new(CACHE_RETRIES as usize + 1);
+        cache.inner.set("key", "\"cached\"", 60).await.unwrap();
+
+        let value: String = get_or_fetch(Some(&cache), "key", 60, || async {
+            Ok("fresh".to_string())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(value, "fresh");
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_removes_entry() {
+        let cache = MemoryCache::default();
+        cache.set("key", "\"v\"", 60).await.unwrap();
+
+        invalidate(Some(&cache), "key").await;
+
+        assert!(!cache.contains("key"));
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_prefix_keeps_other_keys() {
+        let cache = MemoryCache::default();
+        cache.set("food_search:egg:20", "[]", 60).await.unwrap();
+        cache.set("food_search:rice:20", "[]", 60).await.unwrap();
+        cache.set("exercise_library", "[]", 60).await.unwrap();
+
+        invalidate_prefix(Some(&cache), "food_search:").await;
+
+        assert!(!cache.contains("food_search:egg:20"));
+        assert!(!cache.contains("food_search:rice:20"));
+        assert!(cache.contains("exercise_library"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Command line interface for the backend binary
+//!
+//! Running the binary with no subcommand starts the server. The other
+//! subcommands run one-off operational tasks against the configured database
+//! and exit.
+
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Fitness Assistant AI backend
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+impl Cli {
+    /// The subcommand to run, defaulting to `serve`
+    pub fn command(self) -> Command {
+        self.command.unwrap_or(Command::Serve)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Start the HTTP server (default)
+    Serve,
+    /// Apply pending database migrations and exit
+    Migrate,
+    /// Load the standard exercise library and exit
+    SeedExercises {
+        /// Seed file to load instead of the bundled library
+        path: Option<PathBuf>,
+    },
+    /// Create an administrator account and exit
+    CreateAdmin {
+        #[arg(long)]
+        email: String,
+        /// Read from the environment so it doesn't end up in shell history
+        #[arg(long, env = "ADMIN_PASSWORD", hide_env_values = true)]
+        password: String,
+        /// Print an email verification token for the account to stdout
+        #[arg(long)]
+        print_token: bool,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Command {
+        let args = std::iter::once("fitness-assistant-backend").chain(args.iter().copied());
+        Cli::try_parse_from(args).unwrap().command()
+    }
+
+    #[test]
+    fn test_no_subcommand_serves() {
+        assert_eq!(parse(&[]), Command::Serve);
+        assert_eq!(parse(&["serve"]), Command::Serve);
+    }
+
+    #[test]
+    fn test_migrate_subcommand() {
+        assert_eq!(parse(&["migrate"]), Command::Migrate);
+    }
+
+    #[test]
+    fn test_seed_exercises_takes_optional_path() {
+        assert_eq!(parse(&["seed-exercises"]), Command::SeedExercises { path: None });
+        assert_eq!(
+            parse(&["seed-exercises", "extra.json"]),
+            Command::SeedExercises { path: Some(PathBuf::from("extra.json")) }
+        );
+    }
+
+    #[test]
+    fn test_create_admin_requires_email() {
+        let result = Cli::try_parse_from(["fitness-assistant-backend", "create-admin"]);
+        assert!(result.is_err());
+
+        assert_eq!(
+            parse(&["create-admin", "--email", "admin@example.com", "--password", "secret-pass"]),
+            Command::CreateAdmin {
+                email: "admin@example.com".to_string(),
+                password: "secret-pass".to_string(),
+                print_token: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_create_admin_prints_token_only_when_asked() {
+        let command = parse(&[
+            "create-admin",
+            "--email",
+            "admin@example.com",
+            "--password",
+            "secret-pass",
+            "--print-token",
+        ]);
+        assert!(matches!(command, Command::CreateAdmin { print_token: true, .. }));
+    }
+}
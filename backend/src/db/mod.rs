@@ -10,6 +10,11 @@ use std::str::FromStr;
 use std::time::Duration;
 use tracing::{info, warn};
 
+pub mod seed;
+pub mod timing;
+
+pub use seed::seed_exercises;
+
 /// Database configuration for pool creation
 pub struct DbConfig {
     pub url: String,
@@ -86,6 +91,49 @@ pub async fn health_check(pool: &PgPool) -> Result<()> {
         })
 }
 
+/// Whether an error is a unique violation on the given constraint or index
+pub fn is_unique_violation(err: &anyhow::Error, constraint: &str) -> bool {
+    err.downcast_ref::<sqlx::Error>()
+        .and_then(|e| e.as_database_error())
+        .is_some_and(|e| e.is_unique_violation() && e.constraint() == Some(constraint))
+}
+
+/// Snapshot of connection pool usage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Open connections, idle or in use
+    pub size: u32,
+    /// Open connections waiting to be acquired
+    pub idle: u32,
+    /// Connections currently checked out
+    pub active: u32,
+    /// Upper bound the pool may grow to
+    pub max_connections: u32,
+}
+
+impl PoolStats {
+    /// Read the current stats from a pool
+    pub fn from_pool(pool: &PgPool) -> Self {
+        let size = pool.size();
+        let idle = pool.num_idle() as u32;
+        Self {
+            size,
+            idle,
+            active: size.saturating_sub(idle),
+            max_connections: pool.options().get_max_connections(),
+        }
+    }
+
+    /// Whether fewer than 10% of connections are available
+    ///
+    /// Connections the pool has not opened yet count as available, so a
+    /// small pool that can still grow is not considered saturated.
+    pub fn is_saturated(&self) -> bool {
+        let available = self.idle + self.max_connections.saturating_sub(self.size);
+        available * 10 < self.max_connections
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,4 +145,21 @@ mod tests {
         assert_eq!(config.min_connections, 2);
         assert_eq!(config.acquire_timeout_secs, 30);
     }
+
+    #[test]
+    fn test_pool_saturated_when_all_connections_in_use() {
+        let stats = PoolStats { size: 20, idle: 1, active: 19, max_connections: 20 };
+        assert!(stats.is_saturated());
+    }
+
+    #[test]
+    fn test_pool_not_saturated_with_spare_capacity() {
+        // Plenty idle
+        let stats = PoolStats { size: 20, idle: 5, active: 15, max_connections: 20 };
+        assert!(!stats.is_saturated());
+
+        // Everything open is busy, but the pool can still grow
+        let stats = PoolStats { size: 4, idle: 0, active: 4, max_connections: 20 };
+        assert!(!stats.is_saturated());
+    }
 }
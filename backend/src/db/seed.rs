@@ -0,0 +1,87 @@
+//! Seed data loading
+//!
+//! Loads the standard exercise library from a bundled JSON file. Exercises
+//! already present (matched by name, case-insensitively) are skipped, so the
+//! loader is safe to run on every boot.
+
+use crate::cache::CacheStore;
+use crate::repositories::CreateExercise;
+use crate::services::exercise::ExerciseService;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use sqlx::PgPool;
+use std::path::Path;
+use tracing::info;
+
+/// Path of the bundled exercise library
+pub const DEFAULT_EXERCISE_SEED_PATH: &str =
+    concat!(env!("CARGO_MANIFEST_DIR"), "/data/exercises.json");
+
+/// One exercise in the seed file
+#[derive(Debug, Deserialize)]
+pub struct SeedExercise {
+    pub name: String,
+    pub category: String,
+    #[serde(default)]
+    pub muscle_groups: Vec<String>,
+    pub equipment: Option<String>,
+    pub calories_per_minute: Option<f64>,
+    pub description: Option<String>,
+    pub instructions: Option<String>,
+}
+
+/// Read and parse an exercise seed file
+pub fn load_seed_exercises(path: &Path) -> Result<Vec<SeedExercise>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("reading exercise seed file {}", path.display()))?;
+
+    serde_json::from_str(&contents)
+        .with_context(|| format!("parsing exercise seed file {}", path.display()))
+}
+
+/// Insert any exercises from the seed file that aren't in the library yet
+///
+/// Returns the number of exercises inserted.
+pub async fn seed_exercises(
+    pool: &PgPool,
+    cache: Option<&dyn CacheStore>,
+    path: &Path,
+) -> Result<usize> {
+    let exercises = load_seed_exercises(path)?
+        .into_iter()
+        .map(|exercise| CreateExercise {
+            name: exercise.name,
+            category: exercise.category,
+            muscle_groups: exercise.muscle_groups,
+            equipment: exercise.equipment,
+            calories_per_minute: exercise.calories_per_minute,
+            description: exercise.description,
+            instructions: exercise.instructions,
+            is_custom: false,
+            created_by: None,
+        })
+        .collect();
+
+    let inserted = ExerciseService::seed_library(pool, cache, exercises).await?;
+
+    info!(inserted, "Exercise library seeded");
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_bundled_seed_file_parses() {
+        let exercises = load_seed_exercises(Path::new(DEFAULT_EXERCISE_SEED_PATH)).unwrap();
+
+        assert!(exercises.iter().any(|e| e.name == "Bench Press"));
+        assert!(exercises.iter().all(|e| !e.name.trim().is_empty()));
+
+        // Names are the idempotency key, so they must be unique
+        let names: HashSet<_> = exercises.iter().map(|e| e.name.to_lowercase()).collect();
+        assert_eq!(names.len(), exercises.len());
+    }
+}
@@ -0,0 +1,133 @@
+//! Slow query logging
+//!
+//! Repositories tag their queries with `.timed("Repository::method")` so a
+//! warning names the query whenever it runs longer than the threshold.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Default threshold above which a query is logged as slow
+pub const DEFAULT_SLOW_QUERY_THRESHOLD_MS: u64 = 200;
+
+static SLOW_QUERY_THRESHOLD_MS: AtomicU64 = AtomicU64::new(DEFAULT_SLOW_QUERY_THRESHOLD_MS);
+
+/// Set the slow query threshold, normally once at startup from config
+pub fn set_slow_query_threshold(threshold: Duration) {
+    SLOW_QUERY_THRESHOLD_MS.store(threshold.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// The current slow query threshold
+pub fn slow_query_threshold() -> Duration {
+    Duration::from_millis(SLOW_QUERY_THRESHOLD_MS.load(Ordering::Relaxed))
+}
+
+/// Run a query, warning if it takes longer than the configured threshold
+pub async fn timed<F: Future>(tag: &'static str, query: F) -> F::Output {
+    timed_with_threshold(tag, slow_query_threshold(), query).await
+}
+
+/// Run a query, warning if it takes longer than `threshold`
+pub async fn timed_with_threshold<F: Future>(
+    tag: &'static str,
+    threshold: Duration,
+    query: F,
+) -> F::Output {
+    let start = Instant::now();
+    let output = query.await;
+    let elapsed = start.elapsed();
+
+    if elapsed > threshold {
+        warn!(
+            query = tag,
+            elapsed_ms = elapsed.as_millis() as u64,
+            threshold_ms = threshold.as_millis() as u64,
+            "Slow query"
+        );
+    }
+
+    output
+}
+
+/// Adds `.timed(tag)` to query futures
+pub trait QueryTimingExt: Future + Sized {
+    /// Time this query, warning if it exceeds the slow query threshold
+    fn timed(self, tag: &'static str) -> impl Future<Output = Self::Output> {
+        timed(tag, self)
+    }
+}
+
+impl<F: Future> QueryTimingExt for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    /// Log writer that keeps everything written in memory
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl CapturedLogs {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    /// Run `f` with a subscriber writing to an in-memory buffer
+    async fn capture_logs<F: Future>(f: F) -> (F::Output, String) {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let output = f.await;
+        (output, logs.contents())
+    }
+
+    #[tokio::test]
+    async fn test_slow_query_is_logged() {
+        let (output, logs) = capture_logs(timed_with_threshold(
+            "WeightRepository::get_recent",
+            Duration::from_millis(10),
+            async {
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                42
+            },
+        ))
+        .await;
+
+        assert_eq!(output, 42);
+        assert!(logs.contains("Slow query"));
+        assert!(logs.contains("WeightRepository::get_recent"));
+    }
+
+    #[tokio::test]
+    async fn test_fast_query_is_not_logged() {
+        let (output, logs) = capture_logs(timed_with_threshold(
+            "WeightRepository::get_recent",
+            Duration::from_secs(5),
+            async { 42 },
+        ))
+        .await;
+
+        assert_eq!(output, 42);
+        assert!(logs.is_empty());
+    }
+}
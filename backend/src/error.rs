@@ -8,6 +8,8 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use fitness_assistant_shared::units::WeightUnit;
+use fitness_assistant_shared::validation::{ValidationError, ValidationErrors};
 use serde::Serialize;
 use thiserror::Error;
 use tracing::error;
@@ -18,6 +20,9 @@ pub enum ApiError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Validation error: {0}")]
+    InvalidFields(ValidationErrors),
+
     #[error("Resource not found: {0}")]
     NotFound(String),
 
@@ -38,6 +43,17 @@ pub enum ApiError {
 
     #[error("Bad request: {0}")]
     BadRequest(String),
+
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(String),
+
+    /// A weight sent without a unit that reads as `likely_unit` instead
+    #[error("Probable unit mismatch: {message}")]
+    ProbableUnitMismatch {
+        message: String,
+        likely_unit: WeightUnit,
+        converted_kg: f64,
+    },
 }
 
 /// Error response body
@@ -53,17 +69,45 @@ pub struct ErrorDetail {
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub field: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<ValidationErrors>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub likely_unit: Option<WeightUnit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted_kg: Option<f64>,
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        let fields = match &self {
+            ApiError::InvalidFields(errors) => Some(errors.clone()),
+            _ => None,
+        };
+        let (likely_unit, converted_kg) = match &self {
+            ApiError::ProbableUnitMismatch {
+                likely_unit,
+                converted_kg,
+                ..
+            } => (Some(*likely_unit), Some(*converted_kg)),
+            _ => (None, None),
+        };
+
         let (status, code, message) = match &self {
             ApiError::Validation(msg) => (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone()),
+            ApiError::InvalidFields(errors) => {
+                (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", errors.to_string())
+            }
             ApiError::NotFound(msg) => (StatusCode::NOT_FOUND, "NOT_FOUND", msg.clone()),
             ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "UNAUTHORIZED", msg.clone()),
             ApiError::Forbidden(msg) => (StatusCode::FORBIDDEN, "FORBIDDEN", msg.clone()),
             ApiError::Conflict(msg) => (StatusCode::CONFLICT, "CONFLICT", msg.clone()),
             ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, "BAD_REQUEST", msg.clone()),
+            ApiError::ServiceUnavailable(msg) => {
+                (StatusCode::SERVICE_UNAVAILABLE, "SERVICE_UNAVAILABLE", msg.clone())
+            }
+            ApiError::ProbableUnitMismatch { message, .. } => {
+                (StatusCode::BAD_REQUEST, "PROBABLE_UNIT_MISMATCH", message.clone())
+            }
             ApiError::Internal(err) => {
                 error!("Internal error: {:?}", err);
                 (
@@ -87,6 +131,9 @@ impl IntoResponse for ApiError {
                 code: code.to_string(),
                 message,
                 field: None,
+                fields,
+                likely_unit,
+                converted_kg,
             },
         });
 
@@ -94,6 +141,18 @@ impl IntoResponse for ApiError {
     }
 }
 
+impl From<ValidationError> for ApiError {
+    fn from(err: ValidationError) -> Self {
+        ApiError::Validation(err.user_message())
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        ApiError::InvalidFields(errors)
+    }
+}
+
 /// Result type alias for API handlers
 pub type ApiResult<T> = Result<T, ApiError>;
 
@@ -115,10 +174,79 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn test_field_validation_error_is_qualified() {
+        let error: ApiError = ValidationError::new("bpm", "must be between 1 and 299").into();
+        assert!(matches!(
+            &error,
+            ApiError::Validation(msg) if msg == "Heart Rate (BPM): must be between 1 and 299"
+        ));
+        assert_eq!(error.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     fn test_unauthorized_error_status() {
         let error = ApiError::Unauthorized("Invalid token".to_string());
         let response = error.into_response();
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
+    /// Status and envelope `code` of an error response
+    async fn status_and_code(error: ApiError) -> (StatusCode, String) {
+        let response = error.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        (status, body["error"]["code"].as_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_conflict_error_response() {
+        let (status, code) = status_and_code(ApiError::Conflict("Stale version".to_string())).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(code, "CONFLICT");
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_error_response() {
+        let (status, code) =
+            status_and_code(ApiError::Unauthorized("Invalid token".to_string())).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(code, "UNAUTHORIZED");
+    }
+
+    #[tokio::test]
+    async fn test_forbidden_error_response() {
+        let (status, code) = status_and_code(ApiError::Forbidden("Not yours".to_string())).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(code, "FORBIDDEN");
+    }
+
+    #[tokio::test]
+    async fn test_service_unavailable_error_response() {
+        let (status, code) =
+            status_and_code(ApiError::ServiceUnavailable("No mailer".to_string())).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(code, "SERVICE_UNAVAILABLE");
+    }
+
+    #[tokio::test]
+    async fn test_probable_unit_mismatch_response() {
+        let error = ApiError::ProbableUnitMismatch {
+            message: "Check the unit".to_string(),
+            likely_unit: WeightUnit::Lbs,
+            converted_kg: 149.7,
+        };
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "PROBABLE_UNIT_MISMATCH");
+        assert_eq!(body["error"]["likely_unit"], "lbs");
+        assert_eq!(body["error"]["converted_kg"], 149.7);
+    }
 }
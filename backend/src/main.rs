@@ -11,14 +11,24 @@
 //! - Database: PostgreSQL with SQLx
 
 use anyhow::Result;
-use fitness_assistant_backend::{config, db, routes, state::AppState};
+use clap::Parser;
+use fitness_assistant_backend::cli::{Cli, Command};
+use fitness_assistant_backend::cache::CacheStore;
+use fitness_assistant_backend::services::{jobs, DataService, UserService};
+use fitness_assistant_backend::{auth, config, db, routes, state::AppState};
 use redis::aio::ConnectionManager;
+use sqlx::PgPool;
+use std::path::PathBuf;
+use std::time::Duration;
 use tokio::signal;
 use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Parse the command line before anything else so `--help` works without config
+    let command = Cli::parse().command();
+
     // Load environment variables from .env file
     dotenvy::dotenv().ok();
 
@@ -42,18 +52,104 @@ async fn main() -> Result<()> {
     // Create database pool
     info!("Connecting to database...");
     let db_pool = db::create_pool(&config.database.url, config.database.max_connections).await?;
+    db::timing::set_slow_query_threshold(Duration::from_millis(
+        config.database.slow_query_threshold_ms,
+    ));
 
-    // Run migrations (skip in production if using separate migration job)
-    if !config::AppConfig::is_production() {
+    // Run migrations (skip in production unless asked for, since a separate
+    // migration job runs `migrate` there)
+    if command == Command::Migrate || !config::AppConfig::is_production() {
         info!("Running database migrations...");
         db::run_migrations(&db_pool).await?;
     }
 
+    match command {
+        Command::Serve => serve(config, db_pool).await,
+        Command::Migrate => Ok(()),
+        Command::SeedExercises { path } => {
+            let path =
+                path.unwrap_or_else(|| PathBuf::from(db::seed::DEFAULT_EXERCISE_SEED_PATH));
+            let redis_conn = connect_redis(&config.redis.url).await;
+            let cache = redis_conn.as_ref().map(|r| r as &dyn CacheStore);
+            db::seed_exercises(&db_pool, cache, &path).await?;
+            Ok(())
+        }
+        Command::CreateAdmin {
+            email,
+            password,
+            print_token,
+        } => {
+            let user_id = UserService::create_admin(&db_pool, &email, &password).await?;
+            info!(%user_id, %email, "Administrator created");
+
+            // Admin routes need a verified address. The token is a live
+            // credential, so it is only written to stdout (never the log) and
+            // only when the operator asks for it
+            if print_token {
+                let jwt = auth::JwtService::from_config(&config.jwt)?;
+                let token = UserService::issue_verification_token(&db_pool, &jwt, user_id).await?;
+                println!("{}", token);
+            } else {
+                info!("Run with --print-token to get a token for /auth/verify-email");
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Run the HTTP server until a shutdown signal arrives
+async fn serve(config: config::AppConfig, db_pool: PgPool) -> Result<()> {
     // Connect to Redis (optional - gracefully handle connection failure)
     let redis_conn = connect_redis(&config.redis.url).await;
 
     // Create application state
-    let state = AppState::new(db_pool, redis_conn, config.clone());
+    let state = AppState::new(db_pool.clone(), redis_conn, config.clone())?;
+
+    // Background jobs are drained once the server stops
+    let background = state.jobs().clone();
+
+    // Permanently remove soft-deleted entries once the retention window passes
+    background.track(
+        "purge_soft_deleted",
+        tokio::spawn(jobs::run_periodically(
+            "purge_soft_deleted",
+            Duration::from_secs(24 * 60 * 60),
+            background.subscribe(),
+            {
+                let pool = db_pool.clone();
+                move || purge_soft_deleted(pool.clone())
+            },
+        )),
+    );
+
+    // Keep auto-calculated hydration goals in line with the latest weight
+    background.track(
+        "hydration_goals",
+        jobs::spawn_hydration_goal_job(
+            db_pool.clone(),
+            config.hydration.clone(),
+            Duration::from_secs(config.jobs.hydration_goal_interval_secs),
+            background.subscribe(),
+        ),
+    );
+
+    // Build queued data exports in the background
+    background.track(
+        "export_worker",
+        jobs::spawn_export_worker(
+            db_pool,
+            PathBuf::from(&config.jobs.export_dir),
+            Duration::from_secs(config.jobs.export_ttl_secs),
+            Duration::from_secs(config.jobs.export_poll_interval_secs),
+            background.subscribe(),
+        ),
+    );
+
+    // Pick up non-secret setting changes on SIGHUP
+    background.track(
+        "config_watcher",
+        config::watch(state.reloadable.clone(), background.subscribe()),
+    );
 
     // Build application
     let app = routes::create_router(state);
@@ -69,6 +165,13 @@ async fn main() -> Result<()> {
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
+    let aborted = background
+        .shutdown(Duration::from_secs(config.jobs.shutdown_timeout_secs))
+        .await;
+    if aborted > 0 {
+        warn!(aborted, "Aborted background jobs that outlived the shutdown timeout");
+    }
+
     info!("Server shutdown complete");
     Ok(())
 }
@@ -99,6 +202,21 @@ async fn connect_redis(url: &str) -> Option<ConnectionManager> {
     }
 }
 
+/// Days a soft-deleted entry stays restorable before it is purged
+const SOFT_DELETE_RETENTION_DAYS: i64 = 30;
+
+/// Purge soft-deleted weight and food logs
+async fn purge_soft_deleted(pool: PgPool) {
+    match DataService::purge_soft_deleted(&pool, SOFT_DELETE_RETENTION_DAYS).await {
+        Ok(summary) => info!(
+            weight_logs = summary.weight_logs,
+            food_logs = summary.food_logs,
+            "Purged soft-deleted entries"
+        ),
+        Err(e) => warn!("Failed to purge soft-deleted entries: {}", e),
+    }
+}
+
 /// Initialize tracing/logging
 fn init_tracing() {
     let env_filter = tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
@@ -128,8 +246,10 @@ fn init_tracing() {
 fn validate_production_config(config: &config::AppConfig) -> Result<()> {
     let mut errors = Vec::new();
 
-    // Check JWT secret is not default
-    if config.jwt.secret.contains("development") || config.jwt.secret.len() < 32 {
+    // Check JWT secret is not default (RS256 keys are checked when the state is built)
+    if config.jwt.algorithm == config::JwtAlgorithm::HS256
+        && (config.jwt.secret.contains("development") || config.jwt.secret.len() < 32)
+    {
         errors.push("JWT secret must be at least 32 characters and not contain 'development'");
     }
 
@@ -138,6 +258,18 @@ fn validate_production_config(config: &config::AppConfig) -> Result<()> {
         warn!("Database URL contains localhost - ensure this is intentional for production");
     }
 
+    // Check AI settings only when the feature is on
+    if config.ai.enabled {
+        let valid_url = reqwest::Url::parse(&config.ai.ollama_url)
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+        if !valid_url {
+            errors.push("AI ollama_url must be a valid http(s) URL when AI is enabled");
+        }
+        if config.ai.model.trim().is_empty() {
+            errors.push("AI model must not be empty when AI is enabled");
+        }
+    }
+
     if !errors.is_empty() {
         for err in &errors {
             error!("Configuration error: {}", err);
@@ -176,3 +308,50 @@ async fn shutdown_signal() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn production_config() -> config::AppConfig {
+        let mut config = config::AppConfig::default();
+        config.jwt.secret = "a".repeat(32);
+        config
+    }
+
+    #[test]
+    fn test_enabled_ai_requires_model() {
+        let mut config = production_config();
+        config.ai.enabled = true;
+        config.ai.model = "  ".to_string();
+
+        assert!(validate_production_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_enabled_ai_requires_valid_url() {
+        let mut config = production_config();
+        config.ai.enabled = true;
+        config.ai.ollama_url = "not a url".to_string();
+
+        assert!(validate_production_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_valid_ai_config_passes() {
+        let mut config = production_config();
+        config.ai.enabled = true;
+        config.ai.ollama_url = "http://ollama:11434".to_string();
+        config.ai.model = "llama3.2".to_string();
+
+        assert!(validate_production_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_disabled_ai_settings_are_not_checked() {
+        let mut config = production_config();
+        config.ai.model = String::new();
+
+        assert!(validate_production_config(&config).is_ok());
+    }
+}
@@ -0,0 +1,80 @@
+//! Prometheus metrics
+//!
+//! Request counts and latencies are recorded by a middleware layer and
+//! rendered in the Prometheus text exposition format by `/metrics`.
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use once_cell::sync::Lazy;
+use sqlx::PgPool;
+use std::time::Instant;
+
+/// Request counter, labelled by method, route and status
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+
+/// Request latency histogram in seconds
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+
+/// Latency buckets, from fast cache hits to slow report queries
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// The process-wide recorder, installed on first use
+///
+/// The `metrics` facade only allows one global recorder, so every router
+/// built in the process (including in tests) shares this handle.
+static HANDLE: Lazy<PrometheusHandle> = Lazy::new(|| {
+    PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.to_string()),
+            LATENCY_BUCKETS,
+        )
+        .expect("latency buckets are non-empty")
+        .install_recorder()
+        .expect("failed to install Prometheus recorder")
+});
+
+/// Get the Prometheus handle, installing the recorder if needed
+pub fn handle() -> &'static PrometheusHandle {
+    &HANDLE
+}
+
+/// Middleware recording a count and latency for every request
+///
+/// Routes are labelled by their matched template (e.g. `/api/v1/weight/:id`)
+/// rather than the raw path, so IDs don't explode label cardinality.
+pub async fn track_requests(request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.run(request).await;
+
+    let labels = [
+        ("method", method),
+        ("path", path),
+        ("status", response.status().as_u16().to_string()),
+    ];
+    metrics::counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    metrics::histogram!(HTTP_REQUEST_DURATION_SECONDS, &labels)
+        .record(start.elapsed().as_secs_f64());
+
+    response
+}
+
+/// Render all metrics, refreshing the database pool gauges first
+pub fn render(db: &PgPool) -> String {
+    metrics::gauge!("db_pool_connections").set(db.size() as f64);
+    metrics::gauge!("db_pool_idle_connections").set(db.num_idle() as f64);
+
+    handle().render()
+}
@@ -0,0 +1,78 @@
+//! Audit log repository for database operations
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Audit log record from database
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct AuditLogRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub entity: String,
+    pub entity_id: Uuid,
+    pub action: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Audit log repository
+pub struct AuditLogRepository;
+
+impl AuditLogRepository {
+    /// Record a single action
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        entity: &str,
+        entity_id: Uuid,
+        action: &str,
+    ) -> Result<AuditLogRecord> {
+        let record = sqlx::query_as::<_, AuditLogRecord>(
+            r#"
+            INSERT INTO audit_logs (user_id, entity, entity_id, action)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, user_id, entity, entity_id, action, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(entity)
+        .bind(entity_id)
+        .bind(action)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Get a user's actions, most recent first, with total count
+    pub async fn get_paginated(
+        pool: &PgPool,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<AuditLogRecord>, i64)> {
+        let count_row: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) as count FROM audit_logs WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_one(pool)
+                .await?;
+
+        let records = sqlx::query_as::<_, AuditLogRecord>(
+            r#"
+            SELECT id, user_id, entity, entity_id, action, created_at
+            FROM audit_logs
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+        Ok((records, count_row.0))
+    }
+}
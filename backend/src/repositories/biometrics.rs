@@ -1,9 +1,11 @@
 //! Biometrics repository for heart rate and HRV database operations
 
+use crate::timezone::local_day_bounds;
 use anyhow::Result;
 use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
 // ============================================================================
@@ -126,6 +128,38 @@ impl HeartRateLogRepository {
         Ok(records)
     }
 
+    /// Get workout heart rate readings for a range of local dates
+    ///
+    /// Returns `(workout_id, bpm, recorded_at)` ordered by workout, then time.
+    pub async fn get_workout_readings(
+        pool: &PgPool,
+        user_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        tz: Tz,
+    ) -> Result<Vec<(Uuid, i32, DateTime<Utc>)>> {
+        let (start, _) = local_day_bounds(start_date, tz);
+        let (_, end) = local_day_bounds(end_date, tz);
+        let rows = sqlx::query_as::<_, (Uuid, i32, DateTime<Utc>)>(
+            r#"
+            SELECT workout_id, bpm, recorded_at
+            FROM heart_rate_logs
+            WHERE user_id = $1
+              AND workout_id IS NOT NULL
+              AND recorded_at >= $2
+              AND recorded_at < $3
+            ORDER BY workout_id, recorded_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
     /// Get resting heart rate average for a date range (7-day baseline)
     pub async fn get_resting_baseline(
         pool: &PgPool,
@@ -154,14 +188,66 @@ impl HeartRateLogRepository {
         Ok(result.0)
     }
 
-    /// Get heart rate statistics for a date range
+    /// Get the most recent resting heart rate reading
+    pub async fn get_latest_resting(pool: &PgPool, user_id: Uuid) -> Result<Option<i32>> {
+        let bpm = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT bpm
+            FROM heart_rate_logs
+            WHERE user_id = $1 AND context = 'resting'
+            ORDER BY recorded_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(bpm)
+    }
+
+    /// Get resting heart rate readings for a range of local dates, for baselines
+    pub async fn get_resting_readings(
+        pool: &PgPool,
+        user_id: Uuid,
+        end_date: NaiveDate,
+        days: i32,
+        tz: Tz,
+    ) -> Result<Vec<f64>> {
+        let start_date = end_date - chrono::Duration::days(days as i64);
+        let (start, _) = local_day_bounds(start_date, tz);
+        let (_, end) = local_day_bounds(end_date, tz);
+
+        let readings = sqlx::query_scalar::<_, f64>(
+            r#"
+            SELECT bpm::float8
+            FROM heart_rate_logs
+            WHERE user_id = $1
+              AND recorded_at >= $2
+              AND recorded_at < $3
+              AND context = 'resting'
+            "#,
+        )
+        .bind(user_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(readings)
+    }
+
+    /// Get heart rate statistics for a range of local dates
     pub async fn get_stats(
         pool: &PgPool,
         user_id: Uuid,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        tz: Tz,
         context: Option<&str>,
     ) -> Result<HeartRateStats> {
+        let (start, _) = local_day_bounds(start_date, tz);
+        let (_, end) = local_day_bounds(end_date, tz);
         let stats = if let Some(ctx) = context {
             sqlx::query_as::<_, HeartRateStats>(
                 r#"
@@ -172,14 +258,14 @@ impl HeartRateLogRepository {
                     COUNT(*)::bigint as count
                 FROM heart_rate_logs
                 WHERE user_id = $1 
-                  AND DATE(recorded_at) >= $2 
-                  AND DATE(recorded_at) <= $3
+                  AND recorded_at >= $2
+                  AND recorded_at < $3
                   AND context = $4
                 "#,
             )
             .bind(user_id)
-            .bind(start_date)
-            .bind(end_date)
+            .bind(start)
+            .bind(end)
             .bind(ctx)
             .fetch_one(pool)
             .await?
@@ -193,13 +279,13 @@ impl HeartRateLogRepository {
                     COUNT(*)::bigint as count
                 FROM heart_rate_logs
                 WHERE user_id = $1 
-                  AND DATE(recorded_at) >= $2 
-                  AND DATE(recorded_at) <= $3
+                  AND recorded_at >= $2
+                  AND recorded_at < $3
                 "#,
             )
             .bind(user_id)
-            .bind(start_date)
-            .bind(end_date)
+            .bind(start)
+            .bind(end)
             .fetch_one(pool)
             .await?
         };
@@ -221,6 +307,94 @@ impl HeartRateLogRepository {
     }
 }
 
+// ============================================================================
+// Workout Heart Rate Streams
+// ============================================================================
+
+/// Heart rate stream record from database
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct HeartRateStreamRecord {
+    pub id: Uuid,
+    pub workout_id: Uuid,
+    pub user_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub offsets_seconds: Vec<i32>,
+    pub bpm: Vec<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for storing a workout's heart rate stream
+#[derive(Debug, Clone)]
+pub struct CreateHeartRateStream {
+    pub workout_id: Uuid,
+    pub user_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub offsets_seconds: Vec<i32>,
+    pub bpm: Vec<i32>,
+}
+
+/// Workout heart rate stream repository
+pub struct HeartRateStreamRepository;
+
+impl HeartRateStreamRepository {
+    /// Store a workout's stream, replacing any previously ingested one
+    pub async fn upsert<'e>(
+        executor: impl PgExecutor<'e>,
+        input: CreateHeartRateStream,
+    ) -> Result<HeartRateStreamRecord> {
+        let record = sqlx::query_as::<_, HeartRateStreamRecord>(
+            r#"
+            INSERT INTO workout_hr_streams (workout_id, user_id, started_at, offsets_seconds, bpm)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (workout_id) DO UPDATE SET
+                started_at = EXCLUDED.started_at,
+                offsets_seconds = EXCLUDED.offsets_seconds,
+                bpm = EXCLUDED.bpm,
+                created_at = NOW()
+            RETURNING id, workout_id, user_id, started_at, offsets_seconds, bpm, created_at
+            "#,
+        )
+        .bind(input.workout_id)
+        .bind(input.user_id)
+        .bind(input.started_at)
+        .bind(&input.offsets_seconds)
+        .bind(&input.bpm)
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Get the streams of workouts started within a range of local dates
+    pub async fn get_by_date_range(
+        pool: &PgPool,
+        user_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        tz: Tz,
+    ) -> Result<Vec<HeartRateStreamRecord>> {
+        let (start, _) = local_day_bounds(start_date, tz);
+        let (_, end) = local_day_bounds(end_date, tz);
+        let records = sqlx::query_as::<_, HeartRateStreamRecord>(
+            r#"
+            SELECT id, workout_id, user_id, started_at, offsets_seconds, bpm, created_at
+            FROM workout_hr_streams
+            WHERE user_id = $1
+              AND started_at >= $2
+              AND started_at < $3
+            ORDER BY started_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+}
+
 // ============================================================================
 // HRV Logs
 // ============================================================================
@@ -251,6 +425,15 @@ pub struct CreateHrvLog {
     pub notes: Option<String>,
 }
 
+/// Corrections to an existing HRV log
+#[derive(Debug, Clone, Default)]
+pub struct UpdateHrvLog {
+    pub rmssd: Option<Decimal>,
+    pub sdnn: Option<Decimal>,
+    pub context: Option<String>,
+    pub notes: Option<String>,
+}
+
 /// HRV statistics
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct HrvStats {
@@ -332,29 +515,32 @@ impl HrvLogRepository {
         Ok(record)
     }
 
-    /// Get HRV history for a date range
+    /// Get HRV history for a range of local dates
     pub async fn get_history(
         pool: &PgPool,
         user_id: Uuid,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        tz: Tz,
         limit: i64,
         offset: i64,
     ) -> Result<Vec<HrvLogRecord>> {
+        let (start, _) = local_day_bounds(start_date, tz);
+        let (_, end) = local_day_bounds(end_date, tz);
         let records = sqlx::query_as::<_, HrvLogRecord>(
             r#"
             SELECT id, user_id, rmssd, sdnn, context, recorded_at, source, notes, created_at
             FROM hrv_logs
-            WHERE user_id = $1 
-              AND DATE(recorded_at) >= $2 
-              AND DATE(recorded_at) <= $3
+            WHERE user_id = $1
+              AND recorded_at >= $2
+              AND recorded_at < $3
             ORDER BY recorded_at DESC
             LIMIT $4 OFFSET $5
             "#,
         )
         .bind(user_id)
-        .bind(start_date)
-        .bind(end_date)
+        .bind(start)
+        .bind(end)
         .bind(limit)
         .bind(offset)
         .fetch_all(pool)
@@ -363,6 +549,53 @@ impl HrvLogRepository {
         Ok(records)
     }
 
+    /// Source of an HRV log entry, or None if it does not belong to the user
+    pub async fn get_source(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<Option<String>> {
+        let source = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT source
+            FROM hrv_logs
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(source)
+    }
+
+    /// Update an HRV log entry, returning None if it does not belong to the user
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        updates: UpdateHrvLog,
+    ) -> Result<Option<HrvLogRecord>> {
+        let record = sqlx::query_as::<_, HrvLogRecord>(
+            r#"
+            UPDATE hrv_logs SET
+                rmssd = COALESCE($3, rmssd),
+                sdnn = COALESCE($4, sdnn),
+                context = COALESCE($5, context),
+                notes = COALESCE($6, notes)
+            WHERE id = $1 AND user_id = $2
+            RETURNING id, user_id, rmssd, sdnn, context, recorded_at, source, notes, created_at
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(updates.rmssd)
+        .bind(updates.sdnn)
+        .bind(&updates.context)
+        .bind(&updates.notes)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
     /// Delete an HRV log entry
     pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
         let result = sqlx::query(
@@ -0,0 +1,108 @@
+//! Cycle tracking repository for database operations
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Cycle tracking settings record from database
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct CycleSettingsRecord {
+    pub user_id: Uuid,
+    pub enabled: bool,
+    pub cycle_length_days: i32,
+    pub period_length_days: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Input for creating/updating cycle tracking settings
+#[derive(Debug, Clone)]
+pub struct UpsertCycleSettings {
+    pub user_id: Uuid,
+    pub enabled: bool,
+    pub cycle_length_days: i32,
+    pub period_length_days: i32,
+}
+
+/// Cycle tracking repository
+pub struct CycleRepository;
+
+impl CycleRepository {
+    /// Get a user's cycle tracking settings
+    pub async fn get_settings(pool: &PgPool, user_id: Uuid) -> Result<Option<CycleSettingsRecord>> {
+        let record = sqlx::query_as::<_, CycleSettingsRecord>(
+            r#"
+            SELECT user_id, enabled, cycle_length_days, period_length_days, created_at, updated_at
+            FROM cycle_settings
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Create or update a user's cycle tracking settings
+    pub async fn upsert_settings(
+        pool: &PgPool,
+        input: UpsertCycleSettings,
+    ) -> Result<CycleSettingsRecord> {
+        let record = sqlx::query_as::<_, CycleSettingsRecord>(
+            r#"
+            INSERT INTO cycle_settings (user_id, enabled, cycle_length_days, period_length_days)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id) DO UPDATE SET
+                enabled = EXCLUDED.enabled,
+                cycle_length_days = EXCLUDED.cycle_length_days,
+                period_length_days = EXCLUDED.period_length_days,
+                updated_at = NOW()
+            RETURNING user_id, enabled, cycle_length_days, period_length_days, created_at, updated_at
+            "#,
+        )
+        .bind(input.user_id)
+        .bind(input.enabled)
+        .bind(input.cycle_length_days)
+        .bind(input.period_length_days)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Record the first day of a cycle; logging the same day twice is a no-op
+    pub async fn add_start(pool: &PgPool, user_id: Uuid, started_on: NaiveDate) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO cycle_starts (user_id, started_on)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id, started_on) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(started_on)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// All of a user's logged cycle start dates, most recent first
+    pub async fn list_starts(pool: &PgPool, user_id: Uuid) -> Result<Vec<NaiveDate>> {
+        let starts = sqlx::query_scalar::<_, NaiveDate>(
+            r#"
+            SELECT started_on
+            FROM cycle_starts
+            WHERE user_id = $1
+            ORDER BY started_on DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(starts)
+    }
+}
@@ -3,7 +3,8 @@
 use anyhow::Result;
 use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
 // ============================================================================
@@ -11,7 +12,7 @@ use uuid::Uuid;
 // ============================================================================
 
 /// Exercise record from database
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
 pub struct ExerciseRecord {
     pub id: Uuid,
     pub name: String,
@@ -124,19 +125,54 @@ impl ExerciseRepository {
         Ok(records)
     }
 
-    /// Search exercises by name
-    pub async fn search(pool: &PgPool, query: &str, limit: i64) -> Result<Vec<ExerciseRecord>> {
+    /// Get library exercises working any of the given muscle groups, excluding one exercise
+    pub async fn get_sharing_muscle_groups(
+        pool: &PgPool,
+        muscle_groups: &[String],
+        exclude_id: Uuid,
+    ) -> Result<Vec<ExerciseRecord>> {
         let records = sqlx::query_as::<_, ExerciseRecord>(
             r#"
             SELECT id, name, category, muscle_groups, equipment, calories_per_minute,
                    description, instructions, is_custom, created_by, created_at, updated_at
             FROM exercises
-            WHERE LOWER(name) LIKE LOWER($1)
+            WHERE is_custom = FALSE AND muscle_groups && $1 AND id <> $2
             ORDER BY name
-            LIMIT $2
             "#,
         )
-        .bind(format!("%{}%", query))
+        .bind(muscle_groups)
+        .bind(exclude_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Find exercises whose name contains `query`, most relevant first
+    ///
+    /// Names starting with the query come first, then names containing it
+    /// as a whole word, then names containing it mid-word; ties are
+    /// alphabetical. At most `limit` exercises are returned.
+    pub async fn search(pool: &PgPool, query: &str, limit: i64) -> Result<Vec<ExerciseRecord>> {
+        let query = query.trim().to_lowercase();
+        let records = sqlx::query_as::<_, ExerciseRecord>(
+            r#"
+            SELECT id, name, category, muscle_groups, equipment, calories_per_minute,
+                   description, instructions, is_custom, created_by, created_at, updated_at
+            FROM exercises
+            WHERE LOWER(name) LIKE '%' || $1 || '%'
+            ORDER BY
+                CASE
+                    WHEN LOWER(name) LIKE $1 || '%' THEN 0
+                    WHEN LOWER(name) ~ ('(^|[^[:alnum:]])' || $2 || '($|[^[:alnum:]])') THEN 1
+                    ELSE 2
+                END,
+                LOWER(name)
+            LIMIT $3
+            "#,
+        )
+        .bind(escape_like(&query))
+        .bind(escape_regex(&query))
         .bind(limit)
         .fetch_all(pool)
         .await?;
@@ -145,7 +181,10 @@ impl ExerciseRepository {
     }
 
     /// Get exercise by ID
-    pub async fn get_by_id(pool: &PgPool, id: Uuid) -> Result<Option<ExerciseRecord>> {
+    pub async fn get_by_id<'e>(
+        executor: impl PgExecutor<'e>,
+        id: Uuid,
+    ) -> Result<Option<ExerciseRecord>> {
         let record = sqlx::query_as::<_, ExerciseRecord>(
             r#"
             SELECT id, name, category, muscle_groups, equipment, calories_per_minute,
@@ -155,7 +194,7 @@ impl ExerciseRepository {
             "#,
         )
         .bind(id)
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await?;
 
         Ok(record)
@@ -179,10 +218,13 @@ impl ExerciseRepository {
         Ok(records)
     }
 
-    /// Check if exercise exists by name (for seeding)
+    /// Check if a library exercise exists by name (for seeding)
+    ///
+    /// Users' custom exercises are ignored so a custom one can't keep the
+    /// standard exercise of the same name out of the library.
     pub async fn exists_by_name(pool: &PgPool, name: &str) -> Result<bool> {
         let result = sqlx::query_scalar::<_, bool>(
-            r#"SELECT EXISTS(SELECT 1 FROM exercises WHERE LOWER(name) = LOWER($1))"#,
+            r#"SELECT EXISTS(SELECT 1 FROM exercises WHERE LOWER(name) = LOWER($1) AND NOT is_custom)"#,
         )
         .bind(name)
         .fetch_one(pool)
@@ -192,6 +234,30 @@ impl ExerciseRepository {
     }
 }
 
+/// Escape LIKE wildcards so `text` only matches literally
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape regular expression metacharacters so `text` only matches literally
+fn escape_regex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.^$|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 // ============================================================================
 // Workouts
 // ============================================================================
@@ -209,6 +275,8 @@ pub struct WorkoutRecord {
     pub calories_burned: Option<i32>,
     pub avg_heart_rate: Option<i32>,
     pub max_heart_rate: Option<i32>,
+    pub recovery_heart_rate: Option<i32>,
+    pub heart_rate_recovery: Option<i32>,
     pub distance_meters: Option<Decimal>,
     pub pace_seconds_per_km: Option<i32>,
     pub elevation_gain_meters: Option<Decimal>,
@@ -230,6 +298,8 @@ pub struct CreateWorkout {
     pub calories_burned: Option<i32>,
     pub avg_heart_rate: Option<i32>,
     pub max_heart_rate: Option<i32>,
+    pub recovery_heart_rate: Option<i32>,
+    pub heart_rate_recovery: Option<i32>,
     pub distance_meters: Option<f64>,
     pub pace_seconds_per_km: Option<i32>,
     pub elevation_gain_meters: Option<f64>,
@@ -242,15 +312,20 @@ pub struct WorkoutRepository;
 
 impl WorkoutRepository {
     /// Create a new workout
-    pub async fn create(pool: &PgPool, input: CreateWorkout) -> Result<WorkoutRecord> {
+    pub async fn create<'e>(
+        executor: impl PgExecutor<'e>,
+        input: CreateWorkout,
+    ) -> Result<WorkoutRecord> {
         let record = sqlx::query_as::<_, WorkoutRecord>(
             r#"
             INSERT INTO workouts (user_id, name, workout_type, started_at, ended_at, duration_minutes,
-                                  calories_burned, avg_heart_rate, max_heart_rate, distance_meters,
+                                  calories_burned, avg_heart_rate, max_heart_rate,
+                                  recovery_heart_rate, heart_rate_recovery, distance_meters,
                                   pace_seconds_per_km, elevation_gain_meters, source, notes)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
             RETURNING id, user_id, name, workout_type, started_at, ended_at, duration_minutes,
-                      calories_burned, avg_heart_rate, max_heart_rate, distance_meters,
+                      calories_burned, avg_heart_rate, max_heart_rate,
+                      recovery_heart_rate, heart_rate_recovery, distance_meters,
                       pace_seconds_per_km, elevation_gain_meters, source, notes, created_at, updated_at
             "#,
         )
@@ -263,12 +338,14 @@ impl WorkoutRepository {
         .bind(input.calories_burned)
         .bind(input.avg_heart_rate)
         .bind(input.max_heart_rate)
+        .bind(input.recovery_heart_rate)
+        .bind(input.heart_rate_recovery)
         .bind(input.distance_meters)
         .bind(input.pace_seconds_per_km)
         .bind(input.elevation_gain_meters)
         .bind(&input.source)
         .bind(&input.notes)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(record)
@@ -279,7 +356,8 @@ impl WorkoutRepository {
         let record = sqlx::query_as::<_, WorkoutRecord>(
             r#"
             SELECT id, user_id, name, workout_type, started_at, ended_at, duration_minutes,
-                   calories_burned, avg_heart_rate, max_heart_rate, distance_meters,
+                   calories_burned, avg_heart_rate, max_heart_rate,
+                   recovery_heart_rate, heart_rate_recovery, distance_meters,
                    pace_seconds_per_km, elevation_gain_meters, source, notes, created_at, updated_at
             FROM workouts
             WHERE id = $1 AND user_id = $2
@@ -325,7 +403,8 @@ impl WorkoutRepository {
         let records = sqlx::query_as::<_, WorkoutRecord>(
             r#"
             SELECT id, user_id, name, workout_type, started_at, ended_at, duration_minutes,
-                   calories_burned, avg_heart_rate, max_heart_rate, distance_meters,
+                   calories_burned, avg_heart_rate, max_heart_rate,
+                   recovery_heart_rate, heart_rate_recovery, distance_meters,
                    pace_seconds_per_km, elevation_gain_meters, source, notes, created_at, updated_at
             FROM workouts
             WHERE user_id = $1 AND started_at >= $2 AND started_at <= $3
@@ -355,7 +434,8 @@ impl WorkoutRepository {
         let records = sqlx::query_as::<_, WorkoutRecord>(
             r#"
             SELECT id, user_id, name, workout_type, started_at, ended_at, duration_minutes,
-                   calories_burned, avg_heart_rate, max_heart_rate, distance_meters,
+                   calories_burned, avg_heart_rate, max_heart_rate,
+                   recovery_heart_rate, heart_rate_recovery, distance_meters,
                    pace_seconds_per_km, elevation_gain_meters, source, notes, created_at, updated_at
             FROM workouts
             WHERE user_id = $1 AND DATE(started_at) >= $2 AND DATE(started_at) < $3
@@ -371,6 +451,79 @@ impl WorkoutRepository {
         Ok(records)
     }
 
+    /// Get average paces for runs covering at least `min_distance_meters`
+    ///
+    /// A workout is a run when its type is `running` (or `run`) or it
+    /// includes a running exercise, so rides and rows are left out.
+    pub async fn get_run_paces_since(
+        pool: &PgPool,
+        user_id: Uuid,
+        min_distance_meters: f64,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, i32)>> {
+        let rows = sqlx::query_as::<_, (DateTime<Utc>, i32)>(
+            r#"
+            SELECT w.started_at, w.pace_seconds_per_km
+            FROM workouts w
+            WHERE w.user_id = $1
+              AND w.distance_meters >= $2
+              AND w.pace_seconds_per_km IS NOT NULL
+              AND w.started_at >= $3
+              AND (
+                  LOWER(w.workout_type) IN ('running', 'run')
+                  OR EXISTS (
+                      SELECT 1
+                      FROM workout_exercises we
+                      JOIN exercises e ON e.id = we.exercise_id
+                      WHERE we.workout_id = w.id AND LOWER(e.name) LIKE '%running%'
+                  )
+              )
+            ORDER BY w.started_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(Decimal::try_from(min_distance_meters).unwrap_or_default())
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Set a workout's average and max heart rate from an ingested stream
+    ///
+    /// Heart rate recovery is recomputed against the new max when a
+    /// recovery reading was logged.
+    pub async fn set_heart_rate_summary<'e>(
+        executor: impl PgExecutor<'e>,
+        id: Uuid,
+        user_id: Uuid,
+        avg_heart_rate: i32,
+        max_heart_rate: i32,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE workouts SET
+                avg_heart_rate = $3,
+                max_heart_rate = $4,
+                heart_rate_recovery = CASE
+                    WHEN recovery_heart_rate IS NULL THEN heart_rate_recovery
+                    ELSE GREATEST($4 - recovery_heart_rate, 0)
+                END,
+                updated_at = NOW()
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(avg_heart_rate)
+        .bind(max_heart_rate)
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Delete a workout
     pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
         let result = sqlx::query(
@@ -414,7 +567,10 @@ pub struct WorkoutExerciseRepository;
 
 impl WorkoutExerciseRepository {
     /// Add exercise to workout
-    pub async fn create(pool: &PgPool, input: AddWorkoutExercise) -> Result<WorkoutExerciseRecord> {
+    pub async fn create<'e>(
+        executor: impl PgExecutor<'e>,
+        input: AddWorkoutExercise,
+    ) -> Result<WorkoutExerciseRecord> {
         let record = sqlx::query_as::<_, WorkoutExerciseRecord>(
             r#"
             INSERT INTO workout_exercises (workout_id, exercise_id, sort_order, notes)
@@ -426,7 +582,7 @@ impl WorkoutExerciseRepository {
         .bind(input.exercise_id)
         .bind(input.sort_order)
         .bind(&input.notes)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(record)
@@ -493,7 +649,10 @@ pub struct ExerciseSetRepository;
 
 impl ExerciseSetRepository {
     /// Create an exercise set
-    pub async fn create(pool: &PgPool, input: CreateExerciseSet) -> Result<ExerciseSetRecord> {
+    pub async fn create<'e>(
+        executor: impl PgExecutor<'e>,
+        input: CreateExerciseSet,
+    ) -> Result<ExerciseSetRecord> {
         let record = sqlx::query_as::<_, ExerciseSetRecord>(
             r#"
             INSERT INTO exercise_sets (workout_exercise_id, set_number, reps, weight_kg, 
@@ -515,7 +674,7 @@ impl ExerciseSetRepository {
         .bind(input.is_warmup)
         .bind(input.is_dropset)
         .bind(&input.notes)
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?;
 
         Ok(record)
@@ -538,4 +697,347 @@ impl ExerciseSetRepository {
 
         Ok(records)
     }
+
+    /// Get a user's weighted working sets for an exercise since a given time
+    ///
+    /// Warmup sets and sets without reps or load are excluded.
+    pub async fn get_working_sets_for_exercise(
+        pool: &PgPool,
+        user_id: Uuid,
+        exercise_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<LiftSetRecord>> {
+        let records = sqlx::query_as::<_, LiftSetRecord>(
+            r#"
+            SELECT w.started_at AS performed_at, s.weight_kg, s.reps
+            FROM exercise_sets s
+            JOIN workout_exercises we ON we.id = s.workout_exercise_id
+            JOIN workouts w ON w.id = we.workout_id
+            WHERE w.user_id = $1
+              AND we.exercise_id = $2
+              AND w.started_at >= $3
+              AND s.is_warmup = FALSE
+              AND s.reps > 0
+              AND s.weight_kg > 0
+            ORDER BY w.started_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(exercise_id)
+        .bind(since)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Get weighted working sets of an exercise from the user's most recent sessions
+    ///
+    /// Sets are ordered newest session first, then by set number.
+    pub async fn get_recent_session_sets(
+        pool: &PgPool,
+        user_id: Uuid,
+        exercise_id: Uuid,
+        sessions: i64,
+    ) -> Result<Vec<SessionSetRecord>> {
+        let records = sqlx::query_as::<_, SessionSetRecord>(
+            r#"
+            WITH recent AS (
+                SELECT DISTINCT w.id, w.started_at
+                FROM workouts w
+                JOIN workout_exercises we ON we.workout_id = w.id
+                WHERE w.user_id = $1 AND we.exercise_id = $2
+                ORDER BY w.started_at DESC
+                LIMIT $3
+            )
+            SELECT r.id AS workout_id, r.started_at AS performed_at, s.weight_kg, s.reps, s.rpe
+            FROM exercise_sets s
+            JOIN workout_exercises we ON we.id = s.workout_exercise_id
+            JOIN recent r ON r.id = we.workout_id
+            WHERE we.exercise_id = $2
+              AND s.is_warmup = FALSE
+              AND s.reps > 0
+              AND s.weight_kg > 0
+            ORDER BY r.started_at DESC, s.set_number ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(exercise_id)
+        .bind(sessions)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Get weighted working sets of every exercise in workouts started within a range
+    pub async fn get_working_sets_in_range(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<SessionSetRecord>> {
+        let records = sqlx::query_as::<_, SessionSetRecord>(
+            r#"
+            SELECT w.id AS workout_id, w.started_at AS performed_at, s.weight_kg, s.reps, s.rpe
+            FROM exercise_sets s
+            JOIN workout_exercises we ON we.id = s.workout_exercise_id
+            JOIN workouts w ON w.id = we.workout_id
+            WHERE w.user_id = $1
+              AND w.started_at >= $2 AND w.started_at < $3
+              AND s.is_warmup = FALSE
+              AND s.reps > 0
+              AND s.weight_kg > 0
+            ORDER BY w.started_at ASC, s.set_number ASC
+            "#,
+        )
+        .bind(user_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+}
+
+/// Weighted working set with the time it was performed
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LiftSetRecord {
+    pub performed_at: DateTime<Utc>,
+    pub weight_kg: Decimal,
+    pub reps: i32,
+}
+
+/// Weighted working set from a recent session, with its effort rating
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SessionSetRecord {
+    pub workout_id: Uuid,
+    pub performed_at: DateTime<Utc>,
+    pub weight_kg: Decimal,
+    pub reps: i32,
+    pub rpe: Option<Decimal>,
+}
+
+// ============================================================================
+// Workout Templates
+// ============================================================================
+
+/// Workout template record
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct WorkoutTemplateRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub workout_type: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Exercise in a workout template, with its name
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct WorkoutTemplateExerciseRecord {
+    pub id: Uuid,
+    pub template_id: Uuid,
+    pub exercise_id: Uuid,
+    pub exercise_name: String,
+    pub target_sets: i32,
+    pub target_reps: i32,
+    pub target_weight_kg: Option<Decimal>,
+    pub sort_order: i32,
+    pub notes: Option<String>,
+}
+
+/// Prescription for one exercise in a template
+#[derive(Debug, Clone)]
+pub struct CreateWorkoutTemplateExercise {
+    pub exercise_id: Uuid,
+    pub target_sets: i32,
+    pub target_reps: i32,
+    pub target_weight_kg: Option<Decimal>,
+    pub notes: Option<String>,
+}
+
+/// Input for creating or replacing a workout template
+#[derive(Debug, Clone)]
+pub struct CreateWorkoutTemplate {
+    pub user_id: Uuid,
+    pub name: String,
+    pub workout_type: String,
+    pub notes: Option<String>,
+    /// Exercises in display order
+    pub exercises: Vec<CreateWorkoutTemplateExercise>,
+}
+
+/// Workout template repository
+pub struct WorkoutTemplateRepository;
+
+impl WorkoutTemplateRepository {
+    /// Create a template and its exercises atomically
+    pub async fn create(pool: &PgPool, input: CreateWorkoutTemplate) -> Result<WorkoutTemplateRecord> {
+        let mut tx = pool.begin().await?;
+
+        let template = sqlx::query_as::<_, WorkoutTemplateRecord>(
+            r#"
+            INSERT INTO workout_templates (user_id, name, workout_type, notes)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, user_id, name, workout_type, notes, created_at, updated_at
+            "#,
+        )
+        .bind(input.user_id)
+        .bind(&input.name)
+        .bind(&input.workout_type)
+        .bind(&input.notes)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::insert_exercises(&mut tx, template.id, &input.exercises).await?;
+
+        tx.commit().await?;
+
+        Ok(template)
+    }
+
+    /// Replace a template's details and exercises, returning None if the user doesn't own it
+    pub async fn replace(
+        pool: &PgPool,
+        id: Uuid,
+        input: CreateWorkoutTemplate,
+    ) -> Result<Option<WorkoutTemplateRecord>> {
+        let mut tx = pool.begin().await?;
+
+        let template = sqlx::query_as::<_, WorkoutTemplateRecord>(
+            r#"
+            UPDATE workout_templates SET
+                name = $3,
+                workout_type = $4,
+                notes = $5,
+                updated_at = NOW()
+            WHERE id = $1 AND user_id = $2
+            RETURNING id, user_id, name, workout_type, notes, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(input.user_id)
+        .bind(&input.name)
+        .bind(&input.workout_type)
+        .bind(&input.notes)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(template) = template else {
+            return Ok(None);
+        };
+
+        sqlx::query(r#"DELETE FROM template_exercises WHERE template_id = $1"#)
+            .bind(template.id)
+            .execute(&mut *tx)
+            .await?;
+        Self::insert_exercises(&mut tx, template.id, &input.exercises).await?;
+
+        tx.commit().await?;
+
+        Ok(Some(template))
+    }
+
+    async fn insert_exercises(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        template_id: Uuid,
+        exercises: &[CreateWorkoutTemplateExercise],
+    ) -> Result<()> {
+        for (sort_order, exercise) in exercises.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO template_exercises (template_id, exercise_id, target_sets,
+                                               target_reps, target_weight_kg, sort_order, notes)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(template_id)
+            .bind(exercise.exercise_id)
+            .bind(exercise.target_sets)
+            .bind(exercise.target_reps)
+            .bind(exercise.target_weight_kg)
+            .bind(sort_order as i32)
+            .bind(&exercise.notes)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Find template by ID and user (for ownership check)
+    pub async fn find_by_id_and_user(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<WorkoutTemplateRecord>> {
+        let template = sqlx::query_as::<_, WorkoutTemplateRecord>(
+            r#"
+            SELECT id, user_id, name, workout_type, notes, created_at, updated_at
+            FROM workout_templates
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(template)
+    }
+
+    /// Get all templates for a user
+    pub async fn get_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec<WorkoutTemplateRecord>> {
+        let templates = sqlx::query_as::<_, WorkoutTemplateRecord>(
+            r#"
+            SELECT id, user_id, name, workout_type, notes, created_at, updated_at
+            FROM workout_templates
+            WHERE user_id = $1
+            ORDER BY name ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(templates)
+    }
+
+    /// Get the exercises of a template in order
+    pub async fn get_exercises(
+        pool: &PgPool,
+        template_id: Uuid,
+    ) -> Result<Vec<WorkoutTemplateExerciseRecord>> {
+        let exercises = sqlx::query_as::<_, WorkoutTemplateExerciseRecord>(
+            r#"
+            SELECT te.id, te.template_id, te.exercise_id, e.name AS exercise_name,
+                   te.target_sets, te.target_reps, te.target_weight_kg, te.sort_order, te.notes
+            FROM template_exercises te
+            JOIN exercises e ON e.id = te.exercise_id
+            WHERE te.template_id = $1
+            ORDER BY te.sort_order ASC
+            "#,
+        )
+        .bind(template_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(exercises)
+    }
+
+    /// Delete a template
+    pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            r#"DELETE FROM workout_templates WHERE id = $1 AND user_id = $2"#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
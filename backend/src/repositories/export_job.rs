@@ -0,0 +1,163 @@
+//! Export job repository for database operations
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Export job record from database
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ExportJobRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: String,
+    pub include: Option<String>,
+    pub range_start: Option<DateTime<Utc>>,
+    pub range_end: Option<DateTime<Utc>>,
+    pub file_path: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Export job repository
+pub struct ExportJobRepository;
+
+impl ExportJobRepository {
+    /// Queue a new export job
+    pub async fn create(
+        pool: &PgPool,
+        user_id: Uuid,
+        include: Option<&str>,
+        range_start: Option<DateTime<Utc>>,
+        range_end: Option<DateTime<Utc>>,
+    ) -> Result<ExportJobRecord> {
+        let record = sqlx::query_as::<_, ExportJobRecord>(
+            r#"
+            INSERT INTO export_jobs (user_id, include, range_start, range_end)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, user_id, status, include, range_start, range_end, file_path, error,
+                      created_at, started_at, completed_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(include)
+        .bind(range_start)
+        .bind(range_end)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Find one of a user's export jobs
+    pub async fn find_for_user(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ExportJobRecord>> {
+        let record = sqlx::query_as::<_, ExportJobRecord>(
+            r#"
+            SELECT id, user_id, status, include, range_start, range_end, file_path, error,
+                   created_at, started_at, completed_at
+            FROM export_jobs
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Mark the oldest pending job as running and return it
+    ///
+    /// Jobs left running for longer than `stale_after_secs` were abandoned by
+    /// a worker that stopped, and are claimed again. Locked rows are skipped,
+    /// so several workers never claim the same job.
+    pub async fn claim_next_pending(
+        pool: &PgPool,
+        stale_after_secs: f64,
+    ) -> Result<Option<ExportJobRecord>> {
+        let record = sqlx::query_as::<_, ExportJobRecord>(
+            r#"
+            UPDATE export_jobs
+            SET status = 'running', started_at = NOW()
+            WHERE id = (
+                SELECT id FROM export_jobs
+                WHERE status = 'pending'
+                   OR (status = 'running' AND started_at < NOW() - make_interval(secs => $1))
+                ORDER BY created_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, user_id, status, include, range_start, range_end, file_path, error,
+                      created_at, started_at, completed_at
+            "#,
+        )
+        .bind(stale_after_secs)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Record where a finished job's export was written
+    ///
+    /// Returns false when the job no longer exists, e.g. because its user
+    /// deleted their account while it ran.
+    pub async fn mark_complete(pool: &PgPool, id: Uuid, file_path: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE export_jobs
+            SET status = 'complete', file_path = $2, completed_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(file_path)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record why a job failed
+    pub async fn mark_failed(pool: &PgPool, id: Uuid, error: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE export_jobs
+            SET status = 'failed', error = $2, completed_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete jobs that finished before `cutoff`, returning their file paths
+    pub async fn delete_finished_before(
+        pool: &PgPool,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Option<String>>> {
+        let paths = sqlx::query_scalar::<_, Option<String>>(
+            r#"
+            DELETE FROM export_jobs
+            WHERE status IN ('complete', 'failed') AND completed_at < $1
+            RETURNING file_path
+            "#,
+        )
+        .bind(cutoff)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(paths)
+    }
+}
@@ -1,7 +1,9 @@
 //! Hydration repository for database operations
 
+use crate::timezone::local_day_bounds;
 use anyhow::Result;
 use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -19,6 +21,7 @@ pub struct HydrationLogRecord {
     pub consumed_at: DateTime<Utc>,
     pub source: String,
     pub notes: Option<String>,
+    pub caffeine_mg: Option<i32>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -31,6 +34,7 @@ pub struct CreateHydrationLog {
     pub consumed_at: DateTime<Utc>,
     pub source: String,
     pub notes: Option<String>,
+    pub caffeine_mg: Option<i32>,
 }
 
 /// Daily hydration summary
@@ -38,6 +42,7 @@ pub struct CreateHydrationLog {
 pub struct DailyHydrationSummary {
     pub date: NaiveDate,
     pub total_ml: i64,
+    pub total_caffeine_mg: i64,
     pub entry_count: i64,
     pub first_entry: Option<DateTime<Utc>>,
     pub last_entry: Option<DateTime<Utc>>,
@@ -51,9 +56,9 @@ impl HydrationLogRepository {
     pub async fn create(pool: &PgPool, input: CreateHydrationLog) -> Result<HydrationLogRecord> {
         let record = sqlx::query_as::<_, HydrationLogRecord>(
             r#"
-            INSERT INTO hydration_logs (user_id, amount_ml, beverage_type, consumed_at, source, notes)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, user_id, amount_ml, beverage_type, consumed_at, source, notes, created_at
+            INSERT INTO hydration_logs (user_id, amount_ml, beverage_type, consumed_at, source, notes, caffeine_mg)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, user_id, amount_ml, beverage_type, consumed_at, source, notes, caffeine_mg, created_at
             "#,
         )
         .bind(input.user_id)
@@ -62,84 +67,100 @@ impl HydrationLogRepository {
         .bind(input.consumed_at)
         .bind(&input.source)
         .bind(&input.notes)
+        .bind(input.caffeine_mg)
         .fetch_one(pool)
         .await?;
 
         Ok(record)
     }
 
-    /// Get hydration logs for a specific date
+    /// Get hydration logs for a specific local date
     pub async fn get_by_date(
         pool: &PgPool,
         user_id: Uuid,
         date: NaiveDate,
+        tz: Tz,
     ) -> Result<Vec<HydrationLogRecord>> {
+        let (start, end) = local_day_bounds(date, tz);
         let records = sqlx::query_as::<_, HydrationLogRecord>(
             r#"
-            SELECT id, user_id, amount_ml, beverage_type, consumed_at, source, notes, created_at
+            SELECT id, user_id, amount_ml, beverage_type, consumed_at, source, notes, caffeine_mg, created_at
             FROM hydration_logs
-            WHERE user_id = $1 AND DATE(consumed_at) = $2
+            WHERE user_id = $1 AND consumed_at >= $2 AND consumed_at < $3
             ORDER BY consumed_at ASC
             "#,
         )
         .bind(user_id)
-        .bind(date)
+        .bind(start)
+        .bind(end)
         .fetch_all(pool)
         .await?;
 
         Ok(records)
     }
 
-    /// Get daily summary for a specific date
+    /// Get daily summary for a specific local date
     pub async fn get_daily_summary(
         pool: &PgPool,
         user_id: Uuid,
         date: NaiveDate,
+        tz: Tz,
     ) -> Result<DailyHydrationSummary> {
+        let (start, end) = local_day_bounds(date, tz);
         let summary = sqlx::query_as::<_, DailyHydrationSummary>(
             r#"
             SELECT 
                 $2::date as date,
                 COALESCE(SUM(amount_ml), 0)::bigint as total_ml,
+                COALESCE(SUM(caffeine_mg), 0)::bigint as total_caffeine_mg,
                 COUNT(*)::bigint as entry_count,
                 MIN(consumed_at) as first_entry,
                 MAX(consumed_at) as last_entry
             FROM hydration_logs
-            WHERE user_id = $1 AND DATE(consumed_at) = $2
+            WHERE user_id = $1 AND consumed_at >= $3 AND consumed_at < $4
             "#,
         )
         .bind(user_id)
         .bind(date)
+        .bind(start)
+        .bind(end)
         .fetch_one(pool)
         .await?;
 
         Ok(summary)
     }
 
-    /// Get daily summaries for a date range
+    /// Get daily summaries for a range of local dates
+    ///
+    /// Entries are grouped by their date in `tz`.
     pub async fn get_daily_summaries(
         pool: &PgPool,
         user_id: Uuid,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        tz: Tz,
     ) -> Result<Vec<DailyHydrationSummary>> {
+        let (start, _) = local_day_bounds(start_date, tz);
+        let (_, end) = local_day_bounds(end_date, tz);
         let summaries = sqlx::query_as::<_, DailyHydrationSummary>(
             r#"
             SELECT 
-                DATE(consumed_at) as date,
+                DATE(consumed_at AT TIME ZONE $4) as date,
                 SUM(amount_ml)::bigint as total_ml,
+                COALESCE(SUM(caffeine_mg), 0)::bigint as total_caffeine_mg,
                 COUNT(*)::bigint as entry_count,
                 MIN(consumed_at) as first_entry,
                 MAX(consumed_at) as last_entry
             FROM hydration_logs
-            WHERE user_id = $1 AND DATE(consumed_at) >= $2 AND DATE(consumed_at) <= $3
-            GROUP BY DATE(consumed_at)
+            WHERE user_id = $1 AND consumed_at >= $2 AND consumed_at < $3
+            GROUP BY DATE(consumed_at AT TIME ZONE $4)
             ORDER BY date DESC
             "#,
         )
         .bind(user_id)
-        .bind(start_date)
-        .bind(end_date)
+        .bind(start)
+        .bind(end)
+        .bind(tz.name())
         .fetch_all(pool)
         .await?;
 
@@ -245,4 +266,43 @@ impl HydrationGoalRepository {
 
         Ok(record)
     }
+
+    /// Users whose goal is recalculated from their weight
+    pub async fn list_auto_calculated_users(pool: &PgPool) -> Result<Vec<Uuid>> {
+        let user_ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT user_id
+            FROM hydration_goals
+            WHERE is_auto_calculated
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    /// Replace an auto-calculated goal's daily amount
+    ///
+    /// Returns false if the goal is unchanged or was switched to manual in
+    /// the meantime.
+    pub async fn update_auto_calculated_goal(
+        pool: &PgPool,
+        user_id: Uuid,
+        daily_goal_ml: i32,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE hydration_goals
+            SET daily_goal_ml = $2, updated_at = NOW()
+            WHERE user_id = $1 AND is_auto_calculated AND daily_goal_ml <> $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(daily_goal_ml)
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
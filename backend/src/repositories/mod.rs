@@ -2,9 +2,12 @@
 //!
 //! Provides data access layer for database operations.
 
+pub mod audit;
 pub mod biometrics;
 pub mod biomarkers;
+pub mod cycle;
 pub mod exercise;
+pub mod export_job;
 pub mod goals;
 pub mod hydration;
 pub mod nutrition;
@@ -12,21 +15,27 @@ pub mod sleep;
 pub mod user;
 pub mod weight;
 
+pub use audit::{AuditLogRecord, AuditLogRepository};
 pub use biometrics::{
-    CreateHeartRateLog, CreateHrvLog, HeartRateLogRecord, HeartRateLogRepository,
+    CreateHeartRateLog, CreateHeartRateStream, CreateHrvLog, HeartRateLogRecord,
+    HeartRateLogRepository, HeartRateStreamRecord, HeartRateStreamRepository,
     HeartRateZonesRecord, HeartRateZonesRepository, HrvLogRecord, HrvLogRepository,
-    UpsertHeartRateZones,
+    UpdateHrvLog, UpsertHeartRateZones,
 };
 pub use biomarkers::{
     BiomarkerLogRepository, BiomarkerLogWithRange, BiomarkerRangeRecord, BiomarkerRangeRepository,
     CreateBiomarkerLog, CreateSupplement, CreateSupplementLog, SupplementLogRepository,
     SupplementRecord, SupplementRepository,
 };
+pub use cycle::{CycleRepository, CycleSettingsRecord, UpsertCycleSettings};
 pub use exercise::{
-    AddWorkoutExercise, CreateExercise, CreateExerciseSet, CreateWorkout, ExerciseRecord,
-    ExerciseRepository, ExerciseSetRecord, ExerciseSetRepository, WorkoutExerciseRecord,
-    WorkoutExerciseRepository, WorkoutRecord, WorkoutRepository,
+    AddWorkoutExercise, CreateExercise, CreateExerciseSet, CreateWorkout, CreateWorkoutTemplate,
+    CreateWorkoutTemplateExercise, ExerciseRecord, ExerciseRepository, ExerciseSetRecord,
+    ExerciseSetRepository, LiftSetRecord, SessionSetRecord, WorkoutExerciseRecord,
+    WorkoutExerciseRepository, WorkoutRecord, WorkoutRepository, WorkoutTemplateExerciseRecord,
+    WorkoutTemplateRecord, WorkoutTemplateRepository,
 };
+pub use export_job::{ExportJobRecord, ExportJobRepository};
 pub use goals::{
     CreateGoal, CreateMilestone, GoalRecord, GoalRepository, MilestoneRecord,
     MilestoneRepository, UpdateGoal,
@@ -36,15 +45,17 @@ pub use hydration::{
     HydrationLogRecord, HydrationLogRepository, UpsertHydrationGoal,
 };
 pub use nutrition::{
-    AddRecipeIngredient, CreateFoodItem, CreateFoodLog, CreateRecipe, DailyNutritionSummary,
-    FoodItem, FoodItemRepository, FoodLog, FoodLogRepository, Recipe, RecipeIngredient,
-    RecipeRepository,
+    net_carbs, AddRecipeIngredient, CreateFoodItem, CreateFoodLog, CreateMealTemplate,
+    CreateRecipe, DailyNutritionSummary, FavoriteFoodRepository, FoodItem, FoodItemRepository,
+    FoodLog, FoodLogExportRecord, FoodLogRepository, MealTemplate, MealTemplateItem,
+    MealTemplateRepository, Recipe, RecipeIngredient, RecipeRepository, UpdateFoodItem,
 };
 pub use sleep::{
     CreateSleepLog, SleepGoalRecord, SleepGoalRepository, SleepLogRecord, SleepLogRepository,
     SleepSummary, UpsertSleepGoal,
 };
-pub use user::{UpdateUserSettings, UserRepository};
+pub use user::{UpdateUserSettings, UserRecord, UserRepository};
 pub use weight::{
-    BodyCompositionRepository, CreateBodyCompositionLog, CreateWeightLog, WeightRepository,
+    BodyCompositionLogRecord, BodyCompositionRepository, CreateBodyCompositionLog,
+    CreateWeightLog, WeightLogRecord, WeightRepository,
 };
@@ -1,13 +1,22 @@
 //! Nutrition repository - database operations for food items and logs
 
+use crate::db::timing::QueryTimingExt;
+use crate::timezone::local_day_bounds;
 use anyhow::Result;
 use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use std::collections::HashSet;
 use uuid::Uuid;
 
+/// Rows per INSERT when bulk creating food items, keeping well under
+/// Postgres' bind parameter limit
+const FOOD_ITEMS_PER_INSERT: usize = 1000;
+
 /// Food item from the database
-#[derive(Debug, Clone, sqlx::FromRow)]
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
 pub struct FoodItem {
     pub id: Uuid,
     pub name: String,
@@ -24,6 +33,7 @@ pub struct FoodItem {
     pub sodium_mg: Option<Decimal>,
     pub potassium_mg: Option<Decimal>,
     pub cholesterol_mg: Option<Decimal>,
+    pub alcohol_g: Decimal,
     pub source: String,
     pub verified: bool,
     pub created_by: Option<Uuid>,
@@ -44,6 +54,9 @@ pub struct FoodLog {
     pub carbohydrates_g: Decimal,
     pub fat_g: Decimal,
     pub fiber_g: Decimal,
+    pub sugar_g: Decimal,
+    pub sodium_mg: Decimal,
+    pub alcohol_g: Decimal,
     pub meal_type: String,
     pub logged_at: DateTime<Utc>,
     pub consumed_at: DateTime<Utc>,
@@ -51,6 +64,35 @@ pub struct FoodLog {
     pub created_at: DateTime<Utc>,
 }
 
+impl FoodLog {
+    /// Net carbs for this entry
+    pub fn net_carbs_g(&self) -> Decimal {
+        net_carbs(self.carbohydrates_g, self.fiber_g)
+    }
+}
+
+/// Net carbs (total carbs minus fiber), floored at zero
+///
+/// Fiber can exceed carbs on some labels (e.g. fiber supplements), which
+/// would otherwise give negative net carbs.
+pub fn net_carbs(carbs_g: Decimal, fiber_g: Decimal) -> Decimal {
+    (carbs_g - fiber_g).max(Decimal::ZERO)
+}
+
+/// Food log with its food name, as exported
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct FoodLogExportRecord {
+    pub consumed_at: DateTime<Utc>,
+    pub meal_type: String,
+    pub food_name: String,
+    pub servings: Decimal,
+    pub calories: Decimal,
+    pub protein_g: Decimal,
+    pub carbohydrates_g: Decimal,
+    pub fat_g: Decimal,
+    pub fiber_g: Decimal,
+}
+
 /// Input for creating a new food item
 #[derive(Debug, Clone)]
 pub struct CreateFoodItem {
@@ -66,10 +108,28 @@ pub struct CreateFoodItem {
     pub fiber_g: Decimal,
     pub sugar_g: Decimal,
     pub sodium_mg: Option<Decimal>,
+    pub alcohol_g: Decimal,
     pub source: String,
     pub created_by: Option<Uuid>,
 }
 
+/// Input for editing a food item; `None` leaves a field unchanged
+#[derive(Debug, Clone, Default)]
+pub struct UpdateFoodItem {
+    pub name: Option<String>,
+    pub brand: Option<String>,
+    pub serving_size: Option<Decimal>,
+    pub serving_unit: Option<String>,
+    pub calories: Option<Decimal>,
+    pub protein_g: Option<Decimal>,
+    pub carbohydrates_g: Option<Decimal>,
+    pub fat_g: Option<Decimal>,
+    pub fiber_g: Option<Decimal>,
+    pub sugar_g: Option<Decimal>,
+    pub sodium_mg: Option<Decimal>,
+    pub alcohol_g: Option<Decimal>,
+}
+
 /// Input for logging food
 #[derive(Debug, Clone)]
 pub struct CreateFoodLog {
@@ -82,6 +142,9 @@ pub struct CreateFoodLog {
     pub carbohydrates_g: Decimal,
     pub fat_g: Decimal,
     pub fiber_g: Decimal,
+    pub sugar_g: Decimal,
+    pub sodium_mg: Decimal,
+    pub alcohol_g: Decimal,
     pub meal_type: String,
     pub consumed_at: DateTime<Utc>,
     pub notes: Option<String>,
@@ -97,7 +160,7 @@ impl FoodItemRepository {
             r#"
             SELECT id, name, brand, barcode, serving_size, serving_unit,
                    calories, protein_g, carbohydrates_g, fat_g, fiber_g, sugar_g,
-                   sodium_mg, potassium_mg, cholesterol_mg, source, verified,
+                   sodium_mg, potassium_mg, cholesterol_mg, alcohol_g, source, verified,
                    created_by, created_at, updated_at
             FROM food_items
             WHERE to_tsvector('english', name || ' ' || COALESCE(brand, '')) 
@@ -114,6 +177,7 @@ impl FoodItemRepository {
         .bind(format!("%{}%", query))
         .bind(limit)
         .fetch_all(db)
+        .timed("FoodItemRepository::search")
         .await?;
 
         Ok(items)
@@ -125,7 +189,7 @@ impl FoodItemRepository {
             r#"
             SELECT id, name, brand, barcode, serving_size, serving_unit,
                    calories, protein_g, carbohydrates_g, fat_g, fiber_g, sugar_g,
-                   sodium_mg, potassium_mg, cholesterol_mg, source, verified,
+                   sodium_mg, potassium_mg, cholesterol_mg, alcohol_g, source, verified,
                    created_by, created_at, updated_at
             FROM food_items
             WHERE barcode = $1
@@ -133,6 +197,7 @@ impl FoodItemRepository {
         )
         .bind(barcode)
         .fetch_optional(db)
+        .timed("FoodItemRepository::find_by_barcode")
         .await?;
 
         Ok(item)
@@ -145,7 +210,7 @@ impl FoodItemRepository {
             r#"
             SELECT id, name, brand, barcode, serving_size, serving_unit,
                    calories, protein_g, carbohydrates_g, fat_g, fiber_g, sugar_g,
-                   sodium_mg, potassium_mg, cholesterol_mg, source, verified,
+                   sodium_mg, potassium_mg, cholesterol_mg, alcohol_g, source, verified,
                    created_by, created_at, updated_at
             FROM food_items
             WHERE id = $1
@@ -153,11 +218,32 @@ impl FoodItemRepository {
         )
         .bind(id)
         .fetch_optional(db)
+        .timed("FoodItemRepository::find_by_id")
         .await?;
 
         Ok(item)
     }
 
+    /// Find the food items with the given IDs, in no particular order
+    pub async fn find_by_ids(db: &PgPool, ids: &[Uuid]) -> Result<Vec<FoodItem>> {
+        let items = sqlx::query_as::<_, FoodItem>(
+            r#"
+            SELECT id, name, brand, barcode, serving_size, serving_unit,
+                   calories, protein_g, carbohydrates_g, fat_g, fiber_g, sugar_g,
+                   sodium_mg, potassium_mg, cholesterol_mg, alcohol_g, source, verified,
+                   created_by, created_at, updated_at
+            FROM food_items
+            WHERE id = ANY($1)
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(db)
+        .timed("FoodItemRepository::find_by_ids")
+        .await?;
+
+        Ok(items)
+    }
+
     /// Create a new food item
     pub async fn create(db: &PgPool, input: CreateFoodItem) -> Result<FoodItem> {
         let item = sqlx::query_as::<_, FoodItem>(
@@ -165,12 +251,12 @@ impl FoodItemRepository {
             INSERT INTO food_items (
                 name, brand, barcode, serving_size, serving_unit,
                 calories, protein_g, carbohydrates_g, fat_g, fiber_g, sugar_g,
-                sodium_mg, source, created_by
+                sodium_mg, alcohol_g, source, created_by
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             RETURNING id, name, brand, barcode, serving_size, serving_unit,
                       calories, protein_g, carbohydrates_g, fat_g, fiber_g, sugar_g,
-                      sodium_mg, potassium_mg, cholesterol_mg, source, verified,
+                      sodium_mg, potassium_mg, cholesterol_mg, alcohol_g, source, verified,
                       created_by, created_at, updated_at
             "#,
         )
@@ -186,13 +272,129 @@ impl FoodItemRepository {
         .bind(input.fiber_g)
         .bind(input.sugar_g)
         .bind(input.sodium_mg)
+        .bind(input.alcohol_g)
         .bind(&input.source)
         .bind(input.created_by)
         .fetch_one(db)
+        .timed("FoodItemRepository::create")
         .await?;
 
         Ok(item)
     }
+
+    /// Update a food item's details
+    ///
+    /// Food logs keep the nutrition computed when they were logged, so
+    /// existing logs are unaffected.
+    pub async fn update(db: &PgPool, id: Uuid, updates: UpdateFoodItem) -> Result<Option<FoodItem>> {
+        let item = sqlx::query_as::<_, FoodItem>(
+            r#"
+            UPDATE food_items SET
+                name = COALESCE($2, name),
+                brand = COALESCE($3, brand),
+                serving_size = COALESCE($4, serving_size),
+                serving_unit = COALESCE($5, serving_unit),
+                calories = COALESCE($6, calories),
+                protein_g = COALESCE($7, protein_g),
+                carbohydrates_g = COALESCE($8, carbohydrates_g),
+                fat_g = COALESCE($9, fat_g),
+                fiber_g = COALESCE($10, fiber_g),
+                sugar_g = COALESCE($11, sugar_g),
+                alcohol_g = COALESCE($12, alcohol_g),
+                sodium_mg = COALESCE($13, sodium_mg),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, name, brand, barcode, serving_size, serving_unit,
+                      calories, protein_g, carbohydrates_g, fat_g, fiber_g, sugar_g,
+                      sodium_mg, potassium_mg, cholesterol_mg, alcohol_g, source, verified,
+                      created_by, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(&updates.name)
+        .bind(&updates.brand)
+        .bind(updates.serving_size)
+        .bind(&updates.serving_unit)
+        .bind(updates.calories)
+        .bind(updates.protein_g)
+        .bind(updates.carbohydrates_g)
+        .bind(updates.fat_g)
+        .bind(updates.fiber_g)
+        .bind(updates.sugar_g)
+        .bind(updates.alcohol_g)
+        .bind(updates.sodium_mg)
+        .fetch_optional(db)
+        .timed("FoodItemRepository::update")
+        .await?;
+
+        Ok(item)
+    }
+
+    /// Create many food items with multi-row inserts
+    ///
+    /// Barcodes repeated within `items` keep only their first item, and items
+    /// whose barcode is already stored are skipped. Returns the number inserted.
+    pub async fn create_many(db: &PgPool, items: Vec<CreateFoodItem>) -> Result<u64> {
+        let items = dedupe_by_barcode(items);
+        if items.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx = db.begin().await?;
+        let mut inserted = 0;
+
+        for chunk in items.chunks(FOOD_ITEMS_PER_INSERT) {
+            let mut query: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO food_items (
+                    name, brand, barcode, serving_size, serving_unit,
+                    calories, protein_g, carbohydrates_g, fat_g, fiber_g, sugar_g,
+                    sodium_mg, alcohol_g, source, created_by
+                ) ",
+            );
+            query.push_values(chunk, |mut row, input| {
+                row.push_bind(&input.name)
+                    .push_bind(&input.brand)
+                    .push_bind(&input.barcode)
+                    .push_bind(input.serving_size)
+                    .push_bind(&input.serving_unit)
+                    .push_bind(input.calories)
+                    .push_bind(input.protein_g)
+                    .push_bind(input.carbohydrates_g)
+                    .push_bind(input.fat_g)
+                    .push_bind(input.fiber_g)
+                    .push_bind(input.sugar_g)
+                    .push_bind(input.sodium_mg)
+                    .push_bind(input.alcohol_g)
+                    .push_bind(&input.source)
+                    .push_bind(input.created_by);
+            });
+            query.push(" ON CONFLICT (barcode) WHERE barcode IS NOT NULL DO NOTHING");
+
+            inserted += query
+                .build()
+                .execute(&mut *tx)
+                .timed("FoodItemRepository::create_many")
+                .await?
+                .rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(inserted)
+    }
+}
+
+/// Drop items whose barcode already appeared earlier in the batch
+///
+/// Items without a barcode are always kept.
+fn dedupe_by_barcode(items: Vec<CreateFoodItem>) -> Vec<CreateFoodItem> {
+    let mut seen = HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| match &item.barcode {
+            Some(barcode) => seen.insert(barcode.clone()),
+            None => true,
+        })
+        .collect()
 }
 
 /// Food log repository
@@ -206,12 +408,12 @@ impl FoodLogRepository {
             INSERT INTO food_logs (
                 user_id, food_item_id, custom_name, servings,
                 calories, protein_g, carbohydrates_g, fat_g, fiber_g,
-                meal_type, consumed_at, notes
+                sugar_g, sodium_mg, alcohol_g, meal_type, consumed_at, notes
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             RETURNING id, user_id, food_item_id, custom_name, servings,
                       calories, protein_g, carbohydrates_g, fat_g, fiber_g,
-                      meal_type, logged_at, consumed_at, notes, created_at
+                      sugar_g, sodium_mg, alcohol_g, meal_type, logged_at, consumed_at, notes, created_at
             "#,
         )
         .bind(input.user_id)
@@ -223,51 +425,109 @@ impl FoodLogRepository {
         .bind(input.carbohydrates_g)
         .bind(input.fat_g)
         .bind(input.fiber_g)
+        .bind(input.sugar_g)
+        .bind(input.sodium_mg)
+        .bind(input.alcohol_g)
         .bind(&input.meal_type)
         .bind(input.consumed_at)
         .bind(&input.notes)
         .fetch_one(db)
+        .timed("FoodLogRepository::create")
         .await?;
 
         Ok(log)
     }
 
-    /// Get food logs for a user on a specific date
-    pub async fn get_by_date(db: &PgPool, user_id: Uuid, date: NaiveDate) -> Result<Vec<FoodLog>> {
+    /// Log several food entries atomically
+    pub async fn create_many(db: &PgPool, inputs: Vec<CreateFoodLog>) -> Result<Vec<FoodLog>> {
+        let mut tx = db.begin().await?;
+        let mut logs = Vec::with_capacity(inputs.len());
+
+        for input in inputs {
+            let log = sqlx::query_as::<_, FoodLog>(
+                r#"
+                INSERT INTO food_logs (
+                    user_id, food_item_id, custom_name, servings,
+                    calories, protein_g, carbohydrates_g, fat_g, fiber_g,
+                    sugar_g, sodium_mg, alcohol_g, meal_type, consumed_at, notes
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                RETURNING id, user_id, food_item_id, custom_name, servings,
+                          calories, protein_g, carbohydrates_g, fat_g, fiber_g,
+                          sugar_g, sodium_mg, alcohol_g, meal_type, logged_at, consumed_at, notes, created_at
+                "#,
+            )
+            .bind(input.user_id)
+            .bind(input.food_item_id)
+            .bind(&input.custom_name)
+            .bind(input.servings)
+            .bind(input.calories)
+            .bind(input.protein_g)
+            .bind(input.carbohydrates_g)
+            .bind(input.fat_g)
+            .bind(input.fiber_g)
+            .bind(input.sugar_g)
+            .bind(input.sodium_mg)
+            .bind(input.alcohol_g)
+            .bind(&input.meal_type)
+            .bind(input.consumed_at)
+            .bind(&input.notes)
+            .fetch_one(&mut *tx)
+            .timed("FoodLogRepository::create_many")
+            .await?;
+            logs.push(log);
+        }
+
+        tx.commit().await?;
+
+        Ok(logs)
+    }
+
+    /// Get food logs for a user on a specific local date
+    pub async fn get_by_date(
+        db: &PgPool,
+        user_id: Uuid,
+        date: NaiveDate,
+        tz: Tz,
+    ) -> Result<Vec<FoodLog>> {
+        let (start, end) = local_day_bounds(date, tz);
         let logs = sqlx::query_as::<_, FoodLog>(
             r#"
             SELECT id, user_id, food_item_id, custom_name, servings,
                    calories, protein_g, carbohydrates_g, fat_g, fiber_g,
-                   meal_type, logged_at, consumed_at, notes, created_at
+                   sugar_g, sodium_mg, alcohol_g, meal_type, logged_at, consumed_at, notes, created_at
             FROM food_logs
-            WHERE user_id = $1 AND DATE(consumed_at) = $2
+            WHERE user_id = $1 AND consumed_at >= $2 AND consumed_at < $3 AND deleted_at IS NULL
             ORDER BY consumed_at ASC
             "#,
         )
         .bind(user_id)
-        .bind(date)
+        .bind(start)
+        .bind(end)
         .fetch_all(db)
+        .timed("FoodLogRepository::get_by_date")
         .await?;
 
         Ok(logs)
     }
 
-    /// Get food logs for a date range
+    /// Get food logs for a range of local dates
     pub async fn get_by_date_range(
         db: &PgPool,
         user_id: Uuid,
-        start: NaiveDate,
-        end: NaiveDate,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        tz: Tz,
     ) -> Result<Vec<FoodLog>> {
+        let (start, _) = local_day_bounds(start_date, tz);
+        let (_, end) = local_day_bounds(end_date, tz);
         let logs = sqlx::query_as::<_, FoodLog>(
             r#"
             SELECT id, user_id, food_item_id, custom_name, servings,
                    calories, protein_g, carbohydrates_g, fat_g, fiber_g,
-                   meal_type, logged_at, consumed_at, notes, created_at
+                   sugar_g, sodium_mg, alcohol_g, meal_type, logged_at, consumed_at, notes, created_at
             FROM food_logs
-            WHERE user_id = $1 
-              AND DATE(consumed_at) >= $2 
-              AND DATE(consumed_at) <= $3
+            WHERE user_id = $1 AND consumed_at >= $2 AND consumed_at < $3 AND deleted_at IS NULL
             ORDER BY consumed_at ASC
             "#,
         )
@@ -275,23 +535,146 @@ impl FoodLogRepository {
         .bind(start)
         .bind(end)
         .fetch_all(db)
+        .timed("FoodLogRepository::get_by_date_range")
+        .await?;
+
+        Ok(logs)
+    }
+
+    /// Get every food log for a user with its food name, oldest first, for export
+    pub async fn get_for_export(db: &PgPool, user_id: Uuid) -> Result<Vec<FoodLogExportRecord>> {
+        let logs = sqlx::query_as::<_, FoodLogExportRecord>(
+            r#"
+            SELECT fl.consumed_at, fl.meal_type,
+                   COALESCE(fl.custom_name, fi.name, 'Unknown food') AS food_name,
+                   fl.servings, fl.calories, fl.protein_g, fl.carbohydrates_g, fl.fat_g, fl.fiber_g
+            FROM food_logs fl
+            LEFT JOIN food_items fi ON fi.id = fl.food_item_id
+            WHERE fl.user_id = $1 AND fl.deleted_at IS NULL
+            ORDER BY fl.consumed_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(db)
+        .timed("FoodLogRepository::get_for_export")
         .await?;
 
         Ok(logs)
     }
 
-    /// Delete a food log entry
+    /// Get distinct food items the user has logged, most recently logged first
+    pub async fn get_recent_foods(
+        db: &PgPool,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<FoodItem>> {
+        let items = sqlx::query_as::<_, FoodItem>(
+            r#"
+            SELECT fi.id, fi.name, fi.brand, fi.barcode, fi.serving_size, fi.serving_unit,
+                   fi.calories, fi.protein_g, fi.carbohydrates_g, fi.fat_g, fi.fiber_g, fi.sugar_g,
+                   fi.sodium_mg, fi.potassium_mg, fi.cholesterol_mg, fi.alcohol_g, fi.source, fi.verified,
+                   fi.created_by, fi.created_at, fi.updated_at
+            FROM food_items fi
+            JOIN (
+                SELECT food_item_id, MAX(logged_at) AS last_logged_at
+                FROM food_logs
+                WHERE user_id = $1 AND food_item_id IS NOT NULL AND deleted_at IS NULL
+                GROUP BY food_item_id
+            ) recent ON recent.food_item_id = fi.id
+            ORDER BY recent.last_logged_at DESC, fi.name ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(db)
+        .timed("FoodLogRepository::get_recent_foods")
+        .await?;
+
+        Ok(items)
+    }
+
+    /// Soft-delete a food log entry
     pub async fn delete(db: &PgPool, user_id: Uuid, log_id: Uuid) -> Result<bool> {
         let result = sqlx::query(
-            "DELETE FROM food_logs WHERE id = $1 AND user_id = $2"
+            "UPDATE food_logs SET deleted_at = NOW() WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL"
         )
         .bind(log_id)
         .bind(user_id)
         .execute(db)
+        .timed("FoodLogRepository::delete")
         .await?;
 
         Ok(result.rows_affected() > 0)
     }
+
+    /// Permanently remove food logs soft-deleted before the cutoff
+    pub async fn purge_deleted_before(db: &PgPool, cutoff: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM food_logs WHERE deleted_at IS NOT NULL AND deleted_at < $1"
+        )
+        .bind(cutoff)
+        .execute(db)
+        .timed("FoodLogRepository::purge_deleted_before")
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Favorite food repository
+pub struct FavoriteFoodRepository;
+
+impl FavoriteFoodRepository {
+    /// Mark a food as a favorite, returning false if it already was one
+    pub async fn add(db: &PgPool, user_id: Uuid, food_item_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT INTO favorite_foods (user_id, food_item_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+        )
+        .bind(user_id)
+        .bind(food_item_id)
+        .execute(db)
+        .timed("FavoriteFoodRepository::add")
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Unmark a favorite food, returning false if it was not one
+    pub async fn remove(db: &PgPool, user_id: Uuid, food_item_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            "DELETE FROM favorite_foods WHERE user_id = $1 AND food_item_id = $2"
+        )
+        .bind(user_id)
+        .bind(food_item_id)
+        .execute(db)
+        .timed("FavoriteFoodRepository::remove")
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Get a user's favorite food items, most recently favorited first
+    pub async fn get_by_user(db: &PgPool, user_id: Uuid) -> Result<Vec<FoodItem>> {
+        let items = sqlx::query_as::<_, FoodItem>(
+            r#"
+            SELECT fi.id, fi.name, fi.brand, fi.barcode, fi.serving_size, fi.serving_unit,
+                   fi.calories, fi.protein_g, fi.carbohydrates_g, fi.fat_g, fi.fiber_g, fi.sugar_g,
+                   fi.sodium_mg, fi.potassium_mg, fi.cholesterol_mg, fi.alcohol_g, fi.source, fi.verified,
+                   fi.created_by, fi.created_at, fi.updated_at
+            FROM favorite_foods ff
+            JOIN food_items fi ON fi.id = ff.food_item_id
+            WHERE ff.user_id = $1
+            ORDER BY ff.created_at DESC, fi.name ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(db)
+        .timed("FavoriteFoodRepository::get_by_user")
+        .await?;
+
+        Ok(items)
+    }
 }
 
 /// Daily nutrition summary
@@ -303,17 +686,38 @@ pub struct DailyNutritionSummary {
     pub total_carbs_g: Decimal,
     pub total_fat_g: Decimal,
     pub total_fiber_g: Decimal,
+    pub total_sugar_g: Decimal,
+    pub total_sodium_mg: Decimal,
+    /// Sum of each entry's net carbs, so fiber never offsets another food's carbs
+    pub total_net_carbs_g: Decimal,
+    pub total_alcohol_g: Decimal,
     pub meal_count: i64,
 }
 
+/// Summed nutrients and entry count for a day
+type DailyTotalsRow = (
+    Decimal,
+    Decimal,
+    Decimal,
+    Decimal,
+    Decimal,
+    Decimal,
+    Decimal,
+    Decimal,
+    Decimal,
+    i64,
+);
+
 impl FoodLogRepository {
-    /// Get daily nutrition summary
+    /// Get daily nutrition summary for a local date
     pub async fn get_daily_summary(
         db: &PgPool,
         user_id: Uuid,
         date: NaiveDate,
+        tz: Tz,
     ) -> Result<DailyNutritionSummary> {
-        let row = sqlx::query_as::<_, (Decimal, Decimal, Decimal, Decimal, Decimal, i64)>(
+        let (start, end) = local_day_bounds(date, tz);
+        let row = sqlx::query_as::<_, DailyTotalsRow>(
             r#"
             SELECT 
                 COALESCE(SUM(calories), 0) as total_calories,
@@ -321,14 +725,20 @@ impl FoodLogRepository {
                 COALESCE(SUM(carbohydrates_g), 0) as total_carbs,
                 COALESCE(SUM(fat_g), 0) as total_fat,
                 COALESCE(SUM(fiber_g), 0) as total_fiber,
+                COALESCE(SUM(sugar_g), 0) as total_sugar,
+                COALESCE(SUM(sodium_mg), 0) as total_sodium,
+                COALESCE(SUM(GREATEST(carbohydrates_g - fiber_g, 0)), 0) as total_net_carbs,
+                COALESCE(SUM(alcohol_g), 0) as total_alcohol,
                 COUNT(*) as meal_count
             FROM food_logs
-            WHERE user_id = $1 AND DATE(consumed_at) = $2
+            WHERE user_id = $1 AND consumed_at >= $2 AND consumed_at < $3 AND deleted_at IS NULL
             "#,
         )
         .bind(user_id)
-        .bind(date)
+        .bind(start)
+        .bind(end)
         .fetch_one(db)
+        .timed("FoodLogRepository::get_daily_summary")
         .await?;
 
         Ok(DailyNutritionSummary {
@@ -338,7 +748,11 @@ impl FoodLogRepository {
             total_carbs_g: row.2,
             total_fat_g: row.3,
             total_fiber_g: row.4,
-            meal_count: row.5,
+            total_sugar_g: row.5,
+            total_sodium_mg: row.6,
+            total_net_carbs_g: row.7,
+            total_alcohol_g: row.8,
+            meal_count: row.9,
         })
     }
 }
@@ -412,6 +826,7 @@ impl RecipeRepository {
         .bind(input.servings)
         .bind(input.is_public)
         .fetch_one(db)
+        .timed("RecipeRepository::create")
         .await?;
 
         Ok(recipe)
@@ -430,6 +845,7 @@ impl RecipeRepository {
         )
         .bind(id)
         .fetch_optional(db)
+        .timed("RecipeRepository::find_by_id")
         .await?;
 
         Ok(recipe)
@@ -449,6 +865,7 @@ impl RecipeRepository {
         .bind(id)
         .bind(user_id)
         .fetch_optional(db)
+        .timed("RecipeRepository::find_by_id_and_user")
         .await?;
 
         Ok(recipe)
@@ -468,6 +885,7 @@ impl RecipeRepository {
         )
         .bind(user_id)
         .fetch_all(db)
+        .timed("RecipeRepository::get_by_user")
         .await?;
 
         Ok(recipes)
@@ -489,6 +907,7 @@ impl RecipeRepository {
         .bind(input.servings)
         .bind(input.sort_order)
         .fetch_one(db)
+        .timed("RecipeRepository::add_ingredient")
         .await?;
 
         Ok(ingredient)
@@ -506,6 +925,7 @@ impl RecipeRepository {
         )
         .bind(recipe_id)
         .fetch_all(db)
+        .timed("RecipeRepository::get_ingredients")
         .await?;
 
         Ok(ingredients)
@@ -519,6 +939,7 @@ impl RecipeRepository {
         .bind(recipe_id)
         .bind(food_item_id)
         .execute(db)
+        .timed("RecipeRepository::remove_ingredient")
         .await?;
 
         Ok(result.rows_affected() > 0)
@@ -532,8 +953,194 @@ impl RecipeRepository {
         .bind(recipe_id)
         .bind(user_id)
         .execute(db)
+        .timed("RecipeRepository::delete")
         .await?;
 
         Ok(result.rows_affected() > 0)
     }
 }
+
+/// Meal template from the database
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MealTemplate {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Meal template item from the database
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MealTemplateItem {
+    pub id: Uuid,
+    pub template_id: Uuid,
+    pub food_item_id: Uuid,
+    pub servings: Decimal,
+    pub sort_order: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Input for creating a meal template with its items
+#[derive(Debug, Clone)]
+pub struct CreateMealTemplate {
+    pub user_id: Uuid,
+    pub name: String,
+    /// Food item IDs and servings, in display order
+    pub items: Vec<(Uuid, Decimal)>,
+}
+
+/// Meal template repository
+pub struct MealTemplateRepository;
+
+impl MealTemplateRepository {
+    /// Create a template and its items atomically
+    pub async fn create(db: &PgPool, input: CreateMealTemplate) -> Result<MealTemplate> {
+        let mut tx = db.begin().await?;
+
+        let template = sqlx::query_as::<_, MealTemplate>(
+            r#"
+            INSERT INTO meal_templates (user_id, name)
+            VALUES ($1, $2)
+            RETURNING id, user_id, name, created_at, updated_at
+            "#,
+        )
+        .bind(input.user_id)
+        .bind(&input.name)
+        .fetch_one(&mut *tx)
+        .timed("MealTemplateRepository::create")
+        .await?;
+
+        for (sort_order, (food_item_id, servings)) in input.items.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO meal_template_items (template_id, food_item_id, servings, sort_order)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(template.id)
+            .bind(food_item_id)
+            .bind(servings)
+            .bind(sort_order as i32)
+            .execute(&mut *tx)
+            .timed("MealTemplateRepository::create")
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(template)
+    }
+
+    /// Find template by ID and user (for ownership check)
+    pub async fn find_by_id_and_user(
+        db: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<MealTemplate>> {
+        let template = sqlx::query_as::<_, MealTemplate>(
+            r#"
+            SELECT id, user_id, name, created_at, updated_at
+            FROM meal_templates
+            WHERE id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(db)
+        .timed("MealTemplateRepository::find_by_id_and_user")
+        .await?;
+
+        Ok(template)
+    }
+
+    /// Get all templates for a user
+    pub async fn get_by_user(db: &PgPool, user_id: Uuid) -> Result<Vec<MealTemplate>> {
+        let templates = sqlx::query_as::<_, MealTemplate>(
+            r#"
+            SELECT id, user_id, name, created_at, updated_at
+            FROM meal_templates
+            WHERE user_id = $1
+            ORDER BY name ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(db)
+        .timed("MealTemplateRepository::get_by_user")
+        .await?;
+
+        Ok(templates)
+    }
+
+    /// Get items for a template
+    pub async fn get_items(db: &PgPool, template_id: Uuid) -> Result<Vec<MealTemplateItem>> {
+        let items = sqlx::query_as::<_, MealTemplateItem>(
+            r#"
+            SELECT id, template_id, food_item_id, servings, sort_order, created_at
+            FROM meal_template_items
+            WHERE template_id = $1
+            ORDER BY sort_order ASC
+            "#,
+        )
+        .bind(template_id)
+        .fetch_all(db)
+        .timed("MealTemplateRepository::get_items")
+        .await?;
+
+        Ok(items)
+    }
+
+    /// Delete a template
+    pub async fn delete(db: &PgPool, user_id: Uuid, template_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            "DELETE FROM meal_templates WHERE id = $1 AND user_id = $2"
+        )
+        .bind(template_id)
+        .bind(user_id)
+        .execute(db)
+        .timed("MealTemplateRepository::delete")
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn food(name: &str, barcode: Option<&str>) -> CreateFoodItem {
+        CreateFoodItem {
+            name: name.to_string(),
+            brand: None,
+            barcode: barcode.map(str::to_string),
+            serving_size: Decimal::new(100, 0),
+            serving_unit: "g".to_string(),
+            calories: Decimal::new(100, 0),
+            protein_g: Decimal::ZERO,
+            carbohydrates_g: Decimal::ZERO,
+            fat_g: Decimal::ZERO,
+            fiber_g: Decimal::ZERO,
+            sugar_g: Decimal::ZERO,
+            sodium_mg: None,
+            alcohol_g: Decimal::ZERO,
+            source: "import".to_string(),
+            created_by: None,
+        }
+    }
+
+    #[test]
+    fn test_dedupe_by_barcode_keeps_first_and_unbarcoded_items() {
+        let items = vec![
+            food("Oats", Some("111")),
+            food("Oats copy", Some("111")),
+            food("Apple", None),
+            food("Pear", None),
+            food("Rice", Some("222")),
+        ];
+
+        let names: Vec<String> = dedupe_by_barcode(items).into_iter().map(|i| i.name).collect();
+
+        assert_eq!(names, vec!["Oats", "Apple", "Pear", "Rice"]);
+    }
+}
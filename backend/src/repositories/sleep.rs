@@ -18,6 +18,8 @@ pub struct SleepLogRecord {
     pub sleep_start: DateTime<Utc>,
     pub sleep_end: DateTime<Utc>,
     pub total_duration_minutes: i32,
+    pub time_in_bed_minutes: Option<i32>,
+    pub sleep_onset_minutes: Option<i32>,
     pub awake_minutes: i32,
     pub light_minutes: i32,
     pub deep_minutes: i32,
@@ -42,6 +44,8 @@ pub struct CreateSleepLog {
     pub sleep_start: DateTime<Utc>,
     pub sleep_end: DateTime<Utc>,
     pub total_duration_minutes: i32,
+    pub time_in_bed_minutes: Option<i32>,
+    pub sleep_onset_minutes: Option<i32>,
     pub awake_minutes: i32,
     pub light_minutes: i32,
     pub deep_minutes: i32,
@@ -66,6 +70,8 @@ pub struct SleepSummary {
     pub avg_rem_minutes: Option<f64>,
     pub avg_light_minutes: Option<f64>,
     pub avg_awake_minutes: Option<f64>,
+    /// Average over the nights that recorded an onset latency
+    pub avg_onset_latency_minutes: Option<f64>,
     pub total_nights: i64,
 }
 
@@ -78,15 +84,15 @@ impl SleepLogRepository {
         let record = sqlx::query_as::<_, SleepLogRecord>(
             r#"
             INSERT INTO sleep_logs (
-                user_id, sleep_start, sleep_end, total_duration_minutes,
-                awake_minutes, light_minutes, deep_minutes, rem_minutes,
+                user_id, sleep_start, sleep_end, total_duration_minutes, time_in_bed_minutes,
+                sleep_onset_minutes, awake_minutes, light_minutes, deep_minutes, rem_minutes,
                 sleep_efficiency, sleep_score, times_awoken,
                 avg_heart_rate, min_heart_rate, hrv_average, respiratory_rate,
                 source, notes
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
-            RETURNING id, user_id, sleep_start, sleep_end, total_duration_minutes,
-                      awake_minutes, light_minutes, deep_minutes, rem_minutes,
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+            RETURNING id, user_id, sleep_start, sleep_end, total_duration_minutes, time_in_bed_minutes,
+                      sleep_onset_minutes, awake_minutes, light_minutes, deep_minutes, rem_minutes,
                       sleep_efficiency, sleep_score, times_awoken,
                       avg_heart_rate, min_heart_rate, hrv_average, respiratory_rate,
                       source, notes, created_at, updated_at
@@ -96,6 +102,8 @@ impl SleepLogRepository {
         .bind(input.sleep_start)
         .bind(input.sleep_end)
         .bind(input.total_duration_minutes)
+        .bind(input.time_in_bed_minutes)
+        .bind(input.sleep_onset_minutes)
         .bind(input.awake_minutes)
         .bind(input.light_minutes)
         .bind(input.deep_minutes)
@@ -123,8 +131,8 @@ impl SleepLogRepository {
     ) -> Result<Vec<SleepLogRecord>> {
         let records = sqlx::query_as::<_, SleepLogRecord>(
             r#"
-            SELECT id, user_id, sleep_start, sleep_end, total_duration_minutes,
-                   awake_minutes, light_minutes, deep_minutes, rem_minutes,
+            SELECT id, user_id, sleep_start, sleep_end, total_duration_minutes, time_in_bed_minutes,
+                   sleep_onset_minutes, awake_minutes, light_minutes, deep_minutes, rem_minutes,
                    sleep_efficiency, sleep_score, times_awoken,
                    avg_heart_rate, min_heart_rate, hrv_average, respiratory_rate,
                    source, notes, created_at, updated_at
@@ -152,8 +160,8 @@ impl SleepLogRepository {
     ) -> Result<Vec<SleepLogRecord>> {
         let records = sqlx::query_as::<_, SleepLogRecord>(
             r#"
-            SELECT id, user_id, sleep_start, sleep_end, total_duration_minutes,
-                   awake_minutes, light_minutes, deep_minutes, rem_minutes,
+            SELECT id, user_id, sleep_start, sleep_end, total_duration_minutes, time_in_bed_minutes,
+                   sleep_onset_minutes, awake_minutes, light_minutes, deep_minutes, rem_minutes,
                    sleep_efficiency, sleep_score, times_awoken,
                    avg_heart_rate, min_heart_rate, hrv_average, respiratory_rate,
                    source, notes, created_at, updated_at
@@ -217,6 +225,7 @@ impl SleepLogRepository {
                 AVG(rem_minutes)::float8 as avg_rem_minutes,
                 AVG(light_minutes)::float8 as avg_light_minutes,
                 AVG(awake_minutes)::float8 as avg_awake_minutes,
+                AVG(sleep_onset_minutes)::float8 as avg_onset_latency_minutes,
                 COUNT(*)::bigint as total_nights
             FROM sleep_logs
             WHERE user_id = $1 
@@ -237,8 +246,8 @@ impl SleepLogRepository {
     pub async fn get_latest(pool: &PgPool, user_id: Uuid) -> Result<Option<SleepLogRecord>> {
         let record = sqlx::query_as::<_, SleepLogRecord>(
             r#"
-            SELECT id, user_id, sleep_start, sleep_end, total_duration_minutes,
-                   awake_minutes, light_minutes, deep_minutes, rem_minutes,
+            SELECT id, user_id, sleep_start, sleep_end, total_duration_minutes, time_in_bed_minutes,
+                   sleep_onset_minutes, awake_minutes, light_minutes, deep_minutes, rem_minutes,
                    sleep_efficiency, sleep_score, times_awoken,
                    avg_heart_rate, min_heart_rate, hrv_average, respiratory_rate,
                    source, notes, created_at, updated_at
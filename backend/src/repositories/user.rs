@@ -2,8 +2,11 @@
 
 use anyhow::Result;
 use chrono::{DateTime, NaiveDate, Utc};
+use fitness_assistant_shared::models::Role;
 use rust_decimal::Decimal;
+use sqlx::types::Json;
 use sqlx::PgPool;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 /// User record from database
@@ -12,10 +15,20 @@ pub struct UserRecord {
     pub id: Uuid,
     pub email: String,
     pub password_hash: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub sessions_valid_after: Option<DateTime<Utc>>,
+    pub role: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl UserRecord {
+    /// The user's role; unknown values fall back to the least privileged
+    pub fn role(&self) -> Role {
+        self.role.parse().unwrap_or_default()
+    }
+}
+
 /// User settings record from database (extended)
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct UserSettingsRecord {
@@ -24,6 +37,9 @@ pub struct UserSettingsRecord {
     pub distance_unit: String,
     pub energy_unit: String,
     pub timezone: String,
+    pub week_start: String,
+    pub meal_types: Vec<String>,
+    pub max_hr_formula: String,
     pub daily_calorie_goal: Option<i32>,
     pub daily_water_goal_ml: Option<i32>,
     pub daily_step_goal: Option<i32>,
@@ -33,6 +49,9 @@ pub struct UserSettingsRecord {
     pub activity_level: String,
     pub height_unit: String,
     pub temperature_unit: String,
+    pub activity_level_confirmed: bool,
+    pub units_confirmed: bool,
+    pub version: i32,
     pub updated_at: DateTime<Utc>,
 }
 
@@ -43,6 +62,9 @@ pub struct UpdateUserSettings {
     pub distance_unit: Option<String>,
     pub energy_unit: Option<String>,
     pub timezone: Option<String>,
+    pub week_start: Option<String>,
+    pub meal_types: Option<Vec<String>>,
+    pub max_hr_formula: Option<String>,
     pub daily_calorie_goal: Option<i32>,
     pub daily_water_goal_ml: Option<i32>,
     pub daily_step_goal: Option<i32>,
@@ -52,30 +74,35 @@ pub struct UpdateUserSettings {
     pub activity_level: Option<String>,
     pub height_unit: Option<String>,
     pub temperature_unit: Option<String>,
+    /// Version the caller last read; the update is skipped if it has moved on
+    /// (None = last write wins)
+    pub expected_version: Option<i32>,
 }
 
 /// User repository for database operations
 pub struct UserRepository;
 
 impl UserRepository {
-    /// Create a new user with default settings
+    /// Create a new user with the given role and default settings
     pub async fn create(
         pool: &PgPool,
         email: &str,
         password_hash: &str,
+        role: Role,
     ) -> Result<UserRecord> {
         let mut tx = pool.begin().await?;
 
         // Insert user
         let user = sqlx::query_as::<_, UserRecord>(
             r#"
-            INSERT INTO users (email, password_hash)
-            VALUES ($1, $2)
-            RETURNING id, email, password_hash, created_at, updated_at
+            INSERT INTO users (email, password_hash, role)
+            VALUES ($1, $2, $3)
+            RETURNING id, email, password_hash, email_verified_at, sessions_valid_after, role, created_at, updated_at
             "#,
         )
         .bind(email)
         .bind(password_hash)
+        .bind(role.as_str())
         .fetch_one(&mut *tx)
         .await?;
 
@@ -99,7 +126,7 @@ impl UserRepository {
     pub async fn find_by_email(pool: &PgPool, email: &str) -> Result<Option<UserRecord>> {
         let user = sqlx::query_as::<_, UserRecord>(
             r#"
-            SELECT id, email, password_hash, created_at, updated_at
+            SELECT id, email, password_hash, email_verified_at, sessions_valid_after, role, created_at, updated_at
             FROM users
             WHERE email = $1
             "#,
@@ -115,7 +142,7 @@ impl UserRepository {
     pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<UserRecord>> {
         let user = sqlx::query_as::<_, UserRecord>(
             r#"
-            SELECT id, email, password_hash, created_at, updated_at
+            SELECT id, email, password_hash, email_verified_at, sessions_valid_after, role, created_at, updated_at
             FROM users
             WHERE id = $1
             "#,
@@ -131,10 +158,11 @@ impl UserRepository {
     pub async fn get_settings(pool: &PgPool, user_id: Uuid) -> Result<Option<UserSettingsRecord>> {
         let settings = sqlx::query_as::<_, UserSettingsRecord>(
             r#"
-            SELECT user_id, weight_unit, distance_unit, energy_unit, timezone,
-                   daily_calorie_goal, daily_water_goal_ml, daily_step_goal,
+            SELECT user_id, weight_unit, distance_unit, energy_unit, timezone, week_start, meal_types,
+                   max_hr_formula, daily_calorie_goal, daily_water_goal_ml, daily_step_goal,
                    height_cm, date_of_birth, biological_sex, activity_level,
-                   height_unit, temperature_unit, updated_at
+                   height_unit, temperature_unit, activity_level_confirmed,
+                   units_confirmed, version, updated_at
             FROM user_settings
             WHERE user_id = $1
             "#,
@@ -147,11 +175,13 @@ impl UserRepository {
     }
 
     /// Update user settings
+    ///
+    /// Returns None if `expected_version` no longer matches the stored version.
     pub async fn update_settings(
         pool: &PgPool,
         user_id: Uuid,
         updates: UpdateUserSettings,
-    ) -> Result<UserSettingsRecord> {
+    ) -> Result<Option<UserSettingsRecord>> {
         let settings = sqlx::query_as::<_, UserSettingsRecord>(
             r#"
             UPDATE user_settings SET
@@ -168,12 +198,20 @@ impl UserRepository {
                 activity_level = COALESCE($12, activity_level),
                 height_unit = COALESCE($13, height_unit),
                 temperature_unit = COALESCE($14, temperature_unit),
+                week_start = COALESCE($15, week_start),
+                meal_types = COALESCE($16, meal_types),
+                max_hr_formula = COALESCE($18, max_hr_formula),
+                activity_level_confirmed = activity_level_confirmed OR $12 IS NOT NULL,
+                units_confirmed = units_confirmed
+                    OR COALESCE($2, $3, $4, $13, $14) IS NOT NULL,
+                version = version + 1,
                 updated_at = NOW()
-            WHERE user_id = $1
-            RETURNING user_id, weight_unit, distance_unit, energy_unit, timezone,
-                      daily_calorie_goal, daily_water_goal_ml, daily_step_goal,
+            WHERE user_id = $1 AND ($17::INTEGER IS NULL OR version = $17)
+            RETURNING user_id, weight_unit, distance_unit, energy_unit, timezone, week_start, meal_types,
+                      max_hr_formula, daily_calorie_goal, daily_water_goal_ml, daily_step_goal,
                       height_cm, date_of_birth, biological_sex, activity_level,
-                      height_unit, temperature_unit, updated_at
+                      height_unit, temperature_unit, activity_level_confirmed,
+                   units_confirmed, version, updated_at
             "#,
         )
         .bind(user_id)
@@ -190,12 +228,259 @@ impl UserRepository {
         .bind(updates.activity_level)
         .bind(updates.height_unit)
         .bind(updates.temperature_unit)
-        .fetch_one(pool)
+        .bind(updates.week_start)
+        .bind(updates.meal_types)
+        .bind(updates.expected_version)
+        .bind(updates.max_hr_formula)
+        .fetch_optional(pool)
         .await?;
 
         Ok(settings)
     }
 
+    /// Record the user's height as effective from a date
+    pub async fn record_height(
+        pool: &PgPool,
+        user_id: Uuid,
+        height_cm: Decimal,
+        effective_from: NaiveDate,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO height_logs (user_id, height_cm, effective_from)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, effective_from)
+            DO UPDATE SET height_cm = EXCLUDED.height_cm, created_at = NOW()
+            "#,
+        )
+        .bind(user_id)
+        .bind(height_cm)
+        .bind(effective_from)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get the user's height history, oldest first
+    pub async fn get_height_history(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<(NaiveDate, Decimal)>> {
+        let rows = sqlx::query_as::<_, (NaiveDate, Decimal)>(
+            r#"
+            SELECT effective_from, height_cm
+            FROM height_logs
+            WHERE user_id = $1
+            ORDER BY effective_from ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Get a user's feature flags, or None if the user doesn't exist
+    pub async fn get_feature_flags(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Option<BTreeMap<String, bool>>> {
+        let flags = sqlx::query_scalar::<_, Json<BTreeMap<String, bool>>>(
+            r#"
+            SELECT feature_flags FROM users WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(flags.map(|flags| flags.0))
+    }
+
+    /// Turn a feature flag on or off for a user
+    ///
+    /// Returns the user's updated flags, or None if the user doesn't exist.
+    pub async fn set_feature_flag(
+        pool: &PgPool,
+        user_id: Uuid,
+        flag: &str,
+        enabled: bool,
+    ) -> Result<Option<BTreeMap<String, bool>>> {
+        let flags = sqlx::query_scalar::<_, Json<BTreeMap<String, bool>>>(
+            r#"
+            UPDATE users
+            SET feature_flags = feature_flags || jsonb_build_object($2::text, $3::boolean),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING feature_flags
+            "#,
+        )
+        .bind(user_id)
+        .bind(flag)
+        .bind(enabled)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(flags.map(|flags| flags.0))
+    }
+
+    /// Record an issued email verification token
+    pub async fn create_verification_token(
+        pool: &PgPool,
+        token_id: Uuid,
+        user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO email_verification_tokens (id, user_id, expires_at)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(token_id)
+        .bind(user_id)
+        .bind(expires_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Use up a verification token and mark its user's email as verified
+    ///
+    /// Returns the user, or None if the token is unknown, expired or was
+    /// already used.
+    pub async fn verify_email_with_token(pool: &PgPool, token_id: Uuid) -> Result<Option<Uuid>> {
+        let mut tx = pool.begin().await?;
+
+        let user_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE email_verification_tokens
+            SET used_at = NOW()
+            WHERE id = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+        )
+        .bind(token_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if let Some(user_id) = user_id {
+            sqlx::query(
+                r#"
+                UPDATE users
+                SET email_verified_at = COALESCE(email_verified_at, NOW()), updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(user_id)
+    }
+
+    /// Record an issued password reset token
+    pub async fn create_password_reset_token(
+        pool: &PgPool,
+        token_id: Uuid,
+        user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO password_reset_tokens (id, user_id, expires_at)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(token_id)
+        .bind(user_id)
+        .bind(expires_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Use up a password reset token and set its user's new password
+    ///
+    /// Every outstanding reset token for the user is used up too, and the
+    /// user's sessions are invalidated. Returns the user and the time from
+    /// which tokens are accepted again, or None if the token is unknown,
+    /// expired or was already used.
+    pub async fn reset_password_with_token(
+        pool: &PgPool,
+        token_id: Uuid,
+        password_hash: &str,
+    ) -> Result<Option<(Uuid, DateTime<Utc>)>> {
+        let mut tx = pool.begin().await?;
+
+        let user_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE password_reset_tokens
+            SET used_at = NOW()
+            WHERE id = $1 AND used_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+        )
+        .bind(token_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(user_id) = user_id else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE password_reset_tokens
+            SET used_at = NOW()
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let sessions_valid_after = sqlx::query_scalar::<_, DateTime<Utc>>(
+            r#"
+            UPDATE users
+            SET password_hash = $2, sessions_valid_after = NOW(), updated_at = NOW()
+            WHERE id = $1
+            RETURNING sessions_valid_after
+            "#,
+        )
+        .bind(user_id)
+        .bind(password_hash)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some((user_id, sessions_valid_after)))
+    }
+
+    /// Check whether a user has verified their email address
+    ///
+    /// Returns false for unknown users.
+    pub async fn is_email_verified(pool: &PgPool, user_id: Uuid) -> Result<bool> {
+        let verified = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND email_verified_at IS NOT NULL)
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(verified)
+    }
+
     /// Check if email exists
     pub async fn email_exists(pool: &PgPool, email: &str) -> Result<bool> {
         let result = sqlx::query_scalar::<_, bool>(
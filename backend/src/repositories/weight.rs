@@ -1,5 +1,6 @@
 //! Weight and body composition repository for database operations
 
+use crate::db::timing::QueryTimingExt;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
@@ -16,6 +17,7 @@ pub struct WeightLogRecord {
     pub source: String,
     pub notes: Option<String>,
     pub is_anomaly: bool,
+    pub anomaly_cause: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -43,6 +45,7 @@ pub struct CreateWeightLog {
     pub source: String,
     pub notes: Option<String>,
     pub is_anomaly: bool,
+    pub anomaly_cause: Option<String>,
 }
 
 /// Input for creating a body composition log
@@ -66,9 +69,9 @@ impl WeightRepository {
     pub async fn create(pool: &PgPool, input: CreateWeightLog) -> Result<WeightLogRecord> {
         let record = sqlx::query_as::<_, WeightLogRecord>(
             r#"
-            INSERT INTO weight_logs (user_id, weight_kg, recorded_at, source, notes, is_anomaly)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, created_at
+            INSERT INTO weight_logs (user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
             "#,
         )
         .bind(input.user_id)
@@ -77,7 +80,9 @@ impl WeightRepository {
         .bind(&input.source)
         .bind(&input.notes)
         .bind(input.is_anomaly)
+        .bind(&input.anomaly_cause)
         .fetch_one(pool)
+        .timed("WeightRepository::create")
         .await?;
 
         Ok(record)
@@ -96,9 +101,9 @@ impl WeightRepository {
         
         let records = sqlx::query_as::<_, WeightLogRecord>(
             r#"
-            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, created_at
+            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
             FROM weight_logs
-            WHERE user_id = $1 AND recorded_at >= $2 AND recorded_at <= $3
+            WHERE user_id = $1 AND recorded_at >= $2 AND recorded_at <= $3 AND deleted_at IS NULL
             ORDER BY recorded_at DESC
             "#,
         )
@@ -106,6 +111,7 @@ impl WeightRepository {
         .bind(start)
         .bind(end)
         .fetch_all(pool)
+        .timed("WeightRepository::get_by_date_range")
         .await?;
 
         Ok(records)
@@ -130,13 +136,14 @@ impl WeightRepository {
             r#"
             SELECT COUNT(*) as count
             FROM weight_logs
-            WHERE user_id = $1 AND recorded_at >= $2 AND recorded_at <= $3
+            WHERE user_id = $1 AND recorded_at >= $2 AND recorded_at <= $3 AND deleted_at IS NULL
             "#,
         )
         .bind(user_id)
         .bind(start)
         .bind(end)
         .fetch_one(pool)
+        .timed("WeightRepository::get_by_date_range_paginated")
         .await?;
         
         let total_count = count_row.0;
@@ -144,9 +151,9 @@ impl WeightRepository {
         // Get paginated records
         let records = sqlx::query_as::<_, WeightLogRecord>(
             r#"
-            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, created_at
+            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
             FROM weight_logs
-            WHERE user_id = $1 AND recorded_at >= $2 AND recorded_at <= $3
+            WHERE user_id = $1 AND recorded_at >= $2 AND recorded_at <= $3 AND deleted_at IS NULL
             ORDER BY recorded_at DESC
             LIMIT $4 OFFSET $5
             "#,
@@ -157,24 +164,118 @@ impl WeightRepository {
         .bind(limit)
         .bind(offset)
         .fetch_all(pool)
+        .timed("WeightRepository::get_by_date_range_paginated")
         .await?;
 
         Ok((records, total_count))
     }
 
+    /// Find the entry nearest `recorded_at` within `window_secs` whose weight
+    /// is within `tolerance_kg` of `weight_kg`
+    pub async fn find_near_duplicate(
+        pool: &PgPool,
+        user_id: Uuid,
+        weight_kg: f64,
+        recorded_at: DateTime<Utc>,
+        window_secs: f64,
+        tolerance_kg: f64,
+    ) -> Result<Option<WeightLogRecord>> {
+        let record = sqlx::query_as::<_, WeightLogRecord>(
+            r#"
+            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
+            FROM weight_logs
+            WHERE user_id = $1
+              AND deleted_at IS NULL
+              AND recorded_at BETWEEN $3 - make_interval(secs => $4) AND $3 + make_interval(secs => $4)
+              AND ABS(weight_kg - $2::numeric) <= $5::numeric
+            ORDER BY ABS(EXTRACT(EPOCH FROM recorded_at - $3))
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(weight_kg)
+        .bind(recorded_at)
+        .bind(window_secs)
+        .bind(tolerance_kg)
+        .fetch_optional(pool)
+        .timed("WeightRepository::find_near_duplicate")
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Overwrite an entry with a newer reading, keeping its notes unless new
+    /// ones are given
+    ///
+    /// Returns None if the entry doesn't belong to the user or was deleted.
+    pub async fn update_reading(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+        weight_kg: f64,
+        recorded_at: DateTime<Utc>,
+        source: &str,
+        notes: Option<&str>,
+    ) -> Result<Option<WeightLogRecord>> {
+        let record = sqlx::query_as::<_, WeightLogRecord>(
+            r#"
+            UPDATE weight_logs
+            SET weight_kg = $3, recorded_at = $4, source = $5, notes = COALESCE($6, notes)
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
+            RETURNING id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(weight_kg)
+        .bind(recorded_at)
+        .bind(source)
+        .bind(notes)
+        .fetch_optional(pool)
+        .timed("WeightRepository::update_reading")
+        .await?;
+
+        Ok(record)
+    }
+
     /// Get the most recent weight log for a user
     pub async fn get_latest(pool: &PgPool, user_id: Uuid) -> Result<Option<WeightLogRecord>> {
         let record = sqlx::query_as::<_, WeightLogRecord>(
             r#"
-            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, created_at
+            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
             FROM weight_logs
-            WHERE user_id = $1
+            WHERE user_id = $1 AND deleted_at IS NULL
+            ORDER BY recorded_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .timed("WeightRepository::get_latest")
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Get the most recent weight log recorded before a given instant
+    pub async fn get_latest_before(
+        pool: &PgPool,
+        user_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<Option<WeightLogRecord>> {
+        let record = sqlx::query_as::<_, WeightLogRecord>(
+            r#"
+            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
+            FROM weight_logs
+            WHERE user_id = $1 AND recorded_at < $2 AND deleted_at IS NULL
             ORDER BY recorded_at DESC
             LIMIT 1
             "#,
         )
         .bind(user_id)
+        .bind(before)
         .fetch_optional(pool)
+        .timed("WeightRepository::get_latest_before")
         .await?;
 
         Ok(record)
@@ -188,9 +289,9 @@ impl WeightRepository {
     ) -> Result<Vec<WeightLogRecord>> {
         let records = sqlx::query_as::<_, WeightLogRecord>(
             r#"
-            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, created_at
+            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
             FROM weight_logs
-            WHERE user_id = $1
+            WHERE user_id = $1 AND deleted_at IS NULL
             ORDER BY recorded_at DESC
             LIMIT $2
             "#,
@@ -198,6 +299,7 @@ impl WeightRepository {
         .bind(user_id)
         .bind(limit)
         .fetch_all(pool)
+        .timed("WeightRepository::get_recent")
         .await?;
 
         Ok(records)
@@ -211,34 +313,79 @@ impl WeightRepository {
     ) -> Result<Option<WeightLogRecord>> {
         let record = sqlx::query_as::<_, WeightLogRecord>(
             r#"
-            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, created_at
+            SELECT id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
             FROM weight_logs
-            WHERE id = $1 AND user_id = $2
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
             "#,
         )
         .bind(id)
         .bind(user_id)
         .fetch_optional(pool)
+        .timed("WeightRepository::get_by_id")
         .await?;
 
         Ok(record)
     }
 
-    /// Delete a weight log
+    /// Soft-delete a weight log
+    ///
+    /// The row is kept with `deleted_at` set so it can be restored until it
+    /// is purged.
     pub async fn delete(pool: &PgPool, id: Uuid, user_id: Uuid) -> Result<bool> {
         let result = sqlx::query(
             r#"
-            DELETE FROM weight_logs
-            WHERE id = $1 AND user_id = $2
+            UPDATE weight_logs
+            SET deleted_at = NOW()
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL
             "#,
         )
         .bind(id)
         .bind(user_id)
         .execute(pool)
+        .timed("WeightRepository::delete")
         .await?;
 
         Ok(result.rows_affected() > 0)
     }
+
+    /// Restore a soft-deleted weight log
+    pub async fn restore(
+        pool: &PgPool,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<WeightLogRecord>> {
+        let record = sqlx::query_as::<_, WeightLogRecord>(
+            r#"
+            UPDATE weight_logs
+            SET deleted_at = NULL
+            WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL
+            RETURNING id, user_id, weight_kg, recorded_at, source, notes, is_anomaly, anomaly_cause, created_at
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .timed("WeightRepository::restore")
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Permanently remove weight logs soft-deleted before the cutoff
+    pub async fn purge_deleted_before(pool: &PgPool, cutoff: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM weight_logs
+            WHERE deleted_at IS NOT NULL AND deleted_at < $1
+            "#,
+        )
+        .bind(cutoff)
+        .execute(pool)
+        .timed("WeightRepository::purge_deleted_before")
+        .await?;
+
+        Ok(result.rows_affected())
+    }
 }
 
 /// Body composition repository for database operations
@@ -267,6 +414,7 @@ impl BodyCompositionRepository {
         .bind(input.visceral_fat)
         .bind(&input.source)
         .fetch_one(pool)
+        .timed("BodyCompositionRepository::create")
         .await?;
 
         Ok(record)
@@ -295,6 +443,7 @@ impl BodyCompositionRepository {
         .bind(start)
         .bind(end)
         .fetch_all(pool)
+        .timed("BodyCompositionRepository::get_by_date_range")
         .await?;
 
         Ok(records)
@@ -316,6 +465,7 @@ impl BodyCompositionRepository {
         )
         .bind(user_id)
         .fetch_optional(pool)
+        .timed("BodyCompositionRepository::get_latest")
         .await?;
 
         Ok(record)
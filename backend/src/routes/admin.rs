@@ -0,0 +1,154 @@
+//! Admin-only API routes
+//!
+//! Every handler takes `AdminUser`, so other users, and admins whose email
+//! address isn't verified, are answered with 403.
+
+use crate::auth::AdminUser;
+use crate::error::ApiError;
+use crate::repositories::{CreateExercise, CreateFoodItem};
+use crate::services::{ExerciseService, FeatureFlag, FeatureFlags, NutritionService};
+use crate::state::AppState;
+use axum::{
+    extract::{Path, State},
+    routing::{post, put},
+    Json, Router,
+};
+use fitness_assistant_shared::types::{
+    CreateExerciseRequest, ExerciseResponse, FeatureFlagsResponse, ImportFoodItemRequest,
+    ImportFoodItemsResponse, SeedExercisesResponse, SetFeatureFlagRequest,
+};
+use fitness_assistant_shared::validation::ValidationErrors;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Create admin routes
+pub fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/exercises", post(create_library_exercise))
+        .route("/exercises/seed", post(seed_exercises))
+        .route("/foods/import", post(import_foods))
+        .route("/users/:id/features/:flag", put(set_feature_flag))
+}
+
+/// Shared library exercise from a request
+fn library_exercise(req: CreateExerciseRequest) -> CreateExercise {
+    CreateExercise {
+        name: req.name,
+        category: req.category,
+        muscle_groups: req.muscle_groups,
+        equipment: req.equipment,
+        calories_per_minute: req.calories_per_minute,
+        description: req.description,
+        instructions: req.instructions,
+        is_custom: false,
+        created_by: None,
+    }
+}
+
+/// POST /api/v1/admin/exercises - Add an exercise to the shared library
+async fn create_library_exercise(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Json(req): Json<CreateExerciseRequest>,
+) -> Result<Json<ExerciseResponse>, ApiError> {
+    let exercise =
+        ExerciseService::create_library_exercise(state.db(), state.cache(), library_exercise(req))
+            .await?;
+
+    Ok(Json(ExerciseResponse {
+        id: exercise.id.to_string(),
+        name: exercise.name,
+        category: exercise.category,
+        muscle_groups: exercise.muscle_groups,
+        equipment: exercise.equipment,
+        calories_per_minute: exercise.calories_per_minute,
+        description: exercise.description,
+        instructions: exercise.instructions,
+        is_custom: exercise.is_custom,
+    }))
+}
+
+/// POST /api/v1/admin/exercises/seed - Add exercises the library doesn't have yet
+///
+/// Exercises are matched by name; existing ones are left unchanged.
+async fn seed_exercises(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Json(req): Json<Vec<CreateExerciseRequest>>,
+) -> Result<Json<SeedExercisesResponse>, ApiError> {
+    let exercises = req.into_iter().map(library_exercise).collect();
+    let inserted = ExerciseService::seed_library(state.db(), state.cache(), exercises).await?;
+
+    Ok(Json(SeedExercisesResponse { inserted }))
+}
+
+/// Shared food item from the `index`th item of an import request
+///
+/// Numbers that can't be stored as decimals are recorded in `errors`.
+fn imported_food(
+    index: usize,
+    req: ImportFoodItemRequest,
+    errors: &mut ValidationErrors,
+) -> CreateFoodItem {
+    let mut dec = |field: &str, value: f64| {
+        Decimal::try_from(value).unwrap_or_else(|_| {
+            errors.add(&format!("items[{}].{}", index, field), "must be a finite number");
+            Decimal::ZERO
+        })
+    };
+
+    CreateFoodItem {
+        serving_size: dec("serving_size", req.serving_size),
+        calories: dec("calories", req.calories),
+        protein_g: dec("protein_g", req.protein_g),
+        carbohydrates_g: dec("carbohydrates_g", req.carbohydrates_g),
+        fat_g: dec("fat_g", req.fat_g),
+        fiber_g: dec("fiber_g", req.fiber_g),
+        sugar_g: dec("sugar_g", req.sugar_g),
+        sodium_mg: req.sodium_mg.map(|mg| dec("sodium_mg", mg)),
+        alcohol_g: dec("alcohol_g", req.alcohol_g),
+        name: req.name,
+        brand: req.brand,
+        barcode: req.barcode,
+        serving_unit: req.serving_unit,
+        source: "import".to_string(),
+        created_by: None,
+    }
+}
+
+/// POST /api/v1/admin/foods/import - Add a batch of shared food items
+///
+/// Items are matched by barcode; ones already stored are left unchanged.
+async fn import_foods(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Json(req): Json<Vec<ImportFoodItemRequest>>,
+) -> Result<Json<ImportFoodItemsResponse>, ApiError> {
+    let mut errors = ValidationErrors::new();
+    let items = req
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| imported_food(i, item, &mut errors))
+        .collect();
+    errors.into_result()?;
+
+    let inserted = NutritionService::import_food_items(state.db(), state.cache(), items).await?;
+
+    Ok(Json(ImportFoodItemsResponse { inserted }))
+}
+
+/// PUT /api/v1/admin/users/:id/features/:flag - Turn a feature on or off for a user
+async fn set_feature_flag(
+    State(state): State<AppState>,
+    _admin: AdminUser,
+    Path((user_id, flag)): Path<(String, String)>,
+    Json(req): Json<SetFeatureFlagRequest>,
+) -> Result<Json<FeatureFlagsResponse>, ApiError> {
+    let user_id = Uuid::parse_str(&user_id)
+        .map_err(|_| ApiError::Validation("Invalid user ID".to_string()))?;
+    let flag: FeatureFlag = flag.parse()?;
+
+    let flags = FeatureFlags::set(state.db(), user_id, flag, req.enabled).await?;
+
+    Ok(Json(FeatureFlagsResponse { flags }))
+}
@@ -0,0 +1,52 @@
+//! Audit log API routes
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::services::AuditService;
+use crate::state::AppState;
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use fitness_assistant_shared::types::{AuditLogPageResponse, AuditLogQuery, AuditLogResponse};
+
+/// Create audit routes
+pub fn audit_routes() -> Router<AppState> {
+    Router::new().route("/", get(get_audit_log))
+}
+
+/// GET /api/v1/audit - Get the current user's recent actions
+///
+/// Supports pagination with limit (default: 50, max: 100) and offset parameters.
+async fn get_audit_log(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Json<AuditLogPageResponse>, ApiError> {
+    let query = query.normalize();
+
+    let (records, total_count) =
+        AuditService::get_recent(state.db(), auth.user_id, query.limit, query.offset).await?;
+
+    let items: Vec<AuditLogResponse> = records
+        .into_iter()
+        .map(|r| AuditLogResponse {
+            id: r.id.to_string(),
+            entity: r.entity,
+            entity_id: r.entity_id.to_string(),
+            action: r.action,
+            created_at: r.created_at,
+        })
+        .collect();
+
+    let has_more = query.offset + (items.len() as i64) < total_count;
+
+    Ok(Json(AuditLogPageResponse {
+        items,
+        total_count,
+        limit: query.limit,
+        offset: query.offset,
+        has_more,
+    }))
+}
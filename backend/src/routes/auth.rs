@@ -1,18 +1,25 @@
 //! Authentication routes
 //!
-//! Provides endpoints for user registration, login, and token refresh.
+//! Provides endpoints for user registration, login, token refresh, logout,
+//! email verification, password reset and account deletion.
 //!
 //! # Performance Optimizations
 //! 
 //! - Uses pre-computed JWT keys from AppState (no per-request allocation)
 //! - Password hashing runs on blocking thread pool (doesn't block async runtime)
 
-use crate::auth::AuthUser;
-use crate::error::ApiResult;
+use crate::auth::{revocation, AuthUser};
+use crate::error::{ApiError, ApiResult};
+use crate::services::data::DeletionSummary;
+use crate::services::notifications::{self, PasswordResetEmail};
+use crate::services::user::PASSWORD_RESET_TTL_SECS;
 use crate::services::UserService;
 use crate::state::AppState;
-use axum::{extract::State, routing::post, Json, Router};
-use fitness_assistant_shared::types::{AuthTokens, LoginRequest, RegisterRequest, UserProfile};
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use fitness_assistant_shared::types::{
+    AuthTokens, DeleteAccountRequest, LoginRequest, PasswordResetRequest, RegisterRequest,
+    ResetPasswordRequest, UserProfile, VerifyEmailRequest,
+};
 use serde::Deserialize;
 
 /// Create auth routes
@@ -21,7 +28,11 @@ pub fn auth_routes() -> Router<AppState> {
         .route("/register", post(register))
         .route("/login", post(login))
         .route("/refresh", post(refresh_token))
-        .route("/me", axum::routing::get(get_profile))
+        .route("/logout", post(logout))
+        .route("/verify-email", post(verify_email))
+        .route("/password-reset/request", post(request_password_reset))
+        .route("/password-reset", post(reset_password))
+        .route("/me", axum::routing::get(get_profile).delete(delete_account))
 }
 
 /// Register a new user
@@ -33,10 +44,10 @@ pub fn auth_routes() -> Router<AppState> {
 async fn register(
     State(state): State<AppState>,
     Json(req): Json<RegisterRequest>,
-) -> ApiResult<Json<AuthTokens>> {
+) -> ApiResult<(StatusCode, Json<AuthTokens>)> {
     // Use pre-computed JWT service from state
     let tokens = UserService::register(&state.db, state.jwt(), &req.email, &req.password).await?;
-    Ok(Json(tokens))
+    Ok((StatusCode::CREATED, Json(tokens)))
 }
 
 /// Login with email and password
@@ -70,6 +81,82 @@ async fn refresh_token(
     Ok(Json(tokens))
 }
 
+/// Revoke the access token used for this request
+/// 
+/// POST /api/v1/auth/logout
+/// 
+/// # Authentication
+/// Requires valid Bearer token; that token is rejected from then on.
+async fn logout(State(state): State<AppState>, auth_user: AuthUser) -> ApiResult<StatusCode> {
+    if let Some(jti) = &auth_user.token_id {
+        revocation::revoke(state.revocations(), jti, auth_user.token_expires_at)
+            .await
+            .map_err(ApiError::Internal)?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Confirm the user's email address with a verification token
+/// 
+/// POST /api/v1/auth/verify-email
+/// 
+/// Tokens are single-use and expire after 24 hours.
+async fn verify_email(
+    State(state): State<AppState>,
+    Json(req): Json<VerifyEmailRequest>,
+) -> ApiResult<StatusCode> {
+    UserService::verify_email(&state.db, state.jwt(), &req.token).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Start a password reset
+/// 
+/// POST /api/v1/auth/password-reset/request
+/// 
+/// Always answers 202 so registered emails can't be discovered; the token
+/// is emailed in the background. Answers 503 if no mailer is configured.
+async fn request_password_reset(
+    State(state): State<AppState>,
+    Json(req): Json<PasswordResetRequest>,
+) -> ApiResult<StatusCode> {
+    let mailer = state.mailer().ok_or_else(|| {
+        ApiError::ServiceUnavailable("Password reset email is not configured".to_string())
+    })?;
+
+    if let Some(token) =
+        UserService::request_password_reset(&state.db, state.jwt(), &req.email).await?
+    {
+        let email = PasswordResetEmail {
+            email: req.email,
+            token,
+            expires_in_secs: PASSWORD_RESET_TTL_SECS,
+        };
+        notifications::send_password_reset(state.jobs(), mailer, email);
+    }
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Set a new password with a password reset token
+/// 
+/// POST /api/v1/auth/password-reset
+/// 
+/// Tokens are single-use and expire after an hour. Every existing session
+/// is logged out.
+async fn reset_password(
+    State(state): State<AppState>,
+    Json(req): Json<ResetPasswordRequest>,
+) -> ApiResult<StatusCode> {
+    UserService::reset_password(
+        &state.db,
+        state.revocations(),
+        state.jwt(),
+        &req.token,
+        &req.new_password,
+    )
+    .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Get current user profile (requires authentication)
 /// 
 /// GET /api/v1/auth/me
@@ -84,6 +171,22 @@ async fn get_profile(
     Ok(Json(profile))
 }
 
+/// Permanently delete the current user's account and all of their data
+///
+/// DELETE /api/v1/auth/me
+///
+/// # Authentication
+/// Requires valid Bearer token and the account password as confirmation.
+async fn delete_account(
+    State(state): State<AppState>,
+    auth_user: AuthUser,
+    Json(req): Json<DeleteAccountRequest>,
+) -> ApiResult<Json<DeletionSummary>> {
+    UserService::verify_password(&state.db, auth_user.user_id, &req.password).await?;
+    let summary = UserService::delete_account(&state.db, state.cache(), auth_user.user_id).await?;
+    Ok(Json(summary))
+}
+
 #[cfg(test)]
 mod tests {
     // Route tests will be added as integration tests
@@ -2,8 +2,11 @@
 
 use crate::auth::AuthUser;
 use crate::error::ApiError;
-use crate::services::biometrics::{BiometricsService, LogHeartRateInput, LogHrvInput};
+use crate::services::biometrics::{
+    BaselineMethod, BiometricsService, LogHeartRateInput, LogHrvInput, UpdateHrvInput,
+};
 use crate::state::AppState;
+use crate::timezone;
 use axum::{
     extract::{Path, Query, State},
     routing::{get, post},
@@ -11,8 +14,10 @@ use axum::{
 };
 use fitness_assistant_shared::types::{
     BiometricsHistoryQuery, HeartRateLogResponse, HeartRateZoneResponse,
-    HeartRateZonesResponse, HrvLogResponse, LogHeartRateRequest, LogHrvRequest,
-    RecoveryScoreResponse, RestingHrAnalysisResponse,
+    HeartRateStreamSummaryResponse, HeartRateZonesResponse, HrvLogResponse,
+    IngestHeartRateStreamRequest, LogHeartRateRequest, LogHrvRequest,
+    RecoveryScoreResponse, RestingHrAnalysisQuery, RestingHrAnalysisResponse,
+    TrainingReadinessResponse, UpdateHrvRequest, ZoneDistributionQuery, ZoneDistributionResponse,
 };
 
 /// Create biometrics routes
@@ -24,9 +29,12 @@ pub fn biometrics_routes() -> Router<AppState> {
         .route("/hrv", post(log_hrv))
         .route("/hrv/history", get(get_hrv_history))
         .route("/recovery", get(get_recovery_score))
+        .route("/readiness", get(get_training_readiness))
         .route("/zones", get(get_heart_rate_zones))
+        .route("/zones/distribution", get(get_zone_distribution))
+        .route("/workouts/:id/heart-rate-stream", post(ingest_heart_rate_stream))
         .route("/heart-rate/:id", axum::routing::delete(delete_heart_rate))
-        .route("/hrv/:id", axum::routing::delete(delete_hrv))
+        .route("/hrv/:id", axum::routing::put(update_hrv).delete(delete_hrv))
 }
 
 /// POST /api/v1/biometrics/heart-rate - Log heart rate
@@ -50,7 +58,9 @@ async fn log_heart_rate(
         notes: req.notes,
     };
 
-    let log = BiometricsService::log_heart_rate(state.db(), auth.user_id, input).await?;
+    let log =
+        BiometricsService::log_heart_rate(state.db(), &state.config().biometrics, auth.user_id, input)
+            .await?;
 
     Ok(Json(HeartRateLogResponse {
         id: log.id.to_string(),
@@ -109,11 +119,21 @@ async fn get_heart_rate_history(
 }
 
 /// GET /api/v1/biometrics/heart-rate/analysis - Get resting HR analysis
+///
+/// `?baseline=median` uses a median baseline instead of the default mean.
 async fn get_resting_hr_analysis(
     State(state): State<AppState>,
     auth: AuthUser,
+    Query(query): Query<RestingHrAnalysisQuery>,
 ) -> Result<Json<RestingHrAnalysisResponse>, ApiError> {
-    let analysis = BiometricsService::analyze_resting_hr(state.db(), auth.user_id, 7).await?;
+    let method = query
+        .baseline
+        .as_deref()
+        .map(str::parse::<BaselineMethod>)
+        .transpose()?
+        .unwrap_or_default();
+    let analysis =
+        BiometricsService::analyze_resting_hr(state.db(), auth.user_id, 7, method).await?;
 
     Ok(Json(RestingHrAnalysisResponse {
         current_avg: analysis.current_avg,
@@ -139,7 +159,8 @@ async fn log_hrv(
         notes: req.notes,
     };
 
-    let log = BiometricsService::log_hrv(state.db(), auth.user_id, input).await?;
+    let log =
+        BiometricsService::log_hrv(state.db(), &state.config().biometrics, auth.user_id, input).await?;
 
     Ok(Json(HrvLogResponse {
         id: log.id.to_string(),
@@ -162,11 +183,13 @@ async fn get_hrv_history(
     
     use rust_decimal::prelude::ToPrimitive;
     
+    let tz = timezone::user_timezone(state.db(), auth.user_id).await;
     let records = crate::repositories::biometrics::HrvLogRepository::get_history(
         state.db(),
         auth.user_id,
         query.start_date,
         query.end_date,
+        tz,
         query.limit,
         query.offset,
     )
@@ -212,9 +235,53 @@ async fn get_recovery_score(
         resting_hr_current: recovery.resting_hr_current,
         resting_hr_baseline: recovery.resting_hr_baseline,
         status: recovery.status,
+        cycle_phase: recovery.cycle_phase.map(|phase| phase.as_str().to_string()),
     }))
 }
 
+/// GET /api/v1/biometrics/readiness - Get today's training recommendation
+async fn get_training_readiness(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<TrainingReadinessResponse>, ApiError> {
+    let readiness = BiometricsService::get_training_readiness(state.db(), auth.user_id).await?;
+
+    Ok(Json(TrainingReadinessResponse {
+        recommendation: readiness.recommendation.as_str().to_string(),
+        reason: readiness.reason,
+        recovery_score: readiness.recovery_score,
+        hrv_trend_slope: readiness.hrv_trend_slope,
+        resting_hr_anomaly: readiness.resting_hr_anomaly,
+    }))
+}
+
+/// GET /api/v1/biometrics/zones/distribution - Time in zone across workouts in a range
+async fn get_zone_distribution(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<ZoneDistributionQuery>,
+) -> Result<Json<Vec<ZoneDistributionResponse>>, ApiError> {
+    let distribution = BiometricsService::aggregate_zone_distribution(
+        state.db(),
+        auth.user_id,
+        query.start_date,
+        query.end_date,
+    )
+    .await?;
+
+    Ok(Json(
+        distribution
+            .into_iter()
+            .map(|z| ZoneDistributionResponse {
+                zone: z.zone,
+                name: z.name,
+                duration_seconds: z.duration_seconds,
+                percentage: z.percentage,
+            })
+            .collect(),
+    ))
+}
+
 /// GET /api/v1/biometrics/zones - Get heart rate zones
 async fn get_heart_rate_zones(
     State(state): State<AppState>,
@@ -235,6 +302,52 @@ async fn get_heart_rate_zones(
     }))
 }
 
+/// POST /api/v1/biometrics/workouts/:id/heart-rate-stream - Ingest a workout's heart rate stream
+async fn ingest_heart_rate_stream(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+    Json(req): Json<IngestHeartRateStreamRequest>,
+) -> Result<Json<HeartRateStreamSummaryResponse>, ApiError> {
+    let workout_id = uuid::Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid workout ID".to_string()))?;
+
+    let samples = req
+        .samples
+        .into_iter()
+        .map(|sample| (sample.recorded_at, sample.bpm))
+        .collect();
+
+    let summary = BiometricsService::ingest_hr_stream(
+        state.db(),
+        &state.config().biometrics,
+        auth.user_id,
+        workout_id,
+        req.source.as_deref(),
+        samples,
+    )
+    .await?;
+
+    Ok(Json(HeartRateStreamSummaryResponse {
+        workout_id: workout_id.to_string(),
+        sample_count: summary.sample_count,
+        duration_seconds: summary.duration_seconds,
+        avg_bpm: summary.avg_bpm,
+        max_bpm: summary.max_bpm,
+        min_bpm: summary.min_bpm,
+        zone_distribution: summary
+            .zone_distribution
+            .into_iter()
+            .map(|d| ZoneDistributionResponse {
+                zone: d.zone,
+                name: d.name,
+                duration_seconds: d.duration_seconds,
+                percentage: d.percentage,
+            })
+            .collect(),
+    }))
+}
+
 /// DELETE /api/v1/biometrics/heart-rate/:id - Delete heart rate log
 async fn delete_heart_rate(
     State(state): State<AppState>,
@@ -244,17 +357,45 @@ async fn delete_heart_rate(
     let log_id = uuid::Uuid::parse_str(&id)
         .map_err(|_| ApiError::Validation("Invalid log ID".to_string()))?;
 
-    let deleted = crate::repositories::biometrics::HeartRateLogRepository::delete(
-        state.db(), log_id, auth.user_id
-    )
-    .await
-    .map_err(ApiError::Internal)?;
+    BiometricsService::delete_heart_rate_log(state.db(), auth.user_id, log_id).await?;
 
-    if deleted {
-        Ok(Json(serde_json::json!({"deleted": true})))
-    } else {
-        Err(ApiError::NotFound("Heart rate log not found".to_string()))
-    }
+    Ok(Json(serde_json::json!({"deleted": true})))
+}
+
+/// PUT /api/v1/biometrics/hrv/:id - Correct an HRV log
+async fn update_hrv(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateHrvRequest>,
+) -> Result<Json<HrvLogResponse>, ApiError> {
+    let log_id = uuid::Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid log ID".to_string()))?;
+
+    let input = UpdateHrvInput {
+        rmssd: req.rmssd,
+        sdnn: req.sdnn,
+        context: req.context,
+        notes: req.notes,
+    };
+
+    let log = BiometricsService::update_hrv_log(
+        state.db(),
+        &state.config().biometrics,
+        auth.user_id,
+        log_id,
+        input,
+    ).await?;
+
+    Ok(Json(HrvLogResponse {
+        id: log.id.to_string(),
+        rmssd: log.rmssd,
+        sdnn: log.sdnn,
+        context: log.context,
+        recorded_at: log.recorded_at,
+        source: log.source,
+        notes: log.notes,
+    }))
 }
 
 /// DELETE /api/v1/biometrics/hrv/:id - Delete HRV log
@@ -266,15 +407,7 @@ async fn delete_hrv(
     let log_id = uuid::Uuid::parse_str(&id)
         .map_err(|_| ApiError::Validation("Invalid log ID".to_string()))?;
 
-    let deleted = crate::repositories::biometrics::HrvLogRepository::delete(
-        state.db(), log_id, auth.user_id
-    )
-    .await
-    .map_err(ApiError::Internal)?;
+    BiometricsService::delete_hrv_log(state.db(), auth.user_id, log_id).await?;
 
-    if deleted {
-        Ok(Json(serde_json::json!({"deleted": true})))
-    } else {
-        Err(ApiError::NotFound("HRV log not found".to_string()))
-    }
+    Ok(Json(serde_json::json!({"deleted": true})))
 }
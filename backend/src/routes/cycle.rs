@@ -0,0 +1,97 @@
+//! Menstrual cycle tracking API routes
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::services::cycle::{CycleDay, CycleService, CycleSettings, UpdateCycleSettingsInput};
+use crate::state::AppState;
+use crate::timezone;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use fitness_assistant_shared::types::{
+    CyclePhaseQuery, CyclePhaseResponse, CycleSettingsResponse, LogCycleStartRequest,
+    UpdateCycleSettingsRequest,
+};
+
+/// Create cycle tracking routes
+pub fn cycle_routes() -> Router<AppState> {
+    Router::new()
+        .route("/settings", get(get_settings).put(update_settings))
+        .route("/starts", post(log_start))
+        .route("/phase", get(get_phase))
+}
+
+/// GET /api/v1/cycle/settings - Get cycle tracking settings
+async fn get_settings(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<CycleSettingsResponse>, ApiError> {
+    let settings = CycleService::get_settings(state.db(), auth.user_id).await?;
+
+    Ok(Json(settings_response(settings)))
+}
+
+/// PUT /api/v1/cycle/settings - Opt in to or out of cycle tracking
+async fn update_settings(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(req): Json<UpdateCycleSettingsRequest>,
+) -> Result<Json<CycleSettingsResponse>, ApiError> {
+    let input = UpdateCycleSettingsInput {
+        enabled: req.enabled,
+        cycle_length_days: req.cycle_length_days,
+        period_length_days: req.period_length_days,
+    };
+
+    let settings = CycleService::update_settings(state.db(), auth.user_id, input).await?;
+
+    Ok(Json(settings_response(settings)))
+}
+
+/// POST /api/v1/cycle/starts - Log the first day of a cycle
+async fn log_start(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(req): Json<LogCycleStartRequest>,
+) -> Result<StatusCode, ApiError> {
+    let today = timezone::local_today(timezone::user_timezone(state.db(), auth.user_id).await);
+    CycleService::log_start(state.db(), auth.user_id, req.started_on, today).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/v1/cycle/phase - Get the cycle phase on a day (default today)
+async fn get_phase(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<CyclePhaseQuery>,
+) -> Result<Json<CyclePhaseResponse>, ApiError> {
+    let date = match query.date {
+        Some(date) => date,
+        None => timezone::local_today(timezone::user_timezone(state.db(), auth.user_id).await),
+    };
+
+    let day = CycleService::get_phase(state.db(), auth.user_id, date).await?;
+
+    Ok(Json(phase_response(day)))
+}
+
+fn settings_response(settings: CycleSettings) -> CycleSettingsResponse {
+    CycleSettingsResponse {
+        enabled: settings.enabled,
+        cycle_length_days: settings.cycle_length_days,
+        period_length_days: settings.period_length_days,
+    }
+}
+
+fn phase_response(day: CycleDay) -> CyclePhaseResponse {
+    CyclePhaseResponse {
+        date: day.date,
+        phase: day.phase.as_str().to_string(),
+        cycle_day: day.cycle_day,
+        cycle_started_on: day.cycle_started_on,
+    }
+}
@@ -1,11 +1,15 @@
 //! Exercise and workout API routes
 
+use super::pagination;
 use crate::auth::AuthUser;
 use crate::error::ApiError;
 use crate::services::exercise::{
-    ExerciseService, LogExerciseSetInput, LogWorkoutExerciseInput, LogWorkoutInput,
+    ExerciseService, LogExerciseSetInput, MAX_HISTORY_SESSIONS, LogWorkoutExerciseInput, LogWorkoutInput,
+    WorkoutTemplate, WorkoutTemplateExerciseInput, WorkoutTemplateInput,
 };
+use crate::services::ProfileService;
 use crate::state::AppState;
+use crate::timezone;
 use axum::{
     extract::{Path, Query, State},
     routing::{get, post},
@@ -13,20 +17,35 @@ use axum::{
 };
 use chrono::NaiveDate;
 use fitness_assistant_shared::types::{
-    CreateExerciseRequest, DailyWorkoutSummaryResponse, ExerciseLibraryQuery, ExerciseResponse,
-    ExerciseSetInput, ExerciseSetResponse, LogWorkoutRequest, WorkoutDetailResponse,
+    CreateExerciseRequest, DailyWorkoutSummaryResponse, ExerciseHistoryPointResponse,
+    ExerciseHistoryQuery, ExerciseLibraryQuery, ExerciseResponse,
+    ExerciseSetInput, ExerciseSubstitutionQuery, InstantiateTemplateRequest,
+    LoadRecommendationResponse, ExerciseSetResponse, LogWorkoutRequest, WorkoutDetailResponse,
     WorkoutExerciseInput, WorkoutExerciseResponse, WorkoutHistoryQuery, WorkoutHistoryResponse,
-    WorkoutResponse, WorkoutTypeSummaryResponse, WeeklyExerciseSummaryResponse,
+    WorkoutResponse, WorkoutTemplateExerciseResponse, WorkoutTemplateRequest,
+    WorkoutTemplateResponse, WorkoutTypeSummaryResponse, WeeklyExerciseSummaryResponse,
 };
 use uuid::Uuid;
 
+/// Sessions returned by exercise history when no limit is given
+const DEFAULT_EXERCISE_HISTORY_LIMIT: i64 = 20;
+
 /// Create exercise routes
 pub fn exercise_routes() -> Router<AppState> {
     Router::new()
         .route("/library", get(get_exercise_library))
+        .route("/library/:id/substitutions", get(get_substitutions))
+        .route("/library/:id/progression", get(get_load_recommendation))
+        .route("/library/:id/history", get(get_exercise_history))
         .route("/custom", post(create_custom_exercise).get(get_custom_exercises))
         .route("/workout", post(log_workout))
         .route("/workout/:id", get(get_workout).delete(delete_workout))
+        .route("/templates", post(create_template).get(list_templates))
+        .route(
+            "/templates/:id",
+            get(get_template).put(update_template).delete(delete_template),
+        )
+        .route("/templates/:id/instantiate", post(instantiate_template))
         .route("/history", get(get_workout_history))
         .route("/weekly/:date", get(get_weekly_summary))
 }
@@ -41,7 +60,8 @@ async fn get_exercise_library(
 
     // Get exercises based on filters
     if let Some(ref search) = query.search {
-        let results = ExerciseService::search_exercises(state.db(), search, query.limit).await?;
+        let limit = pagination::clamp_limit(query.limit, pagination::DEFAULT_PAGE_SIZE, pagination::MAX_PAGE_SIZE);
+        let results = ExerciseService::search_exercises(state.db(), search, limit).await?;
         exercises.extend(results);
     } else if let Some(ref category) = query.category {
         let results = ExerciseService::get_exercises_by_category(state.db(), category).await?;
@@ -51,7 +71,7 @@ async fn get_exercise_library(
             ExerciseService::get_exercises_by_muscle_group(state.db(), muscle_group).await?;
         exercises.extend(results);
     } else {
-        let results = ExerciseService::get_exercise_library(state.db()).await?;
+        let results = ExerciseService::get_exercise_library(state.db(), state.cache()).await?;
         exercises.extend(results);
     }
 
@@ -111,6 +131,98 @@ async fn create_custom_exercise(
     }))
 }
 
+/// GET /api/v1/exercise/library/:id/substitutions - Suggest alternatives for the available equipment
+async fn get_substitutions(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(id): Path<String>,
+    Query(query): Query<ExerciseSubstitutionQuery>,
+) -> Result<Json<Vec<ExerciseResponse>>, ApiError> {
+    let exercise_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid exercise ID".to_string()))?;
+
+    let available_equipment = query
+        .equipment
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let exercises =
+        ExerciseService::suggest_substitutions(state.db(), exercise_id, available_equipment).await?;
+
+    let response = exercises
+        .into_iter()
+        .map(|e| ExerciseResponse {
+            id: e.id.to_string(),
+            name: e.name,
+            category: e.category,
+            muscle_groups: e.muscle_groups,
+            equipment: e.equipment,
+            calories_per_minute: e.calories_per_minute,
+            description: e.description,
+            instructions: e.instructions,
+            is_custom: e.is_custom,
+        })
+        .collect();
+
+    Ok(Json(response))
+}
+
+/// GET /api/v1/exercise/library/:id/progression - Recommend the next load for an exercise
+async fn get_load_recommendation(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<LoadRecommendationResponse>, ApiError> {
+    let exercise_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid exercise ID".to_string()))?;
+
+    let recommendation =
+        ExerciseService::recommend_next_load(state.db(), auth.user_id, exercise_id).await?;
+
+    Ok(Json(LoadRecommendationResponse {
+        action: recommendation.action,
+        current_weight_kg: recommendation.current_weight_kg,
+        recommended_weight_kg: recommendation.recommended_weight_kg,
+        target_reps: recommendation.target_reps,
+        message: recommendation.message,
+    }))
+}
+
+/// GET /api/v1/exercise/library/:id/history - Per-session history of an exercise for progress charts
+async fn get_exercise_history(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+    Query(query): Query<ExerciseHistoryQuery>,
+) -> Result<Json<Vec<ExerciseHistoryPointResponse>>, ApiError> {
+    let exercise_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid exercise ID".to_string()))?;
+
+    let limit = pagination::clamp_limit(query.limit, DEFAULT_EXERCISE_HISTORY_LIMIT, MAX_HISTORY_SESSIONS);
+    let history = ExerciseService::get_exercise_history(state.db(), auth.user_id, exercise_id, limit)
+        .await?;
+
+    let response = history
+        .into_iter()
+        .map(|p| ExerciseHistoryPointResponse {
+            workout_id: p.workout_id.to_string(),
+            performed_at: p.performed_at,
+            date: p.date,
+            top_set_weight_kg: p.top_set_weight_kg,
+            top_set_reps: p.top_set_reps,
+            estimated_one_rep_max_kg: p.estimated_one_rep_max_kg,
+            total_volume_kg: p.total_volume_kg,
+        })
+        .collect();
+
+    Ok(Json(response))
+}
+
 /// GET /api/v1/exercise/custom - Get user's custom exercises
 async fn get_custom_exercises(
     State(state): State<AppState>,
@@ -151,6 +263,7 @@ async fn log_workout(
         calories_burned: req.calories_burned,
         avg_heart_rate: req.avg_heart_rate,
         max_heart_rate: req.max_heart_rate,
+        recovery_heart_rate: req.recovery_heart_rate,
         distance_meters: req.distance_meters,
         elevation_gain_meters: req.elevation_gain_meters,
         source: req.source,
@@ -158,11 +271,148 @@ async fn log_workout(
         exercises: req
             .exercises
             .into_iter()
-            .map(|e| convert_exercise_input(e))
+            .map(convert_exercise_input)
             .collect::<Result<Vec<_>, _>>()?,
     };
 
-    let detail = ExerciseService::log_workout(state.db(), auth.user_id, input).await?;
+    let detail = ExerciseService::log_workout(state.db(), state.cache(), auth.user_id, input).await?;
+
+    Ok(Json(convert_workout_detail(detail)))
+}
+
+/// Convert a template request, parsing exercise IDs
+fn convert_template_request(req: WorkoutTemplateRequest) -> Result<WorkoutTemplateInput, ApiError> {
+    let exercises = req
+        .exercises
+        .into_iter()
+        .map(|e| {
+            let exercise_id = Uuid::parse_str(&e.exercise_id)
+                .map_err(|_| ApiError::Validation("Invalid exercise ID".to_string()))?;
+            Ok(WorkoutTemplateExerciseInput {
+                exercise_id,
+                target_sets: e.target_sets,
+                target_reps: e.target_reps,
+                target_weight_kg: e.target_weight_kg,
+                notes: e.notes,
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    Ok(WorkoutTemplateInput {
+        name: req.name,
+        workout_type: req.workout_type,
+        notes: req.notes,
+        exercises,
+    })
+}
+
+fn template_response(template: WorkoutTemplate) -> WorkoutTemplateResponse {
+    WorkoutTemplateResponse {
+        id: template.id.to_string(),
+        name: template.name,
+        workout_type: template.workout_type,
+        notes: template.notes,
+        exercises: template
+            .exercises
+            .into_iter()
+            .map(|e| WorkoutTemplateExerciseResponse {
+                exercise_id: e.exercise_id.to_string(),
+                exercise_name: e.exercise_name,
+                target_sets: e.target_sets,
+                target_reps: e.target_reps,
+                target_weight_kg: e.target_weight_kg,
+                notes: e.notes,
+            })
+            .collect(),
+        created_at: template.created_at,
+        updated_at: template.updated_at,
+    }
+}
+
+/// POST /api/v1/exercise/templates - Save a workout template
+async fn create_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(req): Json<WorkoutTemplateRequest>,
+) -> Result<Json<WorkoutTemplateResponse>, ApiError> {
+    let input = convert_template_request(req)?;
+    let template = ExerciseService::create_template(state.db(), auth.user_id, input).await?;
+
+    Ok(Json(template_response(template)))
+}
+
+/// GET /api/v1/exercise/templates - List user's workout templates
+async fn list_templates(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<WorkoutTemplateResponse>>, ApiError> {
+    let templates = ExerciseService::get_templates(state.db(), auth.user_id).await?;
+
+    Ok(Json(templates.into_iter().map(template_response).collect()))
+}
+
+/// GET /api/v1/exercise/templates/:id - Get a workout template
+async fn get_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<WorkoutTemplateResponse>, ApiError> {
+    let template_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid template ID".to_string()))?;
+
+    let template = ExerciseService::get_template(state.db(), auth.user_id, template_id).await?;
+
+    Ok(Json(template_response(template)))
+}
+
+/// PUT /api/v1/exercise/templates/:id - Replace a workout template
+async fn update_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+    Json(req): Json<WorkoutTemplateRequest>,
+) -> Result<Json<WorkoutTemplateResponse>, ApiError> {
+    let template_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid template ID".to_string()))?;
+
+    let input = convert_template_request(req)?;
+    let template =
+        ExerciseService::update_template(state.db(), auth.user_id, template_id, input).await?;
+
+    Ok(Json(template_response(template)))
+}
+
+/// DELETE /api/v1/exercise/templates/:id - Delete a workout template
+async fn delete_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let template_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid template ID".to_string()))?;
+
+    ExerciseService::delete_template(state.db(), auth.user_id, template_id).await?;
+
+    Ok(Json(serde_json::json!({"deleted": true})))
+}
+
+/// POST /api/v1/exercise/templates/:id/instantiate - Create a workout from a template
+async fn instantiate_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+    Json(req): Json<InstantiateTemplateRequest>,
+) -> Result<Json<WorkoutDetailResponse>, ApiError> {
+    let template_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid template ID".to_string()))?;
+
+    let date = match req.date {
+        Some(date) => date,
+        None => timezone::local_today(timezone::user_timezone(state.db(), auth.user_id).await),
+    };
+
+    let detail =
+        ExerciseService::instantiate_template(state.db(), state.cache(), auth.user_id, template_id, date).await?;
 
     Ok(Json(convert_workout_detail(detail)))
 }
@@ -190,7 +440,7 @@ async fn delete_workout(
     let workout_id = Uuid::parse_str(&id)
         .map_err(|_| ApiError::Validation("Invalid workout ID".to_string()))?;
 
-    let deleted = ExerciseService::delete_workout(state.db(), auth.user_id, workout_id).await?;
+    let deleted = ExerciseService::delete_workout(state.db(), state.cache(), auth.user_id, workout_id).await?;
 
     if deleted {
         Ok(Json(serde_json::json!({"deleted": true})))
@@ -205,26 +455,27 @@ async fn get_workout_history(
     auth: AuthUser,
     Query(query): Query<WorkoutHistoryQuery>,
 ) -> Result<Json<WorkoutHistoryResponse>, ApiError> {
-    let query = query.normalize();
+    let limit = pagination::clamp_limit(query.limit, pagination::DEFAULT_PAGE_SIZE, pagination::MAX_PAGE_SIZE);
+    let offset = query.offset.max(0);
 
     let (workouts, total_count) = ExerciseService::get_workout_history(
         state.db(),
         auth.user_id,
         query.start,
         query.end,
-        query.limit,
-        query.offset,
+        limit,
+        offset,
     )
     .await?;
 
     let items: Vec<WorkoutResponse> = workouts.into_iter().map(convert_workout).collect();
-    let has_more = query.offset + (items.len() as i64) < total_count;
+    let has_more = offset + (items.len() as i64) < total_count;
 
     Ok(Json(WorkoutHistoryResponse {
         items,
         total_count,
-        limit: query.limit,
-        offset: query.offset,
+        limit,
+        offset,
         has_more,
     }))
 }
@@ -238,7 +489,9 @@ async fn get_weekly_summary(
     let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
         .map_err(|_| ApiError::Validation("Invalid date format. Use YYYY-MM-DD".to_string()))?;
 
-    let summary = ExerciseService::get_weekly_summary(state.db(), auth.user_id, date).await?;
+    let week_start = ProfileService::get_week_start(state.db(), auth.user_id).await?;
+    let summary =
+        ExerciseService::get_weekly_summary(state.db(), state.cache(), auth.user_id, date, week_start).await?;
 
     Ok(Json(WeeklyExerciseSummaryResponse {
         week_start: summary.week_start,
@@ -307,9 +560,15 @@ fn convert_workout(workout: crate::services::exercise::Workout) -> WorkoutRespon
         calories_burned: workout.calories_burned,
         avg_heart_rate: workout.avg_heart_rate,
         max_heart_rate: workout.max_heart_rate,
+        recovery_heart_rate: workout.recovery_heart_rate,
+        heart_rate_recovery: workout.heart_rate_recovery,
+        heart_rate_recovery_class: workout
+            .heart_rate_recovery_class
+            .map(|class| class.as_str().to_string()),
         distance_meters: workout.distance_meters,
         pace_seconds_per_km: workout.pace_seconds_per_km,
         elevation_gain_meters: workout.elevation_gain_meters,
+        grade_adjusted_pace_seconds_per_km: workout.grade_adjusted_pace_seconds_per_km,
         source: workout.source,
         notes: workout.notes,
     }
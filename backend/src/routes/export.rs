@@ -2,15 +2,19 @@
 
 use crate::auth::AuthUser;
 use crate::error::ApiError;
-use crate::services::export::ExportService;
+use crate::repositories::ExportJobRecord;
+use crate::services::export::{ExportSections, ExportService};
+use crate::services::export_jobs::{ExportJobService, ExportJobStatus};
 use crate::state::AppState;
 use axum::{
-    extract::State,
-    http::{header, HeaderMap, HeaderValue},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::IntoResponse,
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
+use fitness_assistant_shared::types::{ExportJobResponse, ExportQuery};
+use uuid::Uuid;
 
 /// Create export routes
 pub fn export_routes() -> Router<AppState> {
@@ -18,14 +22,102 @@ pub fn export_routes() -> Router<AppState> {
         .route("/json", get(export_json))
         .route("/csv/weight", get(export_weight_csv))
         .route("/csv/sleep", get(export_sleep_csv))
+        .route("/csv/nutrition", get(export_nutrition_csv))
+        .route("/", post(enqueue_export))
+        .route("/:job_id", get(get_export_job))
+        .route("/:job_id/download", get(download_export))
 }
 
-/// GET /api/v1/export/json - Export all user data as JSON
+fn export_job_response(job: ExportJobRecord) -> ExportJobResponse {
+    let download_url = (job.status == ExportJobStatus::Complete.as_str())
+        .then(|| format!("/api/v1/export/{}/download", job.id));
+
+    ExportJobResponse {
+        id: job.id.to_string(),
+        status: job.status,
+        created_at: job.created_at,
+        completed_at: job.completed_at,
+        download_url,
+        error: job.error,
+    }
+}
+
+fn parse_job_id(id: &str) -> Result<Uuid, ApiError> {
+    Uuid::parse_str(id).map_err(|_| ApiError::Validation("Invalid export job ID".to_string()))
+}
+
+/// POST /api/v1/export - Queue a JSON export to build in the background
+///
+/// Takes the same `include`, `start` and `end` parameters as the JSON
+/// export. Poll the returned job until it is complete.
+async fn enqueue_export(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<ExportQuery>,
+) -> Result<(StatusCode, Json<ExportJobResponse>), ApiError> {
+    let job = ExportJobService::enqueue(
+        state.db(),
+        auth.user_id,
+        query.include.as_deref(),
+        query.start,
+        query.end,
+    )
+    .await?;
+
+    Ok((StatusCode::ACCEPTED, Json(export_job_response(job))))
+}
+
+/// GET /api/v1/export/:job_id - Get a queued export's status
+async fn get_export_job(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<ExportJobResponse>, ApiError> {
+    let job = ExportJobService::get(state.db(), auth.user_id, parse_job_id(&job_id)?).await?;
+    Ok(Json(export_job_response(job)))
+}
+
+/// GET /api/v1/export/:job_id/download - Download a completed export
+async fn download_export(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(job_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let file = ExportJobService::read_file(state.db(), auth.user_id, parse_job_id(&job_id)?).await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment; filename=\"fitness-data-export.json\""),
+    );
+
+    Ok((headers, file))
+}
+
+/// GET /api/v1/export/json - Export user data as JSON
+///
+/// `?include=weight,sleep` limits the export to those datasets and
+/// `?start=...&end=...` to logs recorded in that window.
 async fn export_json(
     State(state): State<AppState>,
     auth: AuthUser,
+    Query(query): Query<ExportQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let export = ExportService::export_json(state.db(), auth.user_id).await?;
+    let sections = query
+        .include
+        .as_deref()
+        .map(str::parse::<ExportSections>)
+        .transpose()
+        .map_err(ApiError::Validation)?
+        .unwrap_or_default();
+
+    let export =
+        ExportService::export_json_selective(state.db(), auth.user_id, sections, query.start, query.end)
+            .await?;
     
     let json = serde_json::to_string_pretty(&export)
         .map_err(|e| ApiError::Internal(anyhow::anyhow!("JSON serialization error: {}", e)))?;
@@ -82,3 +174,23 @@ async fn export_sleep_csv(
     
     Ok((headers, csv))
 }
+
+/// GET /api/v1/export/csv/nutrition - Export nutrition data as CSV
+async fn export_nutrition_csv(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<impl IntoResponse, ApiError> {
+    let csv = ExportService::export_nutrition_csv(state.db(), auth.user_id).await?;
+    
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/csv"),
+    );
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_static("attachment; filename=\"nutrition-export.csv\""),
+    );
+    
+    Ok((headers, csv))
+}
@@ -2,7 +2,7 @@
 
 use crate::auth::AuthUser;
 use crate::error::ApiError;
-use crate::services::goals::{CreateGoalInput, GoalsService, UpdateGoalInput};
+use crate::services::goals::{self, CreateGoalInput, Goal, GoalsService, UpdateGoalInput};
 use crate::state::AppState;
 use axum::{
     extract::{Path, Query, State},
@@ -10,16 +10,18 @@ use axum::{
     Json, Router,
 };
 use fitness_assistant_shared::types::{
-    CreateGoalRequest, GoalProgressResponse, GoalResponse, GoalsListQuery, GoalsListResponse,
-    MilestoneResponse, UpdateGoalRequest,
+    CreateGoalRequest, GoalConflictResponse, GoalMetricProjectionResponse, GoalProgressResponse, GoalResponse,
+    GoalsListQuery, GoalsListResponse, MilestoneResponse, UpdateGoalRequest,
 };
 
 /// Create goals routes
 pub fn goals_routes() -> Router<AppState> {
     Router::new()
         .route("/", post(create_goal).get(list_goals))
+        .route("/conflicts", get(get_conflicts))
         .route("/:id", get(get_goal).put(update_goal).delete(delete_goal))
         .route("/:id/progress", get(get_progress))
+        .route("/:id/projection", get(get_projection))
 }
 
 /// POST /api/v1/goals - Create a new goal
@@ -42,20 +44,7 @@ async fn create_goal(
 
     let goal = GoalsService::create_goal(state.db(), auth.user_id, input).await?;
 
-    Ok(Json(GoalResponse {
-        id: goal.id.to_string(),
-        name: goal.name,
-        description: goal.description,
-        goal_type: goal.goal_type,
-        metric: goal.metric,
-        target_value: goal.target_value,
-        start_value: goal.start_value,
-        current_value: goal.current_value,
-        direction: goal.direction,
-        start_date: goal.start_date,
-        target_date: goal.target_date,
-        status: goal.status,
-    }))
+    Ok(Json(convert_goal(goal)))
 }
 
 /// GET /api/v1/goals - List goals
@@ -75,24 +64,30 @@ async fn list_goals(
     Ok(Json(GoalsListResponse {
         goals: goals
             .into_iter()
-            .map(|g| GoalResponse {
-                id: g.id.to_string(),
-                name: g.name,
-                description: g.description,
-                goal_type: g.goal_type,
-                metric: g.metric,
-                target_value: g.target_value,
-                start_value: g.start_value,
-                current_value: g.current_value,
-                direction: g.direction,
-                start_date: g.start_date,
-                target_date: g.target_date,
-                status: g.status,
-            })
+            .map(convert_goal)
             .collect(),
     }))
 }
 
+/// GET /api/v1/goals/conflicts - Active goals with contradictory calorie needs
+async fn get_conflicts(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<GoalConflictResponse>>, ApiError> {
+    let conflicts = GoalsService::detect_conflicts(state.db(), auth.user_id).await?;
+
+    Ok(Json(
+        conflicts
+            .into_iter()
+            .map(|conflict| GoalConflictResponse {
+                goal_ids: [conflict.goal_ids.0.to_string(), conflict.goal_ids.1.to_string()],
+                kind: conflict.kind.as_str().to_string(),
+                message: conflict.message,
+            })
+            .collect(),
+    ))
+}
+
 /// GET /api/v1/goals/:id - Get a specific goal
 async fn get_goal(
     State(state): State<AppState>,
@@ -104,20 +99,7 @@ async fn get_goal(
 
     let goal = GoalsService::get_goal(state.db(), auth.user_id, goal_id).await?;
 
-    Ok(Json(GoalResponse {
-        id: goal.id.to_string(),
-        name: goal.name,
-        description: goal.description,
-        goal_type: goal.goal_type,
-        metric: goal.metric,
-        target_value: goal.target_value,
-        start_value: goal.start_value,
-        current_value: goal.current_value,
-        direction: goal.direction,
-        start_date: goal.start_date,
-        target_date: goal.target_date,
-        status: goal.status,
-    }))
+    Ok(Json(convert_goal(goal)))
 }
 
 /// PUT /api/v1/goals/:id - Update a goal
@@ -139,22 +121,17 @@ async fn update_goal(
         status: req.status,
     };
 
-    let goal = GoalsService::update_goal(state.db(), auth.user_id, goal_id, input).await?;
+    let goal = GoalsService::update_goal(
+        state.db(),
+        state.jobs(),
+        state.notifier(),
+        auth.user_id,
+        goal_id,
+        input,
+    )
+    .await?;
 
-    Ok(Json(GoalResponse {
-        id: goal.id.to_string(),
-        name: goal.name,
-        description: goal.description,
-        goal_type: goal.goal_type,
-        metric: goal.metric,
-        target_value: goal.target_value,
-        start_value: goal.start_value,
-        current_value: goal.current_value,
-        direction: goal.direction,
-        start_date: goal.start_date,
-        target_date: goal.target_date,
-        status: goal.status,
-    }))
+    Ok(Json(convert_goal(goal)))
 }
 
 /// DELETE /api/v1/goals/:id - Delete a goal
@@ -207,3 +184,48 @@ async fn get_progress(
             .collect(),
     }))
 }
+
+/// GET /api/v1/goals/:id/projection - Project goal completion from the metric's trend
+async fn get_projection(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<GoalMetricProjectionResponse>, ApiError> {
+    let goal_id = uuid::Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid goal ID".to_string()))?;
+
+    let projection = goals::project_goal_generic(state.db(), auth.user_id, goal_id).await?;
+
+    Ok(Json(GoalMetricProjectionResponse {
+        goal_id: projection.goal_id.to_string(),
+        metric: projection.metric,
+        current_value: projection.current_value,
+        target_value: projection.target_value,
+        remaining: projection.remaining,
+        average_daily_change: projection.average_daily_change,
+        projected_days: projection.projected_days,
+        projected_date: projection.projected_date,
+        on_track: projection.on_track,
+    }))
+}
+
+/// Convert a goal to its API response, including percent complete
+fn convert_goal(goal: Goal) -> GoalResponse {
+    let progress_percent = GoalsService::calculate_goal_progress(&goal);
+
+    GoalResponse {
+        id: goal.id.to_string(),
+        name: goal.name,
+        description: goal.description,
+        goal_type: goal.goal_type,
+        metric: goal.metric,
+        target_value: goal.target_value,
+        start_value: goal.start_value,
+        current_value: goal.current_value,
+        direction: goal.direction,
+        start_date: goal.start_date,
+        target_date: goal.target_date,
+        status: goal.status,
+        progress_percent,
+    }
+}
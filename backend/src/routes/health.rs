@@ -5,7 +5,10 @@
 //! - /health/ready - Readiness probe (checks dependencies)
 //! - /health/live - Liveness probe (always returns OK if server is running)
 
-use crate::{db, state::AppState};
+use crate::{
+    db::{self, PoolStats},
+    state::AppState,
+};
 use axum::{extract::State, http::StatusCode, Json};
 use serde::Serialize;
 
@@ -22,6 +25,7 @@ pub struct HealthResponse {
 #[derive(Serialize)]
 pub struct HealthChecks {
     pub database: CheckStatus,
+    pub pool: PoolCheck,
 }
 
 /// Status of an individual check
@@ -32,6 +36,17 @@ pub struct CheckStatus {
     pub message: Option<String>,
 }
 
+/// Connection pool usage and status
+#[derive(Serialize)]
+pub struct PoolCheck {
+    /// "healthy", or "degraded" when the pool is near exhaustion
+    pub status: String,
+    pub size: u32,
+    pub idle: u32,
+    pub active: u32,
+    pub max_connections: u32,
+}
+
 /// Basic health check endpoint
 pub async fn health_check() -> Json<HealthResponse> {
     Json(HealthResponse {
@@ -57,18 +72,43 @@ pub async fn readiness_check(
         },
     };
 
-    let is_healthy = db_check.status == "healthy";
+    let response = readiness_response(db_check, PoolStats::from_pool(&state.db));
 
-    let response = HealthResponse {
-        status: if is_healthy { "ready" } else { "not_ready" }.to_string(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
-        checks: Some(HealthChecks { database: db_check }),
+    if response.status == "not_ready" {
+        Err((StatusCode::SERVICE_UNAVAILABLE, Json(response)))
+    } else {
+        Ok(Json(response))
+    }
+}
+
+/// Build the readiness payload from the database check and pool stats
+///
+/// A saturated pool still serves traffic, so it reports "degraded" rather
+/// than failing the probe.
+fn readiness_response(db_check: CheckStatus, pool: PoolStats) -> HealthResponse {
+    let pool_check = PoolCheck {
+        status: if pool.is_saturated() { "degraded" } else { "healthy" }.to_string(),
+        size: pool.size,
+        idle: pool.idle,
+        active: pool.active,
+        max_connections: pool.max_connections,
     };
 
-    if is_healthy {
-        Ok(Json(response))
+    let status = if db_check.status != "healthy" {
+        "not_ready"
+    } else if pool_check.status != "healthy" {
+        "degraded"
     } else {
-        Err((StatusCode::SERVICE_UNAVAILABLE, Json(response)))
+        "ready"
+    };
+
+    HealthResponse {
+        status: status.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        checks: Some(HealthChecks {
+            database: db_check,
+            pool: pool_check,
+        }),
     }
 }
 
@@ -98,4 +138,42 @@ mod tests {
         let response = liveness_check().await;
         assert_eq!(response.status, "alive");
     }
+
+    fn healthy_db() -> CheckStatus {
+        CheckStatus {
+            status: "healthy".to_string(),
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_readiness_includes_pool_numbers() {
+        let stats = PoolStats { size: 6, idle: 4, active: 2, max_connections: 10 };
+        let response = readiness_response(healthy_db(), stats);
+
+        assert_eq!(response.status, "ready");
+        let pool = response.checks.unwrap().pool;
+        assert_eq!(pool.status, "healthy");
+        assert_eq!((pool.size, pool.idle, pool.active, pool.max_connections), (6, 4, 2, 10));
+    }
+
+    #[test]
+    fn test_readiness_degraded_when_pool_saturated() {
+        let stats = PoolStats { size: 10, idle: 0, active: 10, max_connections: 10 };
+        let response = readiness_response(healthy_db(), stats);
+
+        assert_eq!(response.status, "degraded");
+        assert_eq!(response.checks.unwrap().pool.status, "degraded");
+    }
+
+    #[test]
+    fn test_readiness_not_ready_when_database_down() {
+        let db_check = CheckStatus {
+            status: "unhealthy".to_string(),
+            message: Some("connection refused".to_string()),
+        };
+        let stats = PoolStats { size: 0, idle: 0, active: 0, max_connections: 10 };
+
+        assert_eq!(readiness_response(db_check, stats).status, "not_ready");
+    }
 }
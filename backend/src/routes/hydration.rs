@@ -4,6 +4,7 @@ use crate::auth::AuthUser;
 use crate::error::ApiError;
 use crate::services::hydration::{HydrationService, LogHydrationInput, SetHydrationGoalInput};
 use crate::state::AppState;
+use crate::timezone;
 use axum::{
     extract::{Path, Query, State},
     routing::{get, post},
@@ -38,6 +39,7 @@ async fn log_hydration(
         consumed_at: req.consumed_at,
         source: req.source,
         notes: req.notes,
+        caffeine_mg: req.caffeine_mg,
     };
 
     let log = HydrationService::log_hydration(state.db(), auth.user_id, input).await?;
@@ -49,6 +51,7 @@ async fn log_hydration(
         consumed_at: log.consumed_at,
         source: log.source,
         notes: log.notes,
+        caffeine_mg: log.caffeine_mg,
     }))
 }
 
@@ -61,7 +64,15 @@ async fn get_daily_summary(
     let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
         .map_err(|_| ApiError::Validation("Invalid date format. Use YYYY-MM-DD".to_string()))?;
 
-    let summary = HydrationService::get_daily_summary(state.db(), auth.user_id, date).await?;
+    let tz = timezone::user_timezone(state.db(), auth.user_id).await;
+    let summary = HydrationService::get_daily_summary(
+        state.db(),
+        &state.config().hydration,
+        auth.user_id,
+        date,
+        tz,
+    )
+    .await?;
 
     Ok(Json(DailyHydrationResponse {
         date: summary.date,
@@ -69,6 +80,8 @@ async fn get_daily_summary(
         goal_ml: summary.goal_ml,
         progress_percent: summary.progress_percent,
         goal_met: summary.goal_met,
+        total_caffeine_mg: summary.total_caffeine_mg,
+        caffeine_warning: summary.caffeine_warning,
         entry_count: summary.entry_count,
         entries: summary
             .entries
@@ -80,6 +93,7 @@ async fn get_daily_summary(
                 consumed_at: e.consumed_at,
                 source: e.source,
                 notes: e.notes,
+                caffeine_mg: e.caffeine_mg,
             })
             .collect(),
     }))
@@ -90,7 +104,7 @@ async fn get_goal(
     State(state): State<AppState>,
     auth: AuthUser,
 ) -> Result<Json<HydrationGoalResponse>, ApiError> {
-    let goal = HydrationService::get_goal(state.db(), auth.user_id).await?;
+    let goal = HydrationService::get_goal(state.db(), &state.config().hydration, auth.user_id).await?;
 
     Ok(Json(HydrationGoalResponse {
         daily_goal_ml: goal.daily_goal_ml,
@@ -136,7 +150,13 @@ async fn set_goal(
         reminder_end_time,
     };
 
-    let goal = HydrationService::set_goal(state.db(), auth.user_id, input).await?;
+    let goal = HydrationService::set_goal(
+        state.db(),
+        &state.config().hydration,
+        auth.user_id,
+        input,
+    )
+    .await?;
 
     Ok(Json(HydrationGoalResponse {
         daily_goal_ml: goal.daily_goal_ml,
@@ -154,9 +174,16 @@ async fn get_history(
     auth: AuthUser,
     Query(query): Query<HydrationHistoryQuery>,
 ) -> Result<Json<HydrationHistoryResponse>, ApiError> {
-    let summaries =
-        HydrationService::get_history(state.db(), auth.user_id, query.start_date, query.end_date)
-            .await?;
+    let tz = timezone::user_timezone(state.db(), auth.user_id).await;
+    let summaries = HydrationService::get_history(
+        state.db(),
+        &state.config().hydration,
+        auth.user_id,
+        query.start_date,
+        query.end_date,
+        tz,
+    )
+    .await?;
 
     Ok(Json(HydrationHistoryResponse {
         summaries: summaries
@@ -167,6 +194,8 @@ async fn get_history(
                 goal_ml: s.goal_ml,
                 progress_percent: s.progress_percent,
                 goal_met: s.goal_met,
+                total_caffeine_mg: s.total_caffeine_mg,
+                caffeine_warning: s.caffeine_warning,
                 entry_count: s.entry_count,
             })
             .collect(),
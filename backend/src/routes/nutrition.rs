@@ -1,32 +1,68 @@
 //! Nutrition API routes
 
+use super::pagination;
 use crate::auth::AuthUser;
+use crate::config::DisplayPrecision;
 use crate::error::ApiError;
-use crate::repositories::FoodItemRepository;
-use crate::services::NutritionService;
+use crate::repositories::{
+    FoodItem, FoodItemRepository, FoodLog, MealTemplate, Recipe, UpdateFoodItem,
+};
+use crate::config::AiConfig;
+use crate::services::ai::MacroTargets;
+use crate::services::{MealSuggestionService, NutritionService};
 use crate::state::AppState;
+use crate::timezone;
 use axum::{
     extract::{Path, Query, State},
-    routing::{delete, get, post},
+    http::StatusCode,
+    routing::{delete, get, post, put},
     Json, Router,
 };
 use fitness_assistant_shared::types::{
-    AddIngredientRequest, CreateRecipeRequest, DailyNutritionResponse, DateQuery,
-    FoodItemResponse, FoodLogResponse, FoodSearchQuery, LogFoodRequest, RecipeDetailResponse,
-    RecipeIngredientResponse, RecipeResponse,
+    AddIngredientRequest, CopyDayRequest, CopyDayResponse, CreateMealTemplateRequest,
+    CreateRecipeRequest, DailyNutritionResponse, DateQuery, FavoriteFoodResponse, FoodItemResponse, FoodLogResponse, FoodSearchQuery,
+    LogFoodRequest, LogMealTemplateRequest, MealSuggestionRequest, MealSuggestionResponse,
+    MealTemplateItemResponse, MealTemplateResponse, NutritionPreviewQuery, NutritionPreviewResponse,
+    RecentFoodsQuery, RecipeDetailResponse, UpdateFoodItemRequest, RecipeIngredientResponse, RecipeResponse,
 };
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Foods returned by search when no limit is given
+const DEFAULT_SEARCH_LIMIT: i64 = 20;
+
+/// Recent foods returned when no limit is given
+const DEFAULT_RECENT_FOODS_LIMIT: i64 = 10;
+
+/// Most recent foods returned in one request
+const MAX_RECENT_FOODS: i64 = 50;
+
+/// Create nutrition routes that wait on the AI model
+///
+/// Kept apart from [`nutrition_routes`] so they get the longer AI timeout.
+pub fn nutrition_ai_routes() -> Router<AppState> {
+    Router::new().route("/suggestions", post(suggest_meals))
+}
+
 /// Create nutrition routes
 pub fn nutrition_routes() -> Router<AppState> {
     Router::new()
         .route("/search", get(search_foods))
         .route("/barcode/:code", get(lookup_barcode))
+        .route("/recent", get(get_recent_foods))
+        .route("/favorites", get(get_favorite_foods))
+        .route("/favorites/:food_id", post(toggle_favorite))
+        .route("/foods/:food_id", put(update_food_item))
+        .route("/foods/:food_id/preview", get(preview_nutrition))
         .route("/log", post(log_food))
         .route("/log/:id", delete(delete_food_log))
         .route("/daily", get(get_daily_summary))
+        .route("/copy-day", post(copy_day))
+        .route("/templates", post(create_meal_template).get(list_meal_templates))
+        .route("/templates/:id", delete(delete_meal_template))
+        .route("/templates/:id/log", post(log_meal_template))
         .route("/recipes", post(create_recipe).get(list_recipes))
         .route("/recipes/:id", get(get_recipe).delete(delete_recipe))
         .route("/recipes/:id/ingredients", post(add_ingredient))
@@ -43,35 +79,78 @@ fn f64_to_dec(f: f64) -> Decimal {
     Decimal::try_from(f).unwrap_or(Decimal::ZERO)
 }
 
+/// Convert a food item to its API response
+fn food_item_response(item: FoodItem, display: &DisplayPrecision) -> FoodItemResponse {
+    FoodItemResponse {
+        id: item.id.to_string(),
+        name: item.name,
+        brand: item.brand,
+        barcode: item.barcode,
+        serving_size: dec_to_f64(item.serving_size),
+        serving_unit: item.serving_unit,
+        calories: display.calories(item.calories),
+        protein_g: display.macros(item.protein_g),
+        carbohydrates_g: display.macros(item.carbohydrates_g),
+        fat_g: display.macros(item.fat_g),
+        fiber_g: display.macros(item.fiber_g),
+        sugar_g: display.macros(item.sugar_g),
+        sodium_mg: item.sodium_mg.map(dec_to_f64),
+        alcohol_g: display.macros(item.alcohol_g),
+        source: item.source,
+        verified: item.verified,
+    }
+}
+
+/// Convert a food log to its API response, naming custom entries
+fn food_log_response(log: FoodLog, display: &DisplayPrecision) -> FoodLogResponse {
+    let net_carbs_g = display.macros(log.net_carbs_g());
+    FoodLogResponse {
+        id: log.id.to_string(),
+        food_item_id: log.food_item_id.map(|id| id.to_string()),
+        food_name: log.custom_name,
+        servings: dec_to_f64(log.servings),
+        calories: display.calories(log.calories),
+        protein_g: display.macros(log.protein_g),
+        carbohydrates_g: display.macros(log.carbohydrates_g),
+        fat_g: display.macros(log.fat_g),
+        fiber_g: display.macros(log.fiber_g),
+        net_carbs_g,
+        sugar_g: display.macros(log.sugar_g),
+        sodium_mg: dec_to_f64(log.sodium_mg),
+        alcohol_g: display.macros(log.alcohol_g),
+        meal_type: log.meal_type,
+        consumed_at: log.consumed_at,
+        notes: log.notes,
+    }
+}
+
+/// Convert a recipe to its API response
+fn recipe_response(recipe: Recipe, display: &DisplayPrecision) -> RecipeResponse {
+    RecipeResponse {
+        id: recipe.id.to_string(),
+        name: recipe.name,
+        description: recipe.description,
+        servings: dec_to_f64(recipe.servings),
+        calories_per_serving: display.calories(recipe.calories_per_serving),
+        protein_per_serving: display.macros(recipe.protein_per_serving),
+        carbs_per_serving: display.macros(recipe.carbs_per_serving),
+        fat_per_serving: display.macros(recipe.fat_per_serving),
+        fiber_per_serving: display.macros(recipe.fiber_per_serving),
+        is_public: recipe.is_public,
+        created_at: recipe.created_at,
+    }
+}
+
 /// GET /api/v1/nutrition/search - Search food database
 async fn search_foods(
     State(state): State<AppState>,
     _auth: AuthUser,
     Query(query): Query<FoodSearchQuery>,
 ) -> Result<Json<Vec<FoodItemResponse>>, ApiError> {
-    let items = NutritionService::search_foods(state.db(), &query.q, query.limit).await?;
+    let limit = pagination::clamp_limit(query.limit, DEFAULT_SEARCH_LIMIT, pagination::MAX_PAGE_SIZE);
+    let items = NutritionService::search_foods(state.db(), state.cache(), &query.q, limit).await?;
 
-    let response: Vec<FoodItemResponse> = items
-        .into_iter()
-        .map(|item| FoodItemResponse {
-            id: item.id.to_string(),
-            name: item.name,
-            brand: item.brand,
-            barcode: item.barcode,
-            serving_size: dec_to_f64(item.serving_size),
-            serving_unit: item.serving_unit,
-            calories: dec_to_f64(item.calories),
-            protein_g: dec_to_f64(item.protein_g),
-            carbohydrates_g: dec_to_f64(item.carbohydrates_g),
-            fat_g: dec_to_f64(item.fat_g),
-            fiber_g: dec_to_f64(item.fiber_g),
-            sugar_g: dec_to_f64(item.sugar_g),
-            source: item.source,
-            verified: item.verified,
-        })
-        .collect();
-
-    Ok(Json(response))
+    Ok(Json(items.into_iter().map(|item| food_item_response(item, &state.config().display)).collect()))
 }
 
 /// GET /api/v1/nutrition/barcode/:code - Lookup food by barcode
@@ -82,24 +161,111 @@ async fn lookup_barcode(
 ) -> Result<Json<Option<FoodItemResponse>>, ApiError> {
     let item = NutritionService::lookup_barcode(state.db(), &code).await?;
 
-    let response = item.map(|item| FoodItemResponse {
-        id: item.id.to_string(),
-        name: item.name,
-        brand: item.brand,
-        barcode: item.barcode,
-        serving_size: dec_to_f64(item.serving_size),
-        serving_unit: item.serving_unit,
-        calories: dec_to_f64(item.calories),
-        protein_g: dec_to_f64(item.protein_g),
-        carbohydrates_g: dec_to_f64(item.carbohydrates_g),
-        fat_g: dec_to_f64(item.fat_g),
-        fiber_g: dec_to_f64(item.fiber_g),
-        sugar_g: dec_to_f64(item.sugar_g),
-        source: item.source,
-        verified: item.verified,
-    });
+    Ok(Json(item.map(|item| food_item_response(item, &state.config().display))))
+}
 
-    Ok(Json(response))
+/// PUT /api/v1/nutrition/foods/:food_id - Edit a food the user created
+async fn update_food_item(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(food_id): Path<String>,
+    Json(req): Json<UpdateFoodItemRequest>,
+) -> Result<Json<FoodItemResponse>, ApiError> {
+    let food_item_id = Uuid::parse_str(&food_id)
+        .map_err(|_| ApiError::Validation("Invalid food_item_id".to_string()))?;
+
+    let changes = UpdateFoodItem {
+        name: req.name,
+        brand: req.brand,
+        serving_size: req.serving_size.map(f64_to_dec),
+        serving_unit: req.serving_unit,
+        calories: req.calories.map(f64_to_dec),
+        protein_g: req.protein_g.map(f64_to_dec),
+        carbohydrates_g: req.carbohydrates_g.map(f64_to_dec),
+        fat_g: req.fat_g.map(f64_to_dec),
+        fiber_g: req.fiber_g.map(f64_to_dec),
+        sugar_g: req.sugar_g.map(f64_to_dec),
+        sodium_mg: req.sodium_mg.map(f64_to_dec),
+        alcohol_g: req.alcohol_g.map(f64_to_dec),
+    };
+    let item = NutritionService::update_food_item(
+        state.db(),
+        state.cache(),
+        auth.user_id,
+        food_item_id,
+        changes,
+    )
+    .await?;
+
+    Ok(Json(food_item_response(item, &state.config().display)))
+}
+
+/// GET /api/v1/nutrition/recent - Foods the user logged most recently
+async fn get_recent_foods(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<RecentFoodsQuery>,
+) -> Result<Json<Vec<FoodItemResponse>>, ApiError> {
+    let limit = pagination::clamp_limit(query.limit, DEFAULT_RECENT_FOODS_LIMIT, MAX_RECENT_FOODS);
+    let items = NutritionService::get_recent_foods(state.db(), auth.user_id, limit).await?;
+
+    Ok(Json(items.into_iter().map(|item| food_item_response(item, &state.config().display)).collect()))
+}
+
+/// GET /api/v1/nutrition/favorites - List the user's favorite foods
+async fn get_favorite_foods(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<FoodItemResponse>>, ApiError> {
+    let items = NutritionService::get_favorite_foods(state.db(), auth.user_id).await?;
+
+    Ok(Json(items.into_iter().map(|item| food_item_response(item, &state.config().display)).collect()))
+}
+
+/// POST /api/v1/nutrition/favorites/:food_id - Toggle a food's favorite status
+async fn toggle_favorite(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(food_id): Path<String>,
+) -> Result<Json<FavoriteFoodResponse>, ApiError> {
+    let food_item_id = Uuid::parse_str(&food_id)
+        .map_err(|_| ApiError::Validation("Invalid food_item_id".to_string()))?;
+
+    let is_favorite =
+        NutritionService::toggle_favorite(state.db(), auth.user_id, food_item_id).await?;
+
+    Ok(Json(FavoriteFoodResponse {
+        food_item_id: food_item_id.to_string(),
+        is_favorite,
+    }))
+}
+
+/// GET /api/v1/nutrition/foods/:food_id/preview - Nutrition for some servings before logging
+async fn preview_nutrition(
+    State(state): State<AppState>,
+    _auth: AuthUser,
+    Path(food_id): Path<String>,
+    Query(query): Query<NutritionPreviewQuery>,
+) -> Result<Json<NutritionPreviewResponse>, ApiError> {
+    let food_item_id = Uuid::parse_str(&food_id)
+        .map_err(|_| ApiError::Validation("Invalid food_item_id".to_string()))?;
+
+    let preview =
+        NutritionService::preview_nutrition(state.db(), food_item_id, f64_to_dec(query.servings))
+            .await?;
+    let display = &state.config().display;
+
+    Ok(Json(NutritionPreviewResponse {
+        food_item_id: preview.food_item_id.to_string(),
+        servings: dec_to_f64(preview.servings),
+        calories: display.calories(preview.calories),
+        protein_g: display.macros(preview.protein_g),
+        carbohydrates_g: display.macros(preview.carbohydrates_g),
+        fat_g: display.macros(preview.fat_g),
+        fiber_g: display.macros(preview.fiber_g),
+        sugar_g: display.macros(preview.sugar_g),
+        alcohol_g: display.macros(preview.alcohol_g),
+    }))
 }
 
 /// POST /api/v1/nutrition/log - Log a food entry
@@ -138,18 +304,8 @@ async fn log_food(
     };
 
     Ok(Json(FoodLogResponse {
-        id: log.id.to_string(),
-        food_item_id: log.food_item_id.map(|id| id.to_string()),
         food_name,
-        servings: dec_to_f64(log.servings),
-        calories: dec_to_f64(log.calories),
-        protein_g: dec_to_f64(log.protein_g),
-        carbohydrates_g: dec_to_f64(log.carbohydrates_g),
-        fat_g: dec_to_f64(log.fat_g),
-        fiber_g: dec_to_f64(log.fiber_g),
-        meal_type: log.meal_type,
-        consumed_at: log.consumed_at,
-        notes: log.notes,
+        ..food_log_response(log, &state.config().display)
     }))
 }
 
@@ -173,45 +329,88 @@ async fn get_daily_summary(
     auth: AuthUser,
     Query(query): Query<DateQuery>,
 ) -> Result<Json<DailyNutritionResponse>, ApiError> {
-    let summary = NutritionService::get_daily_summary(state.db(), auth.user_id, query.date).await?;
-    let logs = NutritionService::get_logs_by_date(state.db(), auth.user_id, query.date).await?;
+    let tz = timezone::user_timezone(state.db(), auth.user_id).await;
+    let summary =
+        NutritionService::get_daily_summary(state.db(), auth.user_id, query.date, tz).await?;
+    let logs = NutritionService::get_logs_by_date(state.db(), auth.user_id, query.date, tz).await?;
 
-    let log_responses: Vec<FoodLogResponse> = logs
-        .into_iter()
-        .map(|log| FoodLogResponse {
-            id: log.id.to_string(),
-            food_item_id: log.food_item_id.map(|id| id.to_string()),
-            food_name: log.custom_name,
-            servings: dec_to_f64(log.servings),
-            calories: dec_to_f64(log.calories),
-            protein_g: dec_to_f64(log.protein_g),
-            carbohydrates_g: dec_to_f64(log.carbohydrates_g),
-            fat_g: dec_to_f64(log.fat_g),
-            fiber_g: dec_to_f64(log.fiber_g),
-            meal_type: log.meal_type,
-            consumed_at: log.consumed_at,
-            notes: log.notes,
-        })
-        .collect();
+    let log_responses: Vec<FoodLogResponse> = logs.into_iter().map(|log| food_log_response(log, &state.config().display)).collect();
 
+    let display = &state.config().display;
     Ok(Json(DailyNutritionResponse {
         date: summary.date,
-        total_calories: dec_to_f64(summary.total_calories),
-        total_protein_g: dec_to_f64(summary.total_protein_g),
-        total_carbs_g: dec_to_f64(summary.total_carbs_g),
-        total_fat_g: dec_to_f64(summary.total_fat_g),
-        total_fiber_g: dec_to_f64(summary.total_fiber_g),
+        total_calories: display.calories(summary.total_calories),
+        total_protein_g: display.macros(summary.total_protein_g),
+        total_carbs_g: display.macros(summary.total_carbs_g),
+        total_fat_g: display.macros(summary.total_fat_g),
+        total_fiber_g: display.macros(summary.total_fiber_g),
+        total_net_carbs_g: display.macros(summary.total_net_carbs_g),
+        total_sugar_g: display.macros(summary.total_sugar_g),
+        total_sodium_mg: dec_to_f64(summary.total_sodium_mg),
+        total_alcohol_g: display.macros(summary.total_alcohol_g),
         meal_count: summary.meal_count,
         logs: log_responses,
     }))
 }
 
+/// POST /api/v1/nutrition/copy-day - Copy a day's food logs onto another date
+async fn copy_day(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(req): Json<CopyDayRequest>,
+) -> Result<Json<CopyDayResponse>, ApiError> {
+    let tz = timezone::user_timezone(state.db(), auth.user_id).await;
+    let copied =
+        NutritionService::copy_day(state.db(), auth.user_id, req.from_date, req.to_date, tz)
+            .await?;
+
+    Ok(Json(CopyDayResponse { copied }))
+}
+
+/// POST /api/v1/nutrition/suggestions - Suggest meals for the remaining macros
+///
+/// Requires AI to be enabled and the user's `ai_meal_suggestions` flag.
+async fn suggest_meals(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(req): Json<MealSuggestionRequest>,
+) -> Result<Json<Vec<MealSuggestionResponse>>, ApiError> {
+    // The model can be changed by a config reload
+    let config = AiConfig {
+        model: state.reloadable().ai_model.clone(),
+        ..state.config().ai.clone()
+    };
+    let remaining = MacroTargets {
+        calories: req.calories,
+        protein_g: req.protein_g,
+        carbohydrates_g: req.carbohydrates_g,
+        fat_g: req.fat_g,
+    };
+
+    let suggestions =
+        MealSuggestionService::suggest(state.db(), &config, auth.user_id, remaining).await?;
+
+    Ok(Json(
+        suggestions
+            .into_iter()
+            .map(|s| MealSuggestionResponse {
+                name: s.name,
+                description: s.description,
+                calories: s.calories,
+                protein_g: s.protein_g,
+                carbohydrates_g: s.carbohydrates_g,
+                fat_g: s.fat_g,
+            })
+            .collect(),
+    ))
+}
+
 /// POST /api/v1/nutrition/recipes - Create a new recipe
 async fn create_recipe(
     State(state): State<AppState>,
     auth: AuthUser,
     Json(req): Json<CreateRecipeRequest>,
-) -> Result<Json<RecipeResponse>, ApiError> {
+) -> Result<(StatusCode, Json<RecipeResponse>), ApiError> {
     let recipe = NutritionService::create_recipe(
         state.db(),
         auth.user_id,
@@ -241,19 +440,10 @@ async fn create_recipe(
     // Re-fetch recipe to get updated nutrition values
     let updated_recipe = NutritionService::get_recipe(state.db(), auth.user_id, recipe.id).await?;
 
-    Ok(Json(RecipeResponse {
-        id: updated_recipe.id.to_string(),
-        name: updated_recipe.name,
-        description: updated_recipe.description,
-        servings: dec_to_f64(updated_recipe.servings),
-        calories_per_serving: dec_to_f64(updated_recipe.calories_per_serving),
-        protein_per_serving: dec_to_f64(updated_recipe.protein_per_serving),
-        carbs_per_serving: dec_to_f64(updated_recipe.carbs_per_serving),
-        fat_per_serving: dec_to_f64(updated_recipe.fat_per_serving),
-        fiber_per_serving: dec_to_f64(updated_recipe.fiber_per_serving),
-        is_public: updated_recipe.is_public,
-        created_at: updated_recipe.created_at,
-    }))
+    Ok((
+        StatusCode::CREATED,
+        Json(recipe_response(updated_recipe, &state.config().display)),
+    ))
 }
 
 /// GET /api/v1/nutrition/recipes - List user's recipes
@@ -265,19 +455,7 @@ async fn list_recipes(
 
     let response: Vec<RecipeResponse> = recipes
         .into_iter()
-        .map(|r| RecipeResponse {
-            id: r.id.to_string(),
-            name: r.name,
-            description: r.description,
-            servings: dec_to_f64(r.servings),
-            calories_per_serving: dec_to_f64(r.calories_per_serving),
-            protein_per_serving: dec_to_f64(r.protein_per_serving),
-            carbs_per_serving: dec_to_f64(r.carbs_per_serving),
-            fat_per_serving: dec_to_f64(r.fat_per_serving),
-            fiber_per_serving: dec_to_f64(r.fiber_per_serving),
-            is_public: r.is_public,
-            created_at: r.created_at,
-        })
+        .map(|r| recipe_response(r, &state.config().display))
         .collect();
 
     Ok(Json(response))
@@ -315,19 +493,7 @@ async fn get_recipe(
     }
 
     Ok(Json(RecipeDetailResponse {
-        recipe: RecipeResponse {
-            id: recipe.id.to_string(),
-            name: recipe.name,
-            description: recipe.description,
-            servings: dec_to_f64(recipe.servings),
-            calories_per_serving: dec_to_f64(recipe.calories_per_serving),
-            protein_per_serving: dec_to_f64(recipe.protein_per_serving),
-            carbs_per_serving: dec_to_f64(recipe.carbs_per_serving),
-            fat_per_serving: dec_to_f64(recipe.fat_per_serving),
-            fiber_per_serving: dec_to_f64(recipe.fiber_per_serving),
-            is_public: recipe.is_public,
-            created_at: recipe.created_at,
-        },
+        recipe: recipe_response(recipe, &state.config().display),
         ingredients: ingredient_responses,
     }))
 }
@@ -400,3 +566,118 @@ async fn remove_ingredient(
 
     Ok(Json(()))
 }
+
+/// Build a meal template response, resolving food names
+async fn meal_template_response(
+    state: &AppState,
+    user_id: Uuid,
+    template: MealTemplate,
+) -> Result<MealTemplateResponse, ApiError> {
+    let items = NutritionService::get_meal_template_items(state.db(), user_id, template.id).await?;
+
+    let food_ids: Vec<Uuid> = items.iter().map(|item| item.food_item_id).collect();
+    let food_names: HashMap<Uuid, String> =
+        FoodItemRepository::find_by_ids(state.db(), &food_ids)
+            .await
+            .map_err(ApiError::Internal)?
+            .into_iter()
+            .map(|food| (food.id, food.name))
+            .collect();
+
+    let item_responses = items
+        .into_iter()
+        .map(|item| {
+            // Template items cascade with their food, so a miss is a race with a delete
+            let food_name = food_names
+                .get(&item.food_item_id)
+                .cloned()
+                .ok_or_else(|| ApiError::NotFound("Food item not found".to_string()))?;
+
+            Ok(MealTemplateItemResponse {
+                food_item_id: item.food_item_id.to_string(),
+                food_name,
+                servings: dec_to_f64(item.servings),
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    Ok(MealTemplateResponse {
+        id: template.id.to_string(),
+        name: template.name,
+        items: item_responses,
+        created_at: template.created_at,
+    })
+}
+
+/// POST /api/v1/nutrition/templates - Save a meal template
+async fn create_meal_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Json(req): Json<CreateMealTemplateRequest>,
+) -> Result<Json<MealTemplateResponse>, ApiError> {
+    let items = req
+        .items
+        .into_iter()
+        .map(|item| {
+            Uuid::parse_str(&item.food_item_id)
+                .map(|id| (id, f64_to_dec(item.servings)))
+                .map_err(|_| ApiError::Validation("Invalid food_item_id".to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let template =
+        NutritionService::save_meal_template(state.db(), auth.user_id, req.name, items).await?;
+
+    Ok(Json(meal_template_response(&state, auth.user_id, template).await?))
+}
+
+/// GET /api/v1/nutrition/templates - List user's meal templates
+async fn list_meal_templates(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<Vec<MealTemplateResponse>>, ApiError> {
+    let templates = NutritionService::get_meal_templates(state.db(), auth.user_id).await?;
+
+    let mut response = Vec::with_capacity(templates.len());
+    for template in templates {
+        response.push(meal_template_response(&state, auth.user_id, template).await?);
+    }
+
+    Ok(Json(response))
+}
+
+/// POST /api/v1/nutrition/templates/:id/log - Log every food in a template
+async fn log_meal_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+    Json(req): Json<LogMealTemplateRequest>,
+) -> Result<Json<Vec<FoodLogResponse>>, ApiError> {
+    let template_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid template ID".to_string()))?;
+
+    let logs = NutritionService::log_meal_template(
+        state.db(),
+        auth.user_id,
+        template_id,
+        req.meal_type,
+        req.consumed_at,
+    )
+    .await?;
+
+    Ok(Json(logs.into_iter().map(|log| food_log_response(log, &state.config().display)).collect()))
+}
+
+/// DELETE /api/v1/nutrition/templates/:id - Delete a meal template
+async fn delete_meal_template(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<()>, ApiError> {
+    let template_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid template ID".to_string()))?;
+
+    NutritionService::delete_meal_template(state.db(), auth.user_id, template_id).await?;
+
+    Ok(Json(()))
+}
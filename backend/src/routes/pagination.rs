@@ -0,0 +1,37 @@
+//! Page size bounds shared by list endpoints
+
+/// Largest page any list endpoint returns
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+/// Page size used by list endpoints without their own default
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+
+/// Number of items to return for a requested limit
+///
+/// A missing limit uses `default`; anything else is clamped to `1..=max`.
+pub fn clamp_limit(requested: Option<i64>, default: i64, max: i64) -> i64 {
+    requested.unwrap_or(default).clamp(1, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_over_max_limit_is_clamped() {
+        assert_eq!(clamp_limit(Some(500), DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE), MAX_PAGE_SIZE);
+        assert_eq!(clamp_limit(Some(80), 10, 50), 50);
+    }
+
+    #[test]
+    fn test_missing_limit_uses_default() {
+        assert_eq!(clamp_limit(None, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE), DEFAULT_PAGE_SIZE);
+        assert_eq!(clamp_limit(None, 20, MAX_PAGE_SIZE), 20);
+    }
+
+    #[test]
+    fn test_non_positive_limit_returns_one_item() {
+        assert_eq!(clamp_limit(Some(0), DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE), 1);
+        assert_eq!(clamp_limit(Some(-5), DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE), 1);
+    }
+}
@@ -9,8 +9,9 @@ use axum::{
     routing::get,
     Json, Router,
 };
+use fitness_assistant_shared::health_metrics::DerivedMetrics;
 use fitness_assistant_shared::types::{
-    HealthInsightsResponse, UpdateProfileRequest, UpdateSettingsRequest,
+    HealthInsightsResponse, ProfileCompleteness, UpdateProfileRequest, UpdateSettingsRequest,
     UserProfileResponse, UserSettingsResponse,
 };
 
@@ -20,6 +21,8 @@ pub fn profile_routes() -> Router<AppState> {
         .route("/", get(get_profile).put(update_profile))
         .route("/settings", get(get_settings).put(update_settings))
         .route("/insights", get(get_health_insights))
+        .route("/completeness", get(get_completeness))
+        .route("/derived-metrics", get(get_derived_metrics))
 }
 
 /// GET /api/v1/profile - Get user profile
@@ -27,7 +30,7 @@ async fn get_profile(
     State(state): State<AppState>,
     auth: AuthUser,
 ) -> Result<Json<UserProfileResponse>, ApiError> {
-    let profile = ProfileService::get_profile(state.db(), auth.user_id).await?;
+    let profile = ProfileService::get_profile(state.db(), state.cache(), auth.user_id).await?;
     Ok(Json(profile))
 }
 
@@ -43,7 +46,9 @@ async fn update_profile(
     auth: AuthUser,
     Json(req): Json<UpdateProfileRequest>,
 ) -> Result<Json<UserProfileResponse>, ApiError> {
-    let profile = ProfileService::update_profile(state.db(), auth.user_id, req).await?;
+    let profile =
+        ProfileService::update_profile(state.db(), state.jobs(), state.cache(), auth.user_id, req)
+            .await?;
     Ok(Json(profile))
 }
 
@@ -62,7 +67,9 @@ async fn update_settings(
     auth: AuthUser,
     Json(req): Json<UpdateSettingsRequest>,
 ) -> Result<Json<UserSettingsResponse>, ApiError> {
-    let settings = ProfileService::update_settings(state.db(), auth.user_id, req).await?;
+    let settings =
+        ProfileService::update_settings(state.db(), state.jobs(), state.cache(), auth.user_id, req)
+            .await?;
     Ok(Json(settings))
 }
 
@@ -75,6 +82,28 @@ async fn get_health_insights(
     State(state): State<AppState>,
     auth: AuthUser,
 ) -> Result<Json<HealthInsightsResponse>, ApiError> {
-    let insights = HealthInsightsService::get_insights(state.db(), auth.user_id).await?;
+    let insights = HealthInsightsService::get_insights(state.db(), &state.config().tdee, auth.user_id).await?;
     Ok(Json(insights))
 }
+
+/// GET /api/v1/profile/derived-metrics - Get all derived health metrics
+///
+/// Returns 400 listing the missing fields when the profile is incomplete.
+async fn get_derived_metrics(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<DerivedMetrics>, ApiError> {
+    let metrics =
+        HealthInsightsService::get_derived_metrics(state.db(), &state.config().tdee, auth.user_id)
+            .await?;
+    Ok(Json(metrics))
+}
+
+/// GET /api/v1/profile/completeness - Get profile completeness score
+async fn get_completeness(
+    State(state): State<AppState>,
+    auth: AuthUser,
+) -> Result<Json<ProfileCompleteness>, ApiError> {
+    let completeness = ProfileService::completeness(state.db(), auth.user_id).await?;
+    Ok(Json(completeness))
+}
@@ -1,5 +1,6 @@
 //! Sleep tracking API routes
 
+use super::pagination;
 use crate::auth::AuthUser;
 use crate::error::ApiError;
 use crate::services::sleep::{LogSleepInput, SetSleepGoalInput, SleepService};
@@ -14,6 +15,9 @@ use fitness_assistant_shared::types::{
     SleepGoalResponse, SleepHistoryQuery, SleepHistoryResponse, SleepLogResponse,
 };
 
+/// Sleep logs returned by history when no limit is given
+const DEFAULT_HISTORY_LIMIT: i64 = 30;
+
 /// Create sleep routes
 pub fn sleep_routes() -> Router<AppState> {
     Router::new()
@@ -33,6 +37,8 @@ async fn log_sleep(
     let input = LogSleepInput {
         sleep_start: req.sleep_start,
         sleep_end: req.sleep_end,
+        time_in_bed_minutes: req.time_in_bed_minutes,
+        sleep_onset_minutes: req.sleep_onset_minutes,
         awake_minutes: req.awake_minutes,
         light_minutes: req.light_minutes,
         deep_minutes: req.deep_minutes,
@@ -54,6 +60,8 @@ async fn log_sleep(
         sleep_start: log.sleep_start,
         sleep_end: log.sleep_end,
         total_duration_minutes: log.total_duration_minutes,
+        time_in_bed_minutes: log.time_in_bed_minutes,
+        sleep_onset_minutes: log.sleep_onset_minutes,
         awake_minutes: log.awake_minutes,
         light_minutes: log.light_minutes,
         deep_minutes: log.deep_minutes,
@@ -76,19 +84,20 @@ async fn get_history(
     auth: AuthUser,
     Query(query): Query<SleepHistoryQuery>,
 ) -> Result<Json<SleepHistoryResponse>, ApiError> {
-    let query = query.normalize();
-    
+    let limit = pagination::clamp_limit(query.limit, DEFAULT_HISTORY_LIMIT, pagination::MAX_PAGE_SIZE);
+    let offset = query.offset.max(0);
+
     let (logs, total) = SleepService::get_history(
         state.db(),
         auth.user_id,
         query.start_date,
         query.end_date,
-        query.limit,
-        query.offset,
+        limit,
+        offset,
     )
     .await?;
 
-    let has_more = query.offset + (logs.len() as i64) < total;
+    let has_more = offset + (logs.len() as i64) < total;
 
     Ok(Json(SleepHistoryResponse {
         items: logs
@@ -98,6 +107,8 @@ async fn get_history(
                 sleep_start: log.sleep_start,
                 sleep_end: log.sleep_end,
                 total_duration_minutes: log.total_duration_minutes,
+                time_in_bed_minutes: log.time_in_bed_minutes,
+                sleep_onset_minutes: log.sleep_onset_minutes,
                 awake_minutes: log.awake_minutes,
                 light_minutes: log.light_minutes,
                 deep_minutes: log.deep_minutes,
@@ -114,8 +125,8 @@ async fn get_history(
             })
             .collect(),
         total_count: total,
-        limit: query.limit,
-        offset: query.offset,
+        limit,
+        offset,
         has_more,
     }))
 }
@@ -141,6 +152,9 @@ async fn get_analysis(
         avg_rem_percent: analysis.avg_rem_percent,
         avg_light_percent: analysis.avg_light_percent,
         avg_awake_percent: analysis.avg_awake_percent,
+        avg_onset_latency_minutes: analysis.avg_onset_latency_minutes,
+        prolonged_onset_latency: analysis.prolonged_onset_latency,
+        chronotype: analysis.chronotype.as_str().to_string(),
         total_nights: analysis.total_nights,
         sleep_debt_minutes: analysis.sleep_debt_minutes,
         consistency_score: analysis.consistency_score,
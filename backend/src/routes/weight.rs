@@ -1,36 +1,49 @@
 //! Weight and body composition API routes
 
+use super::pagination;
 use crate::auth::AuthUser;
 use crate::error::ApiError;
 use crate::repositories::UserRepository;
-use crate::services::weight::{BodyCompositionInput, WeightEntryInput, WeightService};
+use crate::services::cycle::CycleService;
+use crate::services::formatting;
+use crate::services::weight::{
+    BodyCompositionInput, LogCadence, WeightEntryInput, WeightService,
+};
 use crate::state::AppState;
+use crate::timezone;
 use axum::{
-    extract::{Query, State},
-    routing::{get, post},
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, post},
     Json, Router,
 };
 use fitness_assistant_shared::types::{
     BodyCompositionResponse, GoalProjectionRequest, GoalProjectionResponse,
     LogBodyCompositionRequest, LogWeightRequest, WeightHistoryQuery, WeightHistoryResponse,
-    WeightLogResponse, WeightTrendResponse,
+    WeightLogResponse, WeightReminderQuery, WeightReminderResponse, WeightTrendQuery,
+    WeightTrendResponse,
 };
-use fitness_assistant_shared::units::WeightUnit;
+use fitness_assistant_shared::units::{UnitPreferences, WeightUnit};
+use uuid::Uuid;
 
 /// Create weight routes
 pub fn weight_routes() -> Router<AppState> {
     Router::new()
         .route("/", post(log_weight).get(get_weight_history))
+        .route("/:id", delete(delete_weight))
+        .route("/:id/restore", post(restore_weight))
         .route("/trend", get(get_weight_trend))
+        .route("/reminder", get(get_reminder))
         .route("/projection", post(project_goal))
         .route("/body-composition", post(log_body_composition).get(get_body_composition_history))
 }
 
-/// Parse weight unit from string, defaulting to kg
-fn parse_weight_unit(unit_str: Option<&str>) -> WeightUnit {
+/// Parse the unit a client declared, rejecting ones we don't know
+fn parse_weight_unit(unit_str: Option<&str>) -> Result<Option<WeightUnit>, ApiError> {
     unit_str
-        .and_then(|s| s.parse::<WeightUnit>().ok())
-        .unwrap_or(WeightUnit::Kg)
+        .map(str::parse::<WeightUnit>)
+        .transpose()
+        .map_err(ApiError::Validation)
 }
 
 /// Get user's preferred weight unit from settings
@@ -43,107 +56,195 @@ async fn get_user_weight_unit(state: &AppState, user_id: uuid::Uuid) -> WeightUn
         .unwrap_or(WeightUnit::Kg)
 }
 
+/// Weight with its unit for display, e.g. `154.3 lbs`
+fn weight_display(weight_kg: f64, unit: WeightUnit) -> String {
+    let prefs = UnitPreferences {
+        weight: unit,
+        ..Default::default()
+    };
+    formatting::format_weight(weight_kg, &prefs).display
+}
+
 /// POST /api/v1/weight - Log a weight entry
 /// 
-/// Accepts weight in any unit (kg, lbs, stone). If no unit specified,
-/// defaults to kg. Stores internally in kg, returns in user's preferred unit.
+/// Accepts weight in any unit (kg, lbs, stone). If no unit specified, the
+/// weight is taken as kg unless it looks like a unit mix-up against the
+/// user's recent weight. Stores internally in kg, returns in user's preferred unit.
 async fn log_weight(
     State(state): State<AppState>,
     auth: AuthUser,
     Json(req): Json<LogWeightRequest>,
-) -> Result<Json<WeightLogResponse>, ApiError> {
-    // Parse input unit (defaults to kg if not specified)
-    let input_unit = parse_weight_unit(req.unit.as_deref());
-    
-    // Convert to kg for storage
-    let weight_kg = input_unit.to_kg(req.weight);
-    
+) -> Result<(StatusCode, Json<WeightLogResponse>), ApiError> {
     let input = WeightEntryInput {
-        weight_kg,
+        weight: req.weight,
+        unit: parse_weight_unit(req.unit.as_deref())?,
         recorded_at: req.recorded_at,
         source: req.source,
         notes: req.notes,
+        dedup: req.dedup,
     };
 
-    let log = WeightService::log_weight(state.db(), auth.user_id, input).await?;
+    let log = WeightService::log_weight(
+        state.db(),
+        state.jobs(),
+        &state.config().weight,
+        auth.user_id,
+        input,
+    )
+    .await?;
 
     // Get user's preferred unit for response
     let preferred_unit = get_user_weight_unit(&state, auth.user_id).await;
-    let weight_in_preferred = preferred_unit.from_kg(log.weight_kg);
+    let display = &state.config().display;
+    let weight_in_preferred = display.weight(preferred_unit.from_kg(log.weight_kg));
 
-    Ok(Json(WeightLogResponse {
-        id: log.id.to_string(),
-        weight: weight_in_preferred,
-        unit: preferred_unit.to_string(),
-        weight_kg: log.weight_kg,
-        recorded_at: log.recorded_at,
-        source: log.source,
-        notes: log.notes,
-        is_anomaly: log.is_anomaly,
-    }))
+    Ok((
+        StatusCode::CREATED,
+        Json(WeightLogResponse {
+            id: log.id.to_string(),
+            weight: weight_in_preferred,
+            unit: preferred_unit.to_string(),
+            display: weight_display(log.weight_kg, preferred_unit),
+            weight_kg: log.weight_kg,
+            recorded_at: log.recorded_at,
+            source: log.source,
+            notes: log.notes,
+            is_anomaly: log.is_anomaly,
+            likely_cause: log.likely_cause.map(|cause| cause.hint().to_string()),
+            cycle_phase: None,
+        }),
+    ))
 }
 
 /// GET /api/v1/weight - Get weight history with pagination
 /// 
 /// Returns weight entries in user's preferred unit.
 /// Supports pagination with limit (default: 50, max: 100) and offset parameters.
+/// Entries are tagged with their cycle phase for users tracking their cycle.
 async fn get_weight_history(
     State(state): State<AppState>,
     auth: AuthUser,
     Query(query): Query<WeightHistoryQuery>,
 ) -> Result<Json<WeightHistoryResponse>, ApiError> {
-    // Normalize pagination parameters
-    let query = query.normalize();
-    
+    let limit = pagination::clamp_limit(query.limit, pagination::DEFAULT_PAGE_SIZE, pagination::MAX_PAGE_SIZE);
+    let offset = query.offset.max(0);
+
     let (logs, total_count) = WeightService::get_weight_history_paginated(
         state.db(),
         auth.user_id,
         query.start,
         query.end,
-        query.limit,
-        query.offset,
+        limit,
+        offset,
     )
     .await?;
 
     // Get user's preferred unit
     let preferred_unit = get_user_weight_unit(&state, auth.user_id).await;
+    let display = &state.config().display;
+
+    // Annotate readings with the cycle phase for users tracking their cycle
+    let tracker = CycleService::tracker(state.db(), auth.user_id).await?;
+    let tz = timezone::user_timezone(state.db(), auth.user_id).await;
 
     let items: Vec<WeightLogResponse> = logs
         .into_iter()
         .map(|log| {
-            let weight_in_preferred = preferred_unit.from_kg(log.weight_kg);
+            let weight_in_preferred = display.weight(preferred_unit.from_kg(log.weight_kg));
+            let cycle_phase = tracker
+                .as_ref()
+                .and_then(|tracker| tracker.phase_on(log.recorded_at.with_timezone(&tz).date_naive()))
+                .map(|day| day.phase.as_str().to_string());
             WeightLogResponse {
                 id: log.id.to_string(),
                 weight: weight_in_preferred,
                 unit: preferred_unit.to_string(),
+                display: weight_display(log.weight_kg, preferred_unit),
                 weight_kg: log.weight_kg,
                 recorded_at: log.recorded_at,
                 source: log.source,
                 notes: log.notes,
                 is_anomaly: log.is_anomaly,
+                likely_cause: log.likely_cause.map(|cause| cause.hint().to_string()),
+                cycle_phase,
             }
         })
         .collect();
 
-    let has_more = query.offset + (items.len() as i64) < total_count;
+    let has_more = offset + (items.len() as i64) < total_count;
 
     Ok(Json(WeightHistoryResponse {
         items,
         total_count,
-        limit: query.limit,
-        offset: query.offset,
+        limit,
+        offset,
         has_more,
     }))
 }
 
+/// DELETE /api/v1/weight/:id - Delete a weight entry
+///
+/// Entries are soft-deleted and can be restored until they are purged.
+async fn delete_weight(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let log_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid weight entry ID".to_string()))?;
+
+    WeightService::delete_weight(state.db(), state.jobs(), auth.user_id, log_id).await?;
+
+    Ok(Json(serde_json::json!({"deleted": true})))
+}
+
+/// POST /api/v1/weight/:id/restore - Restore a deleted weight entry
+async fn restore_weight(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<WeightLogResponse>, ApiError> {
+    let log_id = Uuid::parse_str(&id)
+        .map_err(|_| ApiError::Validation("Invalid weight entry ID".to_string()))?;
+
+    let log = WeightService::restore_weight(state.db(), state.jobs(), auth.user_id, log_id).await?;
+    let preferred_unit = get_user_weight_unit(&state, auth.user_id).await;
+
+    Ok(Json(WeightLogResponse {
+        id: log.id.to_string(),
+        weight: state.config().display.weight(preferred_unit.from_kg(log.weight_kg)),
+        unit: preferred_unit.to_string(),
+        display: weight_display(log.weight_kg, preferred_unit),
+        weight_kg: log.weight_kg,
+        recorded_at: log.recorded_at,
+        source: log.source,
+        notes: log.notes,
+        is_anomaly: log.is_anomaly,
+        likely_cause: log.likely_cause.map(|cause| cause.hint().to_string()),
+        cycle_phase: None,
+    }))
+}
+
 /// GET /api/v1/weight/trend - Get weight trend analysis
+///
+/// `?windows=14,90` adds those moving averages to `moving_averages`.
 async fn get_weight_trend(
     State(state): State<AppState>,
     auth: AuthUser,
-    Query(query): Query<WeightHistoryQuery>,
+    Query(query): Query<WeightTrendQuery>,
 ) -> Result<Json<WeightTrendResponse>, ApiError> {
-    let trend =
-        WeightService::get_weight_trend(state.db(), auth.user_id, query.start, query.end).await?;
+    let windows = match query.windows.as_deref() {
+        Some(windows) => WeightService::parse_trend_windows(windows)?,
+        None => Vec::new(),
+    };
+    let trend = WeightService::get_weight_trend(
+        state.db(),
+        auth.user_id,
+        query.start,
+        query.end,
+        &windows,
+    )
+    .await?;
 
     Ok(Json(WeightTrendResponse {
         current_weight: trend.current_weight,
@@ -152,10 +253,34 @@ async fn get_weight_trend(
         average_daily_change: trend.average_daily_change,
         moving_average_7d: trend.moving_average_7d,
         moving_average_30d: trend.moving_average_30d,
+        moving_averages: trend.moving_averages,
         entries_count: trend.entries_count,
     }))
 }
 
+/// GET /api/v1/weight/reminder - Check whether a weigh-in is overdue
+async fn get_reminder(
+    State(state): State<AppState>,
+    auth: AuthUser,
+    Query(query): Query<WeightReminderQuery>,
+) -> Result<Json<WeightReminderResponse>, ApiError> {
+    let cadence = query
+        .cadence
+        .as_deref()
+        .map(str::parse::<LogCadence>)
+        .transpose()
+        .map_err(ApiError::Validation)?
+        .unwrap_or_default();
+
+    let days_since_last_log = WeightService::days_since_last_log(state.db(), auth.user_id).await?;
+
+    Ok(Json(WeightReminderResponse {
+        days_since_last_log,
+        should_remind: days_since_last_log
+            .is_some_and(|days| WeightService::should_remind(days, cadence)),
+    }))
+}
+
 /// POST /api/v1/weight/projection - Project goal completion
 async fn project_goal(
     State(state): State<AppState>,
@@ -181,7 +306,7 @@ async fn log_body_composition(
     State(state): State<AppState>,
     auth: AuthUser,
     Json(req): Json<LogBodyCompositionRequest>,
-) -> Result<Json<BodyCompositionResponse>, ApiError> {
+) -> Result<(StatusCode, Json<BodyCompositionResponse>), ApiError> {
     let input = BodyCompositionInput {
         recorded_at: req.recorded_at,
         body_fat_percent: req.body_fat_percent,
@@ -192,18 +317,22 @@ async fn log_body_composition(
         source: req.source,
     };
 
-    let log = WeightService::log_body_composition(state.db(), auth.user_id, input).await?;
+    let log =
+        WeightService::log_body_composition(state.db(), state.jobs(), auth.user_id, input).await?;
 
-    Ok(Json(BodyCompositionResponse {
-        id: log.id.to_string(),
-        recorded_at: log.recorded_at,
-        body_fat_percent: log.body_fat_percent,
-        muscle_mass_kg: log.muscle_mass_kg,
-        water_percent: log.water_percent,
-        bone_mass_kg: log.bone_mass_kg,
-        visceral_fat: log.visceral_fat,
-        source: log.source,
-    }))
+    Ok((
+        StatusCode::CREATED,
+        Json(BodyCompositionResponse {
+            id: log.id.to_string(),
+            recorded_at: log.recorded_at,
+            body_fat_percent: log.body_fat_percent,
+            muscle_mass_kg: log.muscle_mass_kg,
+            water_percent: log.water_percent,
+            bone_mass_kg: log.bone_mass_kg,
+            visceral_fat: log.visceral_fat,
+            source: log.source,
+        }),
+    ))
 }
 
 /// GET /api/v1/weight/body-composition - Get body composition history
@@ -0,0 +1,514 @@
+//! AI-assisted features backed by a local Ollama model
+//!
+//! Provides:
+//! - Meal suggestions that fit the user's remaining daily macros
+//! - Natural-language food entry parsing
+//!
+//! Model calls go to the Ollama `/api/generate` endpoint configured in
+//! `AiConfig` and are only made when `ai.enabled` is set.
+
+use crate::config::AiConfig;
+use crate::error::ApiError;
+use crate::repositories::FoodLogRepository;
+use crate::services::feature_flags::{FeatureFlag, FeatureFlags};
+use crate::timezone;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Macros the user still has available for the day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacroTargets {
+    pub calories: f64,
+    pub protein_g: f64,
+    pub carbohydrates_g: f64,
+    pub fat_g: f64,
+}
+
+/// A single meal suggested by the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MealSuggestion {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub calories: f64,
+    pub protein_g: f64,
+    pub carbohydrates_g: f64,
+    pub fat_g: f64,
+}
+
+/// A food mentioned in a free-text entry, to be matched against the food database
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParsedFoodItem {
+    pub name: String,
+    pub estimated_servings: f64,
+}
+
+/// Result of parsing a free-text food entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParsedFood {
+    pub items: Vec<ParsedFoodItem>,
+}
+
+/// Shape of the JSON document the model is asked to produce
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SuggestionPayload {
+    Wrapped { suggestions: Vec<MealSuggestion> },
+    List(Vec<MealSuggestion>),
+}
+
+/// Request body for Ollama's generate endpoint
+#[derive(Debug, Serialize)]
+struct GenerateRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+    stream: bool,
+    format: &'a str,
+}
+
+/// Non-streaming response from Ollama's generate endpoint
+#[derive(Debug, Deserialize)]
+struct GenerateResponse {
+    response: String,
+}
+
+/// Meal suggestion service
+pub struct MealSuggestionService;
+
+impl MealSuggestionService {
+    /// Suggest meals that fit within the remaining daily macros
+    ///
+    /// Only available to users with the `ai_meal_suggestions` flag. Meal types
+    /// already logged today are passed to the model so it favours the meals
+    /// the user hasn't eaten yet.
+    pub async fn suggest(
+        pool: &PgPool,
+        config: &AiConfig,
+        user_id: Uuid,
+        remaining_macros: MacroTargets,
+    ) -> Result<Vec<MealSuggestion>, ApiError> {
+        ensure_enabled(config)?;
+        FeatureFlags::require(pool, user_id, FeatureFlag::AiMealSuggestions).await?;
+
+        if remaining_macros.calories <= 0.0 {
+            return Err(ApiError::Validation(
+                "No calories remaining for today".to_string(),
+            ));
+        }
+
+        let tz = timezone::user_timezone(pool, user_id).await;
+        let today = timezone::local_today(tz);
+        let logs = FoodLogRepository::get_by_date(pool, user_id, today, tz)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        let mut logged_meals: Vec<String> = logs.into_iter().map(|l| l.meal_type).collect();
+        logged_meals.sort();
+        logged_meals.dedup();
+
+        Self::request_suggestions(config, &remaining_macros, &logged_meals).await
+    }
+
+    /// Prompt the model and parse its reply into suggestions
+    async fn request_suggestions(
+        config: &AiConfig,
+        remaining: &MacroTargets,
+        logged_meals: &[String],
+    ) -> Result<Vec<MealSuggestion>, ApiError> {
+        let prompt = Self::build_prompt(remaining, logged_meals);
+        let raw = generate(config, &prompt).await?;
+        Self::parse_suggestions(&raw)
+    }
+
+    /// Build the prompt sent to the model
+    fn build_prompt(remaining: &MacroTargets, logged_meals: &[String]) -> String {
+        let logged = if logged_meals.is_empty() {
+            "none".to_string()
+        } else {
+            logged_meals.join(", ")
+        };
+
+        format!(
+            "You are a nutrition assistant. Suggest up to 3 meals that together fit within \
+             these remaining daily macros: {:.0} kcal, {:.0} g protein, {:.0} g carbohydrates, \
+             {:.0} g fat. Meals already logged today: {}.\n\
+             Respond only with JSON of the form {{\"suggestions\": [{{\"name\": string, \
+             \"description\": string, \"calories\": number, \"protein_g\": number, \
+             \"carbohydrates_g\": number, \"fat_g\": number}}]}}.",
+            remaining.calories,
+            remaining.protein_g,
+            remaining.carbohydrates_g,
+            remaining.fat_g,
+            logged
+        )
+    }
+
+    /// Parse the model's JSON output, dropping entries with nonsensical values
+    fn parse_suggestions(raw: &str) -> Result<Vec<MealSuggestion>, ApiError> {
+        let payload: SuggestionPayload = serde_json::from_str(raw.trim()).map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to parse meal suggestions: {}", e))
+        })?;
+
+        let suggestions = match payload {
+            SuggestionPayload::Wrapped { suggestions } => suggestions,
+            SuggestionPayload::List(suggestions) => suggestions,
+        };
+
+        Ok(suggestions
+            .into_iter()
+            .filter(|s| {
+                !s.name.trim().is_empty()
+                    && s.calories >= 0.0
+                    && s.protein_g >= 0.0
+                    && s.carbohydrates_g >= 0.0
+                    && s.fat_g >= 0.0
+            })
+            .collect())
+    }
+}
+
+/// Parse a free-text food entry such as "two scrambled eggs and a slice of toast"
+///
+/// Uses the configured model when AI is enabled. Otherwise falls back to a
+/// deterministic parser that understands simple "N x name" entries.
+pub async fn parse_food_entry(config: &AiConfig, text: &str) -> Result<ParsedFood, ApiError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(ApiError::Validation(
+            "Food entry cannot be empty".to_string(),
+        ));
+    }
+
+    if !config.enabled {
+        return parse_food_entry_fallback(text);
+    }
+
+    let prompt = format!(
+        "Extract every food from this meal description: \"{}\".\n\
+         Respond only with JSON of the form {{\"items\": [{{\"name\": string, \
+         \"estimated_servings\": number}}]}}. Use singular generic food names.",
+        text
+    );
+    let raw = generate(config, &prompt).await?;
+
+    let parsed: ParsedFood = serde_json::from_str(raw.trim())
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Failed to parse food entry: {}", e)))?;
+
+    let items: Vec<ParsedFoodItem> = parsed
+        .items
+        .into_iter()
+        .filter(|i| !i.name.trim().is_empty() && i.estimated_servings > 0.0)
+        .map(|i| ParsedFoodItem {
+            name: i.name.trim().to_string(),
+            estimated_servings: i.estimated_servings,
+        })
+        .collect();
+
+    if items.is_empty() {
+        return Err(ApiError::Validation(
+            "No foods recognised in entry".to_string(),
+        ));
+    }
+
+    Ok(ParsedFood { items })
+}
+
+/// Deterministic parser for entries like "2 x eggs, 1.5 x rice and toast"
+///
+/// Entries are split on commas and "and"; each part is either "N x name"
+/// (also "Nx name") or a bare name counted as one serving.
+pub fn parse_food_entry_fallback(text: &str) -> Result<ParsedFood, ApiError> {
+    let mut items = Vec::new();
+
+    for part in text
+        .split(',')
+        .flat_map(|p| p.split(" and "))
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+    {
+        items.push(parse_quantity_part(part)?);
+    }
+
+    if items.is_empty() {
+        return Err(ApiError::Validation(
+            "No foods recognised in entry".to_string(),
+        ));
+    }
+
+    Ok(ParsedFood { items })
+}
+
+/// Parse a single "N x name" or "name" fragment
+fn parse_quantity_part(part: &str) -> Result<ParsedFoodItem, ApiError> {
+    if let Some((quantity, name)) = part.split_once(['x', 'X']) {
+        if let Ok(servings) = quantity.trim().parse::<f64>() {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(ApiError::Validation(format!(
+                    "Missing food name in \"{}\"",
+                    part
+                )));
+            }
+            if !servings.is_finite() || servings <= 0.0 {
+                return Err(ApiError::Validation(format!(
+                    "Servings must be positive in \"{}\"",
+                    part
+                )));
+            }
+            return Ok(ParsedFoodItem {
+                name: name.to_string(),
+                estimated_servings: servings,
+            });
+        }
+    }
+
+    Ok(ParsedFoodItem {
+        name: part.to_string(),
+        estimated_servings: 1.0,
+    })
+}
+
+/// Reject calls when AI features are turned off
+fn ensure_enabled(config: &AiConfig) -> Result<(), ApiError> {
+    if !config.enabled {
+        return Err(ApiError::Validation("AI features disabled".to_string()));
+    }
+    Ok(())
+}
+
+/// Run a single non-streaming completion and return the raw model output
+async fn generate(config: &AiConfig, prompt: &str) -> Result<String, ApiError> {
+    let client = reqwest::Client::builder()
+        .timeout(config.timeout())
+        .build()
+        .map_err(|e| ApiError::Internal(e.into()))?;
+
+    let url = format!("{}/api/generate", config.ollama_url.trim_end_matches('/'));
+    let body = GenerateRequest {
+        model: &config.model,
+        prompt,
+        stream: false,
+        format: "json",
+    };
+
+    let response = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Ollama request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(ApiError::Internal(anyhow::anyhow!(
+            "Ollama returned status {}",
+            response.status()
+        )));
+    }
+
+    let generated: GenerateResponse = response
+        .json()
+        .await
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid Ollama response: {}", e)))?;
+
+    Ok(generated.response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn remaining() -> MacroTargets {
+        MacroTargets {
+            calories: 800.0,
+            protein_g: 60.0,
+            carbohydrates_g: 80.0,
+            fat_g: 25.0,
+        }
+    }
+
+    fn enabled_config(url: String) -> AiConfig {
+        AiConfig {
+            enabled: true,
+            ollama_url: url,
+            model: "llama3.2".to_string(),
+            timeout_secs: 60,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_suggestions_parsed_from_mock_completion() {
+        let server = MockServer::start().await;
+        let completion = r#"{"suggestions": [
+            {"name": "Grilled chicken salad", "description": "Chicken breast over greens",
+             "calories": 450, "protein_g": 40, "carbohydrates_g": 20, "fat_g": 15},
+            {"name": "Greek yogurt bowl", "calories": 300, "protein_g": 20,
+             "carbohydrates_g": 45, "fat_g": 5}
+        ]}"#;
+
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama3.2",
+                "response": completion,
+                "done": true
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = enabled_config(server.uri());
+        let suggestions = MealSuggestionService::request_suggestions(
+            &config,
+            &remaining(),
+            &["breakfast".to_string()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].name, "Grilled chicken salad");
+        assert_eq!(suggestions[0].protein_g, 40.0);
+        assert_eq!(suggestions[1].description, None);
+        assert_eq!(suggestions[1].calories, 300.0);
+    }
+
+    #[tokio::test]
+    async fn test_upstream_error_is_internal() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let config = enabled_config(server.uri());
+        let result = MealSuggestionService::request_suggestions(&config, &remaining(), &[]).await;
+
+        assert!(matches!(result, Err(ApiError::Internal(_))));
+    }
+
+    #[tokio::test]
+    async fn test_suggest_rejected_when_disabled() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let config = AiConfig::default();
+
+        let result =
+            MealSuggestionService::suggest(&pool, &config, Uuid::new_v4(), remaining()).await;
+
+        match result {
+            Err(ApiError::Validation(msg)) => assert_eq!(msg, "AI features disabled"),
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_accepts_bare_list_and_drops_invalid() {
+        let raw = r#"[
+            {"name": "Oatmeal", "calories": 350, "protein_g": 12, "carbohydrates_g": 60, "fat_g": 7},
+            {"name": "", "calories": 100, "protein_g": 1, "carbohydrates_g": 1, "fat_g": 1},
+            {"name": "Bad", "calories": -5, "protein_g": 1, "carbohydrates_g": 1, "fat_g": 1}
+        ]"#;
+
+        let suggestions = MealSuggestionService::parse_suggestions(raw).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].name, "Oatmeal");
+    }
+
+    #[tokio::test]
+    async fn test_parse_food_entry_from_mock_completion() {
+        let server = MockServer::start().await;
+        let completion = r#"{"items": [
+            {"name": "scrambled egg", "estimated_servings": 2},
+            {"name": "toast", "estimated_servings": 1},
+            {"name": "", "estimated_servings": 1}
+        ]}"#;
+
+        Mock::given(method("POST"))
+            .and(path("/api/generate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model": "llama3.2",
+                "response": completion,
+                "done": true
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let config = enabled_config(server.uri());
+        let parsed = parse_food_entry(&config, "two scrambled eggs and a slice of toast")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            parsed.items,
+            vec![
+                ParsedFoodItem {
+                    name: "scrambled egg".to_string(),
+                    estimated_servings: 2.0
+                },
+                ParsedFoodItem {
+                    name: "toast".to_string(),
+                    estimated_servings: 1.0
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_food_entry_uses_fallback_when_disabled() {
+        let parsed = parse_food_entry(&AiConfig::default(), "2 x eggs")
+            .await
+            .unwrap();
+
+        assert_eq!(parsed.items.len(), 1);
+        assert_eq!(parsed.items[0].name, "eggs");
+        assert_eq!(parsed.items[0].estimated_servings, 2.0);
+    }
+
+    #[test]
+    fn test_fallback_parses_multiple_items() {
+        let parsed = parse_food_entry_fallback("2 x eggs, 1.5x rice and banana").unwrap();
+
+        assert_eq!(parsed.items.len(), 3);
+        assert_eq!(parsed.items[0].name, "eggs");
+        assert_eq!(parsed.items[0].estimated_servings, 2.0);
+        assert_eq!(parsed.items[1].name, "rice");
+        assert_eq!(parsed.items[1].estimated_servings, 1.5);
+        assert_eq!(parsed.items[2].name, "banana");
+        assert_eq!(parsed.items[2].estimated_servings, 1.0);
+    }
+
+    #[test]
+    fn test_fallback_keeps_names_containing_x() {
+        let parsed = parse_food_entry_fallback("extra cheese").unwrap();
+        assert_eq!(parsed.items[0].name, "extra cheese");
+        assert_eq!(parsed.items[0].estimated_servings, 1.0);
+    }
+
+    #[test]
+    fn test_fallback_rejects_invalid_entries() {
+        assert!(matches!(
+            parse_food_entry_fallback("0 x eggs"),
+            Err(ApiError::Validation(_))
+        ));
+        assert!(matches!(
+            parse_food_entry_fallback("3 x "),
+            Err(ApiError::Validation(_))
+        ));
+        assert!(matches!(
+            parse_food_entry_fallback(" , "),
+            Err(ApiError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_prompt_includes_remaining_macros() {
+        let prompt = MealSuggestionService::build_prompt(&remaining(), &[]);
+        assert!(prompt.contains("800 kcal"));
+        assert!(prompt.contains("60 g protein"));
+        assert!(prompt.contains("already logged today: none"));
+    }
+}
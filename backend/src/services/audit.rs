@@ -0,0 +1,125 @@
+//! Audit logging for data mutations
+//!
+//! Mutating services call [`record`] after a change succeeds. Audit writes
+//! run in the background and are best-effort: a failure is logged and never
+//! delays or fails the mutation itself.
+
+use crate::error::ApiError;
+use crate::repositories::{AuditLogRecord, AuditLogRepository};
+use crate::services::jobs::JobManager;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Audited entity names
+pub const ENTITY_WEIGHT_LOG: &str = "weight_log";
+pub const ENTITY_BODY_COMPOSITION: &str = "body_composition";
+pub const ENTITY_PROFILE: &str = "profile";
+pub const ENTITY_SETTINGS: &str = "settings";
+
+/// Kind of change being recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+}
+
+impl AuditAction {
+    /// Value stored in the `action` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Restore => "restore",
+        }
+    }
+}
+
+/// Record an action against an entity in the background
+///
+/// The write is spawned on `jobs` so shutdown waits for it; failures are
+/// logged rather than returned.
+pub fn record(
+    jobs: &JobManager,
+    pool: &PgPool,
+    user_id: Uuid,
+    entity: &'static str,
+    entity_id: Uuid,
+    action: AuditAction,
+) {
+    let pool = pool.clone();
+    jobs.spawn("audit_log", async move {
+        write(&pool, user_id, entity, entity_id, action).await;
+    });
+}
+
+/// Write an audit entry, logging rather than returning failures
+async fn write(pool: &PgPool, user_id: Uuid, entity: &str, entity_id: Uuid, action: AuditAction) {
+    if let Err(e) =
+        AuditLogRepository::create(pool, user_id, entity, entity_id, action.as_str()).await
+    {
+        warn!(
+            user_id = %user_id,
+            entity = entity,
+            entity_id = %entity_id,
+            action = action.as_str(),
+            "Failed to write audit log: {}",
+            e
+        );
+    }
+}
+
+/// Audit log service
+pub struct AuditService;
+
+impl AuditService {
+    /// Get the user's recent actions, most recent first, with total count
+    pub async fn get_recent(
+        pool: &PgPool,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<AuditLogRecord>, i64), ApiError> {
+        AuditLogRepository::get_paginated(pool, user_id, limit, offset)
+            .await
+            .map_err(ApiError::Internal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+    use std::time::Duration;
+
+    #[test]
+    fn test_action_names() {
+        assert_eq!(AuditAction::Create.as_str(), "create");
+        assert_eq!(AuditAction::Update.as_str(), "update");
+        assert_eq!(AuditAction::Delete.as_str(), "delete");
+        assert_eq!(AuditAction::Restore.as_str(), "restore");
+    }
+
+    #[tokio::test]
+    async fn test_record_swallows_database_errors() {
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(100))
+            .connect_lazy("postgres://localhost:1/unused")
+            .unwrap();
+
+        // Must complete without panicking or propagating the failure
+        let jobs = JobManager::new();
+        record(
+            &jobs,
+            &pool,
+            Uuid::new_v4(),
+            ENTITY_WEIGHT_LOG,
+            Uuid::new_v4(),
+            AuditAction::Create,
+        );
+        assert_eq!(jobs.shutdown(Duration::from_secs(5)).await, 0);
+    }
+}
@@ -6,22 +6,29 @@
 //! - Heart rate zone management
 //! - Resting heart rate anomaly detection
 
+use crate::config::{BiometricRanges, BiometricsConfig};
 use crate::error::ApiError;
 use crate::repositories::{
     biometrics::{
-        CreateHeartRateLog, CreateHrvLog, HeartRateLogRepository, HeartRateZonesRepository,
-        HrvLogRepository,
+        CreateHeartRateLog, CreateHeartRateStream, CreateHrvLog, HeartRateLogRepository,
+        HeartRateStreamRepository, HeartRateZonesRepository, HrvLogRepository, UpdateHrvLog,
     },
-    UserRepository,
+    UserRepository, WorkoutRepository,
 };
-use chrono::{DateTime, Datelike, Utc};
+use crate::services::cycle::{CyclePhase, CycleService};
+use crate::timezone;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use fitness_assistant_shared::health_metrics::{self, BiologicalSex, MaxHrFormula};
+use fitness_assistant_shared::validation::{resolve_source, validate_range, ValidationError};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use sqlx::PgPool;
+use std::collections::HashSet;
+use std::str::FromStr;
 use uuid::Uuid;
 
-/// Default max heart rate calculation: 220 - age
-const DEFAULT_MAX_HR_FORMULA_BASE: i32 = 220;
+/// Most samples accepted in one stream: a day at one sample per second
+const MAX_STREAM_SAMPLES: usize = 86_400;
 
 /// Anomaly threshold for resting heart rate (10% deviation)
 const RESTING_HR_ANOMALY_THRESHOLD: f64 = 0.10;
@@ -29,6 +36,21 @@ const RESTING_HR_ANOMALY_THRESHOLD: f64 = 0.10;
 /// Days for baseline calculation
 const BASELINE_DAYS: i32 = 7;
 
+/// Recovery score at or above which hard training is recommended
+const READINESS_GO_HARD_SCORE: f64 = 80.0;
+
+/// Recovery score below which rest is recommended
+const READINESS_REST_SCORE: f64 = 40.0;
+
+/// HRV slope (ms/day) below which HRV counts as declining
+const HRV_DECLINE_SLOPE: f64 = -1.0;
+
+/// One-minute heart-rate recovery below this is poor
+const HRR_NORMAL_MIN: i32 = 12;
+
+/// One-minute heart-rate recovery above this is excellent
+const HRR_EXCELLENT_ABOVE: i32 = 25;
+
 /// Heart rate log entry
 #[derive(Debug, Clone)]
 pub struct HeartRateLog {
@@ -75,6 +97,15 @@ pub struct LogHrvInput {
     pub notes: Option<String>,
 }
 
+/// Corrections to a logged HRV reading
+#[derive(Debug, Clone, Default)]
+pub struct UpdateHrvInput {
+    pub rmssd: Option<f64>,
+    pub sdnn: Option<f64>,
+    pub context: Option<String>,
+    pub notes: Option<String>,
+}
+
 /// Recovery score result
 #[derive(Debug, Clone)]
 pub struct RecoveryScore {
@@ -84,6 +115,8 @@ pub struct RecoveryScore {
     pub resting_hr_current: Option<i32>,
     pub resting_hr_baseline: Option<f64>,
     pub status: String,
+    /// Today's cycle phase, for users tracking their cycle
+    pub cycle_phase: Option<CyclePhase>,
 }
 
 /// Heart rate zone
@@ -113,6 +146,17 @@ pub struct ZoneDistribution {
     pub percentage: f64,
 }
 
+/// Summary of an ingested workout heart rate stream
+#[derive(Debug, Clone)]
+pub struct StreamSummary {
+    pub sample_count: usize,
+    pub duration_seconds: i32,
+    pub avg_bpm: i32,
+    pub max_bpm: i32,
+    pub min_bpm: i32,
+    pub zone_distribution: Vec<ZoneDistribution>,
+}
+
 /// Resting HR analysis result
 #[derive(Debug, Clone)]
 pub struct RestingHrAnalysis {
@@ -123,22 +167,127 @@ pub struct RestingHrAnalysis {
     pub trend: String,
 }
 
+/// Weights for blending HRV and resting HR into a recovery score
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecoveryWeights {
+    /// Multiplier on the HRV ratio term (ratio of 1.0 = 100 points)
+    pub hrv: f64,
+    /// Points lost per percent of resting-HR elevation over baseline
+    pub resting_hr: f64,
+}
+
+impl Default for RecoveryWeights {
+    fn default() -> Self {
+        Self {
+            hrv: 1.0,
+            resting_hr: 2.0,
+        }
+    }
+}
+
+/// How the resting heart rate baseline is computed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BaselineMethod {
+    /// Average of the baseline period's readings
+    #[default]
+    Mean,
+    /// Median of the baseline period's readings, robust to single outliers
+    Median,
+}
+
+impl FromStr for BaselineMethod {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mean" => Ok(BaselineMethod::Mean),
+            "median" => Ok(BaselineMethod::Median),
+            _ => Err(ApiError::Validation(
+                "Invalid baseline. Must be one of: mean, median".to_string(),
+            )),
+        }
+    }
+}
+
+/// One-minute heart-rate recovery classification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HrrClassification {
+    /// Below 12 bpm
+    Poor,
+    /// 12-25 bpm
+    Normal,
+    /// Above 25 bpm
+    Excellent,
+}
+
+impl HrrClassification {
+    /// Classify a one-minute recovery delta in bpm
+    pub fn from_hrr(hrr: i32) -> Self {
+        if hrr < HRR_NORMAL_MIN {
+            HrrClassification::Poor
+        } else if hrr <= HRR_EXCELLENT_ABOVE {
+            HrrClassification::Normal
+        } else {
+            HrrClassification::Excellent
+        }
+    }
+
+    /// Value returned in API responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HrrClassification::Poor => "poor",
+            HrrClassification::Normal => "normal",
+            HrrClassification::Excellent => "excellent",
+        }
+    }
+}
+
+/// What kind of training today's readiness supports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrainingRecommendation {
+    GoHard,
+    Moderate,
+    Rest,
+}
+
+impl TrainingRecommendation {
+    /// Value returned in API responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrainingRecommendation::GoHard => "go hard",
+            TrainingRecommendation::Moderate => "moderate",
+            TrainingRecommendation::Rest => "rest",
+        }
+    }
+}
+
+/// Training readiness recommendation
+#[derive(Debug, Clone)]
+pub struct TrainingReadiness {
+    pub recommendation: TrainingRecommendation,
+    /// Why this recommendation was made
+    pub reason: String,
+    pub recovery_score: Option<f64>,
+    /// HRV trend over the baseline window in ms/day
+    pub hrv_trend_slope: Option<f64>,
+    pub resting_hr_anomaly: Option<bool>,
+}
+
 /// Biometrics service for business logic
 pub struct BiometricsService;
 
 impl BiometricsService {
     /// Log a heart rate reading
+    ///
+    /// The reading must fall in the configured range for its source.
     pub async fn log_heart_rate(
         pool: &PgPool,
+        config: &BiometricsConfig,
         user_id: Uuid,
         input: LogHeartRateInput,
     ) -> Result<HeartRateLog, ApiError> {
-        // Validate BPM
-        if input.bpm <= 0 || input.bpm >= 300 {
-            return Err(ApiError::Validation(
-                "Heart rate must be between 1 and 299 BPM".to_string(),
-            ));
-        }
+        let source = resolve_source(input.source.as_deref())?;
+        Self::validate_bpm(input.bpm, config.ranges_for(&source))?;
 
         let context = input.context.unwrap_or_else(|| "resting".to_string());
         let valid_contexts = ["resting", "active", "workout", "sleep", "recovery"];
@@ -155,7 +304,7 @@ impl BiometricsService {
             context,
             recorded_at: input.recorded_at.unwrap_or_else(Utc::now),
             workout_id: input.workout_id,
-            source: input.source.unwrap_or_else(|| "manual".to_string()),
+            source,
             notes: input.notes,
         };
 
@@ -174,35 +323,45 @@ impl BiometricsService {
         })
     }
 
+    /// Check a heart rate lies within `ranges`
+    pub fn validate_bpm(bpm: i32, ranges: &BiometricRanges) -> Result<(), ValidationError> {
+        validate_range(bpm, ranges.min_bpm, ranges.max_bpm, "bpm")
+    }
+
+    /// Check an HRV measurement lies strictly between 0 and the range's maximum
+    pub fn validate_hrv_value(
+        value: f64,
+        field: &str,
+        ranges: &BiometricRanges,
+    ) -> Result<(), ValidationError> {
+        if value > 0.0 && value < ranges.max_hrv_ms {
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                field,
+                &format!("must be greater than 0 and less than {}", ranges.max_hrv_ms),
+            ))
+        }
+    }
+
     /// Log an HRV reading
     pub async fn log_hrv(
         pool: &PgPool,
+        config: &BiometricsConfig,
         user_id: Uuid,
         input: LogHrvInput,
     ) -> Result<HrvLog, ApiError> {
-        // Validate RMSSD
-        if input.rmssd <= 0.0 || input.rmssd >= 500.0 {
-            return Err(ApiError::Validation(
-                "RMSSD must be between 0 and 500 ms".to_string(),
-            ));
-        }
+        let source = resolve_source(input.source.as_deref())?;
+        let ranges = config.ranges_for(&source);
 
+        // HRV bounds are exclusive: a zero reading means the sensor failed
+        Self::validate_hrv_value(input.rmssd, "rmssd", ranges)?;
         if let Some(sdnn) = input.sdnn {
-            if sdnn <= 0.0 || sdnn >= 500.0 {
-                return Err(ApiError::Validation(
-                    "SDNN must be between 0 and 500 ms".to_string(),
-                ));
-            }
+            Self::validate_hrv_value(sdnn, "sdnn", ranges)?;
         }
 
         let context = input.context.unwrap_or_else(|| "morning".to_string());
-        let valid_contexts = ["morning", "sleep", "recovery", "workout"];
-        if !valid_contexts.contains(&context.as_str()) {
-            return Err(ApiError::Validation(format!(
-                "Invalid context. Must be one of: {}",
-                valid_contexts.join(", ")
-            )));
-        }
+        Self::validate_hrv_context(&context)?;
 
         let create_input = CreateHrvLog {
             user_id,
@@ -210,7 +369,7 @@ impl BiometricsService {
             sdnn: input.sdnn.map(|s| Decimal::try_from(s).unwrap_or_default()),
             context,
             recorded_at: input.recorded_at.unwrap_or_else(Utc::now),
-            source: input.source.unwrap_or_else(|| "manual".to_string()),
+            source,
             notes: input.notes,
         };
 
@@ -218,7 +377,100 @@ impl BiometricsService {
             .await
             .map_err(ApiError::Internal)?;
 
-        Ok(HrvLog {
+        Ok(Self::record_to_hrv_log(record))
+    }
+
+    /// Correct a logged HRV reading, e.g. a mis-entered RMSSD
+    pub async fn update_hrv_log(
+        pool: &PgPool,
+        config: &BiometricsConfig,
+        user_id: Uuid,
+        log_id: Uuid,
+        input: UpdateHrvInput,
+    ) -> Result<HrvLog, ApiError> {
+        if input.rmssd.is_some() || input.sdnn.is_some() {
+            // New values are held to the ranges of the reading's source
+            let source = HrvLogRepository::get_source(pool, log_id, user_id)
+                .await
+                .map_err(ApiError::Internal)?
+                .ok_or_else(|| ApiError::NotFound("HRV log not found".to_string()))?;
+            let ranges = config.ranges_for(&source);
+
+            if let Some(rmssd) = input.rmssd {
+                Self::validate_hrv_value(rmssd, "rmssd", ranges)?;
+            }
+            if let Some(sdnn) = input.sdnn {
+                Self::validate_hrv_value(sdnn, "sdnn", ranges)?;
+            }
+        }
+        if let Some(ref context) = input.context {
+            Self::validate_hrv_context(context)?;
+        }
+
+        let updates = UpdateHrvLog {
+            rmssd: input.rmssd.map(|v| Decimal::try_from(v).unwrap_or_default()),
+            sdnn: input.sdnn.map(|v| Decimal::try_from(v).unwrap_or_default()),
+            context: input.context,
+            notes: input.notes,
+        };
+
+        let record = HrvLogRepository::update(pool, log_id, user_id, updates)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("HRV log not found".to_string()))?;
+
+        Ok(Self::record_to_hrv_log(record))
+    }
+
+    /// Delete an HRV log entry
+    pub async fn delete_hrv_log(
+        pool: &PgPool,
+        user_id: Uuid,
+        log_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let deleted = HrvLogRepository::delete(pool, log_id, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        if deleted {
+            Ok(())
+        } else {
+            Err(ApiError::NotFound("HRV log not found".to_string()))
+        }
+    }
+
+    /// Delete a heart rate log entry
+    pub async fn delete_heart_rate_log(
+        pool: &PgPool,
+        user_id: Uuid,
+        log_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let deleted = HeartRateLogRepository::delete(pool, log_id, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        if deleted {
+            Ok(())
+        } else {
+            Err(ApiError::NotFound("Heart rate log not found".to_string()))
+        }
+    }
+
+    /// Check an HRV context is one of the supported values
+    fn validate_hrv_context(context: &str) -> Result<(), ApiError> {
+        let valid_contexts = ["morning", "sleep", "recovery", "workout"];
+        if !valid_contexts.contains(&context) {
+            return Err(ApiError::Validation(format!(
+                "Invalid context. Must be one of: {}",
+                valid_contexts.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Convert database record to domain model
+    fn record_to_hrv_log(record: crate::repositories::biometrics::HrvLogRecord) -> HrvLog {
+        HrvLog {
             id: record.id,
             rmssd: record.rmssd.to_f64().unwrap_or(0.0),
             sdnn: record.sdnn.and_then(|d| d.to_f64()),
@@ -226,13 +478,14 @@ impl BiometricsService {
             recorded_at: record.recorded_at,
             source: record.source,
             notes: record.notes,
-        })
+        }
     }
 
-    /// Calculate recovery score from HRV
+    /// Calculate recovery score from HRV and resting heart rate
     ///
     /// # Property 17: Recovery Score Calculation
-    /// score = normalize(hrv / baseline) * 100
+    /// score = normalize(hrv / baseline) * 100, lowered by resting-HR
+    /// elevation over baseline when resting readings are available
     pub async fn get_recovery_score(
         pool: &PgPool,
         user_id: Uuid,
@@ -259,18 +512,35 @@ impl BiometricsService {
         )
         .await
         .map_err(ApiError::Internal)?;
+        let resting_hr_current = HeartRateLogRepository::get_latest_resting(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
 
-        // Calculate recovery score
-        let score = Self::calculate_recovery_score(hrv_current, hrv_baseline);
+        // Calculate recovery score, falling back to HRV alone without resting HR
+        let score = match (resting_hr_current, resting_hr_baseline) {
+            (Some(rhr_current), Some(rhr_baseline)) => Self::calculate_recovery_score_v2(
+                hrv_current,
+                hrv_baseline,
+                rhr_current as f64,
+                rhr_baseline,
+            ),
+            _ => Self::calculate_recovery_score(hrv_current, hrv_baseline),
+        };
         let status = Self::recovery_status(score);
 
+        let cycle_phase = CycleService::tracker(pool, user_id)
+            .await?
+            .and_then(|tracker| tracker.phase_on(today))
+            .map(|day| day.phase);
+
         Ok(RecoveryScore {
             score,
             hrv_current,
             hrv_baseline,
-            resting_hr_current: None, // Would need latest resting HR
+            resting_hr_current,
             resting_hr_baseline,
             status,
+            cycle_phase,
         })
     }
 
@@ -289,6 +559,48 @@ impl BiometricsService {
         score.clamp(0.0, 100.0)
     }
 
+    /// Calculate recovery score from HRV and resting heart rate
+    ///
+    /// Blends HRV against baseline (positive) with resting-HR elevation over
+    /// baseline (negative) using the default weights, capped at 0-100.
+    pub fn calculate_recovery_score_v2(
+        hrv_current: f64,
+        hrv_baseline: f64,
+        rhr_current: f64,
+        rhr_baseline: f64,
+    ) -> f64 {
+        Self::calculate_recovery_score_weighted(
+            hrv_current,
+            hrv_baseline,
+            rhr_current,
+            rhr_baseline,
+            RecoveryWeights::default(),
+        )
+    }
+
+    /// Calculate recovery score from HRV and resting heart rate with custom weights
+    ///
+    /// Without a resting-HR baseline this reduces to the HRV-only score.
+    pub fn calculate_recovery_score_weighted(
+        hrv_current: f64,
+        hrv_baseline: f64,
+        rhr_current: f64,
+        rhr_baseline: f64,
+        weights: RecoveryWeights,
+    ) -> f64 {
+        if hrv_baseline <= 0.0 {
+            return 50.0; // Default to neutral if no baseline
+        }
+        if rhr_baseline <= 0.0 {
+            return Self::calculate_recovery_score(hrv_current, hrv_baseline);
+        }
+
+        let hrv_term = weights.hrv * (hrv_current / hrv_baseline) * 100.0;
+        let rhr_elevation_percent = (rhr_current - rhr_baseline) / rhr_baseline * 100.0;
+        let score = hrv_term - weights.resting_hr * rhr_elevation_percent;
+        score.clamp(0.0, 100.0)
+    }
+
     /// Get recovery status from score
     fn recovery_status(score: f64) -> String {
         match score {
@@ -300,6 +612,117 @@ impl BiometricsService {
         }
     }
 
+    /// Recommend how hard to train today
+    ///
+    /// Combines the recovery score, the HRV trend over the baseline window
+    /// and resting-HR anomaly status. Missing inputs degrade to "moderate".
+    pub async fn get_training_readiness(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<TrainingReadiness, ApiError> {
+        let recovery_score = match Self::get_recovery_score(pool, user_id).await {
+            Ok(recovery) => Some(recovery.score),
+            Err(ApiError::NotFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let tz = timezone::user_timezone(pool, user_id).await;
+        let today = timezone::local_today(tz);
+        let start_date = today - chrono::Duration::days(BASELINE_DAYS as i64);
+        let hrv_history =
+            HrvLogRepository::get_history(pool, user_id, start_date, today, tz, 100, 0)
+                .await
+                .map_err(ApiError::Internal)?;
+        let hrv_points: Vec<(f64, f64)> = hrv_history
+            .iter()
+            .map(|log| {
+                let days = (timezone::local_date(log.recorded_at, tz) - start_date).num_days();
+                (days as f64, log.rmssd.to_f64().unwrap_or(0.0))
+            })
+            .collect();
+        let hrv_trend_slope = health_metrics::least_squares_slope(&hrv_points);
+
+        let resting =
+            Self::analyze_resting_hr(pool, user_id, BASELINE_DAYS, BaselineMethod::default())
+                .await?;
+        // A zero average means no resting readings in the window
+        let resting_hr_anomaly = (resting.current_avg > 0.0).then_some(resting.is_anomaly);
+
+        let (recommendation, reason) =
+            Self::recommend_training(recovery_score, hrv_trend_slope, resting_hr_anomaly);
+
+        Ok(TrainingReadiness {
+            recommendation,
+            reason,
+            recovery_score,
+            hrv_trend_slope,
+            resting_hr_anomaly,
+        })
+    }
+
+    /// Derive a training recommendation and its reason
+    ///
+    /// A resting-HR anomaly always means rest. Otherwise any missing input
+    /// yields moderate training, since there is not enough data to push or back off.
+    pub fn recommend_training(
+        recovery_score: Option<f64>,
+        hrv_trend_slope: Option<f64>,
+        resting_hr_anomaly: Option<bool>,
+    ) -> (TrainingRecommendation, String) {
+        if resting_hr_anomaly == Some(true) {
+            return (
+                TrainingRecommendation::Rest,
+                "Resting heart rate deviates more than 10% from baseline".to_string(),
+            );
+        }
+
+        let (Some(score), Some(slope), Some(_)) =
+            (recovery_score, hrv_trend_slope, resting_hr_anomaly)
+        else {
+            let missing: Vec<&str> = [
+                (recovery_score.is_none(), "recovery score"),
+                (hrv_trend_slope.is_none(), "HRV trend"),
+                (resting_hr_anomaly.is_none(), "resting heart rate"),
+            ]
+            .into_iter()
+            .filter_map(|(is_missing, name)| is_missing.then_some(name))
+            .collect();
+            return (
+                TrainingRecommendation::Moderate,
+                format!("Not enough data: missing {}", missing.join(", ")),
+            );
+        };
+
+        if score < READINESS_REST_SCORE {
+            (
+                TrainingRecommendation::Rest,
+                format!("Recovery score {:.0} is low", score),
+            )
+        } else if score >= READINESS_GO_HARD_SCORE && slope >= HRV_DECLINE_SLOPE {
+            (
+                TrainingRecommendation::GoHard,
+                format!("Recovery score {:.0} is high and HRV is stable", score),
+            )
+        } else if slope < HRV_DECLINE_SLOPE {
+            (
+                TrainingRecommendation::Moderate,
+                format!("HRV is declining by {:.1} ms/day", -slope),
+            )
+        } else {
+            (
+                TrainingRecommendation::Moderate,
+                format!("Recovery score {:.0} is moderate", score),
+            )
+        }
+    }
+
+    /// Calculate one-minute heart-rate recovery after exercise
+    ///
+    /// A later reading above the peak is treated as no recovery (0).
+    pub fn calculate_hrr(peak_bpm: i32, bpm_one_min_later: i32) -> i32 {
+        (peak_bpm - bpm_one_min_later).max(0)
+    }
+
     /// Get or calculate heart rate zones
     pub async fn get_heart_rate_zones(
         pool: &PgPool,
@@ -336,7 +759,7 @@ impl BiometricsService {
         })
     }
 
-    /// Calculate max heart rate from user's age
+    /// Calculate max heart rate from user's age using their preferred formula
     async fn calculate_max_heart_rate(pool: &PgPool, user_id: Uuid) -> Result<i32, ApiError> {
         // Get user settings which contains date_of_birth
         let settings = UserRepository::get_settings(pool, user_id)
@@ -344,6 +767,7 @@ impl BiometricsService {
             .map_err(ApiError::Internal)?;
 
         let age = settings
+            .as_ref()
             .and_then(|s| s.date_of_birth)
             .map(|dob| {
                 let today = Utc::now().date_naive();
@@ -351,7 +775,17 @@ impl BiometricsService {
             })
             .unwrap_or(30); // Default to 30 if no DOB
 
-        Ok(DEFAULT_MAX_HR_FORMULA_BASE - age)
+        let sex: Option<BiologicalSex> = settings
+            .as_ref()
+            .and_then(|s| s.biological_sex.as_deref())
+            .and_then(|s| s.parse().ok());
+
+        let formula: MaxHrFormula = settings
+            .as_ref()
+            .and_then(|s| s.max_hr_formula.parse().ok())
+            .unwrap_or_default();
+
+        Ok(health_metrics::calculate_max_heart_rate(age, sex, formula))
     }
 
     /// Calculate zones as percentage of max HR
@@ -431,21 +865,268 @@ impl BiometricsService {
             .collect()
     }
 
+    /// Aggregate time in each heart rate zone across all workouts on a range of
+    /// the user's local dates
+    ///
+    /// Each workout's readings are distributed separately, then summed, so
+    /// percentages are over the combined time of every workout. A workout
+    /// with an ingested wearable stream uses the stream's samples in place
+    /// of its individually logged readings.
+    pub async fn aggregate_zone_distribution(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ZoneDistribution>, ApiError> {
+        if end < start {
+            return Err(ApiError::Validation(
+                "end_date must not be before start_date".to_string(),
+            ));
+        }
+
+        let zones = Self::get_heart_rate_zones(pool, user_id).await?.zones;
+        let tz = timezone::user_timezone(pool, user_id).await;
+        let readings = HeartRateLogRepository::get_workout_readings(pool, user_id, start, end, tz)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        let streams = HeartRateStreamRepository::get_by_date_range(pool, user_id, start, end, tz)
+            .await
+            .map_err(ApiError::Internal)?;
+        let streamed: HashSet<Uuid> = streams.iter().map(|s| s.workout_id).collect();
+
+        let mut workouts: Vec<Vec<(i32, DateTime<Utc>)>> = streams
+            .iter()
+            .map(|stream| {
+                stream
+                    .offsets_seconds
+                    .iter()
+                    .zip(&stream.bpm)
+                    .map(|(offset, bpm)| {
+                        (*bpm, stream.started_at + chrono::Duration::seconds(*offset as i64))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Readings arrive ordered by workout, so each workout is a contiguous run
+        let mut current_workout = None;
+        for (workout_id, bpm, recorded_at) in readings {
+            if streamed.contains(&workout_id) {
+                continue;
+            }
+            if current_workout != Some(workout_id) {
+                workouts.push(Vec::new());
+                current_workout = Some(workout_id);
+            }
+            if let Some(samples) = workouts.last_mut() {
+                samples.push((bpm, recorded_at));
+            }
+        }
+
+        let distributions: Vec<Vec<ZoneDistribution>> = workouts
+            .iter()
+            .map(|samples| {
+                let heart_rates = Self::readings_to_durations(samples);
+                Self::calculate_zone_distribution(&heart_rates, &zones)
+            })
+            .collect();
+
+        Ok(Self::combine_zone_distributions(&distributions, &zones))
+    }
+
+    /// Turn timestamped readings into (bpm, duration_seconds) pairs
+    ///
+    /// Each reading lasts until the next one; the last reuses the previous
+    /// interval. A single reading carries no duration.
+    pub fn readings_to_durations(readings: &[(i32, DateTime<Utc>)]) -> Vec<(i32, i32)> {
+        let mut durations: Vec<(i32, i32)> = readings
+            .windows(2)
+            .map(|pair| {
+                let seconds = (pair[1].1 - pair[0].1).num_seconds().max(0) as i32;
+                (pair[0].0, seconds)
+            })
+            .collect();
+
+        if let (Some(&(bpm, _)), Some(&(_, last_interval))) = (readings.last(), durations.last()) {
+            durations.push((bpm, last_interval));
+        }
+
+        durations
+    }
+
+    /// Ingest a wearable's heart rate stream for a workout
+    ///
+    /// The samples are stored as one compact series and the workout's
+    /// average and max heart rate are replaced by the stream's. Each sample
+    /// must fall in the configured range for `source`.
+    pub async fn ingest_hr_stream(
+        pool: &PgPool,
+        config: &BiometricsConfig,
+        user_id: Uuid,
+        workout_id: Uuid,
+        source: Option<&str>,
+        samples: Vec<(DateTime<Utc>, i32)>,
+    ) -> Result<StreamSummary, ApiError> {
+        let source = resolve_source(source)?;
+
+        WorkoutRepository::get_by_id(pool, workout_id, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Workout not found".to_string()))?;
+
+        let zones = Self::get_heart_rate_zones(pool, user_id).await?.zones;
+        let summary = Self::summarize_hr_stream(&samples, &zones, config.ranges_for(&source))?;
+        // The workout's summary columns only hold 30-250 bpm
+        validate_range(summary.avg_bpm, 30, 250, "avg_bpm")?;
+        validate_range(summary.max_bpm, 30, 250, "max_bpm")?;
+
+        let started_at = samples[0].0;
+        let stream = CreateHeartRateStream {
+            workout_id,
+            user_id,
+            started_at,
+            offsets_seconds: samples
+                .iter()
+                .map(|(at, _)| (*at - started_at).num_seconds() as i32)
+                .collect(),
+            bpm: samples.iter().map(|(_, bpm)| *bpm).collect(),
+        };
+
+        let mut tx = pool.begin().await?;
+        HeartRateStreamRepository::upsert(&mut *tx, stream)
+            .await
+            .map_err(ApiError::Internal)?;
+        WorkoutRepository::set_heart_rate_summary(
+            &mut *tx,
+            workout_id,
+            user_id,
+            summary.avg_bpm,
+            summary.max_bpm,
+        )
+        .await
+        .map_err(ApiError::Internal)?;
+        tx.commit().await?;
+
+        Ok(summary)
+    }
+
+    /// Validate a heart rate stream and summarise it in one pass
+    ///
+    /// Samples must be strictly chronological and within `ranges`. Each
+    /// sample counts until the next one; the last sample reuses the
+    /// preceding interval.
+    pub fn summarize_hr_stream(
+        samples: &[(DateTime<Utc>, i32)],
+        zones: &[HeartRateZone],
+        ranges: &BiometricRanges,
+    ) -> Result<StreamSummary, ApiError> {
+        if samples.is_empty() {
+            return Err(ApiError::Validation("Stream must contain at least one sample".to_string()));
+        }
+        if samples.len() > MAX_STREAM_SAMPLES {
+            return Err(ApiError::Validation(format!(
+                "Stream cannot contain more than {} samples",
+                MAX_STREAM_SAMPLES
+            )));
+        }
+
+        let mut total: i64 = 0;
+        let mut max_bpm = i32::MIN;
+        let mut min_bpm = i32::MAX;
+        let mut durations: Vec<(i32, i32)> = Vec::with_capacity(samples.len());
+        let mut previous: Option<(DateTime<Utc>, i32)> = None;
+
+        for (i, &(recorded_at, bpm)) in samples.iter().enumerate() {
+            Self::validate_bpm(bpm, ranges)?;
+
+            if let Some((previous_at, previous_bpm)) = previous {
+                if recorded_at <= previous_at {
+                    return Err(ApiError::Validation(format!(
+                        "Samples must be in chronological order (sample {} is not after the one before it)",
+                        i
+                    )));
+                }
+                durations.push((previous_bpm, (recorded_at - previous_at).num_seconds() as i32));
+            }
+            previous = Some((recorded_at, bpm));
+
+            total += bpm as i64;
+            max_bpm = max_bpm.max(bpm);
+            min_bpm = min_bpm.min(bpm);
+        }
+
+        if let Some((_, last_bpm)) = previous {
+            let last_interval = durations.last().map(|&(_, seconds)| seconds).unwrap_or(0);
+            durations.push((last_bpm, last_interval));
+        }
+
+        Ok(StreamSummary {
+            sample_count: samples.len(),
+            duration_seconds: durations.iter().map(|&(_, seconds)| seconds).sum(),
+            avg_bpm: (total as f64 / samples.len() as f64).round() as i32,
+            max_bpm,
+            min_bpm,
+            zone_distribution: Self::calculate_zone_distribution(&durations, zones),
+        })
+    }
+
+    /// Sum per-workout zone distributions into one
+    ///
+    /// Percentages are recomputed over the combined total.
+    pub fn combine_zone_distributions(
+        distributions: &[Vec<ZoneDistribution>],
+        zones: &[HeartRateZone],
+    ) -> Vec<ZoneDistribution> {
+        let zone_times: Vec<i32> = zones
+            .iter()
+            .map(|zone| {
+                distributions
+                    .iter()
+                    .flatten()
+                    .filter(|d| d.zone == zone.zone)
+                    .map(|d| d.duration_seconds)
+                    .sum()
+            })
+            .collect();
+        let total_time: i32 = zone_times.iter().sum();
+
+        zones
+            .iter()
+            .zip(zone_times)
+            .map(|(zone, duration)| ZoneDistribution {
+                zone: zone.zone,
+                name: zone.name.clone(),
+                duration_seconds: duration,
+                percentage: if total_time > 0 {
+                    (duration as f64 / total_time as f64) * 100.0
+                } else {
+                    0.0
+                },
+            })
+            .collect()
+    }
+
     /// Detect resting heart rate anomalies
     ///
     /// # Property 19: Resting Heart Rate Anomaly Detection
     /// Flag readings >10% deviation from baseline
+    ///
+    /// The baseline is the previous period of the same length, summarized
+    /// by `method`.
     pub async fn analyze_resting_hr(
         pool: &PgPool,
         user_id: Uuid,
         days: i32,
+        method: BaselineMethod,
     ) -> Result<RestingHrAnalysis, ApiError> {
-        let today = Utc::now().date_naive();
+        let tz = timezone::user_timezone(pool, user_id).await;
+        let today = timezone::local_today(tz);
         let start_date = today - chrono::Duration::days(days as i64);
 
         // Get current period average
         let stats = HeartRateLogRepository::get_stats(
-            pool, user_id, start_date, today, Some("resting")
+            pool, user_id, start_date, today, tz, Some("resting")
         )
         .await
         .map_err(ApiError::Internal)?;
@@ -454,15 +1135,13 @@ impl BiometricsService {
 
         // Get baseline (previous period)
         let baseline_end = start_date - chrono::Duration::days(1);
-        let baseline_start = baseline_end - chrono::Duration::days(days as i64);
-        
-        let baseline_stats = HeartRateLogRepository::get_stats(
-            pool, user_id, baseline_start, baseline_end, Some("resting")
-        )
-        .await
-        .map_err(ApiError::Internal)?;
+        let readings =
+            HeartRateLogRepository::get_resting_readings(pool, user_id, baseline_end, days, tz)
+                .await
+                .map_err(ApiError::Internal)?;
+        let baseline = Self::calculate_baseline(&readings, method);
 
-        let baseline_avg = baseline_stats.avg_bpm.unwrap_or(current_avg);
+        let baseline_avg = baseline.unwrap_or(current_avg);
 
         let (deviation_percent, is_anomaly) = 
             Self::detect_hr_anomaly(current_avg, baseline_avg);
@@ -484,6 +1163,27 @@ impl BiometricsService {
         })
     }
 
+    /// Summarize baseline readings with the given method
+    pub fn calculate_baseline(readings: &[f64], method: BaselineMethod) -> Option<f64> {
+        if readings.is_empty() {
+            return None;
+        }
+
+        match method {
+            BaselineMethod::Mean => Some(readings.iter().sum::<f64>() / readings.len() as f64),
+            BaselineMethod::Median => {
+                let mut sorted = readings.to_vec();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                let mid = sorted.len() / 2;
+                if sorted.len() % 2 == 1 {
+                    Some(sorted[mid])
+                } else {
+                    Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+                }
+            }
+        }
+    }
+
     /// Detect if heart rate deviates more than threshold from baseline
     ///
     /// # Property 19: Resting Heart Rate Anomaly Detection
@@ -688,6 +1388,260 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_readiness_high_recovery_stable_hr_goes_hard() {
+        let (recommendation, _) =
+            BiometricsService::recommend_training(Some(92.0), Some(0.5), Some(false));
+        assert_eq!(recommendation, TrainingRecommendation::GoHard);
+    }
+
+    #[test]
+    fn test_readiness_anomaly_means_rest() {
+        let (recommendation, reason) =
+            BiometricsService::recommend_training(Some(95.0), Some(1.0), Some(true));
+        assert_eq!(recommendation, TrainingRecommendation::Rest);
+        assert!(reason.contains("Resting heart rate"));
+
+        // An anomaly wins even when other data is missing
+        let (recommendation, _) = BiometricsService::recommend_training(None, None, Some(true));
+        assert_eq!(recommendation, TrainingRecommendation::Rest);
+    }
+
+    #[test]
+    fn test_readiness_low_recovery_means_rest() {
+        let (recommendation, _) =
+            BiometricsService::recommend_training(Some(30.0), Some(0.0), Some(false));
+        assert_eq!(recommendation, TrainingRecommendation::Rest);
+    }
+
+    #[test]
+    fn test_readiness_declining_hrv_is_moderate() {
+        let (recommendation, reason) =
+            BiometricsService::recommend_training(Some(85.0), Some(-2.5), Some(false));
+        assert_eq!(recommendation, TrainingRecommendation::Moderate);
+        assert!(reason.contains("declining"));
+    }
+
+    #[test]
+    fn test_readiness_missing_data_is_moderate() {
+        let (recommendation, reason) =
+            BiometricsService::recommend_training(Some(90.0), None, None);
+        assert_eq!(recommendation, TrainingRecommendation::Moderate);
+        assert_eq!(reason, "Not enough data: missing HRV trend, resting heart rate");
+    }
+
+    #[test]
+    fn test_hrr_classification_boundaries() {
+        assert_eq!(BiometricsService::calculate_hrr(170, 159), 11);
+        assert_eq!(HrrClassification::from_hrr(11), HrrClassification::Poor);
+        assert_eq!(HrrClassification::from_hrr(12), HrrClassification::Normal);
+        assert_eq!(HrrClassification::from_hrr(25), HrrClassification::Normal);
+        assert_eq!(HrrClassification::from_hrr(26), HrrClassification::Excellent);
+    }
+
+    #[test]
+    fn test_hrr_clamps_when_later_reading_exceeds_peak() {
+        assert_eq!(BiometricsService::calculate_hrr(150, 160), 0);
+        assert_eq!(HrrClassification::from_hrr(0), HrrClassification::Poor);
+    }
+
+    #[test]
+    fn test_median_baseline_ignores_outlier() {
+        let readings = [58.0, 60.0, 59.0, 61.0, 60.0, 59.0];
+        let mut with_outlier = readings.to_vec();
+        with_outlier.push(95.0);
+
+        let mean = |r: &[f64]| BiometricsService::calculate_baseline(r, BaselineMethod::Mean);
+        let median = |r: &[f64]| BiometricsService::calculate_baseline(r, BaselineMethod::Median);
+
+        // The outlier drags the mean up by several bpm...
+        assert!(mean(&with_outlier).unwrap() - mean(&readings).unwrap() > 4.0);
+        // ...but leaves the median where it was
+        assert_eq!(median(&readings), Some(59.5));
+        assert_eq!(median(&with_outlier), Some(60.0));
+
+        // So a 55 bpm reading is only an anomaly against the mean
+        let (_, mean_anomaly) =
+            BiometricsService::detect_hr_anomaly(55.0, mean(&with_outlier).unwrap());
+        let (_, median_anomaly) =
+            BiometricsService::detect_hr_anomaly(55.0, median(&with_outlier).unwrap());
+        assert!(mean_anomaly);
+        assert!(!median_anomaly);
+    }
+
+    #[test]
+    fn test_baseline_method_parsing() {
+        assert_eq!("mean".parse::<BaselineMethod>().unwrap(), BaselineMethod::Mean);
+        assert_eq!("median".parse::<BaselineMethod>().unwrap(), BaselineMethod::Median);
+        assert!("mode".parse::<BaselineMethod>().is_err());
+        assert_eq!(BaselineMethod::default(), BaselineMethod::Mean);
+        assert_eq!(BiometricsService::calculate_baseline(&[], BaselineMethod::Median), None);
+    }
+
+    #[test]
+    fn test_recovery_score_v2_elevated_resting_hr_lowers_score() {
+        let hrv_only = BiometricsService::calculate_recovery_score(40.0, 50.0);
+        let elevated = BiometricsService::calculate_recovery_score_v2(40.0, 50.0, 66.0, 60.0);
+        let at_baseline = BiometricsService::calculate_recovery_score_v2(40.0, 50.0, 60.0, 60.0);
+
+        assert!((hrv_only - 80.0).abs() < 1e-9);
+        assert!((at_baseline - hrv_only).abs() < 1e-9);
+        // 10% elevation costs 20 points with the default weights
+        assert!((elevated - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_recovery_score_v2_bounds_and_fallbacks() {
+        assert_eq!(BiometricsService::calculate_recovery_score_v2(30.0, 50.0, 120.0, 60.0), 0.0);
+        assert_eq!(BiometricsService::calculate_recovery_score_v2(80.0, 50.0, 50.0, 60.0), 100.0);
+        assert_eq!(BiometricsService::calculate_recovery_score_v2(40.0, 0.0, 66.0, 60.0), 50.0);
+        assert_eq!(
+            BiometricsService::calculate_recovery_score_v2(40.0, 50.0, 66.0, 0.0),
+            BiometricsService::calculate_recovery_score(40.0, 50.0)
+        );
+
+        let hrv_heavy = RecoveryWeights { hrv: 1.0, resting_hr: 0.5 };
+        let score = BiometricsService::calculate_recovery_score_weighted(
+            40.0, 50.0, 66.0, 60.0, hrv_heavy,
+        );
+        assert!((score - 75.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_combine_zone_distributions() {
+        let zones = BiometricsService::calculate_zones_percentage(200);
+        let mid = |i: usize| (zones[i].min_bpm + zones[i].max_bpm) / 2;
+
+        // An easy run mostly in zone 2 and an interval session in zones 4-5
+        let easy = BiometricsService::calculate_zone_distribution(
+            &[(mid(1), 2400), (mid(2), 600)],
+            &zones,
+        );
+        let intervals = BiometricsService::calculate_zone_distribution(
+            &[(mid(1), 600), (mid(3), 900), (mid(4), 300)],
+            &zones,
+        );
+
+        let combined = BiometricsService::combine_zone_distributions(&[easy, intervals], &zones);
+        let durations: Vec<i32> = combined.iter().map(|z| z.duration_seconds).collect();
+        assert_eq!(durations, vec![0, 3000, 600, 900, 300]);
+
+        // 3000 of 4800 seconds is 62.5%, not the average of each workout's share
+        assert!((combined[1].percentage - 62.5).abs() < 1e-9);
+        let total_percent: f64 = combined.iter().map(|z| z.percentage).sum();
+        assert!((total_percent - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_summarize_hr_stream() {
+        let zones = BiometricsService::calculate_zones_percentage(200);
+        let start = Utc::now();
+        // One sample per second ramping from 120 to 179 bpm
+        let samples: Vec<(DateTime<Utc>, i32)> = (0..60)
+            .map(|i| (start + chrono::Duration::seconds(i), 120 + i as i32))
+            .collect();
+
+        let summary = BiometricsService::summarize_hr_stream(&samples, &zones, &BiometricRanges::default()).unwrap();
+
+        assert_eq!(summary.sample_count, 60);
+        assert_eq!(summary.avg_bpm, 150); // 149.5 rounded
+        assert_eq!(summary.max_bpm, 179);
+        assert_eq!(summary.min_bpm, 120);
+        assert_eq!(summary.duration_seconds, 60);
+        let zoned: i32 = summary.zone_distribution.iter().map(|d| d.duration_seconds).sum();
+        assert_eq!(zoned, 60);
+    }
+
+    #[test]
+    fn test_summarize_hr_stream_rejects_out_of_order_samples() {
+        let zones = BiometricsService::calculate_zones_percentage(200);
+        let ranges = BiometricRanges::default();
+        let start = Utc::now();
+        let at = |secs: i64| start + chrono::Duration::seconds(secs);
+
+        let unordered = [(at(0), 120), (at(2), 125), (at(1), 130)];
+        assert!(BiometricsService::summarize_hr_stream(&unordered, &zones, &ranges).is_err());
+
+        let duplicate = [(at(0), 120), (at(0), 125)];
+        assert!(BiometricsService::summarize_hr_stream(&duplicate, &zones, &ranges).is_err());
+
+        assert!(BiometricsService::summarize_hr_stream(&[], &zones, &ranges).is_err());
+    }
+
+    #[test]
+    fn test_summarize_hr_stream_uses_source_ranges() {
+        let zones = BiometricsService::calculate_zones_percentage(200);
+        let config = BiometricsConfig::default();
+        let start = Utc::now();
+        let samples = [(start, 20), (start + chrono::Duration::seconds(1), 120)];
+
+        let manual = config.ranges_for("manual");
+        assert!(BiometricsService::summarize_hr_stream(&samples, &zones, manual).is_ok());
+        let garmin = config.ranges_for("garmin");
+        assert!(BiometricsService::summarize_hr_stream(&samples, &zones, garmin).is_err());
+    }
+
+    #[test]
+    fn test_readings_to_durations() {
+        let start = Utc::now();
+        let at = |secs: i64| start + chrono::Duration::seconds(secs);
+
+        let readings = [(120, at(0)), (140, at(60)), (150, at(90))];
+        assert_eq!(
+            BiometricsService::readings_to_durations(&readings),
+            vec![(120, 60), (140, 30), (150, 30)]
+        );
+        assert!(BiometricsService::readings_to_durations(&[(120, at(0))]).is_empty());
+    }
+
+    #[test]
+    fn test_low_resting_hr_from_chest_strap_accepted() {
+        let mut config = BiometricsConfig::default();
+        // A wrist sensor that misreads low heart rates is held to a narrower range
+        config.sources.insert(
+            "apple_watch".to_string(),
+            BiometricRanges {
+                min_bpm: 40,
+                max_bpm: 220,
+                ..BiometricRanges::default()
+            },
+        );
+
+        assert!(BiometricsService::validate_bpm(35, config.ranges_for("chest_strap")).is_ok());
+        assert!(BiometricsService::validate_bpm(35, config.ranges_for("apple_watch")).is_err());
+
+        // Obviously bogus readings are rejected whatever the source
+        for source in ["chest_strap", "apple_watch", "manual"] {
+            assert!(BiometricsService::validate_bpm(0, config.ranges_for(source)).is_err());
+            assert!(BiometricsService::validate_bpm(400, config.ranges_for(source)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_manual_heart_rate_range_is_wider_than_devices() {
+        let config = BiometricsConfig::default();
+
+        assert!(BiometricsService::validate_bpm(20, config.ranges_for("manual")).is_ok());
+        assert!(BiometricsService::validate_bpm(20, config.ranges_for("garmin")).is_err());
+        assert!(BiometricsService::validate_bpm(270, config.ranges_for("manual")).is_ok());
+        assert!(BiometricsService::validate_bpm(270, config.ranges_for("garmin")).is_err());
+        assert!(BiometricsService::validate_bpm(35, config.ranges_for("chest_strap")).is_ok());
+    }
+
+    #[test]
+    fn test_hrv_range_is_configurable() {
+        let defaults = BiometricRanges::default();
+        assert!(BiometricsService::validate_hrv_value(120.0, "rmssd", &defaults).is_ok());
+        assert!(BiometricsService::validate_hrv_value(0.0, "rmssd", &defaults).is_err());
+        assert!(BiometricsService::validate_hrv_value(500.0, "rmssd", &defaults).is_err());
+
+        let narrow = BiometricRanges {
+            max_hrv_ms: 300.0,
+            ..defaults
+        };
+        assert!(BiometricsService::validate_hrv_value(350.0, "rmssd", &narrow).is_err());
+    }
+
     #[test]
     fn test_recovery_status_categories() {
         assert_eq!(BiometricsService::recovery_status(90.0), "excellent");
@@ -0,0 +1,328 @@
+//! Menstrual cycle tracking service
+//!
+//! Cycle tracking is opt-in. Users log the first day of each cycle and the
+//! phase on any later day is derived from the most recent start and their
+//! typical cycle and period lengths, so analyses such as recovery and weight
+//! history can annotate readings with the phase they fall in.
+
+use crate::error::ApiError;
+use crate::repositories::{CycleRepository, UpsertCycleSettings};
+use chrono::NaiveDate;
+use fitness_assistant_shared::validation::validate_range;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Cycle length assumed until the user sets their own
+const DEFAULT_CYCLE_LENGTH_DAYS: i32 = 28;
+
+/// Period length assumed until the user sets their own
+const DEFAULT_PERIOD_LENGTH_DAYS: i32 = 5;
+
+/// The luteal phase is fairly constant, so ovulation is counted back from
+/// the end of the cycle
+const LUTEAL_PHASE_DAYS: i32 = 14;
+
+/// Phase of the menstrual cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CyclePhase {
+    Menstrual,
+    Follicular,
+    Ovulatory,
+    Luteal,
+}
+
+impl CyclePhase {
+    /// Name used in API responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CyclePhase::Menstrual => "menstrual",
+            CyclePhase::Follicular => "follicular",
+            CyclePhase::Ovulatory => "ovulatory",
+            CyclePhase::Luteal => "luteal",
+        }
+    }
+}
+
+/// Cycle tracking settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleSettings {
+    pub enabled: bool,
+    pub cycle_length_days: i32,
+    pub period_length_days: i32,
+}
+
+impl Default for CycleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cycle_length_days: DEFAULT_CYCLE_LENGTH_DAYS,
+            period_length_days: DEFAULT_PERIOD_LENGTH_DAYS,
+        }
+    }
+}
+
+/// Input for updating cycle tracking settings
+#[derive(Debug, Clone)]
+pub struct UpdateCycleSettingsInput {
+    pub enabled: bool,
+    pub cycle_length_days: Option<i32>,
+    pub period_length_days: Option<i32>,
+}
+
+/// Where a day falls in the user's cycle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleDay {
+    pub date: NaiveDate,
+    pub phase: CyclePhase,
+    /// Day of the cycle, starting at 1
+    pub cycle_day: i32,
+    pub cycle_started_on: NaiveDate,
+}
+
+/// A user's cycle starts and lengths, for looking up phases of many days
+#[derive(Debug, Clone)]
+pub struct CycleTracker {
+    /// Logged cycle starts, most recent first
+    starts: Vec<NaiveDate>,
+    cycle_length_days: i32,
+    period_length_days: i32,
+}
+
+impl CycleTracker {
+    pub fn new(mut starts: Vec<NaiveDate>, settings: &CycleSettings) -> Self {
+        starts.sort_unstable_by(|a, b| b.cmp(a));
+        Self {
+            starts,
+            cycle_length_days: settings.cycle_length_days,
+            period_length_days: settings.period_length_days,
+        }
+    }
+
+    /// Phase on `date`, measured from the latest cycle start on or before it
+    ///
+    /// Returns `None` for days before the first logged start.
+    pub fn phase_on(&self, date: NaiveDate) -> Option<CycleDay> {
+        let started_on = *self.starts.iter().find(|&&start| start <= date)?;
+        let cycle_day = CycleService::cycle_day(started_on, date, self.cycle_length_days)?;
+
+        Some(CycleDay {
+            date,
+            phase: CycleService::phase_for_day(
+                cycle_day,
+                self.cycle_length_days,
+                self.period_length_days,
+            ),
+            cycle_day,
+            cycle_started_on: started_on,
+        })
+    }
+}
+
+/// Cycle tracking service for business logic
+pub struct CycleService;
+
+impl CycleService {
+    /// Get the user's cycle tracking settings (tracking is off until set)
+    pub async fn get_settings(pool: &PgPool, user_id: Uuid) -> Result<CycleSettings, ApiError> {
+        let record = CycleRepository::get_settings(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(record
+            .map(|record| CycleSettings {
+                enabled: record.enabled,
+                cycle_length_days: record.cycle_length_days,
+                period_length_days: record.period_length_days,
+            })
+            .unwrap_or_default())
+    }
+
+    /// Opt in to or out of cycle tracking and set typical lengths
+    ///
+    /// Lengths that aren't given keep their current values.
+    pub async fn update_settings(
+        pool: &PgPool,
+        user_id: Uuid,
+        input: UpdateCycleSettingsInput,
+    ) -> Result<CycleSettings, ApiError> {
+        let current = Self::get_settings(pool, user_id).await?;
+        let cycle_length_days = input.cycle_length_days.unwrap_or(current.cycle_length_days);
+        let period_length_days = input.period_length_days.unwrap_or(current.period_length_days);
+
+        validate_range(cycle_length_days, 20, 45, "cycle_length_days")?;
+        validate_range(period_length_days, 1, 10, "period_length_days")?;
+
+        let record = CycleRepository::upsert_settings(
+            pool,
+            UpsertCycleSettings {
+                user_id,
+                enabled: input.enabled,
+                cycle_length_days,
+                period_length_days,
+            },
+        )
+        .await
+        .map_err(ApiError::Internal)?;
+
+        Ok(CycleSettings {
+            enabled: record.enabled,
+            cycle_length_days: record.cycle_length_days,
+            period_length_days: record.period_length_days,
+        })
+    }
+
+    /// Record the first day of a cycle
+    pub async fn log_start(
+        pool: &PgPool,
+        user_id: Uuid,
+        started_on: NaiveDate,
+        today: NaiveDate,
+    ) -> Result<(), ApiError> {
+        Self::require_enabled(pool, user_id).await?;
+
+        if started_on > today {
+            return Err(ApiError::Validation(
+                "Cycle start cannot be in the future".to_string(),
+            ));
+        }
+
+        CycleRepository::add_start(pool, user_id, started_on)
+            .await
+            .map_err(ApiError::Internal)
+    }
+
+    /// Phase on `date` for an opted-in user
+    pub async fn get_phase(
+        pool: &PgPool,
+        user_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<CycleDay, ApiError> {
+        Self::require_enabled(pool, user_id).await?;
+
+        Self::tracker(pool, user_id)
+            .await?
+            .and_then(|tracker| tracker.phase_on(date))
+            .ok_or_else(|| ApiError::NotFound("No cycle start logged on or before this date".to_string()))
+    }
+
+    /// Tracker for annotating readings, or `None` if the user hasn't opted in
+    pub async fn tracker(pool: &PgPool, user_id: Uuid) -> Result<Option<CycleTracker>, ApiError> {
+        let settings = Self::get_settings(pool, user_id).await?;
+        if !settings.enabled {
+            return Ok(None);
+        }
+
+        let starts = CycleRepository::list_starts(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(Some(CycleTracker::new(starts, &settings)))
+    }
+
+    async fn require_enabled(pool: &PgPool, user_id: Uuid) -> Result<(), ApiError> {
+        if Self::get_settings(pool, user_id).await?.enabled {
+            Ok(())
+        } else {
+            Err(ApiError::Forbidden("Cycle tracking is not enabled".to_string()))
+        }
+    }
+
+    /// Day of the cycle (starting at 1) that `date` falls on
+    ///
+    /// Days past the expected end of the cycle wrap around, projecting the
+    /// next cycle until a new start is logged. Returns `None` for dates
+    /// before `started_on`.
+    pub fn cycle_day(started_on: NaiveDate, date: NaiveDate, cycle_length_days: i32) -> Option<i32> {
+        let days = (date - started_on).num_days();
+        if days < 0 || cycle_length_days <= 0 {
+            return None;
+        }
+        Some((days % i64::from(cycle_length_days)) as i32 + 1)
+    }
+
+    /// Phase for a day of the cycle
+    ///
+    /// Menstruation covers the first `period_length_days`. Ovulation is
+    /// placed 14 days before the end of the cycle and the ovulatory phase
+    /// spans the day either side of it; the follicular phase comes before and
+    /// the luteal phase after.
+    pub fn phase_for_day(cycle_day: i32, cycle_length_days: i32, period_length_days: i32) -> CyclePhase {
+        let ovulation_day = cycle_length_days - LUTEAL_PHASE_DAYS;
+
+        if cycle_day <= period_length_days {
+            CyclePhase::Menstrual
+        } else if cycle_day < ovulation_day - 1 {
+            CyclePhase::Follicular
+        } else if cycle_day <= ovulation_day + 1 {
+            CyclePhase::Ovulatory
+        } else {
+            CyclePhase::Luteal
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_phases_of_a_28_day_cycle() {
+        let phase = |day| CycleService::phase_for_day(day, 28, 5);
+
+        assert_eq!(phase(1), CyclePhase::Menstrual);
+        assert_eq!(phase(5), CyclePhase::Menstrual);
+        assert_eq!(phase(6), CyclePhase::Follicular);
+        assert_eq!(phase(12), CyclePhase::Follicular);
+        assert_eq!(phase(13), CyclePhase::Ovulatory);
+        assert_eq!(phase(14), CyclePhase::Ovulatory);
+        assert_eq!(phase(15), CyclePhase::Ovulatory);
+        assert_eq!(phase(16), CyclePhase::Luteal);
+        assert_eq!(phase(28), CyclePhase::Luteal);
+    }
+
+    #[test]
+    fn test_phase_from_known_cycle_start() {
+        let settings = CycleSettings {
+            enabled: true,
+            cycle_length_days: 30,
+            period_length_days: 4,
+        };
+        let tracker = CycleTracker::new(vec![date("2024-03-01")], &settings);
+
+        let day = tracker.phase_on(date("2024-03-03")).unwrap();
+        assert_eq!(day.cycle_day, 3);
+        assert_eq!(day.phase, CyclePhase::Menstrual);
+
+        // Ovulation falls on day 16 of a 30 day cycle
+        assert_eq!(tracker.phase_on(date("2024-03-10")).unwrap().phase, CyclePhase::Follicular);
+        assert_eq!(tracker.phase_on(date("2024-03-16")).unwrap().phase, CyclePhase::Ovulatory);
+        assert_eq!(tracker.phase_on(date("2024-03-25")).unwrap().phase, CyclePhase::Luteal);
+
+        // Without a new start the next cycle is projected
+        let projected = tracker.phase_on(date("2024-04-01")).unwrap();
+        assert_eq!(projected.cycle_day, 2);
+        assert_eq!(projected.phase, CyclePhase::Menstrual);
+
+        assert_eq!(tracker.phase_on(date("2024-02-28")), None);
+    }
+
+    #[test]
+    fn test_phase_uses_latest_start_before_date() {
+        let tracker = CycleTracker::new(
+            vec![date("2024-01-01"), date("2024-01-27")],
+            &CycleSettings::default(),
+        );
+
+        let day = tracker.phase_on(date("2024-01-29")).unwrap();
+        assert_eq!(day.cycle_started_on, date("2024-01-27"));
+        assert_eq!(day.cycle_day, 3);
+
+        let day = tracker.phase_on(date("2024-01-20")).unwrap();
+        assert_eq!(day.cycle_started_on, date("2024-01-01"));
+        assert_eq!(day.phase, CyclePhase::Luteal);
+    }
+}
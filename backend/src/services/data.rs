@@ -2,12 +2,15 @@
 //!
 //! Provides:
 //! - Complete data deletion (GDPR compliance)
+//! - Purging of soft-deleted log entries after the retention window
 //!
 //! Property 21: Data Deletion Completeness
 //! After deletion, no user data remains in the database
 
 use crate::error::ApiError;
-use anyhow::Context;
+use crate::repositories::{FoodLogRepository, WeightRepository};
+use crate::services::export_jobs::ExportJobService;
+use chrono::{DateTime, Duration, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -163,14 +166,50 @@ impl DataService {
             .map_err(|e| ApiError::Internal(e.into()))?;
         summary.workouts = result.rows_affected() as i64;
 
+        // Delete template exercises (via workout templates)
+        let result = sqlx::query(
+            "DELETE FROM template_exercises WHERE template_id IN (SELECT id FROM workout_templates WHERE user_id = $1)"
+        )
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.template_exercises = result.rows_affected() as i64;
+
+        // Delete workout templates
+        let result = sqlx::query("DELETE FROM workout_templates WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.workout_templates = result.rows_affected() as i64;
+
         // Delete custom exercises
-        let result = sqlx::query("DELETE FROM exercises WHERE user_id = $1")
+        let result = sqlx::query("DELETE FROM exercises WHERE created_by = $1 AND is_custom = TRUE")
             .bind(user_id)
             .execute(&mut *tx)
             .await
             .map_err(|e| ApiError::Internal(e.into()))?;
         summary.custom_exercises = result.rows_affected() as i64;
 
+        // Delete meal template items (via meal templates)
+        let result = sqlx::query(
+            "DELETE FROM meal_template_items WHERE template_id IN (SELECT id FROM meal_templates WHERE user_id = $1)"
+        )
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.meal_template_items = result.rows_affected() as i64;
+
+        // Delete meal templates
+        let result = sqlx::query("DELETE FROM meal_templates WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.meal_templates = result.rows_affected() as i64;
+
         // Delete recipe ingredients (via recipes)
         let result = sqlx::query(
             "DELETE FROM recipe_ingredients WHERE recipe_id IN (SELECT id FROM recipes WHERE user_id = $1)"
@@ -189,6 +228,14 @@ impl DataService {
             .map_err(|e| ApiError::Internal(e.into()))?;
         summary.recipes = result.rows_affected() as i64;
 
+        // Delete favorite foods
+        let result = sqlx::query("DELETE FROM favorite_foods WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.favorite_foods = result.rows_affected() as i64;
+
         // Delete food logs
         let result = sqlx::query("DELETE FROM food_logs WHERE user_id = $1")
             .bind(user_id)
@@ -197,14 +244,39 @@ impl DataService {
             .map_err(|e| ApiError::Internal(e.into()))?;
         summary.food_logs = result.rows_affected() as i64;
 
-        // Delete custom food items
-        let result = sqlx::query("DELETE FROM food_items WHERE user_id = $1")
+        // Delete custom food items that no other user's logs, favorites,
+        // templates or recipes reference
+        let result = sqlx::query(
+            r#"
+            DELETE FROM food_items fi
+            WHERE fi.created_by = $1
+              AND NOT EXISTS (SELECT 1 FROM food_logs WHERE food_item_id = fi.id)
+              AND NOT EXISTS (SELECT 1 FROM favorite_foods WHERE food_item_id = fi.id)
+              AND NOT EXISTS (SELECT 1 FROM meal_template_items WHERE food_item_id = fi.id)
+              AND NOT EXISTS (SELECT 1 FROM recipe_ingredients WHERE food_item_id = fi.id)
+            "#,
+        )
             .bind(user_id)
             .execute(&mut *tx)
             .await
             .map_err(|e| ApiError::Internal(e.into()))?;
         summary.custom_food_items = result.rows_affected() as i64;
 
+        // Keep the rest for the users relying on them, without their creator
+        sqlx::query("UPDATE food_items SET created_by = NULL WHERE created_by = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+
+        // Delete nutrition goals
+        let result = sqlx::query("DELETE FROM nutrition_goals WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.nutrition_goals = result.rows_affected() as i64;
+
         // Delete body composition logs
         let result = sqlx::query("DELETE FROM body_composition_logs WHERE user_id = $1")
             .bind(user_id)
@@ -221,6 +293,30 @@ impl DataService {
             .map_err(|e| ApiError::Internal(e.into()))?;
         summary.weight_logs = result.rows_affected() as i64;
 
+        // Delete weight goals
+        let result = sqlx::query("DELETE FROM user_weight_goals WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.weight_goals = result.rows_affected() as i64;
+
+        // Delete audit logs
+        let result = sqlx::query("DELETE FROM audit_logs WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.audit_logs = result.rows_affected() as i64;
+
+        // Delete height history
+        let result = sqlx::query("DELETE FROM height_logs WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.height_logs = result.rows_affected() as i64;
+
         // Delete user settings
         let result = sqlx::query("DELETE FROM user_settings WHERE user_id = $1")
             .bind(user_id)
@@ -229,6 +325,15 @@ impl DataService {
             .map_err(|e| ApiError::Internal(e.into()))?;
         summary.user_settings = result.rows_affected() as i64;
 
+        // Delete export jobs, keeping their files to remove once committed
+        let export_files: Vec<Option<String>> =
+            sqlx::query_scalar("DELETE FROM export_jobs WHERE user_id = $1 RETURNING file_path")
+                .bind(user_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(|e| ApiError::Internal(e.into()))?;
+        summary.export_jobs = export_files.len() as i64;
+
         // Delete user account
         let result = sqlx::query("DELETE FROM users WHERE id = $1")
             .bind(user_id)
@@ -240,6 +345,8 @@ impl DataService {
         // Commit transaction
         tx.commit().await.map_err(|e| ApiError::Internal(e.into()))?;
 
+        ExportJobService::remove_files(export_files.into_iter().flatten()).await;
+
         Ok(summary)
     }
 
@@ -248,33 +355,39 @@ impl DataService {
     /// # Property 21: Data Deletion Completeness
     /// Returns true if no data exists for the user
     pub async fn verify_deletion(pool: &PgPool, user_id: Uuid) -> Result<bool, ApiError> {
-        // Check each table for remaining data
+        // Check each table (and its owning column) for remaining data
         let tables = [
-            "users",
-            "user_settings",
-            "weight_logs",
-            "body_composition_logs",
-            "food_logs",
-            "recipes",
-            "workouts",
-            "hydration_logs",
-            "hydration_goals",
-            "sleep_logs",
-            "sleep_goals",
-            "heart_rate_logs",
-            "hrv_logs",
-            "heart_rate_zones",
-            "goals",
-            "supplements",
-            "biomarker_logs",
+            ("users", "id"),
+            ("user_settings", "user_id"),
+            ("weight_logs", "user_id"),
+            ("user_weight_goals", "user_id"),
+            ("body_composition_logs", "user_id"),
+            ("food_logs", "user_id"),
+            ("favorite_foods", "user_id"),
+            ("food_items", "created_by"),
+            ("nutrition_goals", "user_id"),
+            ("recipes", "user_id"),
+            ("meal_templates", "user_id"),
+            ("workouts", "user_id"),
+            ("workout_templates", "user_id"),
+            ("exercises", "created_by"),
+            ("hydration_logs", "user_id"),
+            ("hydration_goals", "user_id"),
+            ("sleep_logs", "user_id"),
+            ("sleep_goals", "user_id"),
+            ("heart_rate_logs", "user_id"),
+            ("hrv_logs", "user_id"),
+            ("heart_rate_zones", "user_id"),
+            ("goals", "user_id"),
+            ("supplements", "user_id"),
+            ("biomarker_logs", "user_id"),
+            ("audit_logs", "user_id"),
+            ("height_logs", "user_id"),
+            ("export_jobs", "user_id"),
         ];
 
-        for table in tables {
-            let query = if table == "users" {
-                format!("SELECT COUNT(*) as count FROM {} WHERE id = $1", table)
-            } else {
-                format!("SELECT COUNT(*) as count FROM {} WHERE user_id = $1", table)
-            };
+        for (table, column) in tables {
+            let query = format!("SELECT COUNT(*) as count FROM {} WHERE {} = $1", table, column);
 
             let count: (i64,) = sqlx::query_as(&query)
                 .bind(user_id)
@@ -289,6 +402,47 @@ impl DataService {
 
         Ok(true)
     }
+
+    /// Permanently remove soft-deleted weight and food logs
+    ///
+    /// Only entries deleted more than `older_than_days` ago are purged, so
+    /// anything newer can still be restored.
+    pub async fn purge_soft_deleted(
+        pool: &PgPool,
+        older_than_days: i64,
+    ) -> Result<PurgeSummary, ApiError> {
+        if older_than_days < 0 {
+            return Err(ApiError::Validation(
+                "Retention period cannot be negative".to_string(),
+            ));
+        }
+
+        let cutoff = purge_cutoff(Utc::now(), older_than_days);
+
+        let weight_logs = WeightRepository::purge_deleted_before(pool, cutoff)
+            .await
+            .map_err(ApiError::Internal)?;
+        let food_logs = FoodLogRepository::purge_deleted_before(pool, cutoff)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(PurgeSummary {
+            weight_logs: weight_logs as i64,
+            food_logs: food_logs as i64,
+        })
+    }
+}
+
+/// Entries soft-deleted before this instant are eligible for purging
+fn purge_cutoff(now: DateTime<Utc>, older_than_days: i64) -> DateTime<Utc> {
+    now - Duration::days(older_than_days)
+}
+
+/// Summary of purged soft-deleted records
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct PurgeSummary {
+    pub weight_logs: i64,
+    pub food_logs: i64,
 }
 
 /// Summary of deleted records
@@ -297,14 +451,21 @@ pub struct DeletionSummary {
     pub users: i64,
     pub user_settings: i64,
     pub weight_logs: i64,
+    pub weight_goals: i64,
     pub body_composition_logs: i64,
     pub food_logs: i64,
+    pub favorite_foods: i64,
     pub custom_food_items: i64,
+    pub nutrition_goals: i64,
     pub recipes: i64,
     pub recipe_ingredients: i64,
+    pub meal_templates: i64,
+    pub meal_template_items: i64,
     pub workouts: i64,
     pub workout_exercises: i64,
     pub exercise_sets: i64,
+    pub workout_templates: i64,
+    pub template_exercises: i64,
     pub custom_exercises: i64,
     pub hydration_logs: i64,
     pub hydration_goals: i64,
@@ -318,6 +479,9 @@ pub struct DeletionSummary {
     pub supplements: i64,
     pub supplement_logs: i64,
     pub biomarker_logs: i64,
+    pub audit_logs: i64,
+    pub height_logs: i64,
+    pub export_jobs: i64,
 }
 
 impl DeletionSummary {
@@ -326,14 +490,21 @@ impl DeletionSummary {
         self.users
             + self.user_settings
             + self.weight_logs
+            + self.weight_goals
             + self.body_composition_logs
             + self.food_logs
+            + self.favorite_foods
             + self.custom_food_items
+            + self.nutrition_goals
             + self.recipes
             + self.recipe_ingredients
+            + self.meal_templates
+            + self.meal_template_items
             + self.workouts
             + self.workout_exercises
             + self.exercise_sets
+            + self.workout_templates
+            + self.template_exercises
             + self.custom_exercises
             + self.hydration_logs
             + self.hydration_goals
@@ -347,6 +518,9 @@ impl DeletionSummary {
             + self.supplements
             + self.supplement_logs
             + self.biomarker_logs
+            + self.audit_logs
+            + self.height_logs
+            + self.export_jobs
     }
 }
 
@@ -364,6 +538,13 @@ mod tests {
         assert_eq!(summary.total(), 16);
     }
 
+    #[test]
+    fn test_purge_cutoff_is_retention_days_before_now() {
+        let now = Utc::now();
+        assert_eq!(purge_cutoff(now, 30), now - Duration::days(30));
+        assert_eq!(purge_cutoff(now, 0), now);
+    }
+
     #[test]
     fn test_deletion_summary_default_is_zero() {
         let summary = DeletionSummary::default();
@@ -1,23 +1,79 @@
 //! Exercise and workout service
 //!
 //! Provides business logic for exercise tracking including:
-//! - Exercise library management
+//! - Exercise library management (cached in Redis when available)
 //! - Workout logging with sets and exercises
 //! - Pace calculation for cardio workouts
-//! - Weekly exercise summaries
+//! - Heart-rate recovery when peak and one-minute readings are logged
+//! - Double-progression load recommendations from recent sets and RPE
+//! - Per-session exercise history for strength progress charts
+//! - Workout templates instantiated as editable workouts
+//! - Weekly exercise summaries (cached per user and week in Redis when available)
 
+use crate::cache::{self, CacheStore};
 use crate::error::ApiError;
 use crate::repositories::{
-    AddWorkoutExercise, CreateExercise, CreateExerciseSet, CreateWorkout, ExerciseRecord,
-    ExerciseRepository, ExerciseSetRecord, ExerciseSetRepository,
-    WorkoutExerciseRepository, WorkoutRecord, WorkoutRepository,
+    AddWorkoutExercise, CreateExercise, CreateExerciseSet, CreateWorkout, CreateWorkoutTemplate,
+    CreateWorkoutTemplateExercise, ExerciseRecord, ExerciseRepository, ExerciseSetRecord,
+    ExerciseSetRepository, SessionSetRecord, WorkoutExerciseRepository, WorkoutRecord,
+    WorkoutRepository, WorkoutTemplateRecord, WorkoutTemplateRepository,
 };
+use crate::services::biometrics::{BiometricsService, HrrClassification};
+use crate::timezone;
 use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+use fitness_assistant_shared::validation::{
+    reconcile_duration_minutes, resolve_source, validate_range, ValidationErrors,
+};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Postgres, Transaction};
+use std::future::Future;
 use uuid::Uuid;
 
+/// Cache key for the shared (non-custom) exercise library
+const LIBRARY_CACHE_KEY: &str = "exercise_library";
+
+/// The library only changes on seeding or admin edits, so cache it for a day
+const LIBRARY_CACHE_TTL_SECS: u64 = 86_400;
+
+/// Most sessions returned by one exercise history request
+pub const MAX_HISTORY_SESSIONS: i64 = 100;
+
+/// Prefix for cached weekly summaries, followed by `{user_id}:{week_start}`
+const WEEKLY_SUMMARY_CACHE_PREFIX: &str = "exercise_weekly_summary:";
+
+/// Weekly summaries are invalidated on every workout change, so the TTL only bounds staleness
+const WEEKLY_SUMMARY_CACHE_TTL_SECS: u64 = 3_600;
+
+/// First day of the week when the user has no preference
+pub const DEFAULT_WEEK_START: Weekday = Weekday::Mon;
+
+/// Double-progression rep range: reps are added at a weight until every set reaches the top
+const PROGRESSION_REP_RANGE: (i32, i32) = (8, 12);
+
+/// Weight added once every set reaches the top of the rep range
+const LOAD_INCREMENT_KG: f64 = 2.5;
+
+/// Highest average RPE at which topping the rep range still earns more weight
+const PROGRESSION_MAX_RPE: f64 = 8.0;
+
+/// Average RPE from which a session counts as a grind
+const DELOAD_RPE: f64 = 9.0;
+
+/// Consecutive grinding sessions that call for a deload
+const DELOAD_SESSIONS: usize = 2;
+
+/// Share of the working weight kept when deloading
+const DELOAD_FACTOR: f64 = 0.9;
+
+/// Seconds per kilometre a 1% average grade adds to running pace
+const GRADE_ADJUSTMENT_SECS_PER_PERCENT: f64 = 12.0;
+
+/// Equipment values that mean no equipment is needed
+const BODYWEIGHT_EQUIPMENT: &[&str] = &["bodyweight", "none"];
+
 /// Exercise response for API
 #[derive(Debug, Clone)]
 pub struct Exercise {
@@ -44,9 +100,15 @@ pub struct Workout {
     pub calories_burned: Option<i32>,
     pub avg_heart_rate: Option<i32>,
     pub max_heart_rate: Option<i32>,
+    pub recovery_heart_rate: Option<i32>,
+    /// One-minute heart-rate recovery in bpm
+    pub heart_rate_recovery: Option<i32>,
+    pub heart_rate_recovery_class: Option<HrrClassification>,
     pub distance_meters: Option<f64>,
     pub pace_seconds_per_km: Option<i32>,
     pub elevation_gain_meters: Option<f64>,
+    /// Pace adjusted for the average climbing grade
+    pub grade_adjusted_pace_seconds_per_km: Option<i32>,
     pub source: String,
     pub notes: Option<String>,
 }
@@ -95,6 +157,8 @@ pub struct LogWorkoutInput {
     pub calories_burned: Option<i32>,
     pub avg_heart_rate: Option<i32>,
     pub max_heart_rate: Option<i32>,
+    /// Heart rate one minute after the workout ended
+    pub recovery_heart_rate: Option<i32>,
     pub distance_meters: Option<f64>,
     pub elevation_gain_meters: Option<f64>,
     pub source: Option<String>,
@@ -124,8 +188,89 @@ pub struct LogExerciseSetInput {
     pub notes: Option<String>,
 }
 
-/// Weekly exercise summary
+/// Exercise prescribed by a workout template
+#[derive(Debug, Clone)]
+pub struct WorkoutTemplateExercise {
+    pub exercise_id: Uuid,
+    pub exercise_name: String,
+    pub target_sets: i32,
+    pub target_reps: i32,
+    pub target_weight_kg: Option<f64>,
+    pub notes: Option<String>,
+}
+
+/// Workout template with its exercises in order
 #[derive(Debug, Clone)]
+pub struct WorkoutTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub workout_type: String,
+    pub notes: Option<String>,
+    pub exercises: Vec<WorkoutTemplateExercise>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Input for an exercise in a workout template
+#[derive(Debug, Clone)]
+pub struct WorkoutTemplateExerciseInput {
+    pub exercise_id: Uuid,
+    pub target_sets: i32,
+    pub target_reps: i32,
+    pub target_weight_kg: Option<f64>,
+    pub notes: Option<String>,
+}
+
+/// Input for creating or replacing a workout template
+#[derive(Debug, Clone)]
+pub struct WorkoutTemplateInput {
+    pub name: String,
+    pub workout_type: Option<String>,
+    pub notes: Option<String>,
+    pub exercises: Vec<WorkoutTemplateExerciseInput>,
+}
+
+/// One session's working sets of an exercise, summarised for progression
+#[derive(Debug, Clone)]
+pub struct SessionPerformance {
+    pub performed_at: DateTime<Utc>,
+    /// Heaviest working weight
+    pub weight_kg: f64,
+    /// Fewest reps in any set at that weight
+    pub min_reps: i32,
+    /// Average RPE over the sets that recorded one
+    pub avg_rpe: Option<f64>,
+}
+
+/// One session of an exercise, as a point on a progress chart
+#[derive(Debug, Clone)]
+pub struct ExerciseHistoryPoint {
+    pub workout_id: Uuid,
+    pub performed_at: DateTime<Utc>,
+    /// Day of the session in the user's timezone
+    pub date: NaiveDate,
+    /// Heaviest working set, ties going to the one with more reps
+    pub top_set_weight_kg: f64,
+    pub top_set_reps: i32,
+    /// Best Epley estimate over the session's working sets
+    pub estimated_one_rep_max_kg: f64,
+    /// Weight times reps summed over the session's working sets
+    pub total_volume_kg: f64,
+}
+
+/// Load to use next time an exercise is trained
+#[derive(Debug, Clone)]
+pub struct LoadRecommendation {
+    /// increase, maintain, deload, or none when there is no history
+    pub action: String,
+    pub current_weight_kg: Option<f64>,
+    pub recommended_weight_kg: Option<f64>,
+    pub target_reps: Option<i32>,
+    pub message: String,
+}
+
+/// Weekly exercise summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeeklyExerciseSummary {
     pub week_start: NaiveDate,
     pub week_end: NaiveDate,
@@ -137,7 +282,7 @@ pub struct WeeklyExerciseSummary {
 }
 
 /// Summary by workout type
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkoutTypeSummary {
     pub workout_type: String,
     pub count: usize,
@@ -146,7 +291,7 @@ pub struct WorkoutTypeSummary {
 }
 
 /// Daily workout summary
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyWorkoutSummary {
     pub date: NaiveDate,
     pub workouts: usize,
@@ -159,14 +304,34 @@ pub struct ExerciseService;
 
 impl ExerciseService {
     /// Get exercise library (all non-custom exercises)
-    pub async fn get_exercise_library(pool: &PgPool) -> Result<Vec<Exercise>, ApiError> {
-        let records = ExerciseRepository::get_all(pool)
+    pub async fn get_exercise_library(
+        pool: &PgPool,
+        cache: Option<&dyn CacheStore>,
+    ) -> Result<Vec<Exercise>, ApiError> {
+        Self::get_exercise_library_with(cache, || ExerciseRepository::get_all(pool)).await
+    }
+
+    /// Cached library lookup over an arbitrary fetch, so the cache path can be tested
+    async fn get_exercise_library_with<F, Fut>(
+        cache: Option<&dyn CacheStore>,
+        fetch: F,
+    ) -> Result<Vec<Exercise>, ApiError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<Vec<ExerciseRecord>>>,
+    {
+        let records = cache::get_or_fetch(cache, LIBRARY_CACHE_KEY, LIBRARY_CACHE_TTL_SECS, fetch)
             .await
             .map_err(ApiError::Internal)?;
 
         Ok(records.into_iter().map(Self::record_to_exercise).collect())
     }
 
+    /// Drop the cached exercise library so the next read reloads it
+    pub async fn invalidate_library_cache(cache: Option<&dyn CacheStore>) {
+        cache::invalidate(cache, LIBRARY_CACHE_KEY).await;
+    }
+
     /// Get exercises by category
     pub async fn get_exercises_by_category(
         pool: &PgPool,
@@ -191,7 +356,7 @@ impl ExerciseService {
         Ok(records.into_iter().map(Self::record_to_exercise).collect())
     }
 
-    /// Search exercises by name
+    /// Search exercises by name, most relevant first
     pub async fn search_exercises(
         pool: &PgPool,
         query: &str,
@@ -204,6 +369,261 @@ impl ExerciseService {
         Ok(records.into_iter().map(Self::record_to_exercise).collect())
     }
 
+    /// Suggest library exercises for the same muscles using only the given equipment
+    ///
+    /// Bodyweight exercises are always available. Suggestions sharing the
+    /// most muscle groups with the original come first.
+    pub async fn suggest_substitutions(
+        pool: &PgPool,
+        exercise_id: Uuid,
+        available_equipment: Vec<String>,
+    ) -> Result<Vec<Exercise>, ApiError> {
+        let original = ExerciseRepository::get_by_id(pool, exercise_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Exercise not found".to_string()))?;
+
+        let candidates =
+            ExerciseRepository::get_sharing_muscle_groups(pool, &original.muscle_groups, original.id)
+                .await
+                .map_err(ApiError::Internal)?;
+
+        Ok(Self::rank_substitutions(&original, candidates, &available_equipment)
+            .into_iter()
+            .map(Self::record_to_exercise)
+            .collect())
+    }
+
+    /// Keep candidates usable with the available equipment, most muscle overlap first
+    fn rank_substitutions(
+        original: &ExerciseRecord,
+        candidates: Vec<ExerciseRecord>,
+        available_equipment: &[String],
+    ) -> Vec<ExerciseRecord> {
+        let available: Vec<String> = available_equipment
+            .iter()
+            .map(|e| normalize_equipment(e))
+            .collect();
+
+        let mut ranked: Vec<(usize, ExerciseRecord)> = candidates
+            .into_iter()
+            .filter(|c| c.id != original.id)
+            .filter(|c| match c.equipment.as_deref().map(normalize_equipment) {
+                None => true,
+                Some(equipment) => {
+                    BODYWEIGHT_EQUIPMENT.contains(&equipment.as_str())
+                        || available.contains(&equipment)
+                }
+            })
+            .map(|c| {
+                let overlap = c
+                    .muscle_groups
+                    .iter()
+                    .filter(|m| original.muscle_groups.contains(m))
+                    .count();
+                (overlap, c)
+            })
+            .filter(|(overlap, _)| *overlap > 0)
+            .collect();
+
+        ranked.sort_by(|(a_overlap, a), (b_overlap, b)| {
+            b_overlap.cmp(a_overlap).then_with(|| a.name.cmp(&b.name))
+        });
+        ranked.into_iter().map(|(_, c)| c).collect()
+    }
+
+    /// Recommend the next load for an exercise using double progression
+    ///
+    /// Weight goes up once every working set reaches the top of the rep range
+    /// at a manageable RPE, and comes down after consecutive grinding sessions.
+    pub async fn recommend_next_load(
+        pool: &PgPool,
+        user_id: Uuid,
+        exercise_id: Uuid,
+    ) -> Result<LoadRecommendation, ApiError> {
+        ExerciseRepository::get_by_id(pool, exercise_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Exercise not found".to_string()))?;
+
+        let sets = ExerciseSetRepository::get_recent_session_sets(
+            pool,
+            user_id,
+            exercise_id,
+            DELOAD_SESSIONS as i64,
+        )
+        .await
+        .map_err(ApiError::Internal)?;
+
+        Ok(Self::progress_load(&Self::summarize_sessions(&sets)))
+    }
+
+    /// Get a user's per-session history of an exercise for progress charts
+    ///
+    /// Covers the `limit` most recent sessions with weighted working sets,
+    /// returned oldest first.
+    pub async fn get_exercise_history(
+        pool: &PgPool,
+        user_id: Uuid,
+        exercise_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<ExerciseHistoryPoint>, ApiError> {
+        ExerciseRepository::get_by_id(pool, exercise_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Exercise not found".to_string()))?;
+
+        let sets = ExerciseSetRepository::get_recent_session_sets(
+            pool,
+            user_id,
+            exercise_id,
+            limit.clamp(1, MAX_HISTORY_SESSIONS),
+        )
+        .await
+        .map_err(ApiError::Internal)?;
+
+        let tz = timezone::user_timezone(pool, user_id).await;
+        Ok(Self::build_exercise_history(&sets, tz))
+    }
+
+    /// Turn working sets (newest session first) into chart points, oldest first
+    pub fn build_exercise_history(sets: &[SessionSetRecord], tz: Tz) -> Vec<ExerciseHistoryPoint> {
+        let mut points: Vec<ExerciseHistoryPoint> = group_sessions(sets)
+            .into_iter()
+            .map(|session| {
+                let top_set = session
+                    .iter()
+                    .max_by(|a, b| a.weight_kg.cmp(&b.weight_kg).then(a.reps.cmp(&b.reps)))
+                    .expect("sessions are never empty");
+                let estimated_one_rep_max_kg = session
+                    .iter()
+                    .map(|s| estimate_one_rep_max(decimal_to_f64(&s.weight_kg), s.reps))
+                    .fold(0.0, f64::max);
+                let total_volume_kg: f64 = session
+                    .iter()
+                    .map(|s| decimal_to_f64(&s.weight_kg) * s.reps as f64)
+                    .sum();
+
+                ExerciseHistoryPoint {
+                    workout_id: top_set.workout_id,
+                    performed_at: top_set.performed_at,
+                    date: top_set.performed_at.with_timezone(&tz).date_naive(),
+                    top_set_weight_kg: decimal_to_f64(&top_set.weight_kg),
+                    top_set_reps: top_set.reps,
+                    estimated_one_rep_max_kg: round_to_tenth(estimated_one_rep_max_kg),
+                    total_volume_kg: round_to_tenth(total_volume_kg),
+                }
+            })
+            .collect();
+
+        points.reverse();
+        points
+    }
+
+    /// Group working sets into per-session summaries, keeping their order
+    pub fn summarize_sessions(sets: &[SessionSetRecord]) -> Vec<SessionPerformance> {
+        group_sessions(sets)
+            .into_iter()
+            .map(|session| {
+                let weight_kg = session
+                    .iter()
+                    .map(|s| decimal_to_f64(&s.weight_kg))
+                    .fold(0.0, f64::max);
+                let min_reps = session
+                    .iter()
+                    .filter(|s| decimal_to_f64(&s.weight_kg) >= weight_kg)
+                    .map(|s| s.reps)
+                    .min()
+                    .unwrap_or(0);
+                let rpes: Vec<f64> = session
+                    .iter()
+                    .filter_map(|s| s.rpe.as_ref().map(decimal_to_f64))
+                    .collect();
+                let avg_rpe = if rpes.is_empty() {
+                    None
+                } else {
+                    Some(rpes.iter().sum::<f64>() / rpes.len() as f64)
+                };
+
+                SessionPerformance {
+                    performed_at: session[0].performed_at,
+                    weight_kg,
+                    min_reps,
+                    avg_rpe,
+                }
+            })
+            .collect()
+    }
+
+    /// Apply double progression to recent sessions, newest first
+    pub fn progress_load(sessions: &[SessionPerformance]) -> LoadRecommendation {
+        let (rep_min, rep_max) = PROGRESSION_REP_RANGE;
+
+        let Some(last) = sessions.first() else {
+            return LoadRecommendation {
+                action: "none".to_string(),
+                current_weight_kg: None,
+                recommended_weight_kg: None,
+                target_reps: None,
+                message: "Log a session with this exercise first".to_string(),
+            };
+        };
+
+        let grinding = sessions.len() >= DELOAD_SESSIONS
+            && sessions[..DELOAD_SESSIONS]
+                .iter()
+                .all(|s| s.avg_rpe.is_some_and(|rpe| rpe >= DELOAD_RPE));
+        if grinding {
+            let weight = round_to_increment(last.weight_kg * DELOAD_FACTOR);
+            return LoadRecommendation {
+                action: "deload".to_string(),
+                current_weight_kg: Some(last.weight_kg),
+                recommended_weight_kg: Some(weight),
+                target_reps: Some(rep_min),
+                message: format!(
+                    "RPE has been {} or higher for {} sessions; drop to {} kg and rebuild",
+                    DELOAD_RPE, DELOAD_SESSIONS, weight
+                ),
+            };
+        }
+
+        let topped_range = last.min_reps >= rep_max;
+        if topped_range && !last.avg_rpe.is_some_and(|rpe| rpe > PROGRESSION_MAX_RPE) {
+            let weight = last.weight_kg + LOAD_INCREMENT_KG;
+            return LoadRecommendation {
+                action: "increase".to_string(),
+                current_weight_kg: Some(last.weight_kg),
+                recommended_weight_kg: Some(weight),
+                target_reps: Some(rep_min),
+                message: format!(
+                    "Every set reached {} reps; move up to {} kg and aim for {}",
+                    rep_max, weight, rep_min
+                ),
+            };
+        }
+
+        let target_reps = (last.min_reps + 1).clamp(rep_min, rep_max);
+        let message = if topped_range {
+            format!(
+                "Top of the rep range reached but it felt hard; repeat {} kg until it feels easier",
+                last.weight_kg
+            )
+        } else {
+            format!(
+                "Stay at {} kg and aim for {} reps on every set",
+                last.weight_kg, target_reps
+            )
+        };
+
+        LoadRecommendation {
+            action: "maintain".to_string(),
+            current_weight_kg: Some(last.weight_kg),
+            recommended_weight_kg: Some(last.weight_kg),
+            target_reps: Some(target_reps),
+            message,
+        }
+    }
+
     /// Get user's custom exercises
     pub async fn get_custom_exercises(
         pool: &PgPool,
@@ -240,46 +660,142 @@ impl ExerciseService {
             created_by: Some(user_id),
         };
 
-        let record = ExerciseRepository::create(pool, input)
+        Self::create_exercise_with(None, input, |input| ExerciseRepository::create(pool, input))
+            .await
+    }
+
+    /// Add an exercise to the shared library
+    ///
+    /// Intended for admin use; the cached library is invalidated so the new
+    /// exercise is visible immediately.
+    pub async fn create_library_exercise(
+        pool: &PgPool,
+        cache: Option<&dyn CacheStore>,
+        input: CreateExercise,
+    ) -> Result<Exercise, ApiError> {
+        let input = CreateExercise {
+            is_custom: false,
+            ..input
+        };
+
+        Self::create_exercise_with(cache, input, |input| ExerciseRepository::create(pool, input))
+            .await
+    }
+
+    /// Seed the shared library, skipping exercises that already exist by name
+    ///
+    /// Returns the number of exercises inserted.
+    pub async fn seed_library(
+        pool: &PgPool,
+        cache: Option<&dyn CacheStore>,
+        exercises: Vec<CreateExercise>,
+    ) -> Result<usize, ApiError> {
+        let mut inserted = 0;
+        for input in exercises {
+            let exists = ExerciseRepository::exists_by_name(pool, &input.name)
+                .await
+                .map_err(ApiError::Internal)?;
+            if exists {
+                continue;
+            }
+
+            ExerciseRepository::create(
+                pool,
+                CreateExercise {
+                    is_custom: false,
+                    created_by: None,
+                    ..input
+                },
+            )
             .await
             .map_err(ApiError::Internal)?;
+            inserted += 1;
+        }
+
+        if inserted > 0 {
+            Self::invalidate_library_cache(cache).await;
+        }
+
+        Ok(inserted)
+    }
+
+    /// Create an exercise, invalidating the library cache for non-custom ones
+    async fn create_exercise_with<F, Fut>(
+        cache: Option<&dyn CacheStore>,
+        input: CreateExercise,
+        create: F,
+    ) -> Result<Exercise, ApiError>
+    where
+        F: FnOnce(CreateExercise) -> Fut,
+        Fut: Future<Output = anyhow::Result<ExerciseRecord>>,
+    {
+        let record = create(input).await.map_err(ApiError::Internal)?;
+
+        if !record.is_custom {
+            Self::invalidate_library_cache(cache).await;
+        }
 
         Ok(Self::record_to_exercise(record))
     }
 
     /// Log a workout
     ///
-    /// Creates a workout with exercises and sets. Automatically calculates
+    /// Creates a workout with exercises and sets in a single transaction, so
+    /// a failure on any exercise leaves nothing behind. Automatically calculates
     /// pace for cardio workouts if duration and distance are provided.
     pub async fn log_workout(
         pool: &PgPool,
+        cache: Option<&dyn CacheStore>,
         user_id: Uuid,
         input: LogWorkoutInput,
     ) -> Result<WorkoutDetail, ApiError> {
+        // Derive the duration from the timestamps, or check it agrees with them
+        let duration_minutes = match input.ended_at {
+            Some(ended_at) => Some(
+                reconcile_duration_minutes(input.started_at, ended_at, input.duration_minutes)
+                    .map_err(ApiError::Validation)?,
+            ),
+            None => input.duration_minutes,
+        };
+
         // Calculate pace if this is a cardio workout with distance and duration
         let pace_seconds_per_km = Self::calculate_pace(
-            input.duration_minutes,
+            duration_minutes,
             input.distance_meters,
         );
 
+        if let Some(recovery_heart_rate) = input.recovery_heart_rate {
+            validate_range(recovery_heart_rate, 30, 250, "recovery_heart_rate")?;
+        }
+
+        let heart_rate_recovery = input
+            .max_heart_rate
+            .zip(input.recovery_heart_rate)
+            .map(|(peak, later)| BiometricsService::calculate_hrr(peak, later));
+
         let create_workout = CreateWorkout {
             user_id,
             name: input.name,
             workout_type: input.workout_type,
             started_at: input.started_at,
             ended_at: input.ended_at,
-            duration_minutes: input.duration_minutes,
+            duration_minutes,
             calories_burned: input.calories_burned,
             avg_heart_rate: input.avg_heart_rate,
             max_heart_rate: input.max_heart_rate,
+            recovery_heart_rate: input.recovery_heart_rate,
+            heart_rate_recovery,
             distance_meters: input.distance_meters,
             pace_seconds_per_km,
             elevation_gain_meters: input.elevation_gain_meters,
-            source: input.source.unwrap_or_else(|| "manual".to_string()),
+            source: resolve_source(input.source.as_deref())?,
             notes: input.notes,
         };
 
-        let workout_record = WorkoutRepository::create(pool, create_workout)
+        // Dropping the transaction on an early return rolls everything back
+        let mut tx = pool.begin().await?;
+
+        let workout_record = WorkoutRepository::create(&mut *tx, create_workout)
             .await
             .map_err(ApiError::Internal)?;
 
@@ -287,7 +803,7 @@ impl ExerciseService {
         let mut exercise_details = Vec::new();
         for (sort_order, exercise_input) in input.exercises.into_iter().enumerate() {
             let exercise_detail = Self::add_exercise_to_workout(
-                pool,
+                &mut tx,
                 workout_record.id,
                 exercise_input,
                 sort_order as i32,
@@ -296,6 +812,9 @@ impl ExerciseService {
             exercise_details.push(exercise_detail);
         }
 
+        tx.commit().await?;
+        Self::invalidate_weekly_summaries(cache, user_id).await;
+
         Ok(WorkoutDetail {
             workout: Self::record_to_workout(workout_record),
             exercises: exercise_details,
@@ -304,13 +823,13 @@ impl ExerciseService {
 
     /// Add exercise to workout with sets
     async fn add_exercise_to_workout(
-        pool: &PgPool,
+        tx: &mut Transaction<'_, Postgres>,
         workout_id: Uuid,
         input: LogWorkoutExerciseInput,
         sort_order: i32,
     ) -> Result<WorkoutExerciseDetail, ApiError> {
         // Get exercise details
-        let exercise_record = ExerciseRepository::get_by_id(pool, input.exercise_id)
+        let exercise_record = ExerciseRepository::get_by_id(&mut **tx, input.exercise_id)
             .await
             .map_err(ApiError::Internal)?
             .ok_or_else(|| ApiError::NotFound("Exercise not found".to_string()))?;
@@ -323,7 +842,7 @@ impl ExerciseService {
             notes: input.notes.clone(),
         };
 
-        let workout_exercise = WorkoutExerciseRepository::create(pool, add_input)
+        let workout_exercise = WorkoutExerciseRepository::create(&mut **tx, add_input)
             .await
             .map_err(ApiError::Internal)?;
 
@@ -344,7 +863,7 @@ impl ExerciseService {
                 notes: set_input.notes,
             };
 
-            let set_record = ExerciseSetRepository::create(pool, create_set)
+            let set_record = ExerciseSetRepository::create(&mut **tx, create_set)
                 .await
                 .map_err(ApiError::Internal)?;
 
@@ -436,12 +955,208 @@ impl ExerciseService {
     /// Delete a workout
     pub async fn delete_workout(
         pool: &PgPool,
+        cache: Option<&dyn CacheStore>,
         user_id: Uuid,
         workout_id: Uuid,
     ) -> Result<bool, ApiError> {
-        WorkoutRepository::delete(pool, workout_id, user_id)
+        let deleted = WorkoutRepository::delete(pool, workout_id, user_id)
             .await
-            .map_err(ApiError::Internal)
+            .map_err(ApiError::Internal)?;
+
+        if deleted {
+            Self::invalidate_weekly_summaries(cache, user_id).await;
+        }
+
+        Ok(deleted)
+    }
+
+    // ==================== Workout Template Methods ====================
+
+    /// Save a workout template
+    pub async fn create_template(
+        pool: &PgPool,
+        user_id: Uuid,
+        input: WorkoutTemplateInput,
+    ) -> Result<WorkoutTemplate, ApiError> {
+        let create = Self::validate_template_input(pool, user_id, input).await?;
+
+        let record = WorkoutTemplateRepository::create(pool, create)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Self::load_template(pool, record).await
+    }
+
+    /// Get all workout templates for a user
+    pub async fn get_templates(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<WorkoutTemplate>, ApiError> {
+        let records = WorkoutTemplateRepository::get_by_user(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        let mut templates = Vec::with_capacity(records.len());
+        for record in records {
+            templates.push(Self::load_template(pool, record).await?);
+        }
+
+        Ok(templates)
+    }
+
+    /// Get a workout template the user owns
+    pub async fn get_template(
+        pool: &PgPool,
+        user_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<WorkoutTemplate, ApiError> {
+        let record = WorkoutTemplateRepository::find_by_id_and_user(pool, template_id, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Workout template not found".to_string()))?;
+
+        Self::load_template(pool, record).await
+    }
+
+    /// Replace a workout template's details and exercises
+    pub async fn update_template(
+        pool: &PgPool,
+        user_id: Uuid,
+        template_id: Uuid,
+        input: WorkoutTemplateInput,
+    ) -> Result<WorkoutTemplate, ApiError> {
+        let create = Self::validate_template_input(pool, user_id, input).await?;
+
+        let record = WorkoutTemplateRepository::replace(pool, template_id, create)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Workout template not found".to_string()))?;
+
+        Self::load_template(pool, record).await
+    }
+
+    /// Delete a workout template
+    pub async fn delete_template(
+        pool: &PgPool,
+        user_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let deleted = WorkoutTemplateRepository::delete(pool, template_id, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        if !deleted {
+            return Err(ApiError::NotFound("Workout template not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Create a workout on `date` pre-populated from a template
+    ///
+    /// Each prescribed set becomes a set placeholder carrying the target reps
+    /// and weight, ready to be edited with what was actually lifted.
+    pub async fn instantiate_template(
+        pool: &PgPool,
+        cache: Option<&dyn CacheStore>,
+        user_id: Uuid,
+        template_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<WorkoutDetail, ApiError> {
+        let template = Self::get_template(pool, user_id, template_id).await?;
+
+        let tz = timezone::user_timezone(pool, user_id).await;
+        let started_at = if date == timezone::local_today(tz) {
+            Utc::now()
+        } else {
+            timezone::local_day_bounds(date, tz).0
+        };
+
+        Self::log_workout(pool, cache, user_id, expand_workout_template(&template, started_at)).await
+    }
+
+    /// Validate template input, checking every exercise exists
+    async fn validate_template_input(
+        pool: &PgPool,
+        user_id: Uuid,
+        input: WorkoutTemplateInput,
+    ) -> Result<CreateWorkoutTemplate, ApiError> {
+        let mut errors = ValidationErrors::new();
+        if input.name.trim().is_empty() {
+            errors.add("name", "cannot be empty");
+        }
+        if input.exercises.is_empty() {
+            errors.add("exercises", "must contain at least one exercise");
+        }
+        if input.exercises.iter().any(|e| !(1..=20).contains(&e.target_sets)) {
+            errors.add("target_sets", "must be between 1 and 20");
+        }
+        if input.exercises.iter().any(|e| !(1..=100).contains(&e.target_reps)) {
+            errors.add("target_reps", "must be between 1 and 100");
+        }
+        if input.exercises.iter().any(|e| e.target_weight_kg.is_some_and(|w| w < 0.0)) {
+            errors.add("target_weight_kg", "cannot be negative");
+        }
+        errors.into_result()?;
+
+        for exercise in &input.exercises {
+            ExerciseRepository::get_by_id(pool, exercise.exercise_id)
+                .await
+                .map_err(ApiError::Internal)?
+                .ok_or_else(|| {
+                    ApiError::NotFound(format!("Exercise {} not found", exercise.exercise_id))
+                })?;
+        }
+
+        Ok(CreateWorkoutTemplate {
+            user_id,
+            name: input.name.trim().to_string(),
+            workout_type: input.workout_type.unwrap_or_else(|| "strength".to_string()),
+            notes: input.notes,
+            exercises: input
+                .exercises
+                .into_iter()
+                .map(|e| CreateWorkoutTemplateExercise {
+                    exercise_id: e.exercise_id,
+                    target_sets: e.target_sets,
+                    target_reps: e.target_reps,
+                    target_weight_kg: e
+                        .target_weight_kg
+                        .map(|w| Decimal::try_from(w).unwrap_or_default()),
+                    notes: e.notes,
+                })
+                .collect(),
+        })
+    }
+
+    /// Attach a template record's exercises
+    async fn load_template(
+        pool: &PgPool,
+        record: WorkoutTemplateRecord,
+    ) -> Result<WorkoutTemplate, ApiError> {
+        let exercises = WorkoutTemplateRepository::get_exercises(pool, record.id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(WorkoutTemplate {
+            id: record.id,
+            name: record.name,
+            workout_type: record.workout_type,
+            notes: record.notes,
+            exercises: exercises
+                .into_iter()
+                .map(|e| WorkoutTemplateExercise {
+                    exercise_id: e.exercise_id,
+                    exercise_name: e.exercise_name,
+                    target_sets: e.target_sets,
+                    target_reps: e.target_reps,
+                    target_weight_kg: e.target_weight_kg.map(|w| decimal_to_f64(&w)),
+                    notes: e.notes,
+                })
+                .collect(),
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        })
     }
 
     /// Calculate pace in seconds per kilometer
@@ -461,22 +1176,75 @@ impl ExerciseService {
         }
     }
 
+    /// Estimate the equivalent flat-ground pace for a run with climbing
+    ///
+    /// Each 1% of average grade (elevation gain over distance) is worth about
+    /// 12 s/km, so the adjusted pace is faster than the actual one.
+    pub fn grade_adjusted_pace(
+        pace_seconds_per_km: i32,
+        distance_meters: f64,
+        elevation_gain_meters: f64,
+    ) -> i32 {
+        if distance_meters <= 0.0 || elevation_gain_meters <= 0.0 {
+            return pace_seconds_per_km;
+        }
+
+        let grade_percent = elevation_gain_meters / distance_meters * 100.0;
+        let adjusted = pace_seconds_per_km as f64 - grade_percent * GRADE_ADJUSTMENT_SECS_PER_PERCENT;
+        (adjusted.round() as i32).max(1)
+    }
+
     /// Get weekly exercise summary
     ///
     /// # Property 10: Weekly Exercise Volume
     /// Weekly total equals sum of all workouts in the week
     pub async fn get_weekly_summary(
         pool: &PgPool,
+        cache: Option<&dyn CacheStore>,
         user_id: Uuid,
         date: NaiveDate,
+        week_start: Weekday,
     ) -> Result<WeeklyExerciseSummary, ApiError> {
-        // Find the Monday of the week containing the given date
-        let week_start = Self::get_week_start(date);
-        let week_end = week_start + chrono::Duration::days(6);
+        // Find the first day of the week containing the given date
+        let week_start = Self::get_week_start(date, week_start);
 
-        let workouts = WorkoutRepository::get_by_week(pool, user_id, week_start)
+        Self::get_weekly_summary_with(cache, user_id, week_start, || async move {
+            let workouts = WorkoutRepository::get_by_week(pool, user_id, week_start).await?;
+            Ok(Self::summarize_week(week_start, &workouts))
+        })
+        .await
+    }
+
+    /// Cached weekly summary over an arbitrary computation, so the cache path can be tested
+    async fn get_weekly_summary_with<F, Fut>(
+        cache: Option<&dyn CacheStore>,
+        user_id: Uuid,
+        week_start: NaiveDate,
+        compute: F,
+    ) -> Result<WeeklyExerciseSummary, ApiError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<WeeklyExerciseSummary>>,
+    {
+        let key = format!("{}{}:{}", WEEKLY_SUMMARY_CACHE_PREFIX, user_id, week_start);
+        cache::get_or_fetch(cache, &key, WEEKLY_SUMMARY_CACHE_TTL_SECS, compute)
             .await
-            .map_err(ApiError::Internal)?;
+            .map_err(ApiError::Internal)
+    }
+
+    /// Drop every cached weekly summary for a user
+    ///
+    /// Summaries are keyed by week start, which depends on the user's week
+    /// start preference, so all of the user's weeks are cleared rather than
+    /// only the one containing the changed workout.
+    pub async fn invalidate_weekly_summaries(cache: Option<&dyn CacheStore>, user_id: Uuid) {
+        let prefix = format!("{}{}:", WEEKLY_SUMMARY_CACHE_PREFIX, user_id);
+        cache::invalidate_prefix(cache, &prefix).await;
+    }
+
+    /// Aggregate a week's workouts into totals, per-type and per-day breakdowns
+    fn summarize_week(week_start: NaiveDate, workouts: &[WorkoutRecord]) -> WeeklyExerciseSummary {
+        let week_end = week_start + chrono::Duration::days(6);
 
         // Calculate totals
         let total_workouts = workouts.len();
@@ -492,7 +1260,7 @@ impl ExerciseService {
         // Group by workout type
         let mut type_map: std::collections::HashMap<String, WorkoutTypeSummary> =
             std::collections::HashMap::new();
-        for workout in &workouts {
+        for workout in workouts {
             let entry = type_map
                 .entry(workout.workout_type.clone())
                 .or_insert_with(|| WorkoutTypeSummary {
@@ -510,7 +1278,7 @@ impl ExerciseService {
         // Daily breakdown
         let mut daily_map: std::collections::HashMap<NaiveDate, DailyWorkoutSummary> =
             std::collections::HashMap::new();
-        for workout in &workouts {
+        for workout in workouts {
             let date = workout.started_at.date_naive();
             let entry = daily_map.entry(date).or_insert_with(|| DailyWorkoutSummary {
                 date,
@@ -525,7 +1293,7 @@ impl ExerciseService {
         let mut daily_breakdown: Vec<DailyWorkoutSummary> = daily_map.into_values().collect();
         daily_breakdown.sort_by_key(|d| d.date);
 
-        Ok(WeeklyExerciseSummary {
+        WeeklyExerciseSummary {
             week_start,
             week_end,
             total_workouts,
@@ -533,14 +1301,18 @@ impl ExerciseService {
             total_calories_burned,
             workouts_by_type,
             daily_breakdown,
-        })
+        }
     }
 
-    /// Get the Monday of the week containing the given date
-    fn get_week_start(date: NaiveDate) -> NaiveDate {
-        let weekday = date.weekday();
-        let days_from_monday = weekday.num_days_from_monday() as i64;
-        date - chrono::Duration::days(days_from_monday)
+    /// Get the first day of the week containing the given date
+    ///
+    /// `week_start` is the day weeks begin on; pass [`DEFAULT_WEEK_START`]
+    /// for Monday-start weeks.
+    pub fn get_week_start(date: NaiveDate, week_start: Weekday) -> NaiveDate {
+        let days_since_start = (date.weekday().num_days_from_monday() + 7
+            - week_start.num_days_from_monday())
+            % 7;
+        date - chrono::Duration::days(days_since_start as i64)
     }
 
     /// Convert database record to Exercise
@@ -560,6 +1332,19 @@ impl ExerciseService {
 
     /// Convert database record to Workout
     fn record_to_workout(record: WorkoutRecord) -> Workout {
+        let distance_meters = record.distance_meters.map(|d| decimal_to_f64(&d));
+        let elevation_gain_meters = record.elevation_gain_meters.map(|d| decimal_to_f64(&d));
+        let grade_adjusted_pace_seconds_per_km = match (
+            record.pace_seconds_per_km,
+            distance_meters,
+            elevation_gain_meters,
+        ) {
+            (Some(pace), Some(distance), Some(elevation)) => {
+                Some(Self::grade_adjusted_pace(pace, distance, elevation))
+            }
+            _ => None,
+        };
+
         Workout {
             id: record.id,
             name: record.name,
@@ -570,9 +1355,13 @@ impl ExerciseService {
             calories_burned: record.calories_burned,
             avg_heart_rate: record.avg_heart_rate,
             max_heart_rate: record.max_heart_rate,
-            distance_meters: record.distance_meters.map(|d| decimal_to_f64(&d)),
+            recovery_heart_rate: record.recovery_heart_rate,
+            heart_rate_recovery: record.heart_rate_recovery,
+            heart_rate_recovery_class: record.heart_rate_recovery.map(HrrClassification::from_hrr),
+            distance_meters,
             pace_seconds_per_km: record.pace_seconds_per_km,
-            elevation_gain_meters: record.elevation_gain_meters.map(|d| decimal_to_f64(&d)),
+            elevation_gain_meters,
+            grade_adjusted_pace_seconds_per_km,
             source: record.source,
             notes: record.notes,
         }
@@ -596,6 +1385,86 @@ impl ExerciseService {
     }
 }
 
+/// Normalise an equipment name so "Dumbbell" and "dumbbells" match
+fn normalize_equipment(equipment: &str) -> String {
+    let equipment = equipment.trim().to_lowercase();
+    match equipment.strip_suffix('s') {
+        Some(singular) => singular.to_string(),
+        None => equipment,
+    }
+}
+
+/// Builds a workout from a template with one set placeholder per prescribed set
+pub fn expand_workout_template(template: &WorkoutTemplate, started_at: DateTime<Utc>) -> LogWorkoutInput {
+    LogWorkoutInput {
+        name: Some(template.name.clone()),
+        workout_type: template.workout_type.clone(),
+        started_at,
+        ended_at: None,
+        duration_minutes: None,
+        calories_burned: None,
+        avg_heart_rate: None,
+        max_heart_rate: None,
+        recovery_heart_rate: None,
+        distance_meters: None,
+        elevation_gain_meters: None,
+        source: Some("template".to_string()),
+        notes: template.notes.clone(),
+        exercises: template
+            .exercises
+            .iter()
+            .map(|exercise| LogWorkoutExerciseInput {
+                exercise_id: exercise.exercise_id,
+                notes: exercise.notes.clone(),
+                sets: (0..exercise.target_sets)
+                    .map(|_| LogExerciseSetInput {
+                        reps: Some(exercise.target_reps),
+                        weight_kg: exercise.target_weight_kg,
+                        duration_seconds: None,
+                        distance_meters: None,
+                        rest_seconds: None,
+                        rpe: None,
+                        is_warmup: false,
+                        is_dropset: false,
+                        notes: None,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Split working sets into consecutive per-workout groups, keeping their order
+fn group_sessions(sets: &[SessionSetRecord]) -> Vec<Vec<&SessionSetRecord>> {
+    let mut sessions: Vec<Vec<&SessionSetRecord>> = Vec::new();
+    for set in sets {
+        match sessions.last_mut() {
+            Some(session) if session[0].workout_id == set.workout_id => session.push(set),
+            _ => sessions.push(vec![set]),
+        }
+    }
+    sessions
+}
+
+/// Estimate a one-rep max from a set using the Epley formula
+pub fn estimate_one_rep_max(weight_kg: f64, reps: i32) -> f64 {
+    if reps <= 1 {
+        weight_kg
+    } else {
+        weight_kg * (1.0 + reps as f64 / 30.0)
+    }
+}
+
+/// Round to one decimal place for display
+fn round_to_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Round a load to the nearest plate increment
+fn round_to_increment(weight_kg: f64) -> f64 {
+    (weight_kg / LOAD_INCREMENT_KG).round() * LOAD_INCREMENT_KG
+}
+
 /// Convert Decimal to f64
 fn decimal_to_f64(d: &Decimal) -> f64 {
     d.to_f64().unwrap_or(0.0)
@@ -604,7 +1473,383 @@ fn decimal_to_f64(d: &Decimal) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::memory::MemoryCache;
     use proptest::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Helper to create a test exercise record
+    fn create_test_exercise_record(name: &str, is_custom: bool) -> ExerciseRecord {
+        ExerciseRecord {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            category: "strength".to_string(),
+            muscle_groups: vec!["chest".to_string()],
+            equipment: Some("barbell".to_string()),
+            calories_per_minute: None,
+            description: None,
+            instructions: None,
+            is_custom,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn create_exercise_input(name: &str, is_custom: bool) -> CreateExercise {
+        CreateExercise {
+            name: name.to_string(),
+            category: "strength".to_string(),
+            muscle_groups: vec!["chest".to_string()],
+            equipment: None,
+            calories_per_minute: None,
+            description: None,
+            instructions: None,
+            is_custom,
+            created_by: None,
+        }
+    }
+
+    fn library_exercise(name: &str, muscle_groups: &[&str], equipment: Option<&str>) -> ExerciseRecord {
+        ExerciseRecord {
+            muscle_groups: muscle_groups.iter().map(|m| m.to_string()).collect(),
+            equipment: equipment.map(str::to_string),
+            ..create_test_exercise_record(name, false)
+        }
+    }
+
+    #[test]
+    fn test_substitutions_for_bench_press_with_only_dumbbells() {
+        let bench = library_exercise("Bench Press", &["chest", "triceps", "shoulders"], Some("barbell"));
+        let candidates = vec![
+            bench.clone(),
+            library_exercise("Incline Bench Press", &["chest", "shoulders", "triceps"], Some("barbell")),
+            library_exercise("Dumbbell Fly", &["chest"], Some("dumbbells")),
+            library_exercise("Dumbbell Bench Press", &["chest", "triceps", "shoulders"], Some("dumbbells")),
+            library_exercise("Push-Up", &["chest", "triceps", "shoulders"], None),
+            library_exercise("Cable Crossover", &["chest"], Some("cable")),
+        ];
+
+        let suggestions = ExerciseService::rank_substitutions(
+            &bench,
+            candidates,
+            &["Dumbbell".to_string()],
+        );
+        let names: Vec<&str> = suggestions.iter().map(|e| e.name.as_str()).collect();
+
+        // Full overlap first (alphabetical), then partial overlap
+        assert_eq!(names, vec!["Dumbbell Bench Press", "Push-Up", "Dumbbell Fly"]);
+    }
+
+    #[test]
+    fn test_substitutions_require_shared_muscle_group() {
+        let bench = library_exercise("Bench Press", &["chest", "triceps"], Some("barbell"));
+        let candidates = vec![library_exercise("Goblet Squat", &["quadriceps"], Some("dumbbells"))];
+
+        assert!(ExerciseService::rank_substitutions(&bench, candidates, &["dumbbells".to_string()])
+            .is_empty());
+    }
+
+    /// Working sets for one synthetic session, `days_ago` days back
+    fn session_sets(days_ago: i64, weight_kg: i64, reps: &[i32], rpe: Option<i64>) -> Vec<SessionSetRecord> {
+        let workout_id = Uuid::new_v4();
+        let performed_at = Utc::now() - chrono::Duration::days(days_ago);
+        reps.iter()
+            .map(|&reps| SessionSetRecord {
+                workout_id,
+                performed_at,
+                weight_kg: Decimal::from(weight_kg),
+                reps,
+                rpe: rpe.map(Decimal::from),
+            })
+            .collect()
+    }
+
+    fn recommend(sessions: Vec<Vec<SessionSetRecord>>) -> LoadRecommendation {
+        let sets: Vec<SessionSetRecord> = sessions.into_iter().flatten().collect();
+        ExerciseService::progress_load(&ExerciseService::summarize_sessions(&sets))
+    }
+
+    #[test]
+    fn test_progression_requires_a_prior_session() {
+        let recommendation = recommend(vec![]);
+        assert_eq!(recommendation.action, "none");
+        assert_eq!(recommendation.recommended_weight_kg, None);
+        assert!(recommendation.message.contains("Log a session"));
+    }
+
+    #[test]
+    fn test_progression_increases_at_top_of_range_with_low_rpe() {
+        let recommendation = recommend(vec![session_sets(2, 60, &[12, 12, 12], Some(7))]);
+        assert_eq!(recommendation.action, "increase");
+        assert_eq!(recommendation.current_weight_kg, Some(60.0));
+        assert_eq!(recommendation.recommended_weight_kg, Some(62.5));
+        assert_eq!(recommendation.target_reps, Some(8));
+    }
+
+    #[test]
+    fn test_progression_keeps_weight_until_every_set_tops_range() {
+        let recommendation = recommend(vec![session_sets(2, 60, &[12, 11, 10], Some(7))]);
+        assert_eq!(recommendation.action, "maintain");
+        assert_eq!(recommendation.recommended_weight_kg, Some(60.0));
+        assert_eq!(recommendation.target_reps, Some(11));
+    }
+
+    #[test]
+    fn test_progression_keeps_weight_when_top_of_range_felt_hard() {
+        let recommendation = recommend(vec![
+            session_sets(2, 60, &[12, 12, 12], Some(9)),
+            session_sets(5, 60, &[11, 10, 10], Some(8)),
+        ]);
+        assert_eq!(recommendation.action, "maintain");
+        assert_eq!(recommendation.recommended_weight_kg, Some(60.0));
+    }
+
+    #[test]
+    fn test_progression_deloads_after_consecutive_grinding_sessions() {
+        let recommendation = recommend(vec![
+            session_sets(2, 100, &[8, 7, 6], Some(10)),
+            session_sets(5, 100, &[8, 8, 7], Some(9)),
+        ]);
+        assert_eq!(recommendation.action, "deload");
+        assert_eq!(recommendation.recommended_weight_kg, Some(90.0));
+        assert_eq!(recommendation.target_reps, Some(8));
+    }
+
+    #[test]
+    fn test_summarize_sessions_uses_top_weight_sets() {
+        let mut sets = session_sets(1, 60, &[12, 12], None);
+        let workout_id = sets[0].workout_id;
+        sets.extend(
+            session_sets(1, 70, &[9], Some(8))
+                .into_iter()
+                .map(|s| SessionSetRecord { workout_id, ..s }),
+        );
+
+        let sessions = ExerciseService::summarize_sessions(&sets);
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].weight_kg, 70.0);
+        assert_eq!(sessions[0].min_reps, 9);
+        assert_eq!(sessions[0].avg_rpe, Some(8.0));
+    }
+
+    #[test]
+    fn test_estimate_one_rep_max() {
+        assert_eq!(estimate_one_rep_max(100.0, 1), 100.0);
+        assert!((estimate_one_rep_max(100.0, 5) - 116.666_666_666).abs() < 1e-6);
+        assert!((estimate_one_rep_max(60.0, 10) - 80.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_exercise_history_shows_rising_one_rep_max_oldest_first() {
+        // Newest session first, as the repository returns them
+        let sets: Vec<SessionSetRecord> = [
+            session_sets(2, 65, &[8, 8, 7], None),
+            session_sets(5, 60, &[8, 8, 8], None),
+        ]
+        .concat();
+
+        let history = ExerciseService::build_exercise_history(&sets, Tz::UTC);
+
+        assert_eq!(history.len(), 2);
+        assert!(history[0].performed_at < history[1].performed_at);
+        assert!(history[0].date < history[1].date);
+
+        assert_eq!(history[0].top_set_weight_kg, 60.0);
+        assert_eq!(history[0].top_set_reps, 8);
+        assert_eq!(history[0].estimated_one_rep_max_kg, 76.0);
+        assert_eq!(history[0].total_volume_kg, 1440.0);
+
+        assert_eq!(history[1].top_set_weight_kg, 65.0);
+        assert_eq!(history[1].top_set_reps, 8);
+        assert_eq!(history[1].estimated_one_rep_max_kg, 82.3);
+        assert_eq!(history[1].total_volume_kg, 1495.0);
+
+        assert!(history[1].estimated_one_rep_max_kg > history[0].estimated_one_rep_max_kg);
+    }
+
+    #[test]
+    fn test_expand_workout_template_creates_prescribed_set_placeholders() {
+        let prescription = |name: &str, sets: i32, reps: i32, weight: Option<f64>| {
+            WorkoutTemplateExercise {
+                exercise_id: Uuid::new_v4(),
+                exercise_name: name.to_string(),
+                target_sets: sets,
+                target_reps: reps,
+                target_weight_kg: weight,
+                notes: None,
+            }
+        };
+        let template = WorkoutTemplate {
+            id: Uuid::new_v4(),
+            name: "Push Day".to_string(),
+            workout_type: "strength".to_string(),
+            notes: None,
+            exercises: vec![
+                prescription("Bench Press", 3, 5, Some(80.0)),
+                prescription("Overhead Press", 3, 8, Some(40.0)),
+                prescription("Push-Up", 2, 15, None),
+            ],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let started_at = Utc::now();
+
+        let input = expand_workout_template(&template, started_at);
+
+        assert_eq!(input.name.as_deref(), Some("Push Day"));
+        assert_eq!(input.started_at, started_at);
+        assert_eq!(input.exercises.len(), 3);
+        for (logged, prescribed) in input.exercises.iter().zip(&template.exercises) {
+            assert_eq!(logged.exercise_id, prescribed.exercise_id);
+            assert_eq!(logged.sets.len(), prescribed.target_sets as usize);
+            assert!(logged.sets.iter().all(|set| {
+                set.reps == Some(prescribed.target_reps)
+                    && set.weight_kg == prescribed.target_weight_kg
+                    && !set.is_warmup
+            }));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exercise_library_served_from_cache() {
+        let cache = MemoryCache::default();
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            let library = ExerciseService::get_exercise_library_with(Some(&cache), || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(vec![create_test_exercise_record("Bench Press", false)])
+            })
+            .await
+            .unwrap();
+
+            assert_eq!(library.len(), 1);
+            assert_eq!(library[0].name, "Bench Press");
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_creating_library_exercise_clears_cache() {
+        let cache = MemoryCache::default();
+        ExerciseService::get_exercise_library_with(Some(&cache), || async {
+            Ok(vec![create_test_exercise_record("Bench Press", false)])
+        })
+        .await
+        .unwrap();
+        assert!(cache.contains(LIBRARY_CACHE_KEY));
+
+        let exercise = ExerciseService::create_exercise_with(
+            Some(&cache),
+            create_exercise_input("Incline Press", false),
+            |input| async move { Ok(create_test_exercise_record(&input.name, input.is_custom)) },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(exercise.name, "Incline Press");
+        assert!(!cache.contains(LIBRARY_CACHE_KEY));
+    }
+
+    #[tokio::test]
+    async fn test_creating_custom_exercise_keeps_cache() {
+        let cache = MemoryCache::default();
+        ExerciseService::get_exercise_library_with(Some(&cache), || async {
+            Ok(vec![create_test_exercise_record("Bench Press", false)])
+        })
+        .await
+        .unwrap();
+
+        ExerciseService::create_exercise_with(
+            Some(&cache),
+            create_exercise_input("My Press", true),
+            |input| async move { Ok(create_test_exercise_record(&input.name, input.is_custom)) },
+        )
+        .await
+        .unwrap();
+
+        assert!(cache.contains(LIBRARY_CACHE_KEY));
+    }
+
+    fn empty_week(week_start: NaiveDate) -> WeeklyExerciseSummary {
+        ExerciseService::summarize_week(week_start, &[])
+    }
+
+    #[tokio::test]
+    async fn test_weekly_summary_served_from_cache() {
+        let cache = MemoryCache::default();
+        let calls = AtomicUsize::new(0);
+        let user_id = Uuid::new_v4();
+        let week_start = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        for _ in 0..2 {
+            let summary =
+                ExerciseService::get_weekly_summary_with(Some(&cache), user_id, week_start, || async {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Ok(empty_week(week_start))
+                })
+                .await
+                .unwrap();
+
+            assert_eq!(summary.week_start, week_start);
+            assert_eq!(summary.week_end, NaiveDate::from_ymd_opt(2024, 1, 21).unwrap());
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_workout_change_busts_weekly_summary_cache() {
+        let cache = MemoryCache::default();
+        let calls = AtomicUsize::new(0);
+        let user_id = Uuid::new_v4();
+        let other_user = Uuid::new_v4();
+        let week_start = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        for user in [user_id, other_user] {
+            ExerciseService::get_weekly_summary_with(Some(&cache), user, week_start, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(empty_week(week_start))
+            })
+            .await
+            .unwrap();
+        }
+
+        // Logging or deleting a workout clears the user's cached weeks
+        ExerciseService::invalidate_weekly_summaries(Some(&cache), user_id).await;
+
+        let user_key = format!("{}{}:{}", WEEKLY_SUMMARY_CACHE_PREFIX, user_id, week_start);
+        let other_key = format!("{}{}:{}", WEEKLY_SUMMARY_CACHE_PREFIX, other_user, week_start);
+        assert!(!cache.contains(&user_key));
+        assert!(cache.contains(&other_key));
+
+        ExerciseService::get_weekly_summary_with(Some(&cache), user_id, week_start, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(empty_week(week_start))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_weekly_summary_without_cache_always_computes() {
+        let calls = AtomicUsize::new(0);
+        let week_start = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        for _ in 0..2 {
+            ExerciseService::get_weekly_summary_with(None, Uuid::new_v4(), week_start, || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(empty_week(week_start))
+            })
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
 
     // Feature: fitness-assistant-ai, Property 9: Pace Calculation Correctness
     proptest! {
@@ -648,21 +1893,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_grade_adjusted_pace_unchanged_on_flat_terrain() {
+        assert_eq!(ExerciseService::grade_adjusted_pace(330, 10_000.0, 0.0), 330);
+    }
+
+    #[test]
+    fn test_grade_adjusted_pace_faster_when_climbing() {
+        // 500 m of climbing over 10 km is a 5% average grade: 60 s/km faster
+        let adjusted = ExerciseService::grade_adjusted_pace(420, 10_000.0, 500.0);
+        assert_eq!(adjusted, 360);
+        assert!(adjusted < 420);
+    }
+
+    #[test]
+    fn test_grade_adjusted_pace_ignores_missing_distance() {
+        assert_eq!(ExerciseService::grade_adjusted_pace(400, 0.0, 200.0), 400);
+    }
+
     // Feature: fitness-assistant-ai, Property 10: Weekly Exercise Volume
     #[test]
     fn test_week_start_calculation() {
         // Monday should return itself
         let monday = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap(); // Monday
-        assert_eq!(ExerciseService::get_week_start(monday), monday);
+        assert_eq!(ExerciseService::get_week_start(monday, DEFAULT_WEEK_START), monday);
 
         // Sunday should return previous Monday
         let sunday = NaiveDate::from_ymd_opt(2025, 1, 5).unwrap(); // Sunday
         let expected_monday = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap();
-        assert_eq!(ExerciseService::get_week_start(sunday), expected_monday);
+        assert_eq!(ExerciseService::get_week_start(sunday, DEFAULT_WEEK_START), expected_monday);
 
         // Wednesday should return Monday of same week
         let wednesday = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(); // Wednesday
-        assert_eq!(ExerciseService::get_week_start(wednesday), expected_monday);
+        assert_eq!(ExerciseService::get_week_start(wednesday, DEFAULT_WEEK_START), expected_monday);
+    }
+
+    #[test]
+    fn test_sunday_week_start() {
+        // Wednesday should return the prior Sunday
+        let wednesday = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(); // Wednesday
+        let expected_sunday = NaiveDate::from_ymd_opt(2024, 12, 29).unwrap();
+        assert_eq!(ExerciseService::get_week_start(wednesday, Weekday::Sun), expected_sunday);
+
+        // Sunday should return itself
+        assert_eq!(ExerciseService::get_week_start(expected_sunday, Weekday::Sun), expected_sunday);
+
+        // Saturday is the last day of a Sunday-start week
+        let saturday = NaiveDate::from_ymd_opt(2025, 1, 4).unwrap();
+        assert_eq!(ExerciseService::get_week_start(saturday, Weekday::Sun), expected_sunday);
     }
 
     proptest! {
@@ -678,7 +1956,7 @@ mod tests {
             prop_assume!(date.is_some());
             let date = date.unwrap();
 
-            let week_start = ExerciseService::get_week_start(date);
+            let week_start = ExerciseService::get_week_start(date, DEFAULT_WEEK_START);
 
             // Week start should always be a Monday
             prop_assert_eq!(week_start.weekday(), Weekday::Mon,
@@ -690,7 +1968,26 @@ mod tests {
 
             // Week start should be within 6 days of date
             let days_diff = (date - week_start).num_days();
-            prop_assert!(days_diff >= 0 && days_diff <= 6,
+            prop_assert!((0..=6).contains(&days_diff),
+                "Week start {} is {} days from date {}", week_start, days_diff, date);
+        }
+
+        #[test]
+        fn test_week_start_honors_preference(
+            year in 2020i32..2030,
+            day_of_year in 1u32..366,
+            start_offset in 0u8..7
+        ) {
+            let date = NaiveDate::from_yo_opt(year, day_of_year);
+            prop_assume!(date.is_some());
+            let date = date.unwrap();
+            let preferred = Weekday::try_from(start_offset).unwrap();
+
+            let week_start = ExerciseService::get_week_start(date, preferred);
+
+            prop_assert_eq!(week_start.weekday(), preferred);
+            let days_diff = (date - week_start).num_days();
+            prop_assert!((0..=6).contains(&days_diff),
                 "Week start {} is {} days from date {}", week_start, days_diff, date);
         }
     }
@@ -3,22 +3,265 @@
 //! Supports multiple formats:
 //! - JSON: Full structured export
 //! - CSV: Tabular export for spreadsheets
+//! - Encrypted: JSON sealed with a user-supplied passphrase
 //!
 //! Property 14: Data Import/Export Round-Trip
 //! Exported data can be re-imported equivalently
 
 use crate::error::ApiError;
 use crate::repositories::{
-    BiomarkerLogRepository, BodyCompositionRepository, ExerciseSetRepository, GoalRepository,
-    HeartRateLogRepository, HrvLogRepository, HydrationLogRepository, MilestoneRepository,
-    SleepLogRepository, WeightRepository, WorkoutExerciseRepository, WorkoutRepository,
+    BiomarkerLogRepository, BodyCompositionRepository, ExerciseSetRepository, FoodLogExportRecord,
+    FoodLogRepository, GoalRepository, HeartRateLogRepository, HrvLogRepository,
+    HydrationLogRepository, MilestoneRepository, SleepLogRepository, WeightRepository,
+    WorkoutExerciseRepository, WorkoutRepository,
 };
-use chrono::{DateTime, NaiveDate, Utc};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::password_hash::rand_core::RngCore;
+use argon2::Argon2;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
 use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::future::Future;
+use std::str::FromStr;
 use uuid::Uuid;
 
+/// Length of the random Argon2 salt prepended to encrypted exports
+const ENCRYPTION_SALT_LEN: usize = 16;
+
+/// Length of the AES-GCM nonce that follows the salt
+const ENCRYPTION_NONCE_LEN: usize = 12;
+
+/// Datasets to include in a JSON export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportSections {
+    pub weight: bool,
+    pub body_composition: bool,
+    pub workouts: bool,
+    pub sleep: bool,
+    pub hydration: bool,
+    pub heart_rate: bool,
+    pub hrv: bool,
+    pub biomarkers: bool,
+    pub goals: bool,
+}
+
+impl ExportSections {
+    /// Every dataset
+    pub const ALL: Self = Self {
+        weight: true,
+        body_composition: true,
+        workouts: true,
+        sleep: true,
+        hydration: true,
+        heart_rate: true,
+        hrv: true,
+        biomarkers: true,
+        goals: true,
+    };
+
+    /// No datasets
+    pub const NONE: Self = Self {
+        weight: false,
+        body_composition: false,
+        workouts: false,
+        sleep: false,
+        hydration: false,
+        heart_rate: false,
+        hrv: false,
+        biomarkers: false,
+        goals: false,
+    };
+}
+
+impl Default for ExportSections {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl FromStr for ExportSections {
+    type Err = String;
+
+    /// Parse a comma-separated list such as "weight,sleep"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sections = Self::NONE;
+        for name in s.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            match name.to_lowercase().as_str() {
+                "weight" => sections.weight = true,
+                "body_composition" => sections.body_composition = true,
+                "workouts" => sections.workouts = true,
+                "sleep" => sections.sleep = true,
+                "hydration" => sections.hydration = true,
+                "heart_rate" => sections.heart_rate = true,
+                "hrv" => sections.hrv = true,
+                "biomarkers" => sections.biomarkers = true,
+                "goals" => sections.goals = true,
+                _ => return Err(format!("Unknown export section: {}", name)),
+            }
+        }
+
+        if sections == Self::NONE {
+            return Err("At least one export section is required".to_string());
+        }
+        Ok(sections)
+    }
+}
+
+/// Where the datasets of a JSON export are loaded from
+#[async_trait]
+trait ExportSource: Send + Sync {
+    async fn weight_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<WeightLogExport>, ApiError>;
+
+    async fn body_composition(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BodyCompositionExport>, ApiError>;
+
+    async fn workouts(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<WorkoutExport>, ApiError>;
+
+    async fn sleep_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<SleepLogExport>, ApiError>;
+
+    async fn hydration_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<HydrationLogExport>, ApiError>;
+
+    async fn heart_rate_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<HeartRateLogExport>, ApiError>;
+
+    async fn hrv_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<HrvLogExport>, ApiError>;
+
+    async fn biomarker_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BiomarkerLogExport>, ApiError>;
+
+    async fn goals(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<GoalExport>, ApiError>;
+}
+
+#[async_trait]
+impl ExportSource for PgPool {
+    async fn weight_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<WeightLogExport>, ApiError> {
+        ExportService::fetch_weight_logs(self, user_id, start, end).await
+    }
+
+    async fn body_composition(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BodyCompositionExport>, ApiError> {
+        ExportService::fetch_body_composition(self, user_id, start, end).await
+    }
+
+    async fn workouts(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<WorkoutExport>, ApiError> {
+        ExportService::fetch_workouts(self, user_id, start, end).await
+    }
+
+    async fn sleep_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<SleepLogExport>, ApiError> {
+        ExportService::fetch_sleep_logs(self, user_id, start, end).await
+    }
+
+    async fn hydration_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<HydrationLogExport>, ApiError> {
+        ExportService::fetch_hydration_logs(self, user_id, start, end).await
+    }
+
+    async fn heart_rate_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<HeartRateLogExport>, ApiError> {
+        ExportService::fetch_heart_rate_logs(self, user_id, start, end).await
+    }
+
+    async fn hrv_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<HrvLogExport>, ApiError> {
+        ExportService::fetch_hrv_logs(self, user_id, start, end).await
+    }
+
+    async fn biomarker_logs(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BiomarkerLogExport>, ApiError> {
+        ExportService::fetch_biomarker_logs(self, user_id, start, end).await
+    }
+
+    async fn goals(
+        &self,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<GoalExport>, ApiError> {
+        ExportService::fetch_goals(self, user_id, start, end).await
+    }
+}
+
 /// Complete user data export
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserDataExport {
@@ -189,23 +432,91 @@ pub struct SleepCsvRow {
     pub awake_minutes: i32,
 }
 
+/// CSV export row for nutrition data, one per food log
+#[derive(Debug, Clone, Serialize)]
+pub struct NutritionCsvRow {
+    pub date: String,
+    pub meal_type: String,
+    pub food_name: String,
+    pub servings: f64,
+    pub calories: f64,
+    pub protein_g: f64,
+    pub carbohydrates_g: f64,
+    pub fat_g: f64,
+    pub fiber_g: f64,
+}
+
+impl From<FoodLogExportRecord> for NutritionCsvRow {
+    fn from(r: FoodLogExportRecord) -> Self {
+        Self {
+            date: r.consumed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            meal_type: r.meal_type,
+            food_name: r.food_name,
+            servings: r.servings.to_f64().unwrap_or(0.0),
+            calories: r.calories.to_f64().unwrap_or(0.0),
+            protein_g: r.protein_g.to_f64().unwrap_or(0.0),
+            carbohydrates_g: r.carbohydrates_g.to_f64().unwrap_or(0.0),
+            fat_g: r.fat_g.to_f64().unwrap_or(0.0),
+            fiber_g: r.fiber_g.to_f64().unwrap_or(0.0),
+        }
+    }
+}
+
 /// Data export service
 pub struct ExportService;
 
 impl ExportService {
-    /// Export all user data as JSON
-    pub async fn export_json(pool: &PgPool, user_id: Uuid) -> Result<UserDataExport, ApiError> {
-        // Fetch all data in parallel
+    /// Export all user data as JSON, optionally limited to `start..=end`
+    pub async fn export_json(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<UserDataExport, ApiError> {
+        Self::export_json_selective(pool, user_id, ExportSections::ALL, start, end).await
+    }
+
+    /// Export only the requested datasets as JSON
+    ///
+    /// Sections that weren't requested are left empty and never queried.
+    /// Logs are limited to `start..=end` when given; goals aren't time series
+    /// and are always exported in full.
+    pub async fn export_json_selective(
+        pool: &PgPool,
+        user_id: Uuid,
+        sections: ExportSections,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<UserDataExport, ApiError> {
+        Self::export_json_from(pool, user_id, sections, start, end).await
+    }
+
+    async fn export_json_from<S: ExportSource + ?Sized>(
+        source: &S,
+        user_id: Uuid,
+        sections: ExportSections,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<UserDataExport, ApiError> {
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Err(ApiError::Validation(
+                    "Export start must not be after end".to_string(),
+                ));
+            }
+        }
+
+        // Fetch the requested data in parallel
         let (weights, body_comp, workouts, sleep, hydration, hr, hrv, biomarkers, goals) = tokio::join!(
-            Self::fetch_weight_logs(pool, user_id),
-            Self::fetch_body_composition(pool, user_id),
-            Self::fetch_workouts(pool, user_id),
-            Self::fetch_sleep_logs(pool, user_id),
-            Self::fetch_hydration_logs(pool, user_id),
-            Self::fetch_heart_rate_logs(pool, user_id),
-            Self::fetch_hrv_logs(pool, user_id),
-            Self::fetch_biomarker_logs(pool, user_id),
-            Self::fetch_goals(pool, user_id),
+            Self::section(sections.weight, source.weight_logs(user_id, start, end)),
+            Self::section(sections.body_composition, source.body_composition(user_id, start, end)),
+            Self::section(sections.workouts, source.workouts(user_id, start, end)),
+            Self::section(sections.sleep, source.sleep_logs(user_id, start, end)),
+            Self::section(sections.hydration, source.hydration_logs(user_id, start, end)),
+            Self::section(sections.heart_rate, source.heart_rate_logs(user_id, start, end)),
+            Self::section(sections.hrv, source.hrv_logs(user_id, start, end)),
+            Self::section(sections.biomarkers, source.biomarker_logs(user_id, start, end)),
+            Self::section(sections.goals, source.goals(user_id, start, end)),
         );
 
         Ok(UserDataExport {
@@ -224,9 +535,45 @@ impl ExportService {
         })
     }
 
+    /// Await `fetch` only if its section was requested
+    async fn section<T>(
+        include: bool,
+        fetch: impl Future<Output = Result<Vec<T>, ApiError>>,
+    ) -> Result<Vec<T>, ApiError> {
+        if include {
+            fetch.await
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Export all user data as JSON encrypted with `passphrase`
+    ///
+    /// The output is `salt || nonce || ciphertext`: the key is derived from the
+    /// passphrase with Argon2 and the JSON sealed with AES-256-GCM.
+    pub async fn export_encrypted(
+        pool: &PgPool,
+        user_id: Uuid,
+        passphrase: &str,
+    ) -> Result<Vec<u8>, ApiError> {
+        if passphrase.is_empty() {
+            return Err(ApiError::Validation("Passphrase is required".to_string()));
+        }
+
+        let export = Self::export_json(pool, user_id, None, None).await?;
+        let json = serde_json::to_vec(&export)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("JSON serialization error: {}", e)))?;
+
+        // Key derivation is deliberately CPU-intensive
+        let passphrase = passphrase.to_string();
+        tokio::task::spawn_blocking(move || encrypt_export(&json, &passphrase))
+            .await
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Task join error: {}", e)))?
+    }
+
     /// Export weight data as CSV
     pub async fn export_weight_csv(pool: &PgPool, user_id: Uuid) -> Result<String, ApiError> {
-        let weights = Self::fetch_weight_logs(pool, user_id).await?;
+        let weights = Self::fetch_weight_logs(pool, user_id, None, None).await?;
         
         let rows: Vec<WeightCsvRow> = weights
             .into_iter()
@@ -243,7 +590,7 @@ impl ExportService {
 
     /// Export sleep data as CSV
     pub async fn export_sleep_csv(pool: &PgPool, user_id: Uuid) -> Result<String, ApiError> {
-        let sleep_logs = Self::fetch_sleep_logs(pool, user_id).await?;
+        let sleep_logs = Self::fetch_sleep_logs(pool, user_id, None, None).await?;
         
         let rows: Vec<SleepCsvRow> = sleep_logs
             .into_iter()
@@ -271,6 +618,17 @@ impl ExportService {
         Self::to_csv(&rows)
     }
 
+    /// Export nutrition data as CSV
+    pub async fn export_nutrition_csv(pool: &PgPool, user_id: Uuid) -> Result<String, ApiError> {
+        let food_logs = FoodLogRepository::get_for_export(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        let rows: Vec<NutritionCsvRow> = food_logs.into_iter().map(NutritionCsvRow::from).collect();
+
+        Self::to_csv(&rows)
+    }
+
     /// Convert data to CSV string
     fn to_csv<T: Serialize>(data: &[T]) -> Result<String, ApiError> {
         let mut wtr = csv::Writer::from_writer(vec![]);
@@ -285,8 +643,13 @@ impl ExportService {
             .map_err(|e| ApiError::Internal(anyhow::anyhow!("CSV encoding error: {}", e)))
     }
 
-    async fn fetch_weight_logs(pool: &PgPool, user_id: Uuid) -> Result<Vec<WeightLogExport>, ApiError> {
-        let records = WeightRepository::get_by_date_range(pool, user_id, None, None)
+    async fn fetch_weight_logs(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<WeightLogExport>, ApiError> {
+        let records = WeightRepository::get_by_date_range(pool, user_id, start, end)
             .await
             .map_err(ApiError::Internal)?;
 
@@ -302,8 +665,13 @@ impl ExportService {
             .collect())
     }
 
-    async fn fetch_body_composition(pool: &PgPool, user_id: Uuid) -> Result<Vec<BodyCompositionExport>, ApiError> {
-        let records = BodyCompositionRepository::get_by_date_range(pool, user_id, None, None)
+    async fn fetch_body_composition(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BodyCompositionExport>, ApiError> {
+        let records = BodyCompositionRepository::get_by_date_range(pool, user_id, start, end)
             .await
             .map_err(ApiError::Internal)?;
 
@@ -322,8 +690,13 @@ impl ExportService {
             .collect())
     }
 
-    async fn fetch_workouts(pool: &PgPool, user_id: Uuid) -> Result<Vec<WorkoutExport>, ApiError> {
-        let (workouts, _) = WorkoutRepository::get_by_date_range(pool, user_id, None, None, 10000, 0)
+    async fn fetch_workouts(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<WorkoutExport>, ApiError> {
+        let (workouts, _) = WorkoutRepository::get_by_date_range(pool, user_id, start, end, 10000, 0)
             .await
             .map_err(ApiError::Internal)?;
 
@@ -372,9 +745,13 @@ impl ExportService {
         Ok(exports)
     }
 
-    async fn fetch_sleep_logs(pool: &PgPool, user_id: Uuid) -> Result<Vec<SleepLogExport>, ApiError> {
-        let start_date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
-        let end_date = NaiveDate::from_ymd_opt(2100, 12, 31).unwrap();
+    async fn fetch_sleep_logs(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<SleepLogExport>, ApiError> {
+        let (start_date, end_date) = export_query_dates(start, end);
         
         let records = SleepLogRepository::get_history(pool, user_id, start_date, end_date, 10000, 0)
             .await
@@ -382,6 +759,7 @@ impl ExportService {
 
         Ok(records
             .into_iter()
+            .filter(|r| in_export_range(r.sleep_end, start, end))
             .map(|r| SleepLogExport {
                 id: r.id.to_string(),
                 sleep_start: r.sleep_start,
@@ -397,23 +775,28 @@ impl ExportService {
             .collect())
     }
 
-    async fn fetch_hydration_logs(pool: &PgPool, user_id: Uuid) -> Result<Vec<HydrationLogExport>, ApiError> {
-        // Get all hydration logs by querying a wide date range
-        let start_date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
-        let end_date = NaiveDate::from_ymd_opt(2100, 12, 31).unwrap();
+    async fn fetch_hydration_logs(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<HydrationLogExport>, ApiError> {
+        let (start_date, end_date) = export_query_dates(start, end);
         
-        let summaries = HydrationLogRepository::get_daily_summaries(pool, user_id, start_date, end_date)
+        // Days only drive the per-day lookups below, so any timezone works as
+        // long as both calls agree
+        let summaries = HydrationLogRepository::get_daily_summaries(pool, user_id, start_date, end_date, Tz::UTC)
             .await
             .map_err(ApiError::Internal)?;
 
         // For each day with entries, get the actual logs
         let mut all_logs = Vec::new();
         for summary in summaries {
-            let logs = HydrationLogRepository::get_by_date(pool, user_id, summary.date)
+            let logs = HydrationLogRepository::get_by_date(pool, user_id, summary.date, Tz::UTC)
                 .await
                 .map_err(ApiError::Internal)?;
             
-            for r in logs {
+            for r in logs.into_iter().filter(|r| in_export_range(r.consumed_at, start, end)) {
                 all_logs.push(HydrationLogExport {
                     id: r.id.to_string(),
                     amount_ml: r.amount_ml,
@@ -427,9 +810,13 @@ impl ExportService {
         Ok(all_logs)
     }
 
-    async fn fetch_heart_rate_logs(pool: &PgPool, user_id: Uuid) -> Result<Vec<HeartRateLogExport>, ApiError> {
-        let start_date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
-        let end_date = NaiveDate::from_ymd_opt(2100, 12, 31).unwrap();
+    async fn fetch_heart_rate_logs(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<HeartRateLogExport>, ApiError> {
+        let (start_date, end_date) = export_query_dates(start, end);
         
         let records = HeartRateLogRepository::get_history(pool, user_id, start_date, end_date, None, 10000, 0)
             .await
@@ -437,6 +824,7 @@ impl ExportService {
 
         Ok(records
             .into_iter()
+            .filter(|r| in_export_range(r.recorded_at, start, end))
             .map(|r| HeartRateLogExport {
                 id: r.id.to_string(),
                 bpm: r.bpm,
@@ -447,16 +835,23 @@ impl ExportService {
             .collect())
     }
 
-    async fn fetch_hrv_logs(pool: &PgPool, user_id: Uuid) -> Result<Vec<HrvLogExport>, ApiError> {
-        let start_date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
-        let end_date = NaiveDate::from_ymd_opt(2100, 12, 31).unwrap();
+    async fn fetch_hrv_logs(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<HrvLogExport>, ApiError> {
+        let (start_date, end_date) = export_query_dates(start, end);
         
-        let records = HrvLogRepository::get_history(pool, user_id, start_date, end_date, 10000, 0)
-            .await
-            .map_err(ApiError::Internal)?;
+        // Export bounds are UTC instants, widened by a day and filtered below
+        let records =
+            HrvLogRepository::get_history(pool, user_id, start_date, end_date, Tz::UTC, 10000, 0)
+                .await
+                .map_err(ApiError::Internal)?;
 
         Ok(records
             .into_iter()
+            .filter(|r| in_export_range(r.recorded_at, start, end))
             .map(|r| HrvLogExport {
                 id: r.id.to_string(),
                 rmssd: r.rmssd.to_f64().unwrap_or(0.0),
@@ -468,13 +863,21 @@ impl ExportService {
             .collect())
     }
 
-    async fn fetch_biomarker_logs(pool: &PgPool, user_id: Uuid) -> Result<Vec<BiomarkerLogExport>, ApiError> {
+    async fn fetch_biomarker_logs(
+        pool: &PgPool,
+        user_id: Uuid,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<BiomarkerLogExport>, ApiError> {
         let records = BiomarkerLogRepository::get_by_user(pool, user_id, None, 10000, 0)
             .await
             .map_err(ApiError::Internal)?;
 
+        // Lab results only carry a date, so compare against the bounds' dates
+        let (start_date, end_date) = (start.map(|s| s.date_naive()), end.map(|e| e.date_naive()));
         Ok(records
             .into_iter()
+            .filter(|r| in_export_range(r.test_date, start_date, end_date))
             .map(|r| BiomarkerLogExport {
                 id: r.id.to_string(),
                 biomarker_name: r.biomarker_name,
@@ -487,7 +890,12 @@ impl ExportService {
             .collect())
     }
 
-    async fn fetch_goals(pool: &PgPool, user_id: Uuid) -> Result<Vec<GoalExport>, ApiError> {
+    async fn fetch_goals(
+        pool: &PgPool,
+        user_id: Uuid,
+        _start: Option<DateTime<Utc>>,
+        _end: Option<DateTime<Utc>>,
+    ) -> Result<Vec<GoalExport>, ApiError> {
         let goals = GoalRepository::get_by_user(pool, user_id, None, None)
             .await
             .map_err(ApiError::Internal)?;
@@ -528,11 +936,297 @@ impl ExportService {
     }
 }
 
+/// Dates to pass to day-based repository queries for an export range
+///
+/// Those queries bucket by UTC date, so the window is widened by a day on
+/// each side and the rows are filtered precisely with [`in_export_range`].
+fn export_query_dates(start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> (NaiveDate, NaiveDate) {
+    let start_date = start
+        .map(|s| s.date_naive() - Duration::days(1))
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(2000, 1, 1).unwrap());
+    let end_date = end
+        .map(|e| e.date_naive() + Duration::days(1))
+        .unwrap_or_else(|| NaiveDate::from_ymd_opt(2100, 12, 31).unwrap());
+    (start_date, end_date)
+}
+
+/// Whether `value` falls within the inclusive export range
+fn in_export_range<T: PartialOrd>(value: T, start: Option<T>, end: Option<T>) -> bool {
+    let after_start = match start {
+        Some(start) => value >= start,
+        None => true,
+    };
+    let before_end = match end {
+        Some(end) => value <= end,
+        None => true,
+    };
+    after_start && before_end
+}
+
+/// Derive a 256-bit key from `passphrase` and `salt`
+fn derive_export_key(passphrase: &str, salt: &[u8]) -> Result<Key<Aes256Gcm>, ApiError> {
+    let mut key = Key::<Aes256Gcm>::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| ApiError::Internal(anyhow::anyhow!("Key derivation error: {}", e)))?;
+    Ok(key)
+}
+
+/// Encrypt `plaintext` as `salt || nonce || ciphertext` (blocking)
+pub(crate) fn encrypt_export(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, ApiError> {
+    let mut salt = [0u8; ENCRYPTION_SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_export_key(passphrase, &salt)?;
+
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = Aes256Gcm::new(&key)
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| ApiError::Internal(anyhow::anyhow!("Export encryption failed")))?;
+
+    let mut output = Vec::with_capacity(salt.len() + nonce.len() + ciphertext.len());
+    output.extend_from_slice(&salt);
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Decrypt data produced by [`encrypt_export`] (blocking)
+///
+/// A wrong passphrase and a tampered file are indistinguishable, so both are
+/// reported as the same validation error.
+pub(crate) fn decrypt_export(data: &[u8], passphrase: &str) -> Result<Vec<u8>, ApiError> {
+    let invalid = || {
+        ApiError::Validation("Could not decrypt export: wrong passphrase or corrupted file".to_string())
+    };
+
+    if data.len() < ENCRYPTION_SALT_LEN + ENCRYPTION_NONCE_LEN {
+        return Err(invalid());
+    }
+    let (salt, rest) = data.split_at(ENCRYPTION_SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(ENCRYPTION_NONCE_LEN);
+
+    let key = derive_export_key(passphrase, salt)?;
+    Aes256Gcm::new(&key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| invalid())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    /// Export source that records which datasets were queried
+    #[derive(Default)]
+    struct SpySource {
+        calls: std::sync::Mutex<Vec<&'static str>>,
+    }
+
+    impl SpySource {
+        fn record(&self, section: &'static str) {
+            self.calls.lock().unwrap().push(section);
+        }
+
+        fn calls(&self) -> Vec<&'static str> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl ExportSource for SpySource {
+        async fn weight_logs(
+            &self,
+            _user_id: Uuid,
+            _start: Option<DateTime<Utc>>,
+            _end: Option<DateTime<Utc>>,
+        ) -> Result<Vec<WeightLogExport>, ApiError> {
+            self.record("weight");
+            Ok(vec![WeightLogExport {
+                id: Uuid::new_v4().to_string(),
+                weight_kg: 80.0,
+                recorded_at: Utc::now(),
+                source: "manual".to_string(),
+                notes: None,
+            }])
+        }
+
+        async fn body_composition(
+            &self,
+            _user_id: Uuid,
+            _start: Option<DateTime<Utc>>,
+            _end: Option<DateTime<Utc>>,
+        ) -> Result<Vec<BodyCompositionExport>, ApiError> {
+            self.record("body_composition");
+            Ok(vec![])
+        }
+
+        async fn workouts(
+            &self,
+            _user_id: Uuid,
+            _start: Option<DateTime<Utc>>,
+            _end: Option<DateTime<Utc>>,
+        ) -> Result<Vec<WorkoutExport>, ApiError> {
+            self.record("workouts");
+            Ok(vec![])
+        }
+
+        async fn sleep_logs(
+            &self,
+            _user_id: Uuid,
+            _start: Option<DateTime<Utc>>,
+            _end: Option<DateTime<Utc>>,
+        ) -> Result<Vec<SleepLogExport>, ApiError> {
+            self.record("sleep");
+            Ok(vec![])
+        }
+
+        async fn hydration_logs(
+            &self,
+            _user_id: Uuid,
+            _start: Option<DateTime<Utc>>,
+            _end: Option<DateTime<Utc>>,
+        ) -> Result<Vec<HydrationLogExport>, ApiError> {
+            self.record("hydration");
+            Ok(vec![])
+        }
+
+        async fn heart_rate_logs(
+            &self,
+            _user_id: Uuid,
+            _start: Option<DateTime<Utc>>,
+            _end: Option<DateTime<Utc>>,
+        ) -> Result<Vec<HeartRateLogExport>, ApiError> {
+            self.record("heart_rate");
+            Ok(vec![])
+        }
+
+        async fn hrv_logs(
+            &self,
+            _user_id: Uuid,
+            _start: Option<DateTime<Utc>>,
+            _end: Option<DateTime<Utc>>,
+        ) -> Result<Vec<HrvLogExport>, ApiError> {
+            self.record("hrv");
+            Ok(vec![])
+        }
+
+        async fn biomarker_logs(
+            &self,
+            _user_id: Uuid,
+            _start: Option<DateTime<Utc>>,
+            _end: Option<DateTime<Utc>>,
+        ) -> Result<Vec<BiomarkerLogExport>, ApiError> {
+            self.record("biomarkers");
+            Ok(vec![])
+        }
+
+        async fn goals(
+            &self,
+            _user_id: Uuid,
+            _start: Option<DateTime<Utc>>,
+            _end: Option<DateTime<Utc>>,
+        ) -> Result<Vec<GoalExport>, ApiError> {
+            self.record("goals");
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_selective_export_only_queries_requested_sections() {
+        let source = SpySource::default();
+        let sections: ExportSections = "weight".parse().unwrap();
+
+        let export = ExportService::export_json_from(&source, Uuid::new_v4(), sections, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(source.calls(), vec!["weight"]);
+        assert_eq!(export.weight_logs.len(), 1);
+        assert!(export.body_composition_logs.is_empty());
+        assert!(export.workouts.is_empty());
+        assert!(export.sleep_logs.is_empty());
+        assert!(export.hydration_logs.is_empty());
+        assert!(export.heart_rate_logs.is_empty());
+        assert!(export.hrv_logs.is_empty());
+        assert!(export.biomarker_logs.is_empty());
+        assert!(export.goals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_rejects_start_after_end() {
+        let source = SpySource::default();
+        let start = "2024-06-01T00:00:00Z".parse().unwrap();
+        let end = "2024-01-01T00:00:00Z".parse().unwrap();
+
+        let result =
+            ExportService::export_json_from(&source, Uuid::new_v4(), ExportSections::ALL, Some(start), Some(end))
+                .await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+        assert!(source.calls().is_empty());
+    }
+
+    #[test]
+    fn test_export_range_bounds() {
+        let start: DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
+        let end: DateTime<Utc> = "2024-12-31T23:59:59Z".parse().unwrap();
+
+        assert!(in_export_range(start, Some(start), Some(end)));
+        assert!(in_export_range(end, Some(start), Some(end)));
+        assert!(!in_export_range("2023-12-31T23:59:59Z".parse().unwrap(), Some(start), Some(end)));
+        assert!(!in_export_range("2025-01-01T00:00:00Z".parse().unwrap(), Some(start), Some(end)));
+        assert!(in_export_range("1999-01-01T00:00:00Z".parse().unwrap(), None, Some(end)));
+
+        assert_eq!(
+            export_query_dates(Some(start), Some(end)),
+            (
+                NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_export_sections_parsing() {
+        let sections: ExportSections = " weight, Sleep ,".parse().unwrap();
+        assert_eq!(
+            sections,
+            ExportSections { weight: true, sleep: true, ..ExportSections::NONE }
+        );
+
+        assert!("weight,steps".parse::<ExportSections>().is_err());
+        assert!("".parse::<ExportSections>().is_err());
+        assert_eq!(ExportSections::default(), ExportSections::ALL);
+    }
+
+    #[test]
+    fn test_nutrition_csv_header_and_row() {
+        let record = FoodLogExportRecord {
+            consumed_at: "2024-03-11T12:30:00Z".parse().unwrap(),
+            meal_type: "lunch".to_string(),
+            food_name: "Chicken, grilled".to_string(),
+            servings: "1.5".parse().unwrap(),
+            calories: "247.5".parse().unwrap(),
+            protein_g: "46.5".parse().unwrap(),
+            carbohydrates_g: "0".parse().unwrap(),
+            fat_g: "5.4".parse().unwrap(),
+            fiber_g: "0".parse().unwrap(),
+        };
+
+        let csv = ExportService::to_csv(&[NutritionCsvRow::from(record)]).unwrap();
+        let mut lines = csv.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some("date,meal_type,food_name,servings,calories,protein_g,carbohydrates_g,fat_g,fiber_g")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("2024-03-11 12:30:00,lunch,\"Chicken, grilled\",1.5,247.5,46.5,0.0,5.4,0.0")
+        );
+        assert_eq!(lines.next(), None);
+    }
+
     // Feature: fitness-assistant-ai, Property 14: Data Import/Export Round-Trip
     // Test that export format is consistent and can be serialized/deserialized
     proptest! {
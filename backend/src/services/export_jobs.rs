@@ -0,0 +1,223 @@
+//! Asynchronous data exports
+//!
+//! Large exports can outlive a request, so they are queued instead: the API
+//! records a pending job, the export worker (see `jobs`) builds the JSON
+//! export and writes it under the configured export directory, and clients
+//! poll the job until its file is ready to download. Finished exports are
+//! removed after `jobs.export_ttl_secs`, or with their user's account.
+
+use crate::error::ApiError;
+use crate::repositories::{ExportJobRecord, ExportJobRepository};
+use crate::services::export::{ExportSections, ExportService};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+/// How long a job may stay running before another worker claims it again
+///
+/// Workers that stop mid-export (a crash or deploy) leave their job running;
+/// this is long enough that a slow export is never picked up twice.
+const STALE_JOB_SECS: f64 = 15.0 * 60.0;
+
+/// Lifecycle of an export job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportJobStatus {
+    Pending,
+    Running,
+    Complete,
+    Failed,
+}
+
+impl ExportJobStatus {
+    /// Value stored in the `status` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportJobStatus::Pending => "pending",
+            ExportJobStatus::Running => "running",
+            ExportJobStatus::Complete => "complete",
+            ExportJobStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Export job service
+pub struct ExportJobService;
+
+impl ExportJobService {
+    /// Queue an export of the given datasets and date range
+    ///
+    /// `include` uses the same comma-separated names as the synchronous
+    /// export and is checked before the job is queued.
+    pub async fn enqueue(
+        pool: &PgPool,
+        user_id: Uuid,
+        include: Option<&str>,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<ExportJobRecord, ApiError> {
+        if let Some(include) = include {
+            include.parse::<ExportSections>().map_err(ApiError::Validation)?;
+        }
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Err(ApiError::Validation(
+                    "Export start must not be after end".to_string(),
+                ));
+            }
+        }
+
+        ExportJobRepository::create(pool, user_id, include, start, end)
+            .await
+            .map_err(ApiError::Internal)
+    }
+
+    /// Get one of the user's export jobs
+    pub async fn get(pool: &PgPool, user_id: Uuid, job_id: Uuid) -> Result<ExportJobRecord, ApiError> {
+        ExportJobRepository::find_for_user(pool, job_id, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Export job not found".to_string()))
+    }
+
+    /// Read the finished export of one of the user's jobs
+    pub async fn read_file(pool: &PgPool, user_id: Uuid, job_id: Uuid) -> Result<Vec<u8>, ApiError> {
+        let job = Self::get(pool, user_id, job_id).await?;
+
+        let path = match (job.status.as_str(), job.file_path) {
+            (status, Some(path)) if status == ExportJobStatus::Complete.as_str() => path,
+            _ => return Err(ApiError::Conflict("Export is not ready yet".to_string())),
+        };
+
+        tokio::fs::read(&path).await.map_err(|e| {
+            ApiError::Internal(anyhow::anyhow!("Failed to read export {}: {}", path, e))
+        })
+    }
+
+    /// Build the oldest pending export and write it to `dir`
+    ///
+    /// Jobs abandoned mid-run for `STALE_JOB_SECS` are picked up again.
+    /// Returns the processed job's ID, or None when the queue is empty. A
+    /// failed export marks its job failed rather than returning an error.
+    pub async fn process_next(pool: &PgPool, dir: &Path) -> Result<Option<Uuid>, ApiError> {
+        let Some(job) = ExportJobRepository::claim_next_pending(pool, STALE_JOB_SECS)
+            .await
+            .map_err(ApiError::Internal)?
+        else {
+            return Ok(None);
+        };
+
+        match Self::write_export(pool, &job, dir).await {
+            Ok(path) => {
+                let recorded =
+                    ExportJobRepository::mark_complete(pool, job.id, &path.to_string_lossy())
+                        .await
+                        .map_err(ApiError::Internal)?;
+                // The job was deleted while it ran, so nothing points to the file
+                if !recorded {
+                    Self::remove_files([path.to_string_lossy().into_owned()]).await;
+                }
+            }
+            Err(e) => {
+                warn!(job_id = %job.id, "Export job failed: {}", e);
+                ExportJobRepository::mark_failed(pool, job.id, &e.to_string())
+                    .await
+                    .map_err(ApiError::Internal)?;
+            }
+        }
+
+        Ok(Some(job.id))
+    }
+
+    /// Delete finished jobs older than `ttl` along with their files
+    ///
+    /// Returns how many jobs were removed.
+    pub async fn expire_finished(pool: &PgPool, ttl: Duration) -> Result<usize, ApiError> {
+        let ttl = chrono::Duration::from_std(ttl)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Invalid export TTL: {}", e)))?;
+        let paths = ExportJobRepository::delete_finished_before(pool, Utc::now() - ttl)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        let expired = paths.len();
+        Self::remove_files(paths.into_iter().flatten()).await;
+        Ok(expired)
+    }
+
+    /// Remove export files whose jobs are gone
+    ///
+    /// Files already missing are ignored; other failures are logged, since
+    /// the jobs pointing to them no longer exist.
+    pub async fn remove_files(paths: impl IntoIterator<Item = String>) {
+        for path in paths {
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => warn!("Failed to remove export {}: {}", path, e),
+            }
+        }
+    }
+
+    /// Generate a job's export and write it to `dir`, returning the file path
+    async fn write_export(
+        pool: &PgPool,
+        job: &ExportJobRecord,
+        dir: &Path,
+    ) -> Result<PathBuf, ApiError> {
+        let sections = job
+            .include
+            .as_deref()
+            .map(str::parse::<ExportSections>)
+            .transpose()
+            .map_err(ApiError::Validation)?
+            .unwrap_or_default();
+
+        let export = ExportService::export_json_selective(
+            pool,
+            job.user_id,
+            sections,
+            job.range_start,
+            job.range_end,
+        )
+        .await?;
+        let json = serde_json::to_vec_pretty(&export)
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("JSON serialization error: {}", e)))?;
+
+        let path = Self::file_path(dir, job.id);
+        let write_error = |e: std::io::Error| {
+            ApiError::Internal(anyhow::anyhow!("Failed to write {}: {}", path.display(), e))
+        };
+        tokio::fs::create_dir_all(dir).await.map_err(write_error)?;
+        tokio::fs::write(&path, json).await.map_err(write_error)?;
+
+        Ok(path)
+    }
+
+    /// Where a job's export is written
+    fn file_path(dir: &Path, job_id: Uuid) -> PathBuf {
+        dir.join(format!("{}.json", job_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_file_named_after_job() {
+        let job_id = Uuid::new_v4();
+        let path = ExportJobService::file_path(Path::new("/var/exports"), job_id);
+        assert_eq!(path, PathBuf::from(format!("/var/exports/{}.json", job_id)));
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_rejects_unknown_sections() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+
+        let result = ExportJobService::enqueue(&pool, Uuid::new_v4(), Some("weight,dreams"), None, None).await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+    }
+}
@@ -0,0 +1,116 @@
+//! Per-user feature flags
+//!
+//! Features still being rolled out are switched on per user by an admin.
+//! Flags are stored on the user row; a flag that was never set is off.
+
+use crate::error::ApiError;
+use crate::repositories::UserRepository;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Features that can be enabled per user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureFlag {
+    AiMealSuggestions,
+}
+
+impl FeatureFlag {
+    /// Key stored in the `feature_flags` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeatureFlag::AiMealSuggestions => "ai_meal_suggestions",
+        }
+    }
+
+    /// Name shown to users when the feature is unavailable
+    fn label(&self) -> &'static str {
+        match self {
+            FeatureFlag::AiMealSuggestions => "AI meal suggestions",
+        }
+    }
+}
+
+impl FromStr for FeatureFlag {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ai_meal_suggestions" => Ok(FeatureFlag::AiMealSuggestions),
+            other => Err(ApiError::Validation(format!("Unknown feature flag: {}", other))),
+        }
+    }
+}
+
+/// Feature flag lookups and changes
+pub struct FeatureFlags;
+
+impl FeatureFlags {
+    /// Whether `flag` is on for the user
+    pub async fn is_enabled(
+        pool: &PgPool,
+        user_id: Uuid,
+        flag: FeatureFlag,
+    ) -> Result<bool, ApiError> {
+        let flags = UserRepository::get_feature_flags(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .unwrap_or_default();
+
+        Ok(Self::enabled_in(&flags, flag))
+    }
+
+    /// Answer 403 unless `flag` is on for the user
+    pub async fn require(pool: &PgPool, user_id: Uuid, flag: FeatureFlag) -> Result<(), ApiError> {
+        if !Self::is_enabled(pool, user_id, flag).await? {
+            return Err(ApiError::Forbidden(format!(
+                "{} are not available for this account",
+                flag.label()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Turn `flag` on or off for a user, returning all of their flags
+    pub async fn set(
+        pool: &PgPool,
+        user_id: Uuid,
+        flag: FeatureFlag,
+        enabled: bool,
+    ) -> Result<BTreeMap<String, bool>, ApiError> {
+        UserRepository::set_feature_flag(pool, user_id, flag.as_str(), enabled)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))
+    }
+
+    /// Whether `flag` is on in a user's stored flags
+    fn enabled_in(flags: &BTreeMap<String, bool>, flag: FeatureFlag) -> bool {
+        flags.get(flag.as_str()).copied().unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_flag_is_off() {
+        let mut flags = BTreeMap::new();
+        assert!(!FeatureFlags::enabled_in(&flags, FeatureFlag::AiMealSuggestions));
+
+        flags.insert("ai_meal_suggestions".to_string(), false);
+        assert!(!FeatureFlags::enabled_in(&flags, FeatureFlag::AiMealSuggestions));
+
+        flags.insert("ai_meal_suggestions".to_string(), true);
+        assert!(FeatureFlags::enabled_in(&flags, FeatureFlag::AiMealSuggestions));
+    }
+
+    #[test]
+    fn test_flag_names_round_trip() {
+        let flag = FeatureFlag::AiMealSuggestions;
+        assert_eq!(flag.as_str().parse::<FeatureFlag>().unwrap(), flag);
+        assert!(matches!("beta".parse::<FeatureFlag>(), Err(ApiError::Validation(_))));
+    }
+}
@@ -0,0 +1,170 @@
+//! Display formatting for SI values
+//!
+//! Values are stored in SI units. These helpers convert them to the user's
+//! preferred units and round them for display, so endpoints can return
+//! ready-to-render numbers instead of converting ad hoc.
+
+use crate::config::round_f64;
+use fitness_assistant_shared::units::{FeetInchesHeight, HeightUnit, UnitPreferences};
+use serde::Serialize;
+
+/// A value converted to a display unit
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FormattedValue {
+    /// Rounded value in `unit`
+    pub value: f64,
+    /// Unit abbreviation
+    pub unit: String,
+    /// Human-readable value with its unit, e.g. `154.3 lbs` or `5'9"`
+    pub display: String,
+}
+
+impl FormattedValue {
+    fn new(value: f64, unit: &str, decimals: usize) -> Self {
+        let value = round_f64(value, decimals as u32);
+        Self {
+            value,
+            unit: unit.to_string(),
+            display: format!("{:.*} {}", decimals, value, unit),
+        }
+    }
+}
+
+/// Format a weight in kilograms
+pub fn format_weight(kg: f64, prefs: &UnitPreferences) -> FormattedValue {
+    FormattedValue::new(prefs.weight.from_kg(kg), prefs.weight.abbreviation(), 1)
+}
+
+/// Format a height in centimeters
+///
+/// Feet/inches preferences display as `{feet}'{inches}"`, with the value
+/// given in total inches.
+pub fn format_height(cm: f64, prefs: &UnitPreferences) -> FormattedValue {
+    match prefs.height {
+        HeightUnit::FeetInches => {
+            // Round to whole inches first so 5'11.7" shows as 6'0", not 5'12"
+            let total_inches = (cm / 2.54).round();
+            FormattedValue {
+                value: total_inches,
+                unit: prefs.height.abbreviation().to_string(),
+                display: FeetInchesHeight::from_total_inches(total_inches).to_string(),
+            }
+        }
+        HeightUnit::Meters => {
+            FormattedValue::new(prefs.height.from_cm(cm), prefs.height.abbreviation(), 2)
+        }
+        _ => FormattedValue::new(prefs.height.from_cm(cm), prefs.height.abbreviation(), 1),
+    }
+}
+
+/// Format a distance in meters
+pub fn format_distance(meters: f64, prefs: &UnitPreferences) -> FormattedValue {
+    FormattedValue::new(
+        prefs.distance.from_meters(meters),
+        prefs.distance.abbreviation(),
+        2,
+    )
+}
+
+/// Format an energy amount in kilocalories
+pub fn format_energy(kcal: f64, prefs: &UnitPreferences) -> FormattedValue {
+    FormattedValue::new(prefs.energy.from_kcal(kcal), prefs.energy.abbreviation(), 0)
+}
+
+/// Format a temperature in degrees Celsius
+pub fn format_temperature(celsius: f64, prefs: &UnitPreferences) -> FormattedValue {
+    let value = prefs.temperature.from_celsius(celsius);
+    let unit = prefs.temperature.abbreviation();
+    let value = (value * 10.0).round() / 10.0;
+    FormattedValue {
+        value,
+        unit: unit.to_string(),
+        display: format!("{:.1}{}", value, unit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_weight_imperial() {
+        let formatted = format_weight(70.0, &UnitPreferences::imperial());
+        assert_eq!(formatted.value, 154.3);
+        assert_eq!(formatted.unit, "lbs");
+        assert_eq!(formatted.display, "154.3 lbs");
+    }
+
+    #[test]
+    fn test_format_weight_uk() {
+        let formatted = format_weight(70.0, &UnitPreferences::uk());
+        assert_eq!(formatted.value, 11.0);
+        assert_eq!(formatted.unit, "st");
+        assert_eq!(formatted.display, "11.0 st");
+    }
+
+    #[test]
+    fn test_format_weight_metric() {
+        let formatted = format_weight(70.04, &UnitPreferences::metric());
+        assert_eq!(formatted.value, 70.0);
+        assert_eq!(formatted.display, "70.0 kg");
+    }
+
+    #[test]
+    fn test_format_height_feet_inches() {
+        let formatted = format_height(175.0, &UnitPreferences::imperial());
+        assert_eq!(formatted.value, 69.0);
+        assert_eq!(formatted.display, "5'9\"");
+
+        // 182.5 cm is 71.85 in, which rounds up to a whole 6 ft
+        assert_eq!(
+            format_height(182.5, &UnitPreferences::imperial()).display,
+            "6'0\""
+        );
+    }
+
+    #[test]
+    fn test_format_height_metric() {
+        assert_eq!(
+            format_height(175.0, &UnitPreferences::metric()).display,
+            "175.0 cm"
+        );
+
+        let prefs = UnitPreferences {
+            height: HeightUnit::Meters,
+            ..UnitPreferences::metric()
+        };
+        assert_eq!(format_height(175.0, &prefs).display, "1.75 m");
+    }
+
+    #[test]
+    fn test_format_distance_energy_temperature() {
+        assert_eq!(
+            format_distance(5000.0, &UnitPreferences::metric()).display,
+            "5.00 km"
+        );
+        assert_eq!(
+            format_distance(5000.0, &UnitPreferences::imperial()).display,
+            "3.11 mi"
+        );
+
+        let kj = UnitPreferences {
+            energy: fitness_assistant_shared::units::EnergyUnit::Kj,
+            ..UnitPreferences::metric()
+        };
+        assert_eq!(format_energy(500.0, &kj).display, "2092 kJ");
+        assert_eq!(
+            format_energy(500.0, &UnitPreferences::metric()).display,
+            "500 kcal"
+        );
+
+        assert_eq!(
+            format_temperature(37.0, &UnitPreferences::imperial()).display,
+            "98.6°F"
+        );
+        assert_eq!(
+            format_temperature(37.0, &UnitPreferences::metric()).display,
+            "37.0°C"
+        );
+    }
+}
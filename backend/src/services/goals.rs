@@ -5,20 +5,46 @@
 //! - Progress calculation for increasing/decreasing goals
 //! - Milestone detection and recording
 //! - Goal history preservation
+//! - Completion projection from the goal metric's recent trend
+//! - Detection of active goals with contradictory energy needs
 
 use crate::error::ApiError;
 use crate::repositories::goals::{
     CreateGoal, CreateMilestone, GoalRepository, MilestoneRepository, UpdateGoal,
 };
-use chrono::{NaiveDate, Utc};
+use crate::repositories::{
+    BodyCompositionRepository, ExerciseSetRepository, WeightRepository, WorkoutRepository,
+};
+use crate::services::exercise::estimate_one_rep_max;
+use crate::services::jobs::JobManager;
+use crate::services::notifications::{
+    notify_milestones, AchievedMilestone, MilestoneNotification, Notifier,
+};
+use crate::timezone;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use fitness_assistant_shared::health_metrics;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use sqlx::PgPool;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// Standard milestone percentages
 const MILESTONE_PERCENTAGES: &[i32] = &[25, 50, 75, 100];
 
+/// Days of history used to project goal completion
+const PROJECTION_LOOKBACK_DAYS: i64 = 90;
+
+/// Minimum data points needed for a projection
+const MIN_PROJECTION_POINTS: usize = 3;
+
+/// Minimum run distance counted towards 5k pace
+const FIVE_K_METERS: f64 = 5000.0;
+
+/// Weekly weight loss, as a percentage of body weight, that needs a deficit
+/// too steep to keep building strength
+const AGGRESSIVE_LOSS_PERCENT_PER_WEEK: f64 = 1.0;
+
 /// Goal entry
 #[derive(Debug, Clone)]
 pub struct Goal {
@@ -84,6 +110,301 @@ pub struct Milestone {
     pub actual_value: Option<f64>,
 }
 
+/// Projected completion of a goal from its metric's trend
+#[derive(Debug, Clone)]
+pub struct GoalProjection {
+    pub goal_id: Uuid,
+    pub metric: String,
+    pub current_value: f64,
+    pub target_value: f64,
+    pub remaining: f64,
+    pub average_daily_change: f64,
+    pub projected_days: Option<i64>,
+    pub projected_date: Option<NaiveDate>,
+    pub on_track: bool,
+}
+
+/// Why two goals work against each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// One goal needs a calorie deficit and the other a surplus
+    OppositeEnergyBalance,
+    /// A steep weight loss timeline alongside a strength goal
+    AggressiveDeficitVsStrength,
+}
+
+impl ConflictKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictKind::OppositeEnergyBalance => "opposite_energy_balance",
+            ConflictKind::AggressiveDeficitVsStrength => "aggressive_deficit_vs_strength",
+        }
+    }
+}
+
+/// A pair of active goals whose calorie implications contradict each other
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalConflict {
+    pub goal_ids: (Uuid, Uuid),
+    pub kind: ConflictKind,
+    pub message: String,
+}
+
+/// What a goal asks of the user's energy balance
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EnergyDemand {
+    /// Losing weight, with the required weekly loss as a percentage of body weight
+    Deficit { percent_per_week: Option<f64> },
+    /// Gaining weight or muscle
+    Surplus,
+    /// Increasing a one-rep max
+    Strength,
+}
+
+impl EnergyDemand {
+    /// Energy demand of a goal, or `None` when it has no clear calorie implication
+    fn of(goal: &Goal, today: NaiveDate) -> Option<Self> {
+        let increasing = goal.direction == "increasing";
+        match goal.metric.trim() {
+            "weight" | "weight_kg" if increasing => Some(Self::Surplus),
+            "weight" | "weight_kg" => Some(Self::Deficit {
+                percent_per_week: Self::weekly_loss_percent(goal, today),
+            }),
+            "muscle_mass_kg" | "lean_mass_kg" if increasing => Some(Self::Surplus),
+            metric if increasing && metric.starts_with("one_rep_max:") => Some(Self::Strength),
+            _ => None,
+        }
+    }
+
+    /// Weekly loss needed to hit the target date, as a percentage of current weight
+    fn weekly_loss_percent(goal: &Goal, today: NaiveDate) -> Option<f64> {
+        let current = goal.current_value.or(goal.start_value)?;
+        let days = (goal.target_date? - today).num_days();
+        if days <= 0 || current <= 0.0 {
+            return None;
+        }
+        let loss = (current - goal.target_value).max(0.0);
+        Some(loss / current * 100.0 / (days as f64 / 7.0))
+    }
+}
+
+/// Conflict between two goals, if their energy demands contradict
+fn goal_conflict(a: &Goal, b: &Goal, today: NaiveDate) -> Option<GoalConflict> {
+    use EnergyDemand::*;
+
+    let kind = match (EnergyDemand::of(a, today)?, EnergyDemand::of(b, today)?) {
+        (Deficit { .. }, Surplus) | (Surplus, Deficit { .. }) => ConflictKind::OppositeEnergyBalance,
+        (Deficit { percent_per_week: Some(rate) }, Strength)
+        | (Strength, Deficit { percent_per_week: Some(rate) })
+            if rate > AGGRESSIVE_LOSS_PERCENT_PER_WEEK =>
+        {
+            ConflictKind::AggressiveDeficitVsStrength
+        }
+        _ => return None,
+    };
+
+    let message = match kind {
+        ConflictKind::OppositeEnergyBalance => format!(
+            "'{}' needs a calorie deficit while '{}' needs a surplus",
+            a.name, b.name
+        ),
+        ConflictKind::AggressiveDeficitVsStrength => format!(
+            "'{}' and '{}' conflict: losing more than {}% of body weight a week makes strength gains unlikely",
+            a.name, b.name, AGGRESSIVE_LOSS_PERCENT_PER_WEEK
+        ),
+    };
+
+    Some(GoalConflict {
+        goal_ids: (a.id, b.id),
+        kind,
+        message,
+    })
+}
+
+/// Goal metrics with a time series that can be projected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProjectionMetric {
+    /// `weight_kg` from weight logs
+    Weight,
+    /// `body_fat_percent` from body composition logs
+    BodyFatPercent,
+    /// `one_rep_max:<exercise_id>`, estimated from working sets
+    OneRepMax(Uuid),
+    /// `5k_pace` in seconds per km from runs of at least 5 km
+    FiveKPace,
+}
+
+impl ProjectionMetric {
+    fn parse(metric: &str) -> Result<Self, ApiError> {
+        match metric.trim() {
+            "weight" | "weight_kg" => Ok(Self::Weight),
+            "body_fat_percent" => Ok(Self::BodyFatPercent),
+            "5k_pace" => Ok(Self::FiveKPace),
+            other => other
+                .strip_prefix("one_rep_max:")
+                .and_then(|id| Uuid::parse_str(id).ok())
+                .map(Self::OneRepMax)
+                .ok_or_else(|| {
+                    ApiError::Validation(format!(
+                        "Projection is not supported for metric '{}'",
+                        metric
+                    ))
+                }),
+        }
+    }
+}
+
+/// Project completion of any goal with a supported metric
+///
+/// Pulls the metric's series over the last 90 days and extrapolates its
+/// least-squares daily trend to the goal's target value.
+pub async fn project_goal_generic(
+    pool: &PgPool,
+    user_id: Uuid,
+    goal_id: Uuid,
+) -> Result<GoalProjection, ApiError> {
+    let goal = GoalRepository::get_by_id(pool, goal_id, user_id)
+        .await
+        .map_err(ApiError::Internal)?
+        .ok_or_else(|| ApiError::NotFound("Goal not found".to_string()))?;
+
+    let metric = ProjectionMetric::parse(&goal.metric)?;
+    let tz = timezone::user_timezone(pool, user_id).await;
+    let today = timezone::local_today(tz);
+    let lookback_start = today - Duration::days(PROJECTION_LOOKBACK_DAYS);
+    let (since, _) = timezone::local_day_bounds(lookback_start, tz);
+    let series = load_metric_series(pool, user_id, metric, since).await?;
+
+    if series.len() < MIN_PROJECTION_POINTS {
+        return Err(ApiError::Validation(format!(
+            "Need at least {} {} entries for goal projection",
+            MIN_PROJECTION_POINTS, goal.metric
+        )));
+    }
+
+    Ok(project_series(
+        goal.id,
+        &goal.metric,
+        &series,
+        goal.target_value.to_f64().unwrap_or(0.0),
+        &goal.direction,
+        today,
+    ))
+}
+
+/// Load a metric's time series, oldest first
+async fn load_metric_series(
+    pool: &PgPool,
+    user_id: Uuid,
+    metric: ProjectionMetric,
+    since: DateTime<Utc>,
+) -> Result<Vec<(DateTime<Utc>, f64)>, ApiError> {
+    let mut series: Vec<(DateTime<Utc>, f64)> = match metric {
+        ProjectionMetric::Weight => {
+            WeightRepository::get_by_date_range(pool, user_id, Some(since), None)
+                .await
+                .map_err(ApiError::Internal)?
+                .into_iter()
+                .map(|r| (r.recorded_at, r.weight_kg.to_f64().unwrap_or(0.0)))
+                .collect()
+        }
+        ProjectionMetric::BodyFatPercent => {
+            BodyCompositionRepository::get_by_date_range(pool, user_id, Some(since), None)
+                .await
+                .map_err(ApiError::Internal)?
+                .into_iter()
+                .filter_map(|r| Some((r.recorded_at, r.body_fat_percent?.to_f64()?)))
+                .collect()
+        }
+        ProjectionMetric::OneRepMax(exercise_id) => {
+            let sets = ExerciseSetRepository::get_working_sets_for_exercise(
+                pool,
+                user_id,
+                exercise_id,
+                since,
+            )
+            .await
+            .map_err(ApiError::Internal)?;
+
+            // Best estimated 1RM per workout
+            let mut best: Vec<(DateTime<Utc>, f64)> = Vec::new();
+            for set in sets {
+                let estimate =
+                    estimate_one_rep_max(set.weight_kg.to_f64().unwrap_or(0.0), set.reps);
+                match best.last_mut() {
+                    Some((at, value)) if *at == set.performed_at => *value = value.max(estimate),
+                    _ => best.push((set.performed_at, estimate)),
+                }
+            }
+            best
+        }
+        ProjectionMetric::FiveKPace => {
+            WorkoutRepository::get_run_paces_since(pool, user_id, FIVE_K_METERS, since)
+                .await
+                .map_err(ApiError::Internal)?
+                .into_iter()
+                .map(|(at, pace)| (at, pace as f64))
+                .collect()
+        }
+    };
+
+    series.sort_by_key(|(at, _)| *at);
+    Ok(series)
+}
+
+/// Project completion from a series ordered oldest first
+fn project_series(
+    goal_id: Uuid,
+    metric: &str,
+    series: &[(DateTime<Utc>, f64)],
+    target_value: f64,
+    direction: &str,
+    today: NaiveDate,
+) -> GoalProjection {
+    let current_value = series.last().map(|(_, v)| *v).unwrap_or(0.0);
+    let average_daily_change = daily_trend(series);
+    let remaining = GoalsService::calculate_remaining(current_value, target_value, direction);
+
+    let improving = if direction == "increasing" {
+        average_daily_change > 0.0
+    } else {
+        average_daily_change < 0.0
+    };
+
+    let projected_days = if remaining == 0.0 {
+        Some(0)
+    } else if improving && average_daily_change.abs() > f64::EPSILON {
+        Some((remaining / average_daily_change.abs()).ceil() as i64)
+    } else {
+        None
+    };
+
+    GoalProjection {
+        goal_id,
+        metric: metric.to_string(),
+        current_value,
+        target_value,
+        remaining,
+        average_daily_change,
+        projected_days,
+        projected_date: projected_days.map(|days| today + Duration::days(days)),
+        on_track: remaining == 0.0 || improving,
+    }
+}
+
+/// Least-squares slope of a series in units per day
+fn daily_trend(series: &[(DateTime<Utc>, f64)]) -> f64 {
+    let Some((first, _)) = series.first() else {
+        return 0.0;
+    };
+    let points: Vec<(f64, f64)> = series
+        .iter()
+        .map(|(at, v)| ((*at - *first).num_seconds() as f64 / 86_400.0, *v))
+        .collect();
+
+    health_metrics::least_squares_slope(&points).unwrap_or(0.0)
+}
+
 /// Goals service for business logic
 pub struct GoalsService;
 
@@ -141,6 +462,25 @@ impl GoalsService {
         Ok(Self::record_to_goal(record))
     }
 
+    /// Find pairs of active goals with contradictory calorie implications
+    pub async fn detect_conflicts(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<GoalConflict>, ApiError> {
+        let goals = Self::get_goals(pool, user_id, Some("active"), None).await?;
+        let today = timezone::local_today(timezone::user_timezone(pool, user_id).await);
+        Ok(Self::find_conflicts(&goals, today))
+    }
+
+    /// Conflicts between every pair of the given goals
+    pub fn find_conflicts(goals: &[Goal], today: NaiveDate) -> Vec<GoalConflict> {
+        goals
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| goals[i + 1..].iter().filter_map(move |b| goal_conflict(a, b, today)))
+            .collect()
+    }
+
     /// Create default milestones for a goal
     async fn create_default_milestones(
         pool: &PgPool,
@@ -198,8 +538,13 @@ impl GoalsService {
     }
 
     /// Update a goal
+    ///
+    /// Milestones reached by the new current value are recorded and, when a
+    /// notifier is given, announced in the background.
     pub async fn update_goal(
         pool: &PgPool,
+        jobs: &JobManager,
+        notifier: Option<Arc<dyn Notifier>>,
         user_id: Uuid,
         goal_id: Uuid,
         input: UpdateGoalInput,
@@ -231,22 +576,37 @@ impl GoalsService {
 
         // Check and update milestones if current_value changed
         if input.current_value.is_some() {
-            Self::check_milestones(pool, &record).await?;
+            let achieved = Self::check_milestones(pool, &record).await?;
+
+            if let Some(notifier) = notifier {
+                let notifications = achieved
+                    .into_iter()
+                    .map(|milestone| MilestoneNotification {
+                        user_id,
+                        goal_id: record.id,
+                        milestone,
+                    })
+                    .collect();
+                notify_milestones(jobs, notifier, notifications);
+            }
         }
 
         Ok(Self::record_to_goal(record))
     }
 
     /// Check and update milestones based on current progress
+    ///
+    /// Returns the milestones achieved by this update.
     async fn check_milestones(
         pool: &PgPool,
         goal: &crate::repositories::goals::GoalRecord,
-    ) -> Result<(), ApiError> {
+    ) -> Result<Vec<AchievedMilestone>, ApiError> {
         let current = goal.current_value.and_then(|v| v.to_f64()).unwrap_or(0.0);
         let milestones = MilestoneRepository::get_by_goal(pool, goal.id)
             .await
             .map_err(ApiError::Internal)?;
 
+        let mut newly_achieved = Vec::new();
         for milestone in milestones {
             if milestone.achieved_at.is_some() {
                 continue; // Already achieved
@@ -267,10 +627,18 @@ impl GoalsService {
                 )
                 .await
                 .map_err(ApiError::Internal)?;
+
+                newly_achieved.push(AchievedMilestone {
+                    id: milestone.id,
+                    name: milestone.name,
+                    percentage: milestone.percentage,
+                    target_value: target,
+                    achieved_value: current,
+                });
             }
         }
 
-        Ok(())
+        Ok(newly_achieved)
     }
 
     /// Get goal progress
@@ -337,6 +705,16 @@ impl GoalsService {
         })
     }
 
+    /// Calculate percent complete for a goal
+    ///
+    /// Uses the same defaults as [`Self::get_progress`]: a missing start value
+    /// counts as 0 and a missing current value as no progress from the start.
+    pub fn calculate_goal_progress(goal: &Goal) -> f64 {
+        let start = goal.start_value.unwrap_or(0.0);
+        let current = goal.current_value.unwrap_or(start);
+        Self::calculate_progress(start, current, goal.target_value, &goal.direction)
+    }
+
     /// Calculate progress percentage
     ///
     /// # Property 22: Goal Progress Calculation
@@ -398,6 +776,7 @@ impl GoalsService {
 #[cfg(test)]
 mod tests {
     use super::*;
+
     use proptest::prelude::*;
 
     // Feature: fitness-assistant-ai, Property 22: Goal Progress Calculation
@@ -483,6 +862,70 @@ mod tests {
     // Feature: fitness-assistant-ai, Property 24: Goal History Preservation
     // (Tested via integration tests as it requires database)
 
+    fn body_fat_series(start: &str, days: &[i64], start_percent: f64, per_day: f64) -> Vec<(DateTime<Utc>, f64)> {
+        let start = DateTime::parse_from_rfc3339(start).unwrap().with_timezone(&Utc);
+        days.iter()
+            .map(|d| (start + Duration::days(*d), start_percent + per_day * *d as f64))
+            .collect()
+    }
+
+    #[test]
+    fn test_projection_metric_parse() {
+        let exercise_id = Uuid::new_v4();
+        assert_eq!(ProjectionMetric::parse("weight_kg").unwrap(), ProjectionMetric::Weight);
+        assert_eq!(
+            ProjectionMetric::parse("body_fat_percent").unwrap(),
+            ProjectionMetric::BodyFatPercent
+        );
+        assert_eq!(ProjectionMetric::parse("5k_pace").unwrap(), ProjectionMetric::FiveKPace);
+        assert_eq!(
+            ProjectionMetric::parse(&format!("one_rep_max:{}", exercise_id)).unwrap(),
+            ProjectionMetric::OneRepMax(exercise_id)
+        );
+        assert!(matches!(
+            ProjectionMetric::parse("sleep_hours"),
+            Err(ApiError::Validation(_))
+        ));
+        assert!(matches!(
+            ProjectionMetric::parse("one_rep_max:bench"),
+            Err(ApiError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_body_fat_goal_projection() {
+        // Body fat falling 0.05% per day, measured every other day for four weeks
+        let days: Vec<i64> = (0..=28).step_by(2).collect();
+        let series = body_fat_series("2024-05-01T07:00:00Z", &days, 25.0, -0.05);
+        let today = NaiveDate::from_ymd_opt(2024, 5, 29).unwrap();
+
+        let projection =
+            project_series(Uuid::nil(), "body_fat_percent", &series, 22.02, "decreasing", today);
+
+        assert!((projection.current_value - 23.6).abs() < 1e-9);
+        assert!((projection.remaining - 1.58).abs() < 1e-9);
+        assert!((projection.average_daily_change + 0.05).abs() < 1e-9);
+        assert_eq!(projection.projected_days, Some(32));
+        assert_eq!(
+            projection.projected_date,
+            Some(NaiveDate::from_ymd_opt(2024, 6, 30).unwrap())
+        );
+        assert!(projection.on_track);
+    }
+
+    #[test]
+    fn test_body_fat_goal_projection_moving_away() {
+        let series = body_fat_series("2024-05-01T07:00:00Z", &[0, 7, 14], 20.0, 0.1);
+        let today = NaiveDate::from_ymd_opt(2024, 5, 15).unwrap();
+
+        let projection =
+            project_series(Uuid::nil(), "body_fat_percent", &series, 18.0, "decreasing", today);
+
+        assert_eq!(projection.projected_days, None);
+        assert_eq!(projection.projected_date, None);
+        assert!(!projection.on_track);
+    }
+
     #[test]
     fn test_remaining_increasing() {
         assert_eq!(GoalsService::calculate_remaining(50.0, 100.0, "increasing"), 50.0);
@@ -497,6 +940,96 @@ mod tests {
         assert_eq!(GoalsService::calculate_remaining(60.0, 70.0, "decreasing"), 0.0);
     }
 
+    fn goal(start: Option<f64>, current: Option<f64>, target: f64, direction: &str) -> Goal {
+        Goal {
+            id: Uuid::nil(),
+            name: "Test goal".to_string(),
+            description: None,
+            goal_type: "custom".to_string(),
+            metric: "value".to_string(),
+            target_value: target,
+            start_value: start,
+            current_value: current,
+            direction: direction.to_string(),
+            start_date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            target_date: None,
+            status: "active".to_string(),
+        }
+    }
+
+    fn dated_goal(metric: &str, direction: &str, current: f64, target: f64, target_date: &str) -> Goal {
+        Goal {
+            id: Uuid::new_v4(),
+            metric: metric.to_string(),
+            target_date: Some(NaiveDate::parse_from_str(target_date, "%Y-%m-%d").unwrap()),
+            ..goal(None, Some(current), target, direction)
+        }
+    }
+
+    #[test]
+    fn test_conflicting_goals_are_flagged() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        // 10 kg off 100 kg in four weeks is 2.5% a week
+        let crash_diet = dated_goal("weight_kg", "decreasing", 100.0, 90.0, "2024-03-29");
+        let bench_pr = dated_goal(&format!("one_rep_max:{}", Uuid::new_v4()), "increasing", 100.0, 120.0, "2024-05-01");
+        let conflicts = GoalsService::find_conflicts(&[crash_diet.clone(), bench_pr.clone()], today);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].kind, ConflictKind::AggressiveDeficitVsStrength);
+        assert_eq!(conflicts[0].goal_ids, (crash_diet.id, bench_pr.id));
+
+        let bulk = dated_goal("muscle_mass_kg", "increasing", 35.0, 38.0, "2024-09-01");
+        let conflicts = GoalsService::find_conflicts(&[bulk, crash_diet], today);
+        assert_eq!(conflicts[0].kind, ConflictKind::OppositeEnergyBalance);
+    }
+
+    #[test]
+    fn test_compatible_goals_are_not_flagged() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        // 5 kg off 100 kg over six months is well under 1% a week
+        let slow_cut = dated_goal("weight_kg", "decreasing", 100.0, 95.0, "2024-09-01");
+        let bench_pr = dated_goal(&format!("one_rep_max:{}", Uuid::new_v4()), "increasing", 100.0, 110.0, "2024-09-01");
+        assert!(GoalsService::find_conflicts(&[slow_cut.clone(), bench_pr], today).is_empty());
+
+        let steps = dated_goal("daily_steps", "increasing", 6000.0, 10000.0, "2024-04-01");
+        assert!(GoalsService::find_conflicts(&[slow_cut, steps], today).is_empty());
+    }
+
+    #[test]
+    fn test_goal_progress_increasing() {
+        let g = goal(Some(10.0), Some(16.0), 20.0, "increasing");
+        assert!((GoalsService::calculate_goal_progress(&g) - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_goal_progress_decreasing() {
+        // Losing from 90 to 80, currently at 87.5
+        let g = goal(Some(90.0), Some(87.5), 80.0, "decreasing");
+        assert!((GoalsService::calculate_goal_progress(&g) - 25.0).abs() < 1e-9);
+
+        let gained = goal(Some(90.0), Some(92.0), 80.0, "decreasing");
+        assert_eq!(GoalsService::calculate_goal_progress(&gained), 0.0);
+    }
+
+    #[test]
+    fn test_goal_progress_overshoot_clamped() {
+        let increasing = goal(Some(10.0), Some(25.0), 20.0, "increasing");
+        assert_eq!(GoalsService::calculate_goal_progress(&increasing), 100.0);
+
+        let decreasing = goal(Some(90.0), Some(75.0), 80.0, "decreasing");
+        assert_eq!(GoalsService::calculate_goal_progress(&decreasing), 100.0);
+    }
+
+    #[test]
+    fn test_goal_progress_without_current_value() {
+        let g = goal(Some(90.0), None, 80.0, "decreasing");
+        assert_eq!(GoalsService::calculate_goal_progress(&g), 0.0);
+
+        let same = goal(Some(80.0), None, 80.0, "decreasing");
+        assert_eq!(GoalsService::calculate_goal_progress(&same), 100.0);
+    }
+
     #[test]
     fn test_progress_same_start_target() {
         // When start equals target, should be 100% if current equals target
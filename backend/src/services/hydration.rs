@@ -5,13 +5,17 @@
 //! - Daily progress calculation
 //! - Personalized goal calculation based on weight
 //! - Goal completion detection
+//! - Caffeine totals, estimated from the beverage when not logged
 
+use crate::config::HydrationConfig;
 use crate::error::ApiError;
 use crate::repositories::{
     CreateHydrationLog, HydrationGoalRepository, HydrationLogRepository, UpsertHydrationGoal,
     WeightRepository,
 };
 use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use fitness_assistant_shared::validation::validate_range;
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -22,6 +26,22 @@ const DEFAULT_HYDRATION_GOAL_ML: i32 = 2500;
 /// Standard recommendation is 30-35ml per kg
 const HYDRATION_ML_PER_KG: f64 = 33.0;
 
+/// Volume of a standard cup in ml
+const CUP_ML: f64 = 240.0;
+
+/// Typical caffeine per cup by beverage type (mg)
+const CAFFEINE_MG_PER_CUP: &[(&str, f64)] = &[
+    ("coffee", 95.0),
+    ("espresso", 504.0), // ~63mg per 30ml shot
+    ("tea", 47.0),
+    ("green_tea", 28.0),
+    ("energy_drink", 76.0),
+    ("soda", 22.0),
+];
+
+/// Most caffeine accepted on a single entry (mg)
+const MAX_CAFFEINE_MG: i32 = 2000;
+
 /// Activity level multipliers for hydration
 const ACTIVITY_MULTIPLIERS: &[(&str, f64)] = &[
     ("sedentary", 1.0),
@@ -40,6 +60,7 @@ pub struct HydrationLog {
     pub consumed_at: DateTime<Utc>,
     pub source: String,
     pub notes: Option<String>,
+    pub caffeine_mg: Option<i32>,
 }
 
 /// Input for logging water intake
@@ -50,6 +71,8 @@ pub struct LogHydrationInput {
     pub consumed_at: Option<DateTime<Utc>>,
     pub source: Option<String>,
     pub notes: Option<String>,
+    /// Caffeine in mg; estimated from the beverage type when `None`
+    pub caffeine_mg: Option<i32>,
 }
 
 /// Daily hydration summary
@@ -60,6 +83,8 @@ pub struct DailyHydrationSummary {
     pub goal_ml: i32,
     pub progress_percent: f64,
     pub goal_met: bool,
+    pub total_caffeine_mg: i64,
+    pub caffeine_warning: Option<String>,
     pub entry_count: i64,
     pub entries: Vec<HydrationLog>,
 }
@@ -96,25 +121,24 @@ impl HydrationService {
         user_id: Uuid,
         input: LogHydrationInput,
     ) -> Result<HydrationLog, ApiError> {
-        // Validate amount
-        if input.amount_ml <= 0 {
-            return Err(ApiError::Validation(
-                "Amount must be greater than 0".to_string(),
-            ));
-        }
-        if input.amount_ml > 10000 {
-            return Err(ApiError::Validation(
-                "Amount cannot exceed 10000ml".to_string(),
-            ));
+        validate_range(input.amount_ml, 1, 10000, "amount_ml")?;
+        if let Some(caffeine_mg) = input.caffeine_mg {
+            validate_range(caffeine_mg, 0, MAX_CAFFEINE_MG, "caffeine_mg")?;
         }
 
+        let beverage_type = input.beverage_type.unwrap_or_else(|| "water".to_string());
+        let caffeine_mg = input
+            .caffeine_mg
+            .or_else(|| Self::estimate_caffeine_mg(&beverage_type, input.amount_ml));
+
         let create_input = CreateHydrationLog {
             user_id,
             amount_ml: input.amount_ml,
-            beverage_type: input.beverage_type.unwrap_or_else(|| "water".to_string()),
+            beverage_type,
             consumed_at: input.consumed_at.unwrap_or_else(Utc::now),
             source: input.source.unwrap_or_else(|| "manual".to_string()),
             notes: input.notes,
+            caffeine_mg,
         };
 
         let record = HydrationLogRepository::create(pool, create_input)
@@ -128,6 +152,7 @@ impl HydrationService {
             consumed_at: record.consumed_at,
             source: record.source,
             notes: record.notes,
+            caffeine_mg: record.caffeine_mg,
         })
     }
 
@@ -137,19 +162,21 @@ impl HydrationService {
     /// progress = (consumed / goal) * 100
     pub async fn get_daily_summary(
         pool: &PgPool,
+        config: &HydrationConfig,
         user_id: Uuid,
         date: NaiveDate,
+        tz: Tz,
     ) -> Result<DailyHydrationSummary, ApiError> {
         // Get the user's goal
-        let goal_ml = Self::get_effective_goal(pool, user_id).await?;
+        let goal_ml = Self::get_effective_goal(pool, config, user_id).await?;
 
         // Get daily summary from repository
-        let summary = HydrationLogRepository::get_daily_summary(pool, user_id, date)
+        let summary = HydrationLogRepository::get_daily_summary(pool, user_id, date, tz)
             .await
             .map_err(ApiError::Internal)?;
 
         // Get individual entries
-        let entries = HydrationLogRepository::get_by_date(pool, user_id, date)
+        let entries = HydrationLogRepository::get_by_date(pool, user_id, date, tz)
             .await
             .map_err(ApiError::Internal)?
             .into_iter()
@@ -160,6 +187,7 @@ impl HydrationService {
                 consumed_at: r.consumed_at,
                 source: r.source,
                 notes: r.notes,
+                caffeine_mg: r.caffeine_mg,
             })
             .collect();
 
@@ -173,6 +201,8 @@ impl HydrationService {
             goal_ml,
             progress_percent,
             goal_met,
+            total_caffeine_mg: summary.total_caffeine_mg,
+            caffeine_warning: Self::caffeine_warning(summary.total_caffeine_mg, config.caffeine_warning_mg),
             entry_count: summary.entry_count,
             entries,
         })
@@ -189,6 +219,32 @@ impl HydrationService {
         (consumed_ml as f64 / goal_ml as f64) * 100.0
     }
 
+    /// Estimate caffeine in a drink from its type and volume
+    ///
+    /// Returns `None` for beverages without a typical caffeine content,
+    /// such as water. Estimates are capped at `MAX_CAFFEINE_MG`, the most a
+    /// single log can record.
+    pub fn estimate_caffeine_mg(beverage_type: &str, amount_ml: i32) -> Option<i32> {
+        let beverage_type = beverage_type.trim().to_lowercase();
+        CAFFEINE_MG_PER_CUP
+            .iter()
+            .find(|(beverage, _)| *beverage == beverage_type)
+            .map(|(_, mg_per_cup)| {
+                let mg = (mg_per_cup * f64::from(amount_ml) / CUP_ML).round();
+                mg.min(f64::from(MAX_CAFFEINE_MG)) as i32
+            })
+    }
+
+    /// Warning for a day's caffeine once it exceeds `limit_mg`
+    pub fn caffeine_warning(total_caffeine_mg: i64, limit_mg: i32) -> Option<String> {
+        (total_caffeine_mg > i64::from(limit_mg)).then(|| {
+            format!(
+                "Caffeine today is {}mg, above the recommended {}mg",
+                total_caffeine_mg, limit_mg
+            )
+        })
+    }
+
     /// Check if goal is met (>=100%)
     ///
     /// # Property 13: Hydration Goal Completion Detection
@@ -200,7 +256,11 @@ impl HydrationService {
     }
 
     /// Get user's hydration goal
-    pub async fn get_goal(pool: &PgPool, user_id: Uuid) -> Result<HydrationGoal, ApiError> {
+    pub async fn get_goal(
+        pool: &PgPool,
+        config: &HydrationConfig,
+        user_id: Uuid,
+    ) -> Result<HydrationGoal, ApiError> {
         let goal_record = HydrationGoalRepository::get_by_user(pool, user_id)
             .await
             .map_err(ApiError::Internal)?;
@@ -216,7 +276,7 @@ impl HydrationService {
             }),
             None => {
                 // Return default goal if none set
-                let auto_goal = Self::calculate_personalized_goal(pool, user_id).await?;
+                let auto_goal = Self::calculate_personalized_goal(pool, config, user_id).await?;
                 Ok(HydrationGoal {
                     daily_goal_ml: auto_goal,
                     is_auto_calculated: true,
@@ -232,21 +292,17 @@ impl HydrationService {
     /// Set user's hydration goal
     pub async fn set_goal(
         pool: &PgPool,
+        config: &HydrationConfig,
         user_id: Uuid,
         input: SetHydrationGoalInput,
     ) -> Result<HydrationGoal, ApiError> {
         let daily_goal_ml = if input.auto_calculate {
-            Self::calculate_personalized_goal(pool, user_id).await?
+            Self::calculate_personalized_goal(pool, config, user_id).await?
         } else {
             input.daily_goal_ml.unwrap_or(DEFAULT_HYDRATION_GOAL_ML)
         };
 
-        // Validate goal
-        if daily_goal_ml <= 0 || daily_goal_ml > 20000 {
-            return Err(ApiError::Validation(
-                "Daily goal must be between 1 and 20000ml".to_string(),
-            ));
-        }
+        validate_range(daily_goal_ml, 1, 20000, "daily_goal_ml")?;
 
         let upsert_input = UpsertHydrationGoal {
             user_id,
@@ -278,6 +334,7 @@ impl HydrationService {
     /// goal = weight_kg * 33ml * activity_multiplier
     pub async fn calculate_personalized_goal(
         pool: &PgPool,
+        config: &HydrationConfig,
         user_id: Uuid,
     ) -> Result<i32, ApiError> {
         // Get user's latest weight
@@ -298,14 +355,22 @@ impl HydrationService {
             .map(|s| s.activity_level)
             .unwrap_or_else(|| "moderately_active".to_string());
 
-        Ok(Self::calculate_goal_from_weight(weight_kg, &activity_level))
+        Ok(Self::calculate_goal_from_weight(weight_kg, &activity_level, config.goal_rounding_ml))
     }
 
     /// Calculate hydration goal from weight and activity level
     ///
     /// # Property 12: Personalized Hydration Goal
     /// goal = weight_kg * 33ml * activity_multiplier
-    pub fn calculate_goal_from_weight(weight_kg: f64, activity_level: &str) -> i32 {
+    ///
+    /// The goal is rounded to the nearest multiple of `round_to_ml`, e.g. 250
+    /// for a glass size, and is never less than one step. The step is
+    /// checked when [`HydrationConfig`] is loaded.
+    pub fn calculate_goal_from_weight(
+        weight_kg: f64,
+        activity_level: &str,
+        round_to_ml: i32,
+    ) -> i32 {
         let activity_multiplier = ACTIVITY_MULTIPLIERS
             .iter()
             .find(|(level, _)| *level == activity_level)
@@ -313,20 +378,24 @@ impl HydrationService {
             .unwrap_or(1.2); // Default to moderately active
 
         let goal = weight_kg * HYDRATION_ML_PER_KG * activity_multiplier;
-        
-        // Round to nearest 100ml for cleaner goals
-        ((goal / 100.0).round() * 100.0) as i32
+        let step = f64::from(round_to_ml);
+
+        ((goal / step).round() * step).max(step) as i32
     }
 
     /// Get effective goal (from settings or calculated)
-    async fn get_effective_goal(pool: &PgPool, user_id: Uuid) -> Result<i32, ApiError> {
+    pub(crate) async fn get_effective_goal(
+        pool: &PgPool,
+        config: &HydrationConfig,
+        user_id: Uuid,
+    ) -> Result<i32, ApiError> {
         let goal_record = HydrationGoalRepository::get_by_user(pool, user_id)
             .await
             .map_err(ApiError::Internal)?;
 
         match goal_record {
             Some(record) if !record.is_auto_calculated => Ok(record.daily_goal_ml),
-            _ => Self::calculate_personalized_goal(pool, user_id).await,
+            _ => Self::calculate_personalized_goal(pool, config, user_id).await,
         }
     }
 
@@ -341,16 +410,18 @@ impl HydrationService {
             .map_err(ApiError::Internal)
     }
 
-    /// Get hydration history for a date range
+    /// Get hydration history for a range of local dates
     pub async fn get_history(
         pool: &PgPool,
+        config: &HydrationConfig,
         user_id: Uuid,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        tz: Tz,
     ) -> Result<Vec<DailyHydrationSummary>, ApiError> {
-        let goal_ml = Self::get_effective_goal(pool, user_id).await?;
+        let goal_ml = Self::get_effective_goal(pool, config, user_id).await?;
 
-        let summaries = HydrationLogRepository::get_daily_summaries(pool, user_id, start_date, end_date)
+        let summaries = HydrationLogRepository::get_daily_summaries(pool, user_id, start_date, end_date, tz)
             .await
             .map_err(ApiError::Internal)?;
 
@@ -365,6 +436,8 @@ impl HydrationService {
                     goal_ml,
                     progress_percent,
                     goal_met,
+                    total_caffeine_mg: s.total_caffeine_mg,
+                    caffeine_warning: Self::caffeine_warning(s.total_caffeine_mg, config.caffeine_warning_mg),
                     entry_count: s.entry_count,
                     entries: vec![], // Don't include entries in history view
                 }
@@ -378,6 +451,11 @@ mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    fn default_goal(weight_kg: f64, activity_level: &str) -> i32 {
+        let round_to_ml = HydrationConfig::default().goal_rounding_ml;
+        HydrationService::calculate_goal_from_weight(weight_kg, activity_level, round_to_ml)
+    }
+
     // Feature: fitness-assistant-ai, Property 11: Hydration Progress Calculation
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(100))]
@@ -416,7 +494,7 @@ mod tests {
         fn test_personalized_goal_formula(
             weight_kg in 40.0f64..200.0
         ) {
-            let goal = HydrationService::calculate_goal_from_weight(weight_kg, "moderately_active");
+            let goal = default_goal(weight_kg, "moderately_active");
             
             // Expected: weight * 33 * 1.2 (moderately active multiplier), rounded to 100
             let expected_raw = weight_kg * 33.0 * 1.2;
@@ -429,11 +507,11 @@ mod tests {
 
         #[test]
         fn test_goal_increases_with_activity(weight_kg in 50.0f64..100.0) {
-            let sedentary = HydrationService::calculate_goal_from_weight(weight_kg, "sedentary");
-            let light = HydrationService::calculate_goal_from_weight(weight_kg, "lightly_active");
-            let moderate = HydrationService::calculate_goal_from_weight(weight_kg, "moderately_active");
-            let very = HydrationService::calculate_goal_from_weight(weight_kg, "very_active");
-            let extra = HydrationService::calculate_goal_from_weight(weight_kg, "extra_active");
+            let sedentary = default_goal(weight_kg, "sedentary");
+            let light = default_goal(weight_kg, "lightly_active");
+            let moderate = default_goal(weight_kg, "moderately_active");
+            let very = default_goal(weight_kg, "very_active");
+            let extra = default_goal(weight_kg, "extra_active");
 
             prop_assert!(sedentary <= light, "sedentary {} > light {}", sedentary, light);
             prop_assert!(light <= moderate, "light {} > moderate {}", light, moderate);
@@ -445,8 +523,8 @@ mod tests {
         fn test_goal_increases_with_weight(activity in prop::sample::select(vec![
             "sedentary", "lightly_active", "moderately_active", "very_active", "extra_active"
         ])) {
-            let goal_50 = HydrationService::calculate_goal_from_weight(50.0, activity);
-            let goal_100 = HydrationService::calculate_goal_from_weight(100.0, activity);
+            let goal_50 = default_goal(50.0, activity);
+            let goal_100 = default_goal(100.0, activity);
 
             prop_assert!(goal_50 < goal_100,
                 "Goal for 50kg ({}) >= goal for 100kg ({}) at activity {}",
@@ -492,15 +570,67 @@ mod tests {
     #[test]
     fn test_default_activity_multiplier() {
         // Unknown activity level should default to moderately_active (1.2)
-        let goal = HydrationService::calculate_goal_from_weight(70.0, "unknown");
-        let expected = HydrationService::calculate_goal_from_weight(70.0, "moderately_active");
+        let goal = default_goal(70.0, "unknown");
+        let expected = default_goal(70.0, "moderately_active");
         assert_eq!(goal, expected);
     }
 
     #[test]
     fn test_goal_rounded_to_100() {
         // 70kg * 33 * 1.0 = 2310, should round to 2300
-        let goal = HydrationService::calculate_goal_from_weight(70.0, "sedentary");
+        let goal = default_goal(70.0, "sedentary");
         assert_eq!(goal % 100, 0, "Goal {} not rounded to 100ml", goal);
+        assert_eq!(goal, 2300);
+    }
+
+    #[test]
+    fn test_three_large_coffees_warn_past_400mg() {
+        // A 350ml mug is about 139mg
+        let mug = HydrationService::estimate_caffeine_mg("coffee", 350).unwrap();
+        assert_eq!(mug, 139);
+
+        let two: i64 = 2 * i64::from(mug);
+        let three: i64 = 3 * i64::from(mug);
+        assert!(HydrationService::caffeine_warning(two, 400).is_none());
+        let warning = HydrationService::caffeine_warning(three, 400).unwrap();
+        assert!(warning.contains("417mg"));
+    }
+
+    #[test]
+    fn test_caffeine_estimate_by_beverage() {
+        assert_eq!(HydrationService::estimate_caffeine_mg("coffee", 240), Some(95));
+        assert_eq!(HydrationService::estimate_caffeine_mg(" Green_Tea ", 240), Some(28));
+        assert_eq!(HydrationService::estimate_caffeine_mg("water", 500), None);
+    }
+
+    #[test]
+    fn test_estimate_caffeine_capped_at_log_limit() {
+        // Five litres of espresso would otherwise estimate over 10,000mg
+        assert_eq!(
+            HydrationService::estimate_caffeine_mg("espresso", 5000),
+            Some(MAX_CAFFEINE_MG)
+        );
+    }
+
+    #[test]
+    fn test_goal_rounded_to_glass_size() {
+        for weight_kg in [55.0, 70.0, 82.5, 101.0] {
+            let goal = HydrationService::calculate_goal_from_weight(weight_kg, "very_active", 250);
+            assert_eq!(goal % 250, 0, "Goal {} not rounded to 250ml", goal);
+        }
+
+        // 70kg * 33 * 1.0 = 2310, nearest 250 is 2250
+        let goal = HydrationService::calculate_goal_from_weight(70.0, "sedentary", 250);
+        assert_eq!(goal, 2250);
+
+        let goal = HydrationService::calculate_goal_from_weight(70.0, "sedentary", 1);
+        assert_eq!(goal, 2310);
+    }
+
+    #[test]
+    fn test_goal_never_rounds_below_one_step() {
+        // 10kg * 33 * 1.0 = 330ml, which would round down to nothing at 1000ml
+        let goal = HydrationService::calculate_goal_from_weight(10.0, "sedentary", 1000);
+        assert_eq!(goal, 1000);
     }
 }
@@ -0,0 +1,88 @@
+//! Data import service for reading back user data exports
+//!
+//! Property 14: Data Import/Export Round-Trip
+//! Exported data can be re-imported equivalently
+
+use crate::error::ApiError;
+use crate::services::export::{decrypt_export, UserDataExport};
+
+/// Data import service
+pub struct ImportService;
+
+impl ImportService {
+    /// Decrypt and parse an export produced by `ExportService::export_encrypted`
+    pub async fn import_encrypted(data: &[u8], passphrase: &str) -> Result<UserDataExport, ApiError> {
+        if passphrase.is_empty() {
+            return Err(ApiError::Validation("Passphrase is required".to_string()));
+        }
+
+        // Key derivation is deliberately CPU-intensive
+        let data = data.to_vec();
+        let passphrase = passphrase.to_string();
+        let json = tokio::task::spawn_blocking(move || decrypt_export(&data, &passphrase))
+            .await
+            .map_err(|e| ApiError::Internal(anyhow::anyhow!("Task join error: {}", e)))??;
+
+        serde_json::from_slice(&json)
+            .map_err(|e| ApiError::Validation(format!("Invalid export file: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::export::{encrypt_export, WeightLogExport};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn sample_export() -> UserDataExport {
+        UserDataExport {
+            export_version: "1.0".to_string(),
+            exported_at: Utc::now(),
+            user_id: Uuid::new_v4().to_string(),
+            weight_logs: vec![WeightLogExport {
+                id: Uuid::new_v4().to_string(),
+                weight_kg: 72.4,
+                recorded_at: Utc::now(),
+                source: "manual".to_string(),
+                notes: Some("morning".to_string()),
+            }],
+            body_composition_logs: vec![],
+            workouts: vec![],
+            sleep_logs: vec![],
+            hydration_logs: vec![],
+            heart_rate_logs: vec![],
+            hrv_logs: vec![],
+            biomarker_logs: vec![],
+            goals: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_export_roundtrip() {
+        let export = sample_export();
+        let json = serde_json::to_vec(&export).unwrap();
+        let encrypted = encrypt_export(&json, "correct horse battery staple").unwrap();
+
+        assert!(!encrypted.windows(json.len()).any(|w| w == json.as_slice()));
+
+        let imported = ImportService::import_encrypted(&encrypted, "correct horse battery staple")
+            .await
+            .unwrap();
+        assert_eq!(imported.user_id, export.user_id);
+        assert_eq!(imported.weight_logs.len(), 1);
+        assert_eq!(imported.weight_logs[0].notes.as_deref(), Some("morning"));
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_import_rejects_wrong_passphrase() {
+        let json = serde_json::to_vec(&sample_export()).unwrap();
+        let encrypted = encrypt_export(&json, "correct horse battery staple").unwrap();
+
+        let result = ImportService::import_encrypted(&encrypted, "wrong passphrase").await;
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+
+        let result = ImportService::import_encrypted(&encrypted[..20], "correct horse battery staple").await;
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+    }
+}
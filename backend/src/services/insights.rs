@@ -1,11 +1,20 @@
 //! Health insights service - calculates health metrics from user data
 
+use crate::config::HydrationConfig;
 use crate::error::ApiError;
-use crate::repositories::{UserRepository, WeightRepository};
-use chrono::Utc;
+use crate::repositories::{
+    BodyCompositionRepository, ExerciseSetRepository, FoodLog, FoodLogRepository,
+    HydrationLogRepository, SleepLogRepository, UserRepository, WeightRepository,
+};
+use crate::services::{ExerciseService, HydrationService, ProfileService};
+use crate::timezone;
+use chrono::{Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
 use fitness_assistant_shared::health_metrics::{
-    calculate_bmi_result, calculate_daily_water_ml, calculate_ideal_weight, calculate_tdee_result,
-    classify_body_fat, estimate_body_fat_from_bmi, ActivityLevel, BiologicalSex, HealthProfile,
+    calculate_adapted_tdee, calculate_bmi_result, calculate_daily_water_ml,
+    calculate_derived_metrics, calculate_ideal_weight, calculate_tdee_result, classify_body_fat,
+    estimate_body_fat_from_bmi, ActivityLevel, ActivityMultiplierConfig, BiologicalSex,
+    DerivedMetrics, HealthProfile,
 };
 use fitness_assistant_shared::types::{
     BmiInfo, BodyFatInfo, EnergyInfo, HealthInsightsResponse, HydrationInfo, IdealWeightInfo,
@@ -14,9 +23,120 @@ use fitness_assistant_shared::units::WeightUnit;
 use fitness_assistant_shared::validation::get_field_display_label;
 use rust_decimal::prelude::ToPrimitive;
 use sqlx::PgPool;
+use std::collections::BTreeMap;
 use tracing::instrument;
 use uuid::Uuid;
 
+/// Weeks of food logs examined when looking for a sustained deficit
+const ADAPTATION_LOOKBACK_WEEKS: i64 = 12;
+
+/// Logged days needed for a week to count towards a sustained deficit
+const MIN_LOGGED_DAYS_PER_WEEK: usize = 4;
+
+/// Average daily deficit (kcal) treated as dieting
+const MIN_DEFICIT_KCAL: f64 = 200.0;
+
+/// Consecutive deficit weeks before an adapted TDEE is reported
+const MIN_DEFICIT_WEEKS: u32 = 2;
+
+/// Days in a digest week
+const DIGEST_DAYS: i64 = 7;
+
+/// A day's calories count as on target within this fraction of the goal
+const CALORIE_TARGET_TOLERANCE: f64 = 0.10;
+
+/// Energy stored in a kilogram of body weight (kcal)
+const KCAL_PER_KG: f64 = 7700.0;
+
+/// Days of intake and weight data needed to back-calculate TDEE
+const MIN_TDEE_ESTIMATE_DAYS: i64 = 14;
+
+/// Nights paired with next-day training needed to report a correlation
+const MIN_CORRELATION_PAIRS: usize = 10;
+
+/// One week of activity summarized for an email digest
+///
+/// Each section is `None` when the user logged nothing for it that week.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklyDigest {
+    pub week_start: NaiveDate,
+    pub week_end: NaiveDate,
+    pub exercise: Option<ExerciseDigest>,
+    pub sleep: Option<SleepDigest>,
+    pub hydration: Option<HydrationDigest>,
+    pub weight: Option<WeightDigest>,
+    pub nutrition: Option<NutritionDigest>,
+}
+
+/// How sleep relates to the next day's training
+#[derive(Debug, Clone, PartialEq)]
+pub struct Correlation {
+    /// Nights with a sleep score followed by a training day
+    pub paired_days: usize,
+    /// Pearson r between sleep score and next-day training volume (kg × reps)
+    pub volume: f64,
+    /// Pearson r between sleep score and next-day average RPE, when enough
+    /// sets were rated
+    pub rpe: Option<f64>,
+}
+
+/// Training done on one day
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct DayTraining {
+    volume_kg: f64,
+    rpe_total: f64,
+    rated_sets: u32,
+}
+
+impl DayTraining {
+    fn average_rpe(&self) -> Option<f64> {
+        (self.rated_sets > 0).then(|| self.rpe_total / f64::from(self.rated_sets))
+    }
+}
+
+/// Workout volume for the week
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExerciseDigest {
+    pub workouts: usize,
+    pub total_duration_minutes: i32,
+    pub total_calories_burned: i32,
+}
+
+/// Sleep averages for the week
+#[derive(Debug, Clone, PartialEq)]
+pub struct SleepDigest {
+    pub nights_logged: i64,
+    pub average_duration_minutes: f64,
+}
+
+/// How often the hydration goal was met
+#[derive(Debug, Clone, PartialEq)]
+pub struct HydrationDigest {
+    pub daily_goal_ml: i32,
+    pub days_logged: usize,
+    pub days_goal_met: usize,
+    /// Share of the week's days the goal was met (0-1)
+    pub goal_hit_rate: f64,
+}
+
+/// Weight change between the first and last entry of the week
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightDigest {
+    pub start_weight_kg: f64,
+    pub end_weight_kg: f64,
+    pub change_kg: f64,
+}
+
+/// Calorie intake against the user's goal
+#[derive(Debug, Clone, PartialEq)]
+pub struct NutritionDigest {
+    pub days_logged: usize,
+    pub average_daily_calories: f64,
+    pub daily_calorie_goal: Option<i32>,
+    /// Logged days within 10% of the calorie goal (None without a goal)
+    pub days_on_target: Option<usize>,
+}
+
 /// Health insights service
 pub struct HealthInsightsService;
 
@@ -28,7 +148,11 @@ impl HealthInsightsService {
     /// 
     /// Uses parallel queries for better performance.
     #[instrument(skip(db), fields(user_id = %user_id))]
-    pub async fn get_insights(db: &PgPool, user_id: Uuid) -> Result<HealthInsightsResponse, ApiError> {
+    pub async fn get_insights(
+        db: &PgPool,
+        tdee_config: &ActivityMultiplierConfig,
+        user_id: Uuid,
+    ) -> Result<HealthInsightsResponse, ApiError> {
         // Execute independent queries in parallel for better performance
         let (settings_result, weight_result) = tokio::join!(
             UserRepository::get_settings(db, user_id),
@@ -43,6 +167,16 @@ impl HealthInsightsService {
 
         let latest_weight = weight_result.map_err(ApiError::Internal)?;
 
+        let tz: Tz = settings.timezone.parse().unwrap_or(Tz::UTC);
+        let local_today = timezone::local_today(tz);
+        let lookback_start =
+            local_today - Duration::weeks(ADAPTATION_LOOKBACK_WEEKS) - Duration::days(1);
+
+        let food = FoodLogRepository::get_by_date_range(db, user_id, lookback_start, local_today, tz)
+            .await
+            .map_err(ApiError::Internal)?;
+        let daily_calories = Self::daily_calories(&food, tz);
+
         let weight_kg = latest_weight.map(|w| w.weight_kg.to_f64().unwrap_or(0.0));
         let height_cm = settings.height_cm.map(|h| h.to_f64().unwrap_or(0.0));
 
@@ -51,23 +185,12 @@ impl HealthInsightsService {
             today.years_since(dob).unwrap_or(0) as i32
         });
 
-        let sex = settings
+        let sex: Option<BiologicalSex> = settings
             .biological_sex
             .as_deref()
-            .and_then(|s| match s.to_lowercase().as_str() {
-                "male" => Some(BiologicalSex::Male),
-                "female" => Some(BiologicalSex::Female),
-                _ => None,
-            });
-
-        let activity: ActivityLevel = match settings.activity_level.as_str() {
-            "sedentary" => ActivityLevel::Sedentary,
-            "lightly_active" => ActivityLevel::LightlyActive,
-            "moderately_active" => ActivityLevel::ModeratelyActive,
-            "very_active" => ActivityLevel::VeryActive,
-            "extra_active" => ActivityLevel::ExtraActive,
-            _ => ActivityLevel::LightlyActive,
-        };
+            .and_then(|s| s.parse().ok());
+
+        let activity: ActivityLevel = settings.activity_level.parse().unwrap_or_default();
 
         // Track missing fields with user-friendly labels
         let mut missing_fields = Vec::new();
@@ -85,7 +208,8 @@ impl HealthInsightsService {
         }
 
         let bmi = Self::calculate_bmi(weight_kg, height_cm, &weight_unit);
-        let energy = Self::calculate_energy(weight_kg, height_cm, age_years, sex, activity);
+        let energy = Self::health_profile(weight_kg, height_cm, age_years, sex, activity)
+            .map(|profile| Self::calculate_energy(&profile, tdee_config, &daily_calories, local_today));
         let hydration = Self::calculate_hydration(weight_kg, activity);
         let ideal_weight = Self::calculate_ideal_weight(height_cm, sex, &weight_unit);
         let body_fat = Self::calculate_body_fat(bmi.as_ref(), age_years, sex);
@@ -100,6 +224,348 @@ impl HealthInsightsService {
         })
     }
 
+    /// Get every derived metric for the user's current profile in one payload
+    ///
+    /// Unlike [`Self::get_insights`], a profile missing any required field is
+    /// rejected with a validation error listing what's needed. FFMI is
+    /// included once the user has logged a body fat measurement.
+    #[instrument(skip(db), fields(user_id = %user_id))]
+    pub async fn get_derived_metrics(
+        db: &PgPool,
+        tdee_config: &ActivityMultiplierConfig,
+        user_id: Uuid,
+    ) -> Result<DerivedMetrics, ApiError> {
+        let today = timezone::local_today(timezone::user_timezone(db, user_id).await);
+        let profile = ProfileService::get_profile_at(db, user_id, today).await?;
+
+        let body_fat_percent = BodyCompositionRepository::get_latest(db, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .and_then(|record| record.body_fat_percent)
+            .and_then(|percent| percent.to_f64());
+
+        Ok(calculate_derived_metrics(&profile, tdee_config, body_fat_percent))
+    }
+
+    /// Back-calculate the user's actual TDEE from the last `days` days of data
+    ///
+    /// Uses complete local days ending yesterday. Average logged intake is
+    /// corrected by the observed weight change, with each kilogram lost
+    /// worth [`KCAL_PER_KG`]: `TDEE = avg_intake + weight_change_kg * 7700 / days`.
+    #[instrument(skip(db), fields(user_id = %user_id))]
+    pub async fn estimate_tdee_from_data(
+        db: &PgPool,
+        user_id: Uuid,
+        days: i64,
+    ) -> Result<f64, ApiError> {
+        if days < MIN_TDEE_ESTIMATE_DAYS {
+            return Err(ApiError::Validation(format!(
+                "At least {} days are needed to estimate TDEE",
+                MIN_TDEE_ESTIMATE_DAYS
+            )));
+        }
+
+        let tz = timezone::user_timezone(db, user_id).await;
+        let end = timezone::local_today(tz) - Duration::days(1);
+        let start = end - Duration::days(days - 1);
+        let (range_start, _) = timezone::local_day_bounds(start, tz);
+        let (_, range_end) = timezone::local_day_bounds(end, tz);
+
+        let (weights, food) = tokio::join!(
+            WeightRepository::get_by_date_range(db, user_id, Some(range_start), Some(range_end)),
+            FoodLogRepository::get_by_date_range(db, user_id, start, end, tz)
+        );
+
+        let daily_calories = Self::daily_calories(&food.map_err(ApiError::Internal)?, tz);
+
+        // Weight logs arrive newest first
+        let mut weights: Vec<(NaiveDate, f64)> = weights
+            .map_err(ApiError::Internal)?
+            .iter()
+            .map(|w| {
+                (
+                    timezone::local_date(w.recorded_at, tz),
+                    w.weight_kg.to_f64().unwrap_or(0.0),
+                )
+            })
+            .collect();
+        weights.reverse();
+
+        Self::back_calculate_tdee(&daily_calories, &weights)
+    }
+
+    /// Correlate nightly sleep score with the next day's training over the last `days` days
+    ///
+    /// A night is paired with the training logged on the local day it ended.
+    /// Nights without a sleep score and days without weighted working sets
+    /// are skipped.
+    #[instrument(skip(db), fields(user_id = %user_id))]
+    pub async fn correlate_sleep_performance(
+        db: &PgPool,
+        user_id: Uuid,
+        days: i64,
+    ) -> Result<Correlation, ApiError> {
+        let tz = timezone::user_timezone(db, user_id).await;
+        let end = timezone::local_today(tz);
+        let start = end - Duration::days(days.max(1) - 1);
+        let (range_start, _) = timezone::local_day_bounds(start, tz);
+        let (_, range_end) = timezone::local_day_bounds(end, tz);
+
+        // Sleep history is filtered on the UTC date, so widen it by a day
+        let (sleep, sets) = tokio::join!(
+            SleepLogRepository::get_history(
+                db,
+                user_id,
+                start - Duration::days(1),
+                end + Duration::days(1),
+                days.max(1) * 3,
+                0
+            ),
+            ExerciseSetRepository::get_working_sets_in_range(db, user_id, range_start, range_end)
+        );
+
+        // Take the longest sleep ending on each day, ignoring naps
+        let mut nights: BTreeMap<NaiveDate, (i32, f64)> = BTreeMap::new();
+        for log in sleep.map_err(ApiError::Internal)? {
+            let Some(score) = log.sleep_score else {
+                continue;
+            };
+            let date = timezone::local_date(log.sleep_end, tz);
+            if date < start || date > end {
+                continue;
+            }
+            let night = nights.entry(date).or_insert((log.total_duration_minutes, f64::from(score)));
+            if log.total_duration_minutes > night.0 {
+                *night = (log.total_duration_minutes, f64::from(score));
+            }
+        }
+        let sleep_scores: BTreeMap<NaiveDate, f64> =
+            nights.into_iter().map(|(date, (_, score))| (date, score)).collect();
+
+        let mut training: BTreeMap<NaiveDate, DayTraining> = BTreeMap::new();
+        for set in sets.map_err(ApiError::Internal)? {
+            let day = training.entry(timezone::local_date(set.performed_at, tz)).or_default();
+            day.volume_kg += set.weight_kg.to_f64().unwrap_or(0.0) * f64::from(set.reps);
+            if let Some(rpe) = set.rpe.and_then(|rpe| rpe.to_f64()) {
+                day.rpe_total += rpe;
+                day.rated_sets += 1;
+            }
+        }
+
+        Self::sleep_performance_correlation(&sleep_scores, &training)
+    }
+
+    /// Summarize the week containing `week_of` for a digest email
+    ///
+    /// The week begins on the user's configured week start day, and days are
+    /// the user's local days.
+    #[instrument(skip(db, config), fields(user_id = %user_id))]
+    pub async fn generate_weekly_digest(
+        db: &PgPool,
+        config: &HydrationConfig,
+        user_id: Uuid,
+        week_of: NaiveDate,
+    ) -> Result<WeeklyDigest, ApiError> {
+        let settings = UserRepository::get_settings(db, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Settings not found".to_string()))?;
+        let tz: Tz = settings.timezone.parse().unwrap_or(Tz::UTC);
+
+        let first_day = ProfileService::get_week_start(db, user_id).await?;
+        let week_start = ExerciseService::get_week_start(week_of, first_day);
+        let week_end = week_start + Duration::days(DIGEST_DAYS - 1);
+        let (range_start, _) = timezone::local_day_bounds(week_start, tz);
+        let (_, range_end) = timezone::local_day_bounds(week_end, tz);
+
+        let (exercise, sleep, hydration, weights, food) = tokio::join!(
+            ExerciseService::get_weekly_summary(db, None, user_id, week_of, first_day),
+            SleepLogRepository::get_summary(db, user_id, week_start, week_end),
+            HydrationLogRepository::get_daily_summaries(db, user_id, week_start, week_end, tz),
+            WeightRepository::get_by_date_range(db, user_id, Some(range_start), Some(range_end)),
+            FoodLogRepository::get_by_date_range(db, user_id, week_start, week_end, tz)
+        );
+
+        let exercise = exercise?;
+        let exercise = (exercise.total_workouts > 0).then_some(ExerciseDigest {
+            workouts: exercise.total_workouts,
+            total_duration_minutes: exercise.total_duration_minutes,
+            total_calories_burned: exercise.total_calories_burned,
+        });
+
+        let sleep = sleep.map_err(ApiError::Internal)?;
+        let sleep = match (sleep.total_nights, sleep.avg_duration_minutes) {
+            (nights, Some(average)) if nights > 0 => Some(SleepDigest {
+                nights_logged: nights,
+                average_duration_minutes: average,
+            }),
+            _ => None,
+        };
+
+        let daily_water: Vec<i64> = hydration
+            .map_err(ApiError::Internal)?
+            .into_iter()
+            .map(|day| day.total_ml)
+            .collect();
+        let hydration = if daily_water.is_empty() {
+            None
+        } else {
+            let goal_ml = HydrationService::get_effective_goal(db, config, user_id).await?;
+            Self::hydration_digest(&daily_water, goal_ml)
+        };
+
+        // Weight logs arrive newest first
+        let mut weights: Vec<f64> = weights
+            .map_err(ApiError::Internal)?
+            .iter()
+            .map(|w| w.weight_kg.to_f64().unwrap_or(0.0))
+            .collect();
+        weights.reverse();
+        let weight = Self::weight_digest(&weights);
+
+        let daily_calories = Self::daily_calories(&food.map_err(ApiError::Internal)?, tz);
+        let nutrition = Self::nutrition_digest(&daily_calories, settings.daily_calorie_goal);
+
+        Ok(WeeklyDigest {
+            week_start,
+            week_end,
+            exercise,
+            sleep,
+            hydration,
+            weight,
+            nutrition,
+        })
+    }
+
+    /// Total calories logged on each local day
+    fn daily_calories(food: &[FoodLog], tz: Tz) -> BTreeMap<NaiveDate, f64> {
+        let mut daily_calories: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+        for log in food {
+            *daily_calories
+                .entry(timezone::local_date(log.consumed_at, tz))
+                .or_default() += log.calories.to_f64().unwrap_or(0.0);
+        }
+        daily_calories
+    }
+
+    /// Goal hit rate from each logged day's total intake
+    fn hydration_digest(daily_totals_ml: &[i64], goal_ml: i32) -> Option<HydrationDigest> {
+        if daily_totals_ml.is_empty() {
+            return None;
+        }
+
+        let days_goal_met = daily_totals_ml
+            .iter()
+            .filter(|&&total| HydrationService::is_goal_met(total, goal_ml))
+            .count();
+
+        Some(HydrationDigest {
+            daily_goal_ml: goal_ml,
+            days_logged: daily_totals_ml.len(),
+            days_goal_met,
+            goal_hit_rate: days_goal_met as f64 / DIGEST_DAYS as f64,
+        })
+    }
+
+    /// Correlation between sleep scores and training on the same local day
+    fn sleep_performance_correlation(
+        sleep_scores: &BTreeMap<NaiveDate, f64>,
+        training: &BTreeMap<NaiveDate, DayTraining>,
+    ) -> Result<Correlation, ApiError> {
+        let paired: Vec<(f64, &DayTraining)> = sleep_scores
+            .iter()
+            .filter_map(|(date, score)| training.get(date).map(|day| (*score, day)))
+            .collect();
+
+        if paired.len() < MIN_CORRELATION_PAIRS {
+            return Err(ApiError::Validation(format!(
+                "At least {} nights followed by a workout are needed, found {}",
+                MIN_CORRELATION_PAIRS,
+                paired.len()
+            )));
+        }
+
+        let (scores, volumes): (Vec<f64>, Vec<f64>) =
+            paired.iter().map(|(score, day)| (*score, day.volume_kg)).unzip();
+        let volume = Self::pearson(&scores, &volumes).ok_or_else(|| {
+            ApiError::Validation("Sleep scores or training volume never vary".to_string())
+        })?;
+
+        let (rated_scores, rpes): (Vec<f64>, Vec<f64>) = paired
+            .iter()
+            .filter_map(|(score, day)| day.average_rpe().map(|rpe| (*score, rpe)))
+            .unzip();
+        let rpe = if rated_scores.len() >= MIN_CORRELATION_PAIRS {
+            Self::pearson(&rated_scores, &rpes)
+        } else {
+            None
+        };
+
+        Ok(Correlation {
+            paired_days: paired.len(),
+            volume,
+            rpe,
+        })
+    }
+
+    /// Pearson correlation coefficient of paired samples
+    ///
+    /// `None` when either series is constant.
+    fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+        let n = xs.len().min(ys.len()) as f64;
+        let mean_x = xs.iter().sum::<f64>() / n;
+        let mean_y = ys.iter().sum::<f64>() / n;
+
+        let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+        for (x, y) in xs.iter().zip(ys) {
+            covariance += (x - mean_x) * (y - mean_y);
+            var_x += (x - mean_x).powi(2);
+            var_y += (y - mean_y).powi(2);
+        }
+
+        (var_x > 0.0 && var_y > 0.0).then(|| covariance / (var_x * var_y).sqrt())
+    }
+
+    /// Change between the first and last weigh-in, oldest first
+    ///
+    /// A single weigh-in has no change to report.
+    fn weight_digest(weights_kg: &[f64]) -> Option<WeightDigest> {
+        match weights_kg {
+            [first, .., last] => Some(WeightDigest {
+                start_weight_kg: *first,
+                end_weight_kg: *last,
+                change_kg: last - first,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Average intake and days within tolerance of the calorie goal
+    fn nutrition_digest(
+        daily_calories: &BTreeMap<NaiveDate, f64>,
+        daily_calorie_goal: Option<i32>,
+    ) -> Option<NutritionDigest> {
+        if daily_calories.is_empty() {
+            return None;
+        }
+
+        let days_logged = daily_calories.len();
+        let average_daily_calories = daily_calories.values().sum::<f64>() / days_logged as f64;
+        let days_on_target = daily_calorie_goal.filter(|&goal| goal > 0).map(|goal| {
+            let goal = goal as f64;
+            daily_calories
+                .values()
+                .filter(|&&kcal| (kcal - goal).abs() <= goal * CALORIE_TARGET_TOLERANCE)
+                .count()
+        });
+
+        Some(NutritionDigest {
+            days_logged,
+            average_daily_calories,
+            daily_calorie_goal,
+            days_on_target,
+        })
+    }
 
     fn calculate_bmi(
         weight_kg: Option<f64>,
@@ -122,36 +588,116 @@ impl HealthInsightsService {
         }
     }
 
-    fn calculate_energy(
+    fn health_profile(
         weight_kg: Option<f64>,
         height_cm: Option<f64>,
         age_years: Option<i32>,
         sex: Option<BiologicalSex>,
         activity: ActivityLevel,
-    ) -> Option<EnergyInfo> {
+    ) -> Option<HealthProfile> {
         match (weight_kg, height_cm, age_years, sex) {
-            (Some(w), Some(h), Some(age), Some(s)) if h > 0.0 && age > 0 => {
-                let profile = HealthProfile {
-                    height_cm: h,
-                    weight_kg: w,
-                    age_years: age,
-                    sex: s,
-                    activity_level: activity,
-                };
-                let result = calculate_tdee_result(&profile);
-                Some(EnergyInfo {
-                    bmr: result.bmr.round(),
-                    tdee: result.tdee.round(),
-                    calories_for_loss: result.calories_for_loss.round(),
-                    calories_for_gain: result.calories_for_gain.round(),
-                    calories_for_maintenance: result.calories_for_maintenance.round(),
-                    unit: "kcal".to_string(),
-                })
-            }
+            (Some(w), Some(h), Some(age), Some(s)) if h > 0.0 && age > 0 => Some(HealthProfile {
+                height_cm: h,
+                weight_kg: w,
+                age_years: age,
+                sex: s,
+                activity_level: activity,
+            }),
             _ => None,
         }
     }
 
+    fn calculate_energy(
+        profile: &HealthProfile,
+        tdee_config: &ActivityMultiplierConfig,
+        daily_calories: &BTreeMap<NaiveDate, f64>,
+        today: NaiveDate,
+    ) -> EnergyInfo {
+        let result = calculate_tdee_result(profile, Some(tdee_config));
+        let deficit = Self::sustained_deficit(daily_calories, result.tdee, today);
+        let adapted_tdee = deficit
+            .map(|(weeks, avg)| calculate_adapted_tdee(profile, Some(tdee_config), weeks, avg).round());
+        EnergyInfo {
+            bmr: result.bmr.round(),
+            tdee: result.tdee.round(),
+            calories_for_loss: result.calories_for_loss.round(),
+            calories_for_gain: result.calories_for_gain.round(),
+            calories_for_maintenance: result.calories_for_maintenance.round(),
+            adapted_tdee,
+            weeks_in_deficit: deficit.map(|(weeks, _)| weeks),
+            unit: "kcal".to_string(),
+        }
+    }
+
+    /// Find a sustained calorie deficit in recent food logs
+    ///
+    /// Walks back through complete 7-day windows ending yesterday and counts
+    /// consecutive weeks whose logged intake averages at least
+    /// [`MIN_DEFICIT_KCAL`] below TDEE. Returns the number of weeks and the
+    /// average daily deficit across them.
+    fn sustained_deficit(
+        daily_calories: &BTreeMap<NaiveDate, f64>,
+        tdee: f64,
+        today: NaiveDate,
+    ) -> Option<(u32, f64)> {
+        let mut weeks = 0u32;
+        let mut total_deficit = 0.0;
+
+        for week in 0..ADAPTATION_LOOKBACK_WEEKS {
+            let end = today - Duration::days(7 * week + 1);
+            let start = end - Duration::days(6);
+            let logged: Vec<f64> = daily_calories.range(start..=end).map(|(_, kcal)| *kcal).collect();
+
+            if logged.len() < MIN_LOGGED_DAYS_PER_WEEK {
+                break;
+            }
+            let avg_deficit = tdee - logged.iter().sum::<f64>() / logged.len() as f64;
+            if avg_deficit < MIN_DEFICIT_KCAL {
+                break;
+            }
+
+            weeks += 1;
+            total_deficit += avg_deficit;
+        }
+
+        (weeks >= MIN_DEFICIT_WEEKS).then(|| (weeks, total_deficit / weeks as f64))
+    }
+
+    /// TDEE from daily intake totals and weigh-ins (oldest first)
+    ///
+    /// Needs [`MIN_TDEE_ESTIMATE_DAYS`] logged intake days and first and last
+    /// weigh-ins at least that many days apart. The weight change is spread
+    /// over the days between those weigh-ins.
+    fn back_calculate_tdee(
+        daily_calories: &BTreeMap<NaiveDate, f64>,
+        weights: &[(NaiveDate, f64)],
+    ) -> Result<f64, ApiError> {
+        if (daily_calories.len() as i64) < MIN_TDEE_ESTIMATE_DAYS {
+            return Err(ApiError::Validation(format!(
+                "At least {} days of logged intake are needed to estimate TDEE",
+                MIN_TDEE_ESTIMATE_DAYS
+            )));
+        }
+
+        let span = match (weights.first(), weights.last()) {
+            (Some(&(first_on, first_kg)), Some(&(last_on, last_kg)))
+                if (last_on - first_on).num_days() >= MIN_TDEE_ESTIMATE_DAYS =>
+            {
+                Some(((last_on - first_on).num_days(), first_kg - last_kg))
+            }
+            _ => None,
+        };
+        let (weight_days, weight_lost_kg) = span.ok_or_else(|| {
+            ApiError::Validation(format!(
+                "Weigh-ins spanning at least {} days are needed to estimate TDEE",
+                MIN_TDEE_ESTIMATE_DAYS
+            ))
+        })?;
+
+        let avg_intake = daily_calories.values().sum::<f64>() / daily_calories.len() as f64;
+        Ok(avg_intake + weight_lost_kg * KCAL_PER_KG / weight_days as f64)
+    }
+
     fn calculate_hydration(weight_kg: Option<f64>, activity: ActivityLevel) -> Option<HydrationInfo> {
         weight_kg.map(|w| {
             let ml = calculate_daily_water_ml(w, activity);
@@ -202,3 +748,255 @@ impl HealthInsightsService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn logged_days(today: NaiveDate, days: i64, kcal: f64) -> BTreeMap<NaiveDate, f64> {
+        (1..=days).map(|d| (today - Duration::days(d), kcal)).collect()
+    }
+
+    #[test]
+    fn test_energy_uses_configured_activity_multiplier() {
+        let today = date("2024-06-29");
+        let profile = HealthInsightsService::health_profile(
+            Some(80.0),
+            Some(180.0),
+            Some(30),
+            Some(BiologicalSex::Male),
+            ActivityLevel::LightlyActive,
+        )
+        .unwrap();
+
+        let default = HealthInsightsService::calculate_energy(
+            &profile,
+            &ActivityMultiplierConfig::default(),
+            &BTreeMap::new(),
+            today,
+        );
+        let config = ActivityMultiplierConfig {
+            lightly_active: 1.465,
+            ..Default::default()
+        };
+        let custom = HealthInsightsService::calculate_energy(&profile, &config, &BTreeMap::new(), today);
+
+        // BMR is 1780 kcal
+        assert_eq!(default.tdee, (1780.0_f64 * 1.375).round());
+        assert_eq!(custom.tdee, (1780.0_f64 * 1.465).round());
+    }
+
+    #[test]
+    fn test_back_calculate_tdee_recovers_known_expenditure() {
+        let today = date("2024-06-29");
+        let intake = logged_days(today, 28, 2000.0);
+        let weights = [
+            (today - Duration::days(28), 80.0),
+            (today - Duration::days(14), 79.6),
+            (today, 79.0),
+        ];
+
+        // 1 kg lost over 28 days is 275 kcal/day above intake
+        let tdee = HealthInsightsService::back_calculate_tdee(&intake, &weights).unwrap();
+        assert!((tdee - 2275.0).abs() < 1e-9);
+
+        // Gaining weight puts TDEE below intake
+        let gaining = [(today - Duration::days(14), 70.0), (today, 70.7)];
+        let tdee = HealthInsightsService::back_calculate_tdee(&intake, &gaining).unwrap();
+        assert!((tdee - 1615.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_back_calculate_tdee_requires_two_weeks_of_data() {
+        let today = date("2024-06-29");
+        let weights = [(today - Duration::days(20), 80.0), (today, 79.0)];
+
+        let short_intake = logged_days(today, 13, 2000.0);
+        assert!(matches!(
+            HealthInsightsService::back_calculate_tdee(&short_intake, &weights),
+            Err(ApiError::Validation(_))
+        ));
+
+        let intake = logged_days(today, 20, 2000.0);
+        let close_weigh_ins = [(today - Duration::days(10), 80.0), (today, 79.0)];
+        assert!(matches!(
+            HealthInsightsService::back_calculate_tdee(&intake, &close_weigh_ins),
+            Err(ApiError::Validation(_))
+        ));
+        assert!(HealthInsightsService::back_calculate_tdee(&intake, &[]).is_err());
+    }
+
+    fn training_day(volume_kg: f64, rpe: Option<f64>) -> DayTraining {
+        DayTraining {
+            volume_kg,
+            rpe_total: rpe.unwrap_or(0.0),
+            rated_sets: u32::from(rpe.is_some()),
+        }
+    }
+
+    #[test]
+    fn test_pearson_known_values() {
+        let r = HealthInsightsService::pearson(&[1.0, 2.0, 3.0, 4.0, 5.0], &[2.0, 4.0, 5.0, 4.0, 5.0])
+            .unwrap();
+        assert!((r - 0.7746).abs() < 1e-4);
+
+        let r = HealthInsightsService::pearson(&[1.0, 2.0, 3.0], &[6.0, 4.0, 2.0]).unwrap();
+        assert!((r + 1.0).abs() < 1e-9);
+
+        assert!(HealthInsightsService::pearson(&[1.0, 2.0, 3.0], &[5.0, 5.0, 5.0]).is_none());
+    }
+
+    #[test]
+    fn test_sleep_performance_correlation_on_paired_days() {
+        let first = date("2024-05-01");
+        let mut sleep = BTreeMap::new();
+        let mut training = BTreeMap::new();
+        for d in 0..12 {
+            let day = first + Duration::days(d);
+            let score = 60.0 + 3.0 * d as f64;
+            sleep.insert(day, score);
+            // Volume tracks sleep exactly; RPE falls as sleep improves
+            training.insert(day, training_day(score * 100.0, Some(10.0 - score / 20.0)));
+        }
+        // A night with no workout the next day isn't paired
+        sleep.insert(first + Duration::days(20), 40.0);
+
+        let correlation =
+            HealthInsightsService::sleep_performance_correlation(&sleep, &training).unwrap();
+        assert_eq!(correlation.paired_days, 12);
+        assert!((correlation.volume - 1.0).abs() < 1e-9);
+        assert!((correlation.rpe.unwrap() + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sleep_performance_correlation_needs_ten_pairs() {
+        let first = date("2024-05-01");
+        let sleep: BTreeMap<NaiveDate, f64> =
+            (0..9).map(|d| (first + Duration::days(d), 70.0 + d as f64)).collect();
+        let training: BTreeMap<NaiveDate, DayTraining> = (0..9)
+            .map(|d| (first + Duration::days(d), training_day(5000.0 + d as f64, None)))
+            .collect();
+
+        assert!(matches!(
+            HealthInsightsService::sleep_performance_correlation(&sleep, &training),
+            Err(ApiError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_hydration_digest_rate_is_over_the_whole_week() {
+        let digest = HealthInsightsService::hydration_digest(&[2500, 1800, 2600], 2400).unwrap();
+        assert_eq!(digest.days_logged, 3);
+        assert_eq!(digest.days_goal_met, 2);
+        assert!((digest.goal_hit_rate - 2.0 / 7.0).abs() < 1e-9);
+
+        assert!(HealthInsightsService::hydration_digest(&[], 2400).is_none());
+    }
+
+    #[test]
+    fn test_weight_digest_needs_two_weigh_ins() {
+        let digest = HealthInsightsService::weight_digest(&[80.0, 79.6, 79.2]).unwrap();
+        assert!((digest.change_kg + 0.8).abs() < 1e-9);
+
+        assert!(HealthInsightsService::weight_digest(&[80.0]).is_none());
+        assert!(HealthInsightsService::weight_digest(&[]).is_none());
+    }
+
+    #[test]
+    fn test_nutrition_digest_counts_days_on_target() {
+        let today = date("2024-06-15");
+        let daily: BTreeMap<_, _> = [(0, 2000.0), (1, 2150.0), (2, 2600.0)]
+            .into_iter()
+            .map(|(d, kcal)| (today - Duration::days(d), kcal))
+            .collect();
+
+        let digest = HealthInsightsService::nutrition_digest(&daily, Some(2000)).unwrap();
+        assert_eq!(digest.days_logged, 3);
+        assert_eq!(digest.days_on_target, Some(2));
+        assert!((digest.average_daily_calories - 2250.0).abs() < 1e-9);
+
+        let no_goal = HealthInsightsService::nutrition_digest(&daily, None).unwrap();
+        assert_eq!(no_goal.days_on_target, None);
+
+        assert!(HealthInsightsService::nutrition_digest(&BTreeMap::new(), Some(2000)).is_none());
+    }
+
+    fn food_log(consumed_at: &str, calories: i64) -> FoodLog {
+        let consumed_at = chrono::DateTime::parse_from_rfc3339(consumed_at)
+            .unwrap()
+            .with_timezone(&Utc);
+        FoodLog {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            food_item_id: None,
+            custom_name: None,
+            servings: rust_decimal::Decimal::ONE,
+            calories: rust_decimal::Decimal::from(calories),
+            protein_g: rust_decimal::Decimal::ZERO,
+            carbohydrates_g: rust_decimal::Decimal::ZERO,
+            fat_g: rust_decimal::Decimal::ZERO,
+            fiber_g: rust_decimal::Decimal::ZERO,
+            sugar_g: rust_decimal::Decimal::ZERO,
+            sodium_mg: rust_decimal::Decimal::ZERO,
+            alcohol_g: rust_decimal::Decimal::ZERO,
+            meal_type: "dinner".to_string(),
+            logged_at: consumed_at,
+            consumed_at,
+            notes: None,
+            created_at: consumed_at,
+        }
+    }
+
+    #[test]
+    fn test_daily_calories_sums_by_local_date() {
+        // 22:00 on Jun 14 in New York is 02:00 on Jun 15 in UTC
+        let food = [
+            food_log("2024-06-14T16:00:00Z", 600),
+            food_log("2024-06-15T02:00:00Z", 900),
+            food_log("2024-06-15T16:00:00Z", 700),
+        ];
+
+        let daily = HealthInsightsService::daily_calories(&food, Tz::America__New_York);
+        assert_eq!(
+            daily.into_iter().collect::<Vec<_>>(),
+            vec![(date("2024-06-14"), 1500.0), (date("2024-06-15"), 700.0)]
+        );
+    }
+
+    #[test]
+    fn test_sustained_deficit_counts_consecutive_weeks() {
+        let today = date("2024-06-15");
+        let daily = logged_days(today, 42, 1900.0);
+
+        let (weeks, avg) = HealthInsightsService::sustained_deficit(&daily, 2500.0, today).unwrap();
+        assert_eq!(weeks, 6);
+        assert!((avg - 600.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sustained_deficit_requires_minimum_weeks() {
+        let today = date("2024-06-15");
+        let daily = logged_days(today, 7, 1900.0);
+
+        assert!(HealthInsightsService::sustained_deficit(&daily, 2500.0, today).is_none());
+    }
+
+    #[test]
+    fn test_sustained_deficit_ignores_maintenance_and_sparse_logging() {
+        let today = date("2024-06-15");
+
+        let maintenance = logged_days(today, 42, 2450.0);
+        assert!(HealthInsightsService::sustained_deficit(&maintenance, 2500.0, today).is_none());
+
+        // Only every third day logged, so no week has enough data
+        let sparse: BTreeMap<_, _> = (1..=42)
+            .step_by(3)
+            .map(|d| (today - Duration::days(d), 1500.0))
+            .collect();
+        assert!(HealthInsightsService::sustained_deficit(&sparse, 2500.0, today).is_none());
+    }
+}
@@ -0,0 +1,323 @@
+//! Background jobs
+//!
+//! Periodic maintenance tasks spawned alongside the server. Jobs are tracked
+//! by a [`JobManager`]; every job watches its shutdown channel, and shutdown
+//! waits for running jobs to finish before the process exits.
+
+use crate::config::HydrationConfig;
+use crate::error::ApiError;
+use crate::repositories::HydrationGoalRepository;
+use crate::services::{ExportJobService, HydrationService};
+use sqlx::PgPool;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// A spawned job and the name it is logged under
+type TrackedJob = (&'static str, JoinHandle<()>);
+
+/// Tracks spawned background jobs so shutdown can drain them
+///
+/// Jobs subscribe to the manager's shutdown channel; [`JobManager::shutdown`]
+/// signals it and waits for every job, aborting any still running when the
+/// timeout passes. Clones share the same jobs, so request handlers can spawn
+/// one-off tasks through the copy in `AppState`.
+#[derive(Clone)]
+pub struct JobManager {
+    shutdown_tx: Arc<watch::Sender<bool>>,
+    jobs: Arc<Mutex<Vec<TrackedJob>>>,
+}
+
+impl JobManager {
+    pub fn new() -> Self {
+        let (shutdown_tx, _) = watch::channel(false);
+        Self {
+            shutdown_tx: Arc::new(shutdown_tx),
+            jobs: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Receiver that fires when shutdown starts
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.shutdown_tx.subscribe()
+    }
+
+    /// Track an already spawned job
+    pub fn track(&self, name: &'static str, handle: JoinHandle<()>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        // Forget one-off tasks that already finished so the list doesn't grow forever
+        jobs.retain(|(_, handle)| !handle.is_finished());
+        jobs.push((name, handle));
+    }
+
+    /// Spawn a task and track it
+    pub fn spawn<F>(&self, name: &'static str, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.track(name, tokio::spawn(task));
+    }
+
+    /// Signal shutdown and wait up to `timeout` for every job to stop
+    ///
+    /// Returns the number of jobs aborted because they were still running.
+    pub async fn shutdown(&self, timeout: Duration) -> usize {
+        let _ = self.shutdown_tx.send(true);
+        let deadline = Instant::now() + timeout;
+
+        let jobs = std::mem::take(&mut *self.jobs.lock().unwrap());
+        let mut aborted = 0;
+        for (name, mut handle) in jobs {
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => warn!(job = name, "Background job failed: {}", e),
+                Err(_) => {
+                    warn!(job = name, "Background job still running at shutdown, aborting");
+                    handle.abort();
+                    aborted += 1;
+                }
+            }
+        }
+
+        aborted
+    }
+}
+
+impl Default for JobManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run `job` every `period` until `shutdown` fires
+///
+/// The first run happens immediately. Dropping the sender also counts as a
+/// shutdown.
+pub async fn run_periodically<F, Fut>(
+    name: &'static str,
+    period: Duration,
+    mut shutdown: watch::Receiver<bool>,
+    mut job: F,
+) where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut interval = tokio::time::interval(period);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => job().await,
+            _ = shutdown.changed() => break,
+        }
+    }
+
+    info!(job = name, "Background job stopped");
+}
+
+/// Recompute auto-calculated hydration goals from each user's latest weight
+///
+/// Users whose goal can't be recomputed are logged and skipped. Returns the
+/// number of goals that changed.
+pub async fn recompute_hydration_goals(
+    pool: &PgPool,
+    config: &HydrationConfig,
+) -> Result<usize, ApiError> {
+    let user_ids = HydrationGoalRepository::list_auto_calculated_users(pool)
+        .await
+        .map_err(ApiError::Internal)?;
+
+    let mut updated = 0;
+    for user_id in user_ids {
+        // One user's failure shouldn't leave everyone after them stale
+        match recompute_hydration_goal(pool, config, user_id).await {
+            Ok(true) => updated += 1,
+            Ok(false) => {}
+            Err(e) => warn!(%user_id, "Failed to recompute hydration goal: {}", e),
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Recompute one user's auto-calculated goal, returning whether it changed
+async fn recompute_hydration_goal(
+    pool: &PgPool,
+    config: &HydrationConfig,
+    user_id: Uuid,
+) -> Result<bool, ApiError> {
+    let daily_goal_ml = HydrationService::calculate_personalized_goal(pool, config, user_id).await?;
+
+    HydrationGoalRepository::update_auto_calculated_goal(pool, user_id, daily_goal_ml)
+        .await
+        .map_err(ApiError::Internal)
+}
+
+/// Spawn the hydration goal job, recomputing goals every `period`
+pub fn spawn_hydration_goal_job(
+    pool: PgPool,
+    config: HydrationConfig,
+    period: Duration,
+    shutdown: watch::Receiver<bool>,
+) -> JoinHandle<()> {
+    tokio::spawn(run_periodically("hydration_goals", period, shutdown, move || {
+        let pool = pool.clone();
+        let config = config.clone();
+        async move {
+            match recompute_hydration_goals(&pool, &config).await {
+                Ok(updated) => info!(updated, "Recomputed hydration goals"),
+                Err(e) => warn!("Failed to recompute hydration goals: {}", e),
+            }
+        }
+    }))
+}
+
+/// Spawn the export worker, draining queued exports into `dir` every `period`
+///
+/// Each run also deletes finished exports older than `ttl`.
+pub fn spawn_export_worker(
+    pool: PgPool,
+    dir: PathBuf,
+    ttl: Duration,
+    period: Duration,
+    shutdown: watch::Receiver<bool>,
+) -> JoinHandle<()> {
+    tokio::spawn(run_periodically("export_worker", period, shutdown, move || {
+        let pool = pool.clone();
+        let dir = dir.clone();
+        async move {
+            loop {
+                match ExportJobService::process_next(&pool, &dir).await {
+                    Ok(Some(job_id)) => info!(job_id = %job_id, "Processed export job"),
+                    Ok(None) => break,
+                    Err(e) => {
+                        warn!("Failed to process export jobs: {}", e);
+                        break;
+                    }
+                }
+            }
+
+            match ExportJobService::expire_finished(&pool, ttl).await {
+                Ok(0) => {}
+                Ok(expired) => info!(expired, "Deleted expired exports"),
+                Err(e) => warn!("Failed to delete expired exports: {}", e),
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_periodic_job_runs_until_shutdown() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+        let counter = runs.clone();
+        let handle = tokio::spawn(run_periodically(
+            "test",
+            Duration::from_millis(10),
+            shutdown_rx,
+            move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async {}
+            },
+        ));
+
+        tokio::time::sleep(Duration::from_millis(35)).await;
+        shutdown_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("job did not stop on shutdown")
+            .unwrap();
+
+        let runs_at_shutdown = runs.load(Ordering::SeqCst);
+        assert!(runs_at_shutdown >= 2);
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), runs_at_shutdown);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shutdown_cancels_and_awaits_running_job() {
+        let manager = JobManager::new();
+        let finished = Arc::new(AtomicUsize::new(0));
+
+        let counter = finished.clone();
+        let mut shutdown = manager.subscribe();
+        manager.track(
+            "long_running",
+            tokio::spawn(async move {
+                // Works until told to stop, then cleans up briefly
+                let _ = shutdown.changed().await;
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+
+        let aborted = manager.shutdown(Duration::from_secs(10)).await;
+
+        assert_eq!(aborted, 0);
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shutdown_waits_for_task_spawned_through_clone() {
+        let manager = JobManager::new();
+        let finished = Arc::new(AtomicUsize::new(0));
+
+        // As a request handler would, through the copy in AppState
+        let counter = finished.clone();
+        manager.clone().spawn("one_off", async move {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let aborted = manager.shutdown(Duration::from_secs(10)).await;
+
+        assert_eq!(aborted, 0);
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shutdown_aborts_job_past_timeout() {
+        let manager = JobManager::new();
+        let started = Instant::now();
+        manager.track(
+            "stuck",
+            tokio::spawn(async {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+            }),
+        );
+
+        let aborted = manager.shutdown(Duration::from_secs(5)).await;
+
+        assert_eq!(aborted, 1);
+        assert_eq!(started.elapsed(), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_periodic_job_stops_when_sender_dropped() {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handle = tokio::spawn(run_periodically(
+            "test",
+            Duration::from_secs(3600),
+            shutdown_rx,
+            || async {},
+        ));
+
+        drop(shutdown_tx);
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("job did not stop when the sender was dropped")
+            .unwrap();
+    }
+}
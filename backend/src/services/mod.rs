@@ -3,27 +3,42 @@
 //! Services encapsulate business logic and coordinate between
 //! repositories and external systems.
 
+pub mod ai;
+pub mod audit;
 pub mod biometrics;
 pub mod biomarkers;
+pub mod cycle;
 pub mod data;
 pub mod exercise;
 pub mod export;
+pub mod export_jobs;
+pub mod feature_flags;
+pub mod formatting;
 pub mod goals;
 pub mod hydration;
+pub mod import;
 pub mod insights;
+pub mod jobs;
+pub mod notifications;
 pub mod nutrition;
 pub mod profile;
 pub mod sleep;
 pub mod user;
 pub mod weight;
 
+pub use ai::MealSuggestionService;
+pub use audit::AuditService;
 pub use biometrics::BiometricsService;
 pub use biomarkers::BiomarkersService;
+pub use cycle::CycleService;
 pub use data::DataService;
 pub use exercise::ExerciseService;
 pub use export::ExportService;
+pub use export_jobs::ExportJobService;
+pub use feature_flags::{FeatureFlag, FeatureFlags};
 pub use goals::GoalsService;
 pub use hydration::HydrationService;
+pub use import::ImportService;
 pub use insights::HealthInsightsService;
 pub use nutrition::NutritionService;
 pub use profile::ProfileService;
@@ -0,0 +1,290 @@
+//! User notifications
+//!
+//! Notifications are best-effort: delivery happens in the background, is
+//! retried with exponential backoff, and a final failure is only logged.
+
+use crate::services::jobs::JobManager;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Delivery attempts before a webhook notification is dropped
+const WEBHOOK_MAX_ATTEMPTS: u32 = 4;
+
+/// Delay before the first retry; doubled after each failed attempt
+const WEBHOOK_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Timeout for a single webhook request
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+/// A goal milestone that was just achieved
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MilestoneNotification {
+    pub user_id: Uuid,
+    pub goal_id: Uuid,
+    pub milestone: AchievedMilestone,
+}
+
+/// Milestone details included in a notification
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AchievedMilestone {
+    pub id: Uuid,
+    pub name: String,
+    pub percentage: i32,
+    pub target_value: f64,
+    pub achieved_value: f64,
+}
+
+/// A password reset token to send to an account's email address
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PasswordResetEmail {
+    pub email: String,
+    pub token: String,
+    pub expires_in_secs: i64,
+}
+
+/// Delivers notifications to users
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Notify that a goal milestone was achieved
+    async fn milestone_achieved(&self, notification: &MilestoneNotification) -> Result<()>;
+}
+
+/// Delivers account emails to users
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    /// Send a password reset token to the account's email address
+    async fn send_password_reset(&self, email: &PasswordResetEmail) -> Result<()>;
+}
+
+/// Notifier and mailer that POSTs messages as JSON to a webhook URL
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+    max_attempts: u32,
+    initial_backoff: Duration,
+}
+
+impl WebhookNotifier {
+    /// Create a notifier posting to `url`
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+            .build()?;
+
+        Ok(Self {
+            client,
+            url: url.into(),
+            max_attempts: WEBHOOK_MAX_ATTEMPTS,
+            initial_backoff: WEBHOOK_INITIAL_BACKOFF,
+        })
+    }
+
+    /// Override the retry policy
+    pub fn with_retry(mut self, max_attempts: u32, initial_backoff: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// POST `body`, retrying failed attempts with exponential backoff
+    async fn post_with_retry<T: Serialize + Sync>(&self, body: &T) -> Result<()> {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 1;
+
+        loop {
+            let result = self
+                .client
+                .post(&self.url)
+                .json(body)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+
+            match result {
+                Ok(_) => return Ok(()),
+                Err(e) if attempt >= self.max_attempts => {
+                    return Err(anyhow::anyhow!(
+                        "Webhook failed after {} attempts: {}",
+                        attempt,
+                        e
+                    ));
+                }
+                Err(e) => {
+                    warn!(attempt, url = %self.url, "Webhook delivery failed, retrying: {}", e);
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn milestone_achieved(&self, notification: &MilestoneNotification) -> Result<()> {
+        self.post_with_retry(notification).await
+    }
+}
+
+#[async_trait]
+impl Mailer for WebhookNotifier {
+    async fn send_password_reset(&self, email: &PasswordResetEmail) -> Result<()> {
+        self.post_with_retry(email).await
+    }
+}
+
+/// Send milestone notifications in the background
+///
+/// Returns immediately so the request that achieved the milestone isn't held
+/// up by retries; delivery failures are logged. The task is spawned through
+/// `jobs` so shutdown waits for it.
+pub fn notify_milestones(
+    jobs: &JobManager,
+    notifier: Arc<dyn Notifier>,
+    notifications: Vec<MilestoneNotification>,
+) {
+    if notifications.is_empty() {
+        return;
+    }
+
+    jobs.spawn("milestone_notifications", async move {
+        for notification in notifications {
+            if let Err(e) = notifier.milestone_achieved(&notification).await {
+                warn!(
+                    user_id = %notification.user_id,
+                    goal_id = %notification.goal_id,
+                    "Failed to send milestone notification: {}",
+                    e
+                );
+            }
+        }
+    });
+}
+
+/// Send a password reset email in the background
+///
+/// Returns immediately so answering a reset request takes the same time
+/// whether or not the email is registered. Failures are logged without the
+/// token.
+pub fn send_password_reset(jobs: &JobManager, mailer: Arc<dyn Mailer>, email: PasswordResetEmail) {
+    jobs.spawn("password_reset_email", async move {
+        if let Err(e) = mailer.send_password_reset(&email).await {
+            warn!("Failed to send password reset email: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn notification() -> MilestoneNotification {
+        MilestoneNotification {
+            user_id: Uuid::new_v4(),
+            goal_id: Uuid::new_v4(),
+            milestone: AchievedMilestone {
+                id: Uuid::new_v4(),
+                name: "25% Complete".to_string(),
+                percentage: 25,
+                target_value: 77.5,
+                achieved_value: 77.0,
+            },
+        }
+    }
+
+    fn notifier(server: &MockServer) -> WebhookNotifier {
+        WebhookNotifier::new(format!("{}/hooks/milestones", server.uri()))
+            .unwrap()
+            .with_retry(3, Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn test_webhook_posts_notification_body() {
+        let server = MockServer::start().await;
+        let notification = notification();
+
+        Mock::given(method("POST"))
+            .and(path("/hooks/milestones"))
+            .and(body_json(serde_json::json!({
+                "user_id": notification.user_id,
+                "goal_id": notification.goal_id,
+                "milestone": {
+                    "id": notification.milestone.id,
+                    "name": "25% Complete",
+                    "percentage": 25,
+                    "target_value": 77.5,
+                    "achieved_value": 77.0
+                }
+            })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        notifier(&server).milestone_achieved(&notification).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retries_until_success() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        notifier(&server).milestone_achieved(&notification()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_webhook_gives_up_after_max_attempts() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let result = notifier(&server).milestone_achieved(&notification()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_posts_password_reset_email() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(body_json(serde_json::json!({
+                "email": "user@example.com",
+                "token": "reset-token",
+                "expires_in_secs": 3600
+            })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let email = PasswordResetEmail {
+            email: "user@example.com".to_string(),
+            token: "reset-token".to_string(),
+            expires_in_secs: 3600,
+        };
+        notifier(&server).send_password_reset(&email).await.unwrap();
+    }
+}
@@ -1,37 +1,168 @@
 //! Nutrition service - business logic for food tracking
 
+use crate::cache::{self, CacheStore};
+use crate::db;
 use crate::error::ApiError;
 use crate::repositories::{
-    AddRecipeIngredient, CreateFoodItem, CreateFoodLog, CreateRecipe, DailyNutritionSummary,
-    FoodItem, FoodItemRepository, FoodLog, FoodLogRepository, Recipe, RecipeIngredient,
-    RecipeRepository,
+    AddRecipeIngredient, CreateFoodItem, CreateFoodLog, CreateMealTemplate, CreateRecipe,
+    DailyNutritionSummary, FavoriteFoodRepository, FoodItem, FoodItemRepository, FoodLog,
+    FoodLogRepository, MealTemplate, MealTemplateItem, MealTemplateRepository, Recipe,
+    RecipeIngredient, RecipeRepository, UpdateFoodItem,
 };
-use chrono::{DateTime, NaiveDate, Utc};
+use crate::services::ProfileService;
+use crate::timezone::local_date;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use fitness_assistant_shared::validation::{normalize_meal_type, ValidationErrors};
 use rust_decimal::Decimal;
 use sqlx::PgPool;
+use std::future::Future;
 use uuid::Uuid;
 
+/// How long food search results stay cached
+const FOOD_SEARCH_CACHE_TTL_SECS: u64 = 60;
+
+/// Prefix shared by all cached food search results
+pub const FOOD_SEARCH_CACHE_PREFIX: &str = "food_search:";
+
+/// Longest food name or brand the database stores
+const MAX_FOOD_NAME_LENGTH: usize = 255;
+
+/// Longest serving unit the database stores
+const MAX_SERVING_UNIT_LENGTH: usize = 50;
+
+/// Longest barcode the database stores
+const MAX_BARCODE_LENGTH: usize = 50;
+
+/// Largest per-serving amount that fits DECIMAL(10, 2), in hundredths
+const MAX_FOOD_AMOUNT_CENTS: i64 = 9_999_999_999;
+
+/// How far a food's calories may stray from its macros, as a percent of the expected calories
+const CALORIE_MISMATCH_TOLERANCE_PERCENT: i64 = 20;
+
+/// Minimum allowed calorie mismatch, so rounding on tiny servings isn't rejected
+const CALORIE_MISMATCH_MIN_KCAL: i64 = 10;
+
+/// Share of calories from protein on every day of a macro cycle
+const CYCLE_PROTEIN_SHARE: f64 = 0.30;
+
+/// Share of calories from fat on every day of a macro cycle
+const CYCLE_FAT_SHARE: f64 = 0.25;
+
+/// Days of the week in planning order
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
 /// Nutrition service
 pub struct NutritionService;
 
+/// Nutrition for some servings of a food item, computed without logging it
+#[derive(Debug, Clone, PartialEq)]
+pub struct NutritionPreview {
+    pub food_item_id: Uuid,
+    pub servings: Decimal,
+    pub calories: Decimal,
+    pub protein_g: Decimal,
+    pub carbohydrates_g: Decimal,
+    pub fat_g: Decimal,
+    pub fiber_g: Decimal,
+    pub sugar_g: Decimal,
+    pub sodium_mg: Decimal,
+    pub alcohol_g: Decimal,
+}
+
+impl NutritionPreview {
+    /// Scale a food item's per-serving nutrition, as logging it would
+    pub fn scaled(food: &FoodItem, servings: Decimal) -> Self {
+        Self {
+            food_item_id: food.id,
+            servings,
+            calories: food.calories * servings,
+            protein_g: food.protein_g * servings,
+            carbohydrates_g: food.carbohydrates_g * servings,
+            fat_g: food.fat_g * servings,
+            fiber_g: food.fiber_g * servings,
+            sugar_g: food.sugar_g * servings,
+            sodium_mg: food.sodium_mg.unwrap_or(Decimal::ZERO) * servings,
+            alcohol_g: food.alcohol_g * servings,
+        }
+    }
+}
+
+/// A custom food item as submitted by a user, per serving
+#[derive(Debug, Clone, Default)]
+pub struct CreateFoodItemInput {
+    pub name: String,
+    pub brand: Option<String>,
+    pub barcode: Option<String>,
+    pub serving_size: Decimal,
+    pub serving_unit: String,
+    pub calories: Decimal,
+    pub protein_g: Decimal,
+    pub carbohydrates_g: Decimal,
+    pub fat_g: Decimal,
+    pub fiber_g: Decimal,
+    pub sugar_g: Decimal,
+    pub sodium_mg: Option<Decimal>,
+    pub alcohol_g: Decimal,
+}
+
 impl NutritionService {
     /// Search for food items
+    ///
+    /// Results are cached by normalized query and limit for a short time.
+    /// Searches go straight to the database when no cache is available.
     pub async fn search_foods(
         db: &PgPool,
+        cache: Option<&dyn CacheStore>,
         query: &str,
-        limit: Option<i64>,
+        limit: i64,
     ) -> Result<Vec<FoodItem>, ApiError> {
-        let limit = limit.unwrap_or(20).min(100);
-        
+        Self::search_foods_with(cache, query, limit, |q, l| async move {
+            FoodItemRepository::search(db, &q, l).await
+        })
+        .await
+    }
+
+    /// Cached search over an arbitrary lookup, so the cache path can be tested
+    async fn search_foods_with<F, Fut>(
+        cache: Option<&dyn CacheStore>,
+        query: &str,
+        limit: i64,
+        search: F,
+    ) -> Result<Vec<FoodItem>, ApiError>
+    where
+        F: FnOnce(String, i64) -> Fut,
+        Fut: Future<Output = anyhow::Result<Vec<FoodItem>>>,
+    {
         if query.trim().is_empty() {
             return Err(ApiError::Validation("Search query cannot be empty".to_string()));
         }
 
-        let items = FoodItemRepository::search(db, query, limit)
-            .await
-            .map_err(ApiError::Internal)?;
+        let normalized = Self::normalize_search_query(query);
+        let key = format!("{}{}:{}", FOOD_SEARCH_CACHE_PREFIX, normalized, limit);
 
-        Ok(items)
+        cache::get_or_fetch(cache, &key, FOOD_SEARCH_CACHE_TTL_SECS, || {
+            search(normalized.clone(), limit)
+        })
+        .await
+        .map_err(ApiError::Internal)
+    }
+
+    /// Lowercase and collapse whitespace so equivalent queries share a cache entry
+    fn normalize_search_query(query: &str) -> String {
+        query
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
     }
 
     /// Look up food by barcode
@@ -47,57 +178,140 @@ impl NutritionService {
         Ok(item)
     }
 
+    /// Bulk import shared food items, e.g. when seeding the food database
+    ///
+    /// Every item is validated before anything is written; items whose
+    /// barcode repeats within the batch or already exists are skipped.
+    /// Returns the number of items inserted.
+    pub async fn import_food_items(
+        db: &PgPool,
+        cache: Option<&dyn CacheStore>,
+        items: Vec<CreateFoodItem>,
+    ) -> Result<u64, ApiError> {
+        let mut errors = ValidationErrors::new();
+        for (i, item) in items.iter().enumerate() {
+            validate_food_item(&mut errors, &format!("items[{}].", i), item);
+        }
+        errors.into_result()?;
+
+        let inserted = FoodItemRepository::create_many(db, items)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        if inserted > 0 {
+            cache::invalidate_prefix(cache, FOOD_SEARCH_CACHE_PREFIX).await;
+        }
+
+        Ok(inserted)
+    }
+
     /// Create a custom food item
     pub async fn create_food_item(
         db: &PgPool,
         user_id: Uuid,
-        name: String,
-        serving_size: Decimal,
-        serving_unit: String,
-        calories: Decimal,
-        protein_g: Decimal,
-        carbs_g: Decimal,
-        fat_g: Decimal,
-        fiber_g: Decimal,
-        sugar_g: Decimal,
-        brand: Option<String>,
-        barcode: Option<String>,
+        input: CreateFoodItemInput,
     ) -> Result<FoodItem, ApiError> {
-        // Validate inputs
-        if name.trim().is_empty() {
-            return Err(ApiError::Validation("Food name cannot be empty".to_string()));
-        }
-        if serving_size <= Decimal::ZERO {
-            return Err(ApiError::Validation("Serving size must be positive".to_string()));
-        }
-        if calories < Decimal::ZERO {
-            return Err(ApiError::Validation("Calories cannot be negative".to_string()));
-        }
-
-        let input = CreateFoodItem {
-            name,
-            brand,
-            barcode,
-            serving_size,
-            serving_unit,
-            calories,
-            protein_g,
-            carbohydrates_g: carbs_g,
-            fat_g,
-            fiber_g,
-            sugar_g,
-            sodium_mg: None,
+        let food = CreateFoodItem {
+            name: input.name,
+            brand: input.brand,
+            barcode: input.barcode,
+            serving_size: input.serving_size,
+            serving_unit: input.serving_unit,
+            calories: input.calories,
+            protein_g: input.protein_g,
+            carbohydrates_g: input.carbohydrates_g,
+            fat_g: input.fat_g,
+            fiber_g: input.fiber_g,
+            sugar_g: input.sugar_g,
+            sodium_mg: input.sodium_mg,
+            alcohol_g: input.alcohol_g,
             source: "user".to_string(),
             created_by: Some(user_id),
         };
 
-        let item = FoodItemRepository::create(db, input)
-            .await
-            .map_err(ApiError::Internal)?;
+        // Report every invalid field at once rather than one per submission
+        let mut errors = ValidationErrors::new();
+        validate_food_item(&mut errors, "", &food);
+        errors.into_result()?;
+
+        // The unique index catches duplicates even when two requests race
+        let item = FoodItemRepository::create(db, food).await.map_err(|err| {
+            if db::is_unique_violation(&err, "idx_food_items_barcode") {
+                ApiError::Conflict("A food with this barcode already exists".to_string())
+            } else {
+                ApiError::Internal(err)
+            }
+        })?;
 
         Ok(item)
     }
 
+    /// Edit a food item the user created
+    ///
+    /// Logged foods store the nutrition computed at the time, so edits only
+    /// affect future logs.
+    pub async fn update_food_item(
+        db: &PgPool,
+        cache: Option<&dyn CacheStore>,
+        user_id: Uuid,
+        food_item_id: Uuid,
+        changes: UpdateFoodItem,
+    ) -> Result<FoodItem, ApiError> {
+        let item = FoodItemRepository::find_by_id(db, food_item_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Food item not found".to_string()))?;
+        Self::ensure_food_editable(&item, user_id)?;
+
+        // Unchanged fields keep their current values, so check the food as it will be saved
+        let edited = CreateFoodItem {
+            name: changes.name.clone().unwrap_or(item.name),
+            brand: changes.brand.clone().or(item.brand),
+            barcode: item.barcode,
+            serving_size: changes.serving_size.unwrap_or(item.serving_size),
+            serving_unit: changes.serving_unit.clone().unwrap_or(item.serving_unit),
+            calories: changes.calories.unwrap_or(item.calories),
+            protein_g: changes.protein_g.unwrap_or(item.protein_g),
+            carbohydrates_g: changes.carbohydrates_g.unwrap_or(item.carbohydrates_g),
+            fat_g: changes.fat_g.unwrap_or(item.fat_g),
+            fiber_g: changes.fiber_g.unwrap_or(item.fiber_g),
+            sugar_g: changes.sugar_g.unwrap_or(item.sugar_g),
+            sodium_mg: changes.sodium_mg.or(item.sodium_mg),
+            alcohol_g: changes.alcohol_g.unwrap_or(item.alcohol_g),
+            source: item.source,
+            created_by: item.created_by,
+        };
+        let mut errors = ValidationErrors::new();
+        validate_food_item(&mut errors, "", &edited);
+        errors.into_result()?;
+
+        let updated = FoodItemRepository::update(db, food_item_id, changes)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Food item not found".to_string()))?;
+
+        // Cached searches would keep serving the old name and nutrition
+        cache::invalidate_prefix(cache, FOOD_SEARCH_CACHE_PREFIX).await;
+
+        Ok(updated)
+    }
+
+    /// Only the creator may edit a user-submitted food; database-sourced foods are read-only
+    fn ensure_food_editable(item: &FoodItem, user_id: Uuid) -> Result<(), ApiError> {
+        if item.source != "user" {
+            return Err(ApiError::Forbidden(format!(
+                "Foods from {} are read-only",
+                item.source
+            )));
+        }
+        if item.created_by != Some(user_id) {
+            return Err(ApiError::Forbidden(
+                "Only the creator can edit this food".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Log a food entry
     pub async fn log_food(
         db: &PgPool,
@@ -109,33 +323,19 @@ impl NutritionService {
         consumed_at: Option<DateTime<Utc>>,
         notes: Option<String>,
     ) -> Result<FoodLog, ApiError> {
-        // Validate meal type
-        let valid_meal_types = ["breakfast", "lunch", "dinner", "snack"];
-        if !valid_meal_types.contains(&meal_type.to_lowercase().as_str()) {
-            return Err(ApiError::Validation(format!(
-                "Invalid meal type. Must be one of: {}",
-                valid_meal_types.join(", ")
-            )));
-        }
+        let allowed_meal_types = ProfileService::get_meal_types(db, user_id).await?;
+        let meal_type = Self::validate_meal_type(&meal_type, &allowed_meal_types)?;
 
         if servings <= Decimal::ZERO {
             return Err(ApiError::Validation("Servings must be positive".to_string()));
         }
 
-        // Get nutritional values
-        let (calories, protein_g, carbs_g, fat_g, fiber_g) = if let Some(item_id) = food_item_id {
-            let item = FoodItemRepository::find_by_id(db, item_id)
+        // Look up the food item to take nutrition from
+        let item = if let Some(item_id) = food_item_id {
+            FoodItemRepository::find_by_id(db, item_id)
                 .await
                 .map_err(ApiError::Internal)?
-                .ok_or_else(|| ApiError::NotFound("Food item not found".to_string()))?;
-
-            (
-                item.calories * servings,
-                item.protein_g * servings,
-                item.carbohydrates_g * servings,
-                item.fat_g * servings,
-                item.fiber_g * servings,
-            )
+                .ok_or_else(|| ApiError::NotFound("Food item not found".to_string()))?
         } else if custom_name.is_some() {
             // Custom entry - calories must be provided separately
             return Err(ApiError::Validation(
@@ -148,18 +348,15 @@ impl NutritionService {
         };
 
         let input = CreateFoodLog {
-            user_id,
-            food_item_id,
             custom_name,
-            servings,
-            calories,
-            protein_g,
-            carbohydrates_g: carbs_g,
-            fat_g,
-            fiber_g,
-            meal_type: meal_type.to_lowercase(),
-            consumed_at: consumed_at.unwrap_or_else(Utc::now),
             notes,
+            ..scaled_food_log(
+                user_id,
+                &item,
+                servings,
+                &meal_type,
+                consumed_at.unwrap_or_else(Utc::now),
+            )
         };
 
         let log = FoodLogRepository::create(db, input)
@@ -169,27 +366,59 @@ impl NutritionService {
         Ok(log)
     }
 
+    /// Preview the nutrition of some servings of a food item without logging it
+    pub async fn preview_nutrition(
+        db: &PgPool,
+        food_item_id: Uuid,
+        servings: Decimal,
+    ) -> Result<NutritionPreview, ApiError> {
+        if servings <= Decimal::ZERO {
+            return Err(ApiError::Validation("Servings must be positive".to_string()));
+        }
 
-    /// Get daily nutrition summary
+        let item = FoodItemRepository::find_by_id(db, food_item_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Food item not found".to_string()))?;
+
+        Ok(NutritionPreview::scaled(&item, servings))
+    }
+
+    /// Check a meal type is one the user has configured, returning it normalized
+    fn validate_meal_type(meal_type: &str, allowed: &[String]) -> Result<String, ApiError> {
+        let meal_type = normalize_meal_type(meal_type);
+        if !allowed.contains(&meal_type) {
+            return Err(ApiError::Validation(format!(
+                "Invalid meal type. Must be one of: {}",
+                allowed.join(", ")
+            )));
+        }
+
+        Ok(meal_type)
+    }
+
+    /// Get daily nutrition summary for a date in the user's timezone
     pub async fn get_daily_summary(
         db: &PgPool,
         user_id: Uuid,
         date: NaiveDate,
+        tz: Tz,
     ) -> Result<DailyNutritionSummary, ApiError> {
-        let summary = FoodLogRepository::get_daily_summary(db, user_id, date)
+        let summary = FoodLogRepository::get_daily_summary(db, user_id, date, tz)
             .await
             .map_err(ApiError::Internal)?;
 
         Ok(summary)
     }
 
-    /// Get food logs for a specific date
+    /// Get food logs for a date in the user's timezone
     pub async fn get_logs_by_date(
         db: &PgPool,
         user_id: Uuid,
         date: NaiveDate,
+        tz: Tz,
     ) -> Result<Vec<FoodLog>, ApiError> {
-        let logs = FoodLogRepository::get_by_date(db, user_id, date)
+        let logs = FoodLogRepository::get_by_date(db, user_id, date, tz)
             .await
             .map_err(ApiError::Internal)?;
 
@@ -213,6 +442,223 @@ impl NutritionService {
         Ok(())
     }
 
+    /// Copy all of a local day's food logs onto another date
+    ///
+    /// Each copy keeps the original local time of day. Returns the number
+    /// of entries created.
+    pub async fn copy_day(
+        db: &PgPool,
+        user_id: Uuid,
+        from_date: NaiveDate,
+        to_date: NaiveDate,
+        tz: Tz,
+    ) -> Result<usize, ApiError> {
+        if from_date == to_date {
+            return Err(ApiError::Validation(
+                "Cannot copy a day onto itself".to_string(),
+            ));
+        }
+
+        let logs = FoodLogRepository::get_by_date(db, user_id, from_date, tz)
+            .await
+            .map_err(ApiError::Internal)?;
+        if logs.is_empty() {
+            return Ok(0);
+        }
+
+        let inputs: Vec<CreateFoodLog> = logs
+            .iter()
+            .map(|log| copy_log_to_date(log, to_date, tz))
+            .collect();
+
+        let created = FoodLogRepository::create_many(db, inputs)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(created.len())
+    }
+
+    // ==================== Quick-Add Methods ====================
+
+    /// Get foods the user logged most recently, one entry per food
+    pub async fn get_recent_foods(
+        db: &PgPool,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<FoodItem>, ApiError> {
+        let items = FoodLogRepository::get_recent_foods(db, user_id, limit)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(items)
+    }
+
+    /// Get the user's favorite foods
+    pub async fn get_favorite_foods(
+        db: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<FoodItem>, ApiError> {
+        let items = FavoriteFoodRepository::get_by_user(db, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(items)
+    }
+
+    /// Flip a food's favorite status, returning whether it is now a favorite
+    pub async fn toggle_favorite(
+        db: &PgPool,
+        user_id: Uuid,
+        food_item_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        FoodItemRepository::find_by_id(db, food_item_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Food item not found".to_string()))?;
+
+        let removed = FavoriteFoodRepository::remove(db, user_id, food_item_id)
+            .await
+            .map_err(ApiError::Internal)?;
+        if removed {
+            return Ok(false);
+        }
+
+        // A concurrent toggle may have added it first; it is a favorite either way
+        FavoriteFoodRepository::add(db, user_id, food_item_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(true)
+    }
+
+    // ==================== Meal Template Methods ====================
+
+    /// Save a named set of foods and servings for quick logging
+    pub async fn save_meal_template(
+        db: &PgPool,
+        user_id: Uuid,
+        name: String,
+        items: Vec<(Uuid, Decimal)>,
+    ) -> Result<MealTemplate, ApiError> {
+        let mut errors = ValidationErrors::new();
+        if name.trim().is_empty() {
+            errors.add("name", "cannot be empty");
+        }
+        if items.is_empty() {
+            errors.add("items", "must contain at least one food");
+        }
+        if items.iter().any(|(_, servings)| *servings <= Decimal::ZERO) {
+            errors.add("servings", "must be positive");
+        }
+        errors.into_result()?;
+
+        for (food_item_id, _) in &items {
+            FoodItemRepository::find_by_id(db, *food_item_id)
+                .await
+                .map_err(ApiError::Internal)?
+                .ok_or_else(|| ApiError::NotFound(format!("Food item {} not found", food_item_id)))?;
+        }
+
+        let input = CreateMealTemplate {
+            user_id,
+            name,
+            items,
+        };
+
+        let template = MealTemplateRepository::create(db, input)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(template)
+    }
+
+    /// Get all meal templates for a user
+    pub async fn get_meal_templates(
+        db: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<MealTemplate>, ApiError> {
+        let templates = MealTemplateRepository::get_by_user(db, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(templates)
+    }
+
+    /// Get the items of a meal template the user owns
+    pub async fn get_meal_template_items(
+        db: &PgPool,
+        user_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<Vec<MealTemplateItem>, ApiError> {
+        MealTemplateRepository::find_by_id_and_user(db, template_id, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Meal template not found".to_string()))?;
+
+        let items = MealTemplateRepository::get_items(db, template_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(items)
+    }
+
+    /// Log every food in a template as one meal
+    ///
+    /// All food logs are written in a single transaction, so the meal is
+    /// either logged in full or not at all.
+    pub async fn log_meal_template(
+        db: &PgPool,
+        user_id: Uuid,
+        template_id: Uuid,
+        meal_type: String,
+        consumed_at: Option<DateTime<Utc>>,
+    ) -> Result<Vec<FoodLog>, ApiError> {
+        let allowed_meal_types = ProfileService::get_meal_types(db, user_id).await?;
+        let meal_type = Self::validate_meal_type(&meal_type, &allowed_meal_types)?;
+        let items = Self::get_meal_template_items(db, user_id, template_id).await?;
+
+        let mut foods = Vec::with_capacity(items.len());
+        for item in items {
+            let food = FoodItemRepository::find_by_id(db, item.food_item_id)
+                .await
+                .map_err(ApiError::Internal)?
+                .ok_or_else(|| {
+                    ApiError::NotFound(format!("Food item {} not found", item.food_item_id))
+                })?;
+            foods.push((food, item.servings));
+        }
+
+        let inputs = expand_meal_template(
+            user_id,
+            &foods,
+            &meal_type,
+            consumed_at.unwrap_or_else(Utc::now),
+        );
+
+        let logs = FoodLogRepository::create_many(db, inputs)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        Ok(logs)
+    }
+
+    /// Delete a meal template
+    pub async fn delete_meal_template(
+        db: &PgPool,
+        user_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let deleted = MealTemplateRepository::delete(db, user_id, template_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        if !deleted {
+            return Err(ApiError::NotFound("Meal template not found".to_string()));
+        }
+
+        Ok(())
+    }
+
     // ==================== Recipe Methods ====================
 
     /// Create a new recipe
@@ -374,20 +820,319 @@ impl NutritionService {
     }
 }
 
+/// Nutrient totals for a day's food logs
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NutritionTotals {
+    pub calories: Decimal,
+    pub protein_g: Decimal,
+    pub carbohydrates_g: Decimal,
+    pub fat_g: Decimal,
+    pub fiber_g: Decimal,
+    pub sugar_g: Decimal,
+    pub sodium_mg: Decimal,
+}
+
 /// Aggregates daily nutrition totals from a list of food logs
-pub fn aggregate_daily_nutrition(logs: &[FoodLog]) -> (Decimal, Decimal, Decimal, Decimal, Decimal) {
-    logs.iter().fold(
-        (Decimal::ZERO, Decimal::ZERO, Decimal::ZERO, Decimal::ZERO, Decimal::ZERO),
-        |(cal, pro, carb, fat, fib), log| {
-            (
-                cal + log.calories,
-                pro + log.protein_g,
-                carb + log.carbohydrates_g,
-                fat + log.fat_g,
-                fib + log.fiber_g,
-            )
-        },
-    )
+pub fn aggregate_daily_nutrition(logs: &[FoodLog]) -> NutritionTotals {
+    logs.iter().fold(NutritionTotals::default(), |totals, log| NutritionTotals {
+        calories: totals.calories + log.calories,
+        protein_g: totals.protein_g + log.protein_g,
+        carbohydrates_g: totals.carbohydrates_g + log.carbohydrates_g,
+        fat_g: totals.fat_g + log.fat_g,
+        fiber_g: totals.fiber_g + log.fiber_g,
+        sugar_g: totals.sugar_g + log.sugar_g,
+        sodium_mg: totals.sodium_mg + log.sodium_mg,
+    })
+}
+
+/// Checks a food item fits the database and its calories add up
+///
+/// Problems are recorded against each field name with `prefix` prepended,
+/// so a batch can report which item they belong to.
+fn validate_food_item(errors: &mut ValidationErrors, prefix: &str, item: &CreateFoodItem) {
+    let field = |name: &str| format!("{}{}", prefix, name);
+    // Amounts are stored as DECIMAL(10, 2)
+    let max_amount = Decimal::new(MAX_FOOD_AMOUNT_CENTS, 2);
+
+    let name = item.name.trim();
+    if name.is_empty() {
+        errors.add(&field("name"), "cannot be empty");
+    } else if name.chars().count() > MAX_FOOD_NAME_LENGTH {
+        errors.add(
+            &field("name"),
+            &format!("cannot exceed {} characters", MAX_FOOD_NAME_LENGTH),
+        );
+    }
+    if matches!(&item.brand, Some(brand) if brand.chars().count() > MAX_FOOD_NAME_LENGTH) {
+        errors.add(
+            &field("brand"),
+            &format!("cannot exceed {} characters", MAX_FOOD_NAME_LENGTH),
+        );
+    }
+    if matches!(&item.barcode, Some(barcode) if barcode.chars().count() > MAX_BARCODE_LENGTH) {
+        errors.add(
+            &field("barcode"),
+            &format!("cannot exceed {} characters", MAX_BARCODE_LENGTH),
+        );
+    }
+    if item.serving_unit.chars().count() > MAX_SERVING_UNIT_LENGTH {
+        errors.add(
+            &field("serving_unit"),
+            &format!("cannot exceed {} characters", MAX_SERVING_UNIT_LENGTH),
+        );
+    }
+
+    if item.serving_size <= Decimal::ZERO {
+        errors.add(&field("serving_size"), "must be positive");
+    } else if item.serving_size > max_amount {
+        errors.add(&field("serving_size"), &format!("cannot exceed {}", max_amount));
+    }
+    let amounts = [
+        ("calories", Some(item.calories)),
+        ("protein_g", Some(item.protein_g)),
+        ("carbohydrates_g", Some(item.carbohydrates_g)),
+        ("fat_g", Some(item.fat_g)),
+        ("fiber_g", Some(item.fiber_g)),
+        ("sugar_g", Some(item.sugar_g)),
+        ("sodium_mg", item.sodium_mg),
+        ("alcohol_g", Some(item.alcohol_g)),
+    ];
+    for (name, value) in amounts {
+        let Some(value) = value else { continue };
+        if value < Decimal::ZERO {
+            errors.add(&field(name), "cannot be negative");
+        } else if value > max_amount {
+            errors.add(&field(name), &format!("cannot exceed {}", max_amount));
+        }
+    }
+
+    if let Err(expected) = check_calories_match_macros(
+        item.calories,
+        item.protein_g,
+        item.carbohydrates_g,
+        item.fat_g,
+        item.alcohol_g,
+    ) {
+        errors.add(&field("calories"), &calorie_mismatch_message(expected));
+    }
+}
+
+/// Validation message for calories that don't reconcile with the macros
+fn calorie_mismatch_message(expected: Decimal) -> String {
+    format!("don't match the macros (expected about {} kcal)", expected.round())
+}
+
+/// Calories implied by macros using Atwater factors
+///
+/// 4 kcal/g for protein and carbs, 9 for fat and 7 for alcohol. Useful for
+/// checking a label's calories reconcile with its macros.
+pub fn expected_calories(
+    protein_g: Decimal,
+    carbs_g: Decimal,
+    fat_g: Decimal,
+    alcohol_g: Decimal,
+) -> Decimal {
+    Decimal::from(4) * protein_g
+        + Decimal::from(4) * carbs_g
+        + Decimal::from(9) * fat_g
+        + Decimal::from(7) * alcohol_g
+}
+
+/// Checks a food's calories reconcile with its macros
+///
+/// Labels round and count fiber differently, so calories within 20% of the
+/// Atwater estimate are accepted. Foods with no macros can't be checked.
+/// Returns the expected calories on a mismatch.
+pub fn check_calories_match_macros(
+    calories: Decimal,
+    protein_g: Decimal,
+    carbs_g: Decimal,
+    fat_g: Decimal,
+    alcohol_g: Decimal,
+) -> Result<(), Decimal> {
+    let expected = expected_calories(protein_g, carbs_g, fat_g, alcohol_g);
+    if expected.is_zero() {
+        return Ok(());
+    }
+
+    let tolerance = (expected * Decimal::new(CALORIE_MISMATCH_TOLERANCE_PERCENT, 2))
+        .max(Decimal::from(CALORIE_MISMATCH_MIN_KCAL));
+    if (calories - expected).abs() > tolerance {
+        return Err(expected);
+    }
+    Ok(())
+}
+
+/// Targets for one day of a macro cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyMacroPlan {
+    pub weekday: Weekday,
+    pub training_day: bool,
+    pub calories: f64,
+    pub protein_g: f64,
+    pub carbohydrates_g: f64,
+    pub fat_g: f64,
+}
+
+/// Plans a week of daily targets that average out to a weekly calorie target
+///
+/// Protein and fat stay the same every day. Training days get `carb_skew`
+/// more carbs than an even split (0.3 is 30% more) and rest days give up the
+/// same carbs between them, so the week still totals the target. Calories
+/// are rounded to whole kcal and grams to one decimal place.
+pub fn plan_macro_cycle(
+    weekly_calorie_target: f64,
+    training_days: Vec<Weekday>,
+    carb_skew: f64,
+) -> Result<Vec<DailyMacroPlan>, ApiError> {
+    if !weekly_calorie_target.is_finite() || weekly_calorie_target <= 0.0 {
+        return Err(ApiError::Validation(
+            "Weekly calorie target must be positive".to_string(),
+        ));
+    }
+    if !carb_skew.is_finite() || carb_skew < 0.0 {
+        return Err(ApiError::Validation(
+            "Carb skew cannot be negative".to_string(),
+        ));
+    }
+
+    let average_calories = weekly_calorie_target / 7.0;
+    let protein_calories = average_calories * CYCLE_PROTEIN_SHARE;
+    let fat_calories = average_calories * CYCLE_FAT_SHARE;
+    let average_carb_calories = average_calories - protein_calories - fat_calories;
+
+    let is_training = |day: &Weekday| training_days.contains(day);
+    let training_count = WEEKDAYS.iter().filter(|day| is_training(day)).count() as f64;
+    let rest_count = 7.0 - training_count;
+
+    // With no rest days (or no training days) there's nothing to shift carbs between
+    let nothing_to_shift = training_count == 0.0 || rest_count == 0.0;
+    let (training_carb_calories, rest_carb_calories) = if nothing_to_shift {
+        (average_carb_calories, average_carb_calories)
+    } else {
+        let training = average_carb_calories * (1.0 + carb_skew);
+        let rest = (average_carb_calories * 7.0 - training * training_count) / rest_count;
+        (training, rest)
+    };
+    if rest_carb_calories < 0.0 {
+        return Err(ApiError::Validation(
+            "Carb skew leaves no carbs for rest days".to_string(),
+        ));
+    }
+
+    let plan: Vec<DailyMacroPlan> = WEEKDAYS
+        .iter()
+        .map(|&weekday| {
+            let training_day = is_training(&weekday);
+            let carb_calories = if training_day {
+                training_carb_calories
+            } else {
+                rest_carb_calories
+            };
+            DailyMacroPlan {
+                weekday,
+                training_day,
+                calories: (protein_calories + fat_calories + carb_calories).round(),
+                protein_g: round_tenth(protein_calories / 4.0),
+                carbohydrates_g: round_tenth(carb_calories / 4.0),
+                fat_g: round_tenth(fat_calories / 9.0),
+            }
+        })
+        .collect();
+
+    // Each day may round by up to half a kcal
+    let planned: f64 = plan.iter().map(|day| day.calories).sum();
+    if (planned - weekly_calorie_target).abs() > 3.5 {
+        return Err(ApiError::Internal(anyhow::anyhow!(
+            "Macro cycle totals {} kcal for a {} kcal target",
+            planned,
+            weekly_calorie_target
+        )));
+    }
+
+    Ok(plan)
+}
+
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Builds one food log per template food, scaled by its servings
+pub fn expand_meal_template(
+    user_id: Uuid,
+    foods: &[(FoodItem, Decimal)],
+    meal_type: &str,
+    consumed_at: DateTime<Utc>,
+) -> Vec<CreateFoodLog> {
+    foods
+        .iter()
+        .map(|(food, servings)| scaled_food_log(user_id, food, *servings, meal_type, consumed_at))
+        .collect()
+}
+
+/// Builds a food log for some servings of a food item
+///
+/// Nutrition is snapshotted at log time so later edits to the food item
+/// don't rewrite history. Missing sodium counts as zero.
+fn scaled_food_log(
+    user_id: Uuid,
+    food: &FoodItem,
+    servings: Decimal,
+    meal_type: &str,
+    consumed_at: DateTime<Utc>,
+) -> CreateFoodLog {
+    let scaled = NutritionPreview::scaled(food, servings);
+    CreateFoodLog {
+        user_id,
+        food_item_id: Some(food.id),
+        custom_name: None,
+        servings,
+        calories: scaled.calories,
+        protein_g: scaled.protein_g,
+        carbohydrates_g: scaled.carbohydrates_g,
+        fat_g: scaled.fat_g,
+        fiber_g: scaled.fiber_g,
+        sugar_g: scaled.sugar_g,
+        sodium_mg: scaled.sodium_mg,
+        alcohol_g: scaled.alcohol_g,
+        meal_type: meal_type.to_string(),
+        consumed_at,
+        notes: None,
+    }
+}
+
+/// Builds a copy of a food log moved to another local date
+///
+/// The local time of day is kept. If that time does not exist on the target
+/// date (a DST gap), the log is shifted by whole days instead.
+pub fn copy_log_to_date(log: &FoodLog, to_date: NaiveDate, tz: Tz) -> CreateFoodLog {
+    let local_time = log.consumed_at.with_timezone(&tz).time();
+    let consumed_at = tz
+        .from_local_datetime(&to_date.and_time(local_time))
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| {
+            let days = (to_date - local_date(log.consumed_at, tz)).num_days();
+            log.consumed_at + chrono::Duration::days(days)
+        });
+
+    CreateFoodLog {
+        user_id: log.user_id,
+        food_item_id: log.food_item_id,
+        custom_name: log.custom_name.clone(),
+        servings: log.servings,
+        calories: log.calories,
+        protein_g: log.protein_g,
+        carbohydrates_g: log.carbohydrates_g,
+        fat_g: log.fat_g,
+        fiber_g: log.fiber_g,
+        sugar_g: log.sugar_g,
+        sodium_mg: log.sodium_mg,
+        alcohol_g: log.alcohol_g,
+        meal_type: log.meal_type.clone(),
+        consumed_at,
+        notes: log.notes.clone(),
+    }
 }
 
 /// Ingredient with its nutritional information for recipe calculation
@@ -449,7 +1194,7 @@ pub fn calculate_recipe_nutrition(
         total_calories: total_cal,
         total_protein: total_pro,
         total_carbs: total_carb,
-        total_fat: total_fat,
+        total_fat,
         total_fiber: total_fib,
         calories_per_serving: total_cal / servings,
         protein_per_serving: total_pro / servings,
@@ -462,16 +1207,19 @@ pub fn calculate_recipe_nutrition(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::memory::MemoryCache;
+    use crate::repositories::net_carbs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[test]
     fn test_aggregate_daily_nutrition_empty() {
         let logs: Vec<FoodLog> = vec![];
-        let (cal, pro, carb, fat, fib) = aggregate_daily_nutrition(&logs);
-        assert_eq!(cal, Decimal::ZERO);
-        assert_eq!(pro, Decimal::ZERO);
-        assert_eq!(carb, Decimal::ZERO);
-        assert_eq!(fat, Decimal::ZERO);
-        assert_eq!(fib, Decimal::ZERO);
+        let totals = aggregate_daily_nutrition(&logs);
+        assert_eq!(totals.calories, Decimal::ZERO);
+        assert_eq!(totals.protein_g, Decimal::ZERO);
+        assert_eq!(totals.carbohydrates_g, Decimal::ZERO);
+        assert_eq!(totals.fat_g, Decimal::ZERO);
+        assert_eq!(totals.fiber_g, Decimal::ZERO);
     }
 
     #[test]
@@ -483,12 +1231,12 @@ mod tests {
             Decimal::new(20, 0),   // 20g fat
             Decimal::new(5, 0),    // 5g fiber
         )];
-        let (cal, pro, carb, fat, fib) = aggregate_daily_nutrition(&logs);
-        assert_eq!(cal, Decimal::new(500, 0));
-        assert_eq!(pro, Decimal::new(30, 0));
-        assert_eq!(carb, Decimal::new(50, 0));
-        assert_eq!(fat, Decimal::new(20, 0));
-        assert_eq!(fib, Decimal::new(5, 0));
+        let totals = aggregate_daily_nutrition(&logs);
+        assert_eq!(totals.calories, Decimal::new(500, 0));
+        assert_eq!(totals.protein_g, Decimal::new(30, 0));
+        assert_eq!(totals.carbohydrates_g, Decimal::new(50, 0));
+        assert_eq!(totals.fat_g, Decimal::new(20, 0));
+        assert_eq!(totals.fiber_g, Decimal::new(5, 0));
     }
 
     #[test]
@@ -509,12 +1257,559 @@ mod tests {
                 Decimal::new(7, 0),
             ),
         ];
-        let (cal, pro, carb, fat, fib) = aggregate_daily_nutrition(&logs);
-        assert_eq!(cal, Decimal::new(750, 0));
-        assert_eq!(pro, Decimal::new(55, 0));
-        assert_eq!(carb, Decimal::new(70, 0));
-        assert_eq!(fat, Decimal::new(25, 0));
-        assert_eq!(fib, Decimal::new(10, 0));
+        let totals = aggregate_daily_nutrition(&logs);
+        assert_eq!(totals.calories, Decimal::new(750, 0));
+        assert_eq!(totals.protein_g, Decimal::new(55, 0));
+        assert_eq!(totals.carbohydrates_g, Decimal::new(70, 0));
+        assert_eq!(totals.fat_g, Decimal::new(25, 0));
+        assert_eq!(totals.fiber_g, Decimal::new(10, 0));
+    }
+
+    #[test]
+    fn test_aggregate_daily_nutrition_sums_sugar_and_sodium() {
+        let logs: Vec<FoodLog> = [(12, 480), (0, 1150), (25, 95)]
+            .into_iter()
+            .map(|(sugar, sodium)| FoodLog {
+                sugar_g: Decimal::new(sugar, 0),
+                sodium_mg: Decimal::new(sodium, 0),
+                ..create_test_food_log(
+                    Decimal::new(200, 0),
+                    Decimal::ZERO,
+                    Decimal::ZERO,
+                    Decimal::ZERO,
+                    Decimal::ZERO,
+                )
+            })
+            .collect();
+
+        let totals = aggregate_daily_nutrition(&logs);
+
+        assert_eq!(totals.sugar_g, Decimal::new(37, 0));
+        assert_eq!(totals.sodium_mg, Decimal::new(1725, 0));
+    }
+
+    #[test]
+    fn test_net_carbs_subtracts_fiber() {
+        assert_eq!(
+            net_carbs(Decimal::new(30, 0), Decimal::new(10, 0)),
+            Decimal::new(20, 0)
+        );
+    }
+
+    #[test]
+    fn test_net_carbs_floors_at_zero_when_fiber_exceeds_carbs() {
+        assert_eq!(
+            net_carbs(Decimal::new(4, 0), Decimal::new(9, 0)),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_food_log_net_carbs() {
+        let log = create_test_food_log(
+            Decimal::new(180, 0),
+            Decimal::new(5, 0),
+            Decimal::new(30, 0),
+            Decimal::new(6, 0),
+            Decimal::new(10, 0),
+        );
+        assert_eq!(log.net_carbs_g(), Decimal::new(20, 0));
+    }
+
+    #[test]
+    fn test_expected_calories_counts_alcohol_at_seven_per_gram() {
+        // A regular beer: 1.6g protein, 13g carbs, 14g alcohol
+        let calories = expected_calories(
+            Decimal::new(16, 1),
+            Decimal::new(13, 0),
+            Decimal::ZERO,
+            Decimal::new(14, 0),
+        );
+        assert_eq!(calories, Decimal::new(1564, 1));
+    }
+
+    #[test]
+    fn test_expected_calories_without_alcohol() {
+        let calories = expected_calories(
+            Decimal::new(10, 0),
+            Decimal::new(20, 0),
+            Decimal::new(5, 0),
+            Decimal::ZERO,
+        );
+        assert_eq!(calories, Decimal::new(165, 0));
+    }
+
+    #[test]
+    fn test_calories_within_tolerance_of_macros_match() {
+        // A 150 kcal beer with 13g carbs and 14g alcohol estimates 150 kcal
+        let result = check_calories_match_macros(
+            Decimal::new(150, 0),
+            Decimal::ZERO,
+            Decimal::new(13, 0),
+            Decimal::ZERO,
+            Decimal::new(14, 0),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_calories_ignoring_alcohol_are_rejected() {
+        // Only the carbs were counted, leaving out 98 kcal of alcohol
+        let result = check_calories_match_macros(
+            Decimal::new(52, 0),
+            Decimal::ZERO,
+            Decimal::new(13, 0),
+            Decimal::ZERO,
+            Decimal::new(14, 0),
+        );
+        assert_eq!(result, Err(Decimal::new(150, 0)));
+    }
+
+    fn valid_food_item() -> CreateFoodItem {
+        CreateFoodItem {
+            name: "Rolled Oats".to_string(),
+            brand: None,
+            barcode: None,
+            serving_size: Decimal::new(40, 0),
+            serving_unit: "g".to_string(),
+            calories: Decimal::new(150, 0),
+            protein_g: Decimal::new(5, 0),
+            carbohydrates_g: Decimal::new(27, 0),
+            fat_g: Decimal::new(3, 0),
+            fiber_g: Decimal::new(4, 0),
+            sugar_g: Decimal::ONE,
+            sodium_mg: None,
+            alcohol_g: Decimal::ZERO,
+            source: "import".to_string(),
+            created_by: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_food_item_accepts_valid_item() {
+        let mut errors = ValidationErrors::new();
+        validate_food_item(&mut errors, "", &valid_food_item());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_validate_food_item_rejects_values_the_database_cannot_store() {
+        let item = CreateFoodItem {
+            name: "x".repeat(MAX_FOOD_NAME_LENGTH + 1),
+            sugar_g: Decimal::new(100_000_000, 0),
+            sodium_mg: Some(Decimal::new(-5, 0)),
+            ..valid_food_item()
+        };
+
+        let mut errors = ValidationErrors::new();
+        validate_food_item(&mut errors, "items[3].", &item);
+
+        let Err(errors) = errors.into_result() else {
+            panic!("invalid item was accepted");
+        };
+        assert!(errors.get("items[3].name").is_some());
+        assert!(errors.get("items[3].sugar_g").is_some());
+        assert_eq!(
+            errors.get("items[3].sodium_mg").unwrap(),
+            ["cannot be negative".to_string()]
+        );
+        assert!(errors.get("items[3].calories").is_none());
+    }
+
+    #[test]
+    fn test_calories_without_macros_are_not_checked() {
+        let result = check_calories_match_macros(
+            Decimal::new(250, 0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Decimal::ZERO,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_preview_half_serving_scales_calories_and_macros() {
+        let food = FoodItem {
+            calories: Decimal::new(200, 0),
+            protein_g: Decimal::new(20, 0),
+            carbohydrates_g: Decimal::new(15, 0),
+            fat_g: Decimal::new(7, 0),
+            sodium_mg: Some(Decimal::new(300, 0)),
+            ..create_test_food_item("Yogurt")
+        };
+
+        let preview = NutritionPreview::scaled(&food, Decimal::new(5, 1));
+        assert_eq!(preview.food_item_id, food.id);
+        assert_eq!(preview.calories, Decimal::new(100, 0));
+        assert_eq!(preview.protein_g, Decimal::new(10, 0));
+        assert_eq!(preview.carbohydrates_g, Decimal::new(75, 1));
+        assert_eq!(preview.fat_g, Decimal::new(35, 1));
+        assert_eq!(preview.sodium_mg, Decimal::new(150, 0));
+    }
+
+    #[test]
+    fn test_macro_cycle_shifts_carbs_to_training_days_and_keeps_weekly_total() {
+        let training = vec![Weekday::Mon, Weekday::Wed, Weekday::Fri];
+        let plan = plan_macro_cycle(14000.0, training, 0.4).unwrap();
+
+        assert_eq!(plan.len(), 7);
+        let monday = &plan[0];
+        let tuesday = &plan[1];
+        assert!(monday.training_day);
+        assert!(!tuesday.training_day);
+        assert!(monday.carbohydrates_g > tuesday.carbohydrates_g);
+        assert_eq!(monday.protein_g, tuesday.protein_g);
+        assert_eq!(monday.fat_g, tuesday.fat_g);
+
+        // 900 kcal of carbs on an even day, 40% more on training days
+        assert_eq!(monday.carbohydrates_g, 315.0);
+        assert_eq!(monday.calories, 2360.0);
+        assert_eq!(tuesday.calories, 1730.0);
+
+        let total: f64 = plan.iter().map(|day| day.calories).sum();
+        assert!((total - 14000.0).abs() <= 3.5);
+    }
+
+    #[test]
+    fn test_macro_cycle_without_training_days_is_even() {
+        let plan = plan_macro_cycle(14000.0, vec![], 0.5).unwrap();
+        assert!(plan.iter().all(|day| day.calories == 2000.0 && !day.training_day));
+    }
+
+    #[test]
+    fn test_macro_cycle_rejects_skew_leaving_no_rest_day_carbs() {
+        let training = vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+        assert!(matches!(
+            plan_macro_cycle(14000.0, training, 1.0),
+            Err(ApiError::Validation(_))
+        ));
+        assert!(plan_macro_cycle(14000.0, vec![Weekday::Mon], -0.1).is_err());
+        assert!(plan_macro_cycle(0.0, vec![Weekday::Mon], 0.2).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_foods_second_identical_search_served_from_cache() {
+        let cache = MemoryCache::default();
+        let calls = AtomicUsize::new(0);
+
+        // Same search with different casing/spacing should share one entry
+        for query in ["Chicken  Breast", "chicken breast"] {
+            let items = NutritionService::search_foods_with(Some(&cache), query, 10, |q, l| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    assert_eq!(q, "chicken breast");
+                    assert_eq!(l, 10);
+                    Ok(vec![create_test_food_item("Chicken Breast")])
+                }
+            })
+            .await
+            .unwrap();
+
+            assert_eq!(items.len(), 1);
+            assert_eq!(items[0].name, "Chicken Breast");
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(cache.contains("food_search:chicken breast:10"));
+    }
+
+    #[tokio::test]
+    async fn test_search_foods_without_cache_hits_repository() {
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            let items = NutritionService::search_foods_with(None, "oats", 20, |_, l| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    assert_eq!(l, 20);
+                    Ok(vec![create_test_food_item("Oats")])
+                }
+            })
+            .await
+            .unwrap();
+            assert_eq!(items.len(), 1);
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_search_foods_rejects_empty_query() {
+        let result = NutritionService::search_foods_with(None, "   ", 20, |_, _| async {
+            Ok(Vec::new())
+        })
+        .await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_food_item_reports_all_invalid_fields() {
+        // Validation fails before the pool is ever used
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+
+        let result = NutritionService::create_food_item(
+            &pool,
+            Uuid::new_v4(),
+            CreateFoodItemInput {
+                name: "  ".to_string(),
+                serving_size: Decimal::new(100, 0),
+                serving_unit: "g".to_string(),
+                calories: Decimal::new(-50, 0),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let Err(ApiError::InvalidFields(errors)) = result else {
+            panic!("expected field errors, got {:?}", result);
+        };
+        assert_eq!(errors.get("name").unwrap(), ["cannot be empty".to_string()]);
+        assert_eq!(errors.get("calories").unwrap(), ["cannot be negative".to_string()]);
+        assert!(errors.get("serving_size").is_none());
+    }
+
+    #[test]
+    fn test_expand_meal_template_creates_log_per_food() {
+        let user_id = Uuid::new_v4();
+        let consumed_at = Utc::now();
+
+        let oats = create_test_food_item("Oats");
+        let mut milk = create_test_food_item("Milk");
+        milk.calories = Decimal::new(60, 0);
+        milk.protein_g = Decimal::new(3, 0);
+        milk.carbohydrates_g = Decimal::new(5, 0);
+        milk.fat_g = Decimal::new(3, 0);
+        let mut berries = create_test_food_item("Blueberries");
+        berries.calories = Decimal::new(57, 0);
+        berries.protein_g = Decimal::new(7, 1);
+        berries.carbohydrates_g = Decimal::new(14, 0);
+        berries.fat_g = Decimal::new(3, 1);
+        berries.fiber_g = Decimal::new(24, 1);
+
+        let foods = vec![
+            (oats.clone(), Decimal::ONE),
+            (milk.clone(), Decimal::new(2, 0)),
+            (berries.clone(), Decimal::new(5, 1)),
+        ];
+
+        let logs = expand_meal_template(user_id, &foods, "breakfast", consumed_at);
+
+        assert_eq!(logs.len(), 3);
+        let ids: Vec<Option<Uuid>> = logs.iter().map(|l| l.food_item_id).collect();
+        assert_eq!(ids, vec![Some(oats.id), Some(milk.id), Some(berries.id)]);
+        assert!(logs.iter().all(|l| l.user_id == user_id
+            && l.meal_type == "breakfast"
+            && l.consumed_at == consumed_at));
+
+        let total = |f: fn(&CreateFoodLog) -> Decimal| logs.iter().map(f).sum::<Decimal>();
+        // 165 + 2 * 60 + 0.5 * 57
+        assert_eq!(total(|l| l.calories), Decimal::new(3135, 1));
+        // 31 + 2 * 3 + 0.5 * 0.7
+        assert_eq!(total(|l| l.protein_g), Decimal::new(3735, 2));
+        // 0 + 2 * 5 + 0.5 * 14
+        assert_eq!(total(|l| l.carbohydrates_g), Decimal::new(17, 0));
+        // 3.6 + 2 * 3 + 0.5 * 0.3
+        assert_eq!(total(|l| l.fat_g), Decimal::new(975, 2));
+        assert_eq!(total(|l| l.fiber_g), Decimal::new(12, 1));
+    }
+
+    #[test]
+    fn test_copy_day_moves_logs_keeping_local_time() {
+        let tz: Tz = "America/New_York".parse().unwrap();
+        let to_date = NaiveDate::from_ymd_opt(2024, 3, 12).unwrap();
+
+        // Four entries on 2024-03-11 local time, the last one late evening
+        let logs: Vec<FoodLog> = ["12:30:00", "16:00:00", "20:15:00", "03:45:00"]
+            .iter()
+            .enumerate()
+            .map(|(i, utc_time)| {
+                let day = if i == 3 { 12 } else { 11 };
+                let mut log = create_test_food_log(
+                    Decimal::new(100 * (i as i64 + 1), 0),
+                    Decimal::new(10, 0),
+                    Decimal::new(20, 0),
+                    Decimal::new(5, 0),
+                    Decimal::new(2, 0),
+                );
+                log.consumed_at = DateTime::parse_from_rfc3339(&format!(
+                    "2024-03-{}T{}Z",
+                    day, utc_time
+                ))
+                .unwrap()
+                .with_timezone(&Utc);
+                log
+            })
+            .collect();
+
+        let copies: Vec<CreateFoodLog> = logs
+            .iter()
+            .map(|log| copy_log_to_date(log, to_date, tz))
+            .collect();
+
+        assert_eq!(copies.len(), 4);
+        for (log, copy) in logs.iter().zip(&copies) {
+            assert_eq!(local_date(copy.consumed_at, tz), to_date);
+            assert_eq!(
+                copy.consumed_at.with_timezone(&tz).time(),
+                log.consumed_at.with_timezone(&tz).time()
+            );
+            assert_eq!(copy.user_id, log.user_id);
+            assert_eq!(copy.calories, log.calories);
+            assert_eq!(copy.protein_g, log.protein_g);
+            assert_eq!(copy.carbohydrates_g, log.carbohydrates_g);
+            assert_eq!(copy.fat_g, log.fat_g);
+            assert_eq!(copy.fiber_g, log.fiber_g);
+            assert_eq!(copy.meal_type, log.meal_type);
+        }
+        // 23:45 local on the 11th stays 23:45 local on the 12th
+        assert_eq!(
+            copies[3].consumed_at,
+            DateTime::parse_from_rfc3339("2024-03-13T03:45:00Z").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_copy_log_into_dst_gap_shifts_by_whole_days() {
+        let tz: Tz = "America/New_York".parse().unwrap();
+        let mut log = create_test_food_log(
+            Decimal::new(200, 0),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Decimal::ZERO,
+        );
+        // 02:30 local on 2024-03-09; 02:30 does not exist on 2024-03-10
+        log.consumed_at = DateTime::parse_from_rfc3339("2024-03-09T07:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let copy = copy_log_to_date(&log, NaiveDate::from_ymd_opt(2024, 3, 10).unwrap(), tz);
+
+        assert_eq!(
+            copy.consumed_at,
+            DateTime::parse_from_rfc3339("2024-03-10T07:30:00Z").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_day_rejects_same_date() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+
+        let result = NutritionService::copy_day(&pool, Uuid::new_v4(), date, date, Tz::UTC).await;
+
+        assert!(matches!(result, Err(ApiError::Validation(_))));
+    }
+
+    #[test]
+    fn test_custom_meal_type_accepted_only_when_configured() {
+        let defaults: Vec<String> = fitness_assistant_shared::validation::DEFAULT_MEAL_TYPES
+            .iter()
+            .map(|m| m.to_string())
+            .collect();
+        let mut custom = defaults.clone();
+        custom.push("pre-workout".to_string());
+        custom.push("second breakfast".to_string());
+
+        assert_eq!(
+            NutritionService::validate_meal_type("Pre-Workout", &custom).unwrap(),
+            "pre-workout"
+        );
+        assert_eq!(
+            NutritionService::validate_meal_type("Second  Breakfast", &custom).unwrap(),
+            "second breakfast"
+        );
+        assert!(matches!(
+            NutritionService::validate_meal_type("pre-workout", &defaults),
+            Err(ApiError::Validation(_))
+        ));
+        assert_eq!(
+            NutritionService::validate_meal_type("LUNCH", &defaults).unwrap(),
+            "lunch"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_meal_template_rejects_empty_template() {
+        let pool = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+
+        let result =
+            NutritionService::save_meal_template(&pool, Uuid::new_v4(), " ".to_string(), vec![])
+                .await;
+
+        let Err(ApiError::InvalidFields(errors)) = result else {
+            panic!("expected field errors, got {:?}", result);
+        };
+        assert!(errors.get("name").is_some());
+        assert!(errors.get("items").is_some());
+    }
+
+    #[test]
+    fn test_owner_can_edit_own_food() {
+        let user_id = Uuid::new_v4();
+        let food = FoodItem {
+            source: "user".to_string(),
+            created_by: Some(user_id),
+            ..create_test_food_item("Homemade Granola")
+        };
+
+        assert!(NutritionService::ensure_food_editable(&food, user_id).is_ok());
+    }
+
+    #[test]
+    fn test_non_owner_cannot_edit_food() {
+        let food = FoodItem {
+            source: "user".to_string(),
+            created_by: Some(Uuid::new_v4()),
+            ..create_test_food_item("Homemade Granola")
+        };
+
+        let result = NutritionService::ensure_food_editable(&food, Uuid::new_v4());
+        assert!(matches!(result, Err(ApiError::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_database_sourced_food_is_read_only() {
+        let user_id = Uuid::new_v4();
+        for source in ["usda", "off"] {
+            let food = FoodItem {
+                source: source.to_string(),
+                created_by: Some(user_id),
+                ..create_test_food_item("Chicken Breast")
+            };
+
+            let result = NutritionService::ensure_food_editable(&food, user_id);
+            assert!(matches!(result, Err(ApiError::Forbidden(_))), "{} should be read-only", source);
+        }
+    }
+
+    /// Helper to create a test FoodItem with the given name
+    fn create_test_food_item(name: &str) -> FoodItem {
+        FoodItem {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            brand: None,
+            barcode: None,
+            serving_size: Decimal::new(100, 0),
+            serving_unit: "g".to_string(),
+            calories: Decimal::new(165, 0),
+            protein_g: Decimal::new(31, 0),
+            carbohydrates_g: Decimal::ZERO,
+            fat_g: Decimal::new(36, 1),
+            fiber_g: Decimal::ZERO,
+            sugar_g: Decimal::ZERO,
+            sodium_mg: None,
+            potassium_mg: None,
+            cholesterol_mg: None,
+            alcohol_g: Decimal::ZERO,
+            source: "usda".to_string(),
+            verified: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
     }
 
     /// Helper to create a test FoodLog with specified nutrition values
@@ -536,6 +1831,9 @@ mod tests {
             carbohydrates_g,
             fat_g,
             fiber_g,
+            sugar_g: Decimal::ZERO,
+            sodium_mg: Decimal::ZERO,
+            alcohol_g: Decimal::ZERO,
             meal_type: "lunch".to_string(),
             logged_at: Utc::now(),
             consumed_at: Utc::now(),
@@ -591,8 +1889,10 @@ mod property_tests {
             nutrition_value_strategy(), // carbs
             nutrition_value_strategy(), // fat
             nutrition_value_strategy(), // fiber
+            nutrition_value_strategy(), // sugar
+            nutrition_value_strategy(), // sodium
         )
-            .prop_map(|(cal, pro, carb, fat, fib)| FoodLog {
+            .prop_map(|(cal, pro, carb, fat, fib, sugar, sodium)| FoodLog {
                 id: Uuid::new_v4(),
                 user_id: Uuid::new_v4(),
                 food_item_id: None,
@@ -603,6 +1903,9 @@ mod property_tests {
                 carbohydrates_g: carb,
                 fat_g: fat,
                 fiber_g: fib,
+                sugar_g: sugar,
+                sodium_mg: sodium,
+                alcohol_g: Decimal::ZERO,
                 meal_type: "lunch".to_string(),
                 logged_at: Utc::now(),
                 consumed_at: Utc::now(),
@@ -616,7 +1919,7 @@ mod property_tests {
 
         /// Property 2: Nutrition Aggregation Correctness
         /// For any set of food log entries on a given day, the daily nutrition summary
-        /// totals (calories, protein, carbs, fat, fiber) should equal the sum of the
+        /// totals (calories, protein, carbs, fat, fiber, sugar, sodium) should equal the sum of the
         /// individual entry values.
         /// Feature: fitness-assistant-ai, Property 2: Nutrition Aggregation Correctness
         /// **Validates: Requirements 2.4**
@@ -630,22 +1933,27 @@ mod property_tests {
             let expected_carbs: Decimal = logs.iter().map(|l| l.carbohydrates_g).sum();
             let expected_fat: Decimal = logs.iter().map(|l| l.fat_g).sum();
             let expected_fiber: Decimal = logs.iter().map(|l| l.fiber_g).sum();
+            let expected_sugar: Decimal = logs.iter().map(|l| l.sugar_g).sum();
+            let expected_sodium: Decimal = logs.iter().map(|l| l.sodium_mg).sum();
 
             // Get actual totals from aggregate function
-            let (actual_cal, actual_pro, actual_carb, actual_fat, actual_fib) = 
-                aggregate_daily_nutrition(&logs);
+            let actual = aggregate_daily_nutrition(&logs);
 
             // Property: aggregated totals must equal sum of individual entries
-            prop_assert_eq!(actual_cal, expected_calories,
-                "Calories mismatch: got {}, expected {}", actual_cal, expected_calories);
-            prop_assert_eq!(actual_pro, expected_protein,
-                "Protein mismatch: got {}, expected {}", actual_pro, expected_protein);
-            prop_assert_eq!(actual_carb, expected_carbs,
-                "Carbs mismatch: got {}, expected {}", actual_carb, expected_carbs);
-            prop_assert_eq!(actual_fat, expected_fat,
-                "Fat mismatch: got {}, expected {}", actual_fat, expected_fat);
-            prop_assert_eq!(actual_fib, expected_fiber,
-                "Fiber mismatch: got {}, expected {}", actual_fib, expected_fiber);
+            prop_assert_eq!(actual.calories, expected_calories,
+                "Calories mismatch: got {}, expected {}", actual.calories, expected_calories);
+            prop_assert_eq!(actual.protein_g, expected_protein,
+                "Protein mismatch: got {}, expected {}", actual.protein_g, expected_protein);
+            prop_assert_eq!(actual.carbohydrates_g, expected_carbs,
+                "Carbs mismatch: got {}, expected {}", actual.carbohydrates_g, expected_carbs);
+            prop_assert_eq!(actual.fat_g, expected_fat,
+                "Fat mismatch: got {}, expected {}", actual.fat_g, expected_fat);
+            prop_assert_eq!(actual.fiber_g, expected_fiber,
+                "Fiber mismatch: got {}, expected {}", actual.fiber_g, expected_fiber);
+            prop_assert_eq!(actual.sugar_g, expected_sugar,
+                "Sugar mismatch: got {}, expected {}", actual.sugar_g, expected_sugar);
+            prop_assert_eq!(actual.sodium_mg, expected_sodium,
+                "Sodium mismatch: got {}, expected {}", actual.sodium_mg, expected_sodium);
         }
 
         /// Property: Aggregation is commutative (order doesn't matter)
@@ -653,19 +1961,21 @@ mod property_tests {
         fn prop_nutrition_aggregation_commutative(
             logs in proptest::collection::vec(food_log_strategy(), 2..20)
         ) {
-            let (cal1, pro1, carb1, fat1, fib1) = aggregate_daily_nutrition(&logs);
+            let forward = aggregate_daily_nutrition(&logs);
             
             // Reverse the order
             let mut reversed = logs.clone();
             reversed.reverse();
-            let (cal2, pro2, carb2, fat2, fib2) = aggregate_daily_nutrition(&reversed);
+            let backward = aggregate_daily_nutrition(&reversed);
 
             // Results should be identical regardless of order
-            prop_assert_eq!(cal1, cal2, "Calories should be order-independent");
-            prop_assert_eq!(pro1, pro2, "Protein should be order-independent");
-            prop_assert_eq!(carb1, carb2, "Carbs should be order-independent");
-            prop_assert_eq!(fat1, fat2, "Fat should be order-independent");
-            prop_assert_eq!(fib1, fib2, "Fiber should be order-independent");
+            prop_assert_eq!(forward.calories, backward.calories, "Calories should be order-independent");
+            prop_assert_eq!(forward.protein_g, backward.protein_g, "Protein should be order-independent");
+            prop_assert_eq!(forward.carbohydrates_g, backward.carbohydrates_g, "Carbs should be order-independent");
+            prop_assert_eq!(forward.fat_g, backward.fat_g, "Fat should be order-independent");
+            prop_assert_eq!(forward.fiber_g, backward.fiber_g, "Fiber should be order-independent");
+            prop_assert_eq!(forward.sugar_g, backward.sugar_g, "Sugar should be order-independent");
+            prop_assert_eq!(forward.sodium_mg, backward.sodium_mg, "Sodium should be order-independent");
         }
 
         /// Property: Empty input yields zero totals
@@ -674,20 +1984,20 @@ mod property_tests {
             logs in proptest::collection::vec(food_log_strategy(), 1..10)
         ) {
             let empty: Vec<FoodLog> = vec![];
-            let (cal, pro, carb, fat, fib) = aggregate_daily_nutrition(&empty);
+            let totals = aggregate_daily_nutrition(&empty);
             
             // Empty aggregation should be zero (identity element)
-            prop_assert_eq!(cal, Decimal::ZERO);
-            prop_assert_eq!(pro, Decimal::ZERO);
-            prop_assert_eq!(carb, Decimal::ZERO);
-            prop_assert_eq!(fat, Decimal::ZERO);
-            prop_assert_eq!(fib, Decimal::ZERO);
+            prop_assert_eq!(totals.calories, Decimal::ZERO);
+            prop_assert_eq!(totals.protein_g, Decimal::ZERO);
+            prop_assert_eq!(totals.carbohydrates_g, Decimal::ZERO);
+            prop_assert_eq!(totals.fat_g, Decimal::ZERO);
+            prop_assert_eq!(totals.fiber_g, Decimal::ZERO);
 
             // Adding empty to any set should not change the result
-            let (cal_with_data, _, _, _, _) = aggregate_daily_nutrition(&logs);
+            let with_data = aggregate_daily_nutrition(&logs);
             let combined: Vec<FoodLog> = logs.iter().chain(empty.iter()).cloned().collect();
-            let (cal_combined, _, _, _, _) = aggregate_daily_nutrition(&combined);
-            prop_assert_eq!(cal_with_data, cal_combined);
+            let combined = aggregate_daily_nutrition(&combined);
+            prop_assert_eq!(with_data, combined);
         }
 
         /// Property 6: Food Search Relevance
@@ -2,15 +2,18 @@
 //!
 //! Provides business logic for sleep tracking including:
 //! - Sleep logging with stage breakdown
-//! - Sleep efficiency calculation
-//! - Sleep trend analysis
+//! - Sleep efficiency calculation, against time in bed when recorded
+//! - Sleep trend analysis, including onset latency and chronotype
 //! - Sleep goal management
 
 use crate::error::ApiError;
 use crate::repositories::{
     CreateSleepLog, SleepGoalRepository, SleepLogRepository, UpsertSleepGoal,
 };
-use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use crate::timezone;
+use chrono::{DateTime, NaiveDate, NaiveTime, Timelike, Utc};
+use chrono_tz::Tz;
+use fitness_assistant_shared::validation::{reconcile_duration_minutes, resolve_source};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use sqlx::PgPool;
@@ -19,13 +22,68 @@ use uuid::Uuid;
 /// Default sleep goal in minutes (8 hours)
 const DEFAULT_SLEEP_GOAL_MINUTES: i32 = 480;
 
+/// Average onset latency above which falling asleep is considered chronically slow
+pub const PROLONGED_ONSET_LATENCY_MINUTES: f64 = 30.0;
+
+/// Nights needed before a chronotype is inferred from sleep timing
+pub const MIN_CHRONOTYPE_NIGHTS: usize = 5;
+
+/// Most recent nights considered when classifying chronotype
+const CHRONOTYPE_MAX_NIGHTS: i64 = 90;
+
+/// Average sleep midpoints earlier than this (minutes after midnight) are larks
+const LARK_MIDPOINT_BEFORE_MINUTES: f64 = 180.0;
+
+/// Average sleep midpoints later than this (minutes after midnight) are owls
+const OWL_MIDPOINT_AFTER_MINUTES: f64 = 300.0;
+
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+/// Circadian preference inferred from when someone sleeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chronotype {
+    /// Sleep midpoint before 03:00
+    Lark,
+    /// Sleep midpoint between 03:00 and 05:00
+    Intermediate,
+    /// Sleep midpoint after 05:00
+    Owl,
+}
+
+impl Chronotype {
+    /// Classify an average sleep midpoint in minutes relative to local midnight
+    pub fn from_midpoint_minutes(midpoint_minutes: f64) -> Self {
+        if midpoint_minutes < LARK_MIDPOINT_BEFORE_MINUTES {
+            Chronotype::Lark
+        } else if midpoint_minutes <= OWL_MIDPOINT_AFTER_MINUTES {
+            Chronotype::Intermediate
+        } else {
+            Chronotype::Owl
+        }
+    }
+
+    /// Value returned in API responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Chronotype::Lark => "lark",
+            Chronotype::Intermediate => "intermediate",
+            Chronotype::Owl => "owl",
+        }
+    }
+}
+
 /// Sleep log entry
 #[derive(Debug, Clone)]
 pub struct SleepLog {
     pub id: Uuid,
     pub sleep_start: DateTime<Utc>,
     pub sleep_end: DateTime<Utc>,
+    /// Minutes from sleep start to sleep end
     pub total_duration_minutes: i32,
+    /// Minutes in bed, when recorded separately from the sleep period
+    pub time_in_bed_minutes: Option<i32>,
+    /// Minutes from lights-out to falling asleep
+    pub sleep_onset_minutes: Option<i32>,
     pub awake_minutes: i32,
     pub light_minutes: i32,
     pub deep_minutes: i32,
@@ -46,6 +104,8 @@ pub struct SleepLog {
 pub struct LogSleepInput {
     pub sleep_start: DateTime<Utc>,
     pub sleep_end: DateTime<Utc>,
+    pub time_in_bed_minutes: Option<i32>,
+    pub sleep_onset_minutes: Option<i32>,
     pub awake_minutes: Option<i32>,
     pub light_minutes: Option<i32>,
     pub deep_minutes: Option<i32>,
@@ -69,6 +129,12 @@ pub struct SleepAnalysis {
     pub avg_rem_percent: f64,
     pub avg_light_percent: f64,
     pub avg_awake_percent: f64,
+    /// Average onset latency over nights that recorded one
+    pub avg_onset_latency_minutes: Option<f64>,
+    /// Average onset latency is above [`PROLONGED_ONSET_LATENCY_MINUTES`],
+    /// a possible sign of insomnia
+    pub prolonged_onset_latency: bool,
+    pub chronotype: Chronotype,
     pub total_nights: i64,
     pub sleep_debt_minutes: i64,
     pub consistency_score: f64,
@@ -104,16 +170,10 @@ impl SleepService {
         user_id: Uuid,
         input: LogSleepInput,
     ) -> Result<SleepLog, ApiError> {
-        // Validate sleep times
-        if input.sleep_end <= input.sleep_start {
-            return Err(ApiError::Validation(
-                "Sleep end time must be after start time".to_string(),
-            ));
-        }
-
-        // Calculate total duration
-        let duration = input.sleep_end - input.sleep_start;
-        let total_duration_minutes = duration.num_minutes() as i32;
+        // Validate sleep times and calculate total duration
+        let total_duration_minutes =
+            reconcile_duration_minutes(input.sleep_start, input.sleep_end, None)
+                .map_err(|msg| ApiError::Validation(format!("Sleep {}", msg.to_lowercase())))?;
 
         if total_duration_minutes <= 0 {
             return Err(ApiError::Validation(
@@ -127,6 +187,14 @@ impl SleepService {
             ));
         }
 
+        if let Some(time_in_bed) = input.time_in_bed_minutes {
+            Self::validate_time_in_bed(total_duration_minutes, time_in_bed)?;
+        }
+
+        if let Some(onset) = input.sleep_onset_minutes {
+            Self::validate_onset_latency(total_duration_minutes, onset)?;
+        }
+
         // Get stage minutes (default to 0 if not provided)
         let awake_minutes = input.awake_minutes.unwrap_or(0);
         let light_minutes = input.light_minutes.unwrap_or(0);
@@ -154,13 +222,19 @@ impl SleepService {
         }
 
         // Calculate sleep efficiency
-        let sleep_efficiency = Self::calculate_efficiency(total_duration_minutes, awake_minutes);
+        let sleep_efficiency = Self::calculate_efficiency_in_bed(
+            total_duration_minutes,
+            awake_minutes,
+            input.time_in_bed_minutes,
+        );
 
         let create_input = CreateSleepLog {
             user_id,
             sleep_start: input.sleep_start,
             sleep_end: input.sleep_end,
             total_duration_minutes,
+            time_in_bed_minutes: input.time_in_bed_minutes,
+            sleep_onset_minutes: input.sleep_onset_minutes,
             awake_minutes,
             light_minutes,
             deep_minutes,
@@ -172,7 +246,7 @@ impl SleepService {
             min_heart_rate: input.min_heart_rate,
             hrv_average: input.hrv_average.map(|h| Decimal::try_from(h).unwrap_or_default()),
             respiratory_rate: input.respiratory_rate.map(|r| Decimal::try_from(r).unwrap_or_default()),
-            source: input.source.unwrap_or_else(|| "manual".to_string()),
+            source: resolve_source(input.source.as_deref())?,
             notes: input.notes,
         };
 
@@ -200,6 +274,100 @@ impl SleepService {
         Some((actual_sleep as f64 / total_duration_minutes as f64) * 100.0)
     }
 
+    /// Calculate sleep efficiency against time in bed when it was recorded
+    ///
+    /// efficiency = (duration - awake) / time_in_bed * 100, falling back to
+    /// [`Self::calculate_efficiency`] when time in bed is unknown.
+    pub fn calculate_efficiency_in_bed(
+        total_duration_minutes: i32,
+        awake_minutes: i32,
+        time_in_bed_minutes: Option<i32>,
+    ) -> Option<f64> {
+        match time_in_bed_minutes {
+            Some(time_in_bed) if time_in_bed > 0 => {
+                let actual_sleep = (total_duration_minutes - awake_minutes).max(0);
+                Some((actual_sleep as f64 / time_in_bed as f64) * 100.0)
+            }
+            _ => Self::calculate_efficiency(total_duration_minutes, awake_minutes),
+        }
+    }
+
+    /// Validate that the sleep period fits within the time in bed
+    pub fn validate_time_in_bed(
+        total_duration_minutes: i32,
+        time_in_bed_minutes: i32,
+    ) -> Result<(), ApiError> {
+        if time_in_bed_minutes > 1440 {
+            return Err(ApiError::Validation(
+                "Time in bed cannot exceed 24 hours".to_string(),
+            ));
+        }
+        if total_duration_minutes > time_in_bed_minutes {
+            return Err(ApiError::Validation(format!(
+                "Time asleep ({} min) cannot exceed time in bed ({} min)",
+                total_duration_minutes, time_in_bed_minutes
+            )));
+        }
+        Ok(())
+    }
+
+    /// Classify chronotype from the average local sleep midpoint
+    ///
+    /// Midpoints are averaged on the 24-hour clock so nights either side of
+    /// midnight average correctly (23:30 and 00:30 give 00:00, not 12:00).
+    /// Fewer than [`MIN_CHRONOTYPE_NIGHTS`] nights is not enough to tell, so
+    /// the result is [`Chronotype::Intermediate`].
+    pub fn classify_chronotype(logs: &[SleepLog], tz: Tz) -> Chronotype {
+        if logs.len() < MIN_CHRONOTYPE_NIGHTS {
+            return Chronotype::Intermediate;
+        }
+
+        let (sin_sum, cos_sum) = logs
+            .iter()
+            .map(|log| {
+                let midpoint = log.sleep_start + (log.sleep_end - log.sleep_start) / 2;
+                let local = midpoint.with_timezone(&tz);
+                let minutes = (local.hour() * 60 + local.minute()) as f64;
+                minutes / MINUTES_PER_DAY * std::f64::consts::TAU
+            })
+            .fold((0.0, 0.0), |(sin, cos), angle| (sin + angle.sin(), cos + angle.cos()));
+
+        let mean_minutes = sin_sum.atan2(cos_sum) / std::f64::consts::TAU * MINUTES_PER_DAY;
+        // atan2 gives -12h..12h around midnight; shift so late-afternoon
+        // midpoints (day sleepers) count as very late rather than very early
+        let midpoint_minutes = if mean_minutes < -480.0 {
+            mean_minutes + MINUTES_PER_DAY
+        } else {
+            mean_minutes
+        };
+
+        Chronotype::from_midpoint_minutes(midpoint_minutes)
+    }
+
+    /// Validate that onset latency is non-negative and shorter than the sleep period
+    pub fn validate_onset_latency(
+        total_duration_minutes: i32,
+        sleep_onset_minutes: i32,
+    ) -> Result<(), ApiError> {
+        if sleep_onset_minutes < 0 {
+            return Err(ApiError::Validation(
+                "Sleep onset latency cannot be negative".to_string(),
+            ));
+        }
+        if sleep_onset_minutes >= total_duration_minutes {
+            return Err(ApiError::Validation(format!(
+                "Sleep onset latency ({} min) must be less than total duration ({} min)",
+                sleep_onset_minutes, total_duration_minutes
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether an average onset latency suggests chronic trouble falling asleep
+    pub fn is_prolonged_onset_latency(avg_onset_latency_minutes: Option<f64>) -> bool {
+        avg_onset_latency_minutes.is_some_and(|avg| avg > PROLONGED_ONSET_LATENCY_MINUTES)
+    }
+
     /// Validate that sleep stages sum to total duration
     ///
     /// # Property 16: Sleep Stage Time Consistency
@@ -275,6 +443,21 @@ impl SleepService {
             0.0
         };
 
+        // Chronotype from the local sleep midpoints of the most recent nights
+        let recent = SleepLogRepository::get_history(
+            pool,
+            user_id,
+            start_date,
+            end_date,
+            CHRONOTYPE_MAX_NIGHTS,
+            0,
+        )
+        .await
+        .map_err(ApiError::Internal)?;
+        let recent: Vec<SleepLog> = recent.into_iter().map(Self::record_to_sleep_log).collect();
+        let tz = timezone::user_timezone(pool, user_id).await;
+        let chronotype = Self::classify_chronotype(&recent, tz);
+
         Ok(SleepAnalysis {
             avg_duration_minutes: avg_duration,
             avg_efficiency: summary.avg_efficiency.unwrap_or(0.0),
@@ -282,6 +465,11 @@ impl SleepService {
             avg_rem_percent,
             avg_light_percent,
             avg_awake_percent,
+            avg_onset_latency_minutes: summary.avg_onset_latency_minutes,
+            prolonged_onset_latency: Self::is_prolonged_onset_latency(
+                summary.avg_onset_latency_minutes,
+            ),
+            chronotype,
             total_nights: summary.total_nights,
             sleep_debt_minutes: sleep_debt.max(0),
             consistency_score,
@@ -367,6 +555,8 @@ impl SleepService {
             sleep_start: record.sleep_start,
             sleep_end: record.sleep_end,
             total_duration_minutes: record.total_duration_minutes,
+            time_in_bed_minutes: record.time_in_bed_minutes,
+            sleep_onset_minutes: record.sleep_onset_minutes,
             awake_minutes: record.awake_minutes,
             light_minutes: record.light_minutes,
             deep_minutes: record.deep_minutes,
@@ -549,6 +739,121 @@ mod tests {
         assert!((eff - 85.714).abs() < 0.01);
     }
 
+    #[test]
+    fn test_time_in_bed_changes_efficiency_denominator() {
+        // 7 hours asleep with 20 min awake, out of 8 hours in bed
+        let without = SleepService::calculate_efficiency_in_bed(420, 20, None).unwrap();
+        let with = SleepService::calculate_efficiency_in_bed(420, 20, Some(480)).unwrap();
+
+        assert!((without - 95.238).abs() < 0.01);
+        assert!((with - 83.333).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_time_in_bed_must_cover_time_asleep() {
+        assert!(SleepService::validate_time_in_bed(420, 480).is_ok());
+        assert!(SleepService::validate_time_in_bed(420, 420).is_ok());
+        assert!(matches!(
+            SleepService::validate_time_in_bed(480, 420),
+            Err(ApiError::Validation(_))
+        ));
+        assert!(SleepService::validate_time_in_bed(420, 1441).is_err());
+    }
+
+    #[test]
+    fn test_onset_latency_must_be_shorter_than_sleep() {
+        assert!(SleepService::validate_onset_latency(480, 0).is_ok());
+        assert!(SleepService::validate_onset_latency(480, 45).is_ok());
+        assert!(SleepService::validate_onset_latency(480, -5).is_err());
+        assert!(SleepService::validate_onset_latency(480, 480).is_err());
+    }
+
+    #[test]
+    fn test_prolonged_onset_latency_flag_trips_above_threshold() {
+        assert!(!SleepService::is_prolonged_onset_latency(None));
+        assert!(!SleepService::is_prolonged_onset_latency(Some(12.5)));
+        assert!(!SleepService::is_prolonged_onset_latency(Some(30.0)));
+        assert!(SleepService::is_prolonged_onset_latency(Some(35.0)));
+    }
+
+    /// Sleep logs on consecutive nights between the given UTC clock times
+    fn nights(count: i64, bedtime: (u32, u32), wake: (u32, u32)) -> Vec<SleepLog> {
+        (0..count)
+            .map(|day| {
+                let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap() + chrono::Duration::days(day);
+                let start = date.and_hms_opt(bedtime.0, bedtime.1, 0).unwrap().and_utc();
+                let end_date = if wake < bedtime { date.succ_opt().unwrap() } else { date };
+                let end = end_date.and_hms_opt(wake.0, wake.1, 0).unwrap().and_utc();
+                SleepLog {
+                    id: Uuid::new_v4(),
+                    sleep_start: start,
+                    sleep_end: end,
+                    total_duration_minutes: (end - start).num_minutes() as i32,
+                    time_in_bed_minutes: None,
+                    sleep_onset_minutes: None,
+                    awake_minutes: 0,
+                    light_minutes: 0,
+                    deep_minutes: 0,
+                    rem_minutes: 0,
+                    sleep_efficiency: None,
+                    sleep_score: None,
+                    times_awoken: None,
+                    avg_heart_rate: None,
+                    min_heart_rate: None,
+                    hrv_average: None,
+                    respiratory_rate: None,
+                    source: "manual".to_string(),
+                    notes: None,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_early_sleeper_is_lark() {
+        // 21:00-05:00, midpoint 01:00
+        let logs = nights(7, (21, 0), (5, 0));
+        assert_eq!(SleepService::classify_chronotype(&logs, Tz::UTC), Chronotype::Lark);
+    }
+
+    #[test]
+    fn test_late_sleeper_is_owl() {
+        // 02:00-10:00, midpoint 06:00
+        let logs = nights(7, (2, 0), (10, 0));
+        assert_eq!(SleepService::classify_chronotype(&logs, Tz::UTC), Chronotype::Owl);
+    }
+
+    #[test]
+    fn test_chronotype_averages_across_midnight() {
+        // Midpoints of 23:30 and 00:30 average to midnight, not noon
+        let mut logs = nights(3, (19, 30), (3, 30));
+        logs.extend(nights(3, (20, 30), (4, 30)));
+        assert_eq!(SleepService::classify_chronotype(&logs, Tz::UTC), Chronotype::Lark);
+    }
+
+    #[test]
+    fn test_chronotype_uses_local_time() {
+        // 23:00-07:00 UTC is 18:00-02:00 in New York, midpoint 22:00 local
+        let logs = nights(5, (23, 0), (7, 0));
+        assert_eq!(
+            SleepService::classify_chronotype(&logs, Tz::UTC),
+            Chronotype::Intermediate
+        );
+        assert_eq!(
+            SleepService::classify_chronotype(&logs, chrono_tz::America::New_York),
+            Chronotype::Lark
+        );
+    }
+
+    #[test]
+    fn test_chronotype_needs_minimum_nights() {
+        let logs = nights(4, (2, 0), (10, 0));
+        assert_eq!(
+            SleepService::classify_chronotype(&logs, Tz::UTC),
+            Chronotype::Intermediate
+        );
+    }
+
     #[test]
     fn test_stage_consistency_exact_match() {
         assert!(SleepService::validate_stage_consistency(480, 30, 240, 120, 90));
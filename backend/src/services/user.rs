@@ -6,14 +6,28 @@
 //! - JWT service is passed by reference (pre-computed keys)
 //! - Database queries use connection pooling
 
-use crate::auth::{JwtService, PasswordService};
+use crate::auth::{revocation, Claims, JwtService, PasswordService};
+use crate::cache::{self, CacheStore};
 use crate::error::ApiError;
-use crate::repositories::UserRepository;
+use crate::repositories::{UserRecord, UserRepository};
+use crate::services::data::{DataService, DeletionSummary};
+use crate::services::exercise::ExerciseService;
+use crate::services::nutrition::FOOD_SEARCH_CACHE_PREFIX;
+use crate::services::profile::ProfileService;
+use chrono::{Duration, Utc};
+use fitness_assistant_shared::models::Role;
 use fitness_assistant_shared::types::{AuthTokens, UserProfile};
+use fitness_assistant_shared::validation::ValidationErrors;
 use sqlx::PgPool;
 use uuid::Uuid;
 use validator::ValidateEmail;
 
+/// Lifetime of an email verification token (24 hours)
+pub const EMAIL_VERIFICATION_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// Lifetime of a password reset token (1 hour)
+pub const PASSWORD_RESET_TTL_SECS: i64 = 60 * 60;
+
 /// User service for authentication operations
 pub struct UserService;
 
@@ -28,17 +42,48 @@ impl UserService {
         email: &str,
         password: &str,
     ) -> Result<AuthTokens, ApiError> {
+        let user = Self::create_account(pool, email, password, Role::User).await?;
+
+        // Generate tokens (uses pre-computed keys - fast)
+        let access_token = jwt_service
+            .generate_access_token(user.id, user.role())
+            .map_err(ApiError::Internal)?;
+        let refresh_token = jwt_service
+            .generate_refresh_token(user.id)
+            .map_err(ApiError::Internal)?;
+
+        Ok(AuthTokens {
+            access_token,
+            refresh_token,
+            token_type: "Bearer".to_string(),
+            expires_in: jwt_service.access_token_expiry_secs(),
+        })
+    }
+
+    /// Create an administrator account
+    ///
+    /// Used by the `create-admin` command; the same email and password rules
+    /// as registration apply. The account is created with the admin role in
+    /// one step, so a failure never leaves an ordinary user behind.
+    pub async fn create_admin(pool: &PgPool, email: &str, password: &str) -> Result<Uuid, ApiError> {
+        let user = Self::create_account(pool, email, password, Role::Admin).await?;
+
+        Ok(user.id)
+    }
+
+    /// Validate credentials and create the user with default settings
+    async fn create_account(
+        pool: &PgPool,
+        email: &str,
+        password: &str,
+        role: Role,
+    ) -> Result<UserRecord, ApiError> {
         // Validate email format
         if !email.validate_email() {
             return Err(ApiError::Validation("Invalid email format".to_string()));
         }
 
-        // Validate password strength
-        if password.len() < 8 {
-            return Err(ApiError::Validation(
-                "Password must be at least 8 characters".to_string(),
-            ));
-        }
+        validate_password("password", password)?;
 
         // Check if email already exists
         if UserRepository::email_exists(pool, email)
@@ -55,24 +100,9 @@ impl UserService {
             .map_err(ApiError::Internal)?;
 
         // Create user
-        let user = UserRepository::create(pool, email, &password_hash)
+        UserRepository::create(pool, email, &password_hash, role)
             .await
-            .map_err(ApiError::Internal)?;
-
-        // Generate tokens (uses pre-computed keys - fast)
-        let access_token = jwt_service
-            .generate_access_token(user.id)
-            .map_err(ApiError::Internal)?;
-        let refresh_token = jwt_service
-            .generate_refresh_token(user.id)
-            .map_err(ApiError::Internal)?;
-
-        Ok(AuthTokens {
-            access_token,
-            refresh_token,
-            token_type: "Bearer".to_string(),
-            expires_in: jwt_service.access_token_expiry_secs(),
-        })
+            .map_err(ApiError::Internal)
     }
 
     /// Login with email and password
@@ -104,7 +134,7 @@ impl UserService {
 
         // Generate tokens (uses pre-computed keys - fast)
         let access_token = jwt_service
-            .generate_access_token(user.id)
+            .generate_access_token(user.id, user.role())
             .map_err(ApiError::Internal)?;
         let refresh_token = jwt_service
             .generate_refresh_token(user.id)
@@ -134,14 +164,21 @@ impl UserService {
             .map_err(|_| ApiError::Unauthorized("Invalid user ID in token".to_string()))?;
 
         // Verify user still exists
-        let _user = UserRepository::find_by_id(pool, user_id)
+        let user = UserRepository::find_by_id(pool, user_id)
             .await
             .map_err(ApiError::Internal)?
             .ok_or_else(|| ApiError::Unauthorized("User not found".to_string()))?;
 
-        // Generate new tokens
+        // Reject tokens issued before the last password reset
+        if let Some(valid_after) = user.sessions_valid_after {
+            if claims.iat < valid_after.timestamp() {
+                return Err(ApiError::Unauthorized("Refresh token has been revoked".to_string()));
+            }
+        }
+
+        // Generate new tokens, picking up any role change
         let access_token = jwt_service
-            .generate_access_token(user_id)
+            .generate_access_token(user_id, user.role())
             .map_err(ApiError::Internal)?;
         let new_refresh_token = jwt_service
             .generate_refresh_token(user_id)
@@ -164,10 +201,215 @@ impl UserService {
 
         Ok(UserProfile {
             id: user.id.to_string(),
+            role: user.role(),
             email: user.email,
+            email_verified: user.email_verified_at.is_some(),
             created_at: user.created_at,
         })
     }
+
+    /// Issue a single-use email verification token for a user
+    ///
+    /// The token is a signed JWT whose ID is recorded so it can only be
+    /// redeemed once, within `EMAIL_VERIFICATION_TTL_SECS`.
+    pub async fn issue_verification_token(
+        pool: &PgPool,
+        jwt_service: &JwtService,
+        user_id: Uuid,
+    ) -> Result<String, ApiError> {
+        let (token, claims) = jwt_service
+            .generate_email_verification_token(user_id, EMAIL_VERIFICATION_TTL_SECS)
+            .map_err(ApiError::Internal)?;
+        let token_id = token_id(&claims)
+            .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Verification token has no ID")))?;
+
+        UserRepository::create_verification_token(
+            pool,
+            token_id,
+            user_id,
+            Utc::now() + Duration::seconds(EMAIL_VERIFICATION_TTL_SECS),
+        )
+        .await
+        .map_err(ApiError::Internal)?;
+
+        Ok(token)
+    }
+
+    /// Redeem an email verification token, marking its user as verified
+    ///
+    /// Returns the verified user's ID.
+    pub async fn verify_email(
+        pool: &PgPool,
+        jwt_service: &JwtService,
+        token: &str,
+    ) -> Result<Uuid, ApiError> {
+        let invalid = || ApiError::Validation("Invalid or expired verification token".to_string());
+
+        let claims = jwt_service
+            .validate_email_verification_token(token)
+            .map_err(|_| invalid())?;
+        let token_id = token_id(&claims).ok_or_else(invalid)?;
+
+        UserRepository::verify_email_with_token(pool, token_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(invalid)
+    }
+
+    /// Check whether a user has verified their email address
+    pub async fn is_email_verified(pool: &PgPool, user_id: Uuid) -> Result<bool, ApiError> {
+        UserRepository::is_email_verified(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)
+    }
+
+    /// Start a password reset for the account registered under `email`
+    ///
+    /// Returns the single-use reset token, valid for
+    /// `PASSWORD_RESET_TTL_SECS`, or None if no account uses that email.
+    /// Callers must answer both cases the same way so the endpoint can't be
+    /// used to discover registered emails.
+    pub async fn request_password_reset(
+        pool: &PgPool,
+        jwt_service: &JwtService,
+        email: &str,
+    ) -> Result<Option<String>, ApiError> {
+        let Some(user) = UserRepository::find_by_email(pool, email)
+            .await
+            .map_err(ApiError::Internal)?
+        else {
+            return Ok(None);
+        };
+
+        let (token, claims) = jwt_service
+            .generate_password_reset_token(user.id, PASSWORD_RESET_TTL_SECS)
+            .map_err(ApiError::Internal)?;
+        let token_id = token_id(&claims)
+            .ok_or_else(|| ApiError::Internal(anyhow::anyhow!("Password reset token has no ID")))?;
+
+        UserRepository::create_password_reset_token(
+            pool,
+            token_id,
+            user.id,
+            Utc::now() + Duration::seconds(PASSWORD_RESET_TTL_SECS),
+        )
+        .await
+        .map_err(ApiError::Internal)?;
+
+        Ok(Some(token))
+    }
+
+    /// Set a new password using a password reset token
+    ///
+    /// All tokens issued to the user before the reset are revoked, logging
+    /// out every session.
+    ///
+    /// # Performance
+    /// Password hashing is offloaded to blocking thread pool.
+    pub async fn reset_password(
+        pool: &PgPool,
+        revocations: &dyn CacheStore,
+        jwt_service: &JwtService,
+        token: &str,
+        new_password: &str,
+    ) -> Result<(), ApiError> {
+        let invalid = || ApiError::Validation("Invalid or expired password reset token".to_string());
+
+        let claims = jwt_service
+            .validate_password_reset_token(token)
+            .map_err(|_| invalid())?;
+        let token_id = token_id(&claims).ok_or_else(invalid)?;
+
+        validate_password("new_password", new_password)?;
+        let password_hash = PasswordService::hash_async(new_password.to_string())
+            .await
+            .map_err(ApiError::Internal)?;
+
+        let (user_id, sessions_valid_after) =
+            UserRepository::reset_password_with_token(pool, token_id, &password_hash)
+                .await
+                .map_err(ApiError::Internal)?
+                .ok_or_else(invalid)?;
+
+        // Refresh tokens are checked against the database; access tokens
+        // only need the cutoff for as long as they live
+        let ttl_secs = u64::try_from(jwt_service.access_token_expiry_secs()).unwrap_or(0);
+        revocation::revoke_all_before(
+            revocations,
+            user_id,
+            sessions_valid_after.timestamp(),
+            ttl_secs,
+        )
+        .await
+        .map_err(ApiError::Internal)?;
+
+        Ok(())
+    }
+
+    /// Confirm a user's password before a sensitive operation
+    ///
+    /// # Performance
+    /// Password verification is offloaded to blocking thread pool.
+    pub async fn verify_password(
+        pool: &PgPool,
+        user_id: Uuid,
+        password: &str,
+    ) -> Result<(), ApiError> {
+        let user = UserRepository::find_by_id(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("User not found".to_string()))?;
+
+        let valid = PasswordService::verify_async(password.to_string(), user.password_hash)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        if !valid {
+            return Err(ApiError::Unauthorized("Invalid credentials".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Permanently delete a user's account and all of their data
+    ///
+    /// All health tables are cleared in a single transaction before the user
+    /// row itself is removed. Cached food searches are dropped afterwards
+    /// since they may include the user's custom foods.
+    pub async fn delete_account(
+        pool: &PgPool,
+        cache: Option<&dyn CacheStore>,
+        user_id: Uuid,
+    ) -> Result<DeletionSummary, ApiError> {
+        let summary = DataService::delete_all_user_data(pool, user_id).await?;
+
+        if summary.users == 0 {
+            return Err(ApiError::NotFound("User not found".to_string()));
+        }
+
+        cache::invalidate_prefix(cache, FOOD_SEARCH_CACHE_PREFIX).await;
+        ExerciseService::invalidate_weekly_summaries(cache, user_id).await;
+        ProfileService::invalidate_profile(cache, user_id).await;
+
+        Ok(summary)
+    }
+}
+
+/// Check a new password meets the strength rules, reporting every failure
+/// against `field`
+fn validate_password(field: &str, password: &str) -> Result<(), ApiError> {
+    PasswordService::validate_strength(password).map_err(|failures| {
+        let mut errors = ValidationErrors::new();
+        for failure in &failures {
+            errors.add(field, failure);
+        }
+        ApiError::InvalidFields(errors)
+    })
+}
+
+/// The ID of a single-use token
+fn token_id(claims: &Claims) -> Option<Uuid> {
+    claims.jti.as_deref().and_then(|jti| Uuid::parse_str(jti).ok())
 }
 
 #[cfg(test)]
@@ -4,27 +4,133 @@
 //! - Weight logging with anomaly detection
 //! - Moving average calculations
 //! - Goal projection
+//! - Check-in reminders for users who have stopped logging
 
+use crate::config::WeightConfig;
 use crate::error::ApiError;
 use crate::repositories::{
-    BodyCompositionRepository, CreateBodyCompositionLog, CreateWeightLog, WeightRepository,
+    BodyCompositionRepository, CreateBodyCompositionLog, CreateWeightLog, WeightLogRecord,
+    WeightRepository,
 };
-use chrono::{DateTime, Utc};
+use crate::services::audit::{self, AuditAction};
+use crate::services::jobs::JobManager;
+use crate::timezone;
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use chrono_tz::Tz;
+use fitness_assistant_shared::units::WeightUnit;
+use fitness_assistant_shared::validation::{resolve_source, validate_range};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
 
 /// Anomaly detection threshold: 2% daily change
 const ANOMALY_THRESHOLD_PERCENT: f64 = 2.0;
 
+/// Moving-average windows reported when the caller doesn't ask for any
+const DEFAULT_TREND_WINDOWS: [usize; 2] = [7, 30];
+
+/// Weights this close count as the same reading when deduplicating
+const DEDUP_TOLERANCE_KG: f64 = 0.1;
+
+/// Fastest change per day that body water alone plausibly explains
+const MAX_PLAUSIBLE_DAILY_CHANGE_PERCENT: f64 = 5.0;
+
+/// Change between consecutive entries too large to be real at any pace
+const MAX_PLAUSIBLE_CHANGE_PERCENT: f64 = 25.0;
+
+/// Readings at least this many hours apart in the day may differ by food
+/// and drink rather than a real change
+const TIME_OF_DAY_GAP_HOURS: i64 = 6;
+
+/// Likely explanation for a weight anomaly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyCause {
+    WaterRetention,
+    FluidLoss,
+    TimeOfDay,
+    MeasurementError,
+}
+
+impl AnomalyCause {
+    /// Name stored in the `anomaly_cause` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnomalyCause::WaterRetention => "water_retention",
+            AnomalyCause::FluidLoss => "fluid_loss",
+            AnomalyCause::TimeOfDay => "time_of_day",
+            AnomalyCause::MeasurementError => "measurement_error",
+        }
+    }
+
+    /// Hint shown to users alongside the anomaly flag
+    pub fn hint(&self) -> &'static str {
+        match self {
+            AnomalyCause::WaterRetention => "possible water retention",
+            AnomalyCause::FluidLoss => "possible fluid loss",
+            AnomalyCause::TimeOfDay => "possible time-of-day fluctuation",
+            AnomalyCause::MeasurementError => "possible measurement error",
+        }
+    }
+}
+
+impl std::str::FromStr for AnomalyCause {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "water_retention" => Ok(AnomalyCause::WaterRetention),
+            "fluid_loss" => Ok(AnomalyCause::FluidLoss),
+            "time_of_day" => Ok(AnomalyCause::TimeOfDay),
+            "measurement_error" => Ok(AnomalyCause::MeasurementError),
+            _ => Err(format!("Unknown anomaly cause: {}", s)),
+        }
+    }
+}
+
+/// How often a user means to weigh in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogCadence {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl LogCadence {
+    /// Days between expected check-ins
+    pub fn interval_days(&self) -> i64 {
+        match self {
+            LogCadence::Daily => 1,
+            LogCadence::Weekly => 7,
+        }
+    }
+}
+
+impl std::str::FromStr for LogCadence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "daily" => Ok(LogCadence::Daily),
+            "weekly" => Ok(LogCadence::Weekly),
+            _ => Err("Invalid cadence. Must be one of: daily, weekly".to_string()),
+        }
+    }
+}
+
 /// Weight entry input
 #[derive(Debug, Clone)]
 pub struct WeightEntryInput {
-    pub weight_kg: f64,
+    pub weight: f64,
+    /// Unit the client declared for `weight`; without one it is taken as kg
+    /// and checked against the user's recent weight for a unit mix-up
+    pub unit: Option<WeightUnit>,
     pub recorded_at: DateTime<Utc>,
     pub source: Option<String>,
     pub notes: Option<String>,
+    /// Update a matching entry logged moments earlier instead of adding one
+    pub dedup: bool,
 }
 
 /// Body composition entry input
@@ -48,6 +154,8 @@ pub struct WeightLog {
     pub source: String,
     pub notes: Option<String>,
     pub is_anomaly: bool,
+    /// Likely explanation when `is_anomaly` is set
+    pub likely_cause: Option<AnomalyCause>,
 }
 
 /// Body composition log response
@@ -72,6 +180,7 @@ pub struct WeightTrend {
     pub average_daily_change: f64,
     pub moving_average_7d: Option<f64>,
     pub moving_average_30d: Option<f64>,
+    pub moving_averages: HashMap<usize, f64>,
     pub entries_count: usize,
 }
 
@@ -96,57 +205,208 @@ impl WeightService {
     /// # Property 5: Anomaly Detection Threshold
     /// If the absolute percentage change from the previous entry exceeds 2%,
     /// the entry is flagged as anomalous.
+    ///
+    /// With `input.dedup`, an entry within `config.dedup_window_secs` of one
+    /// with the same weight (±0.1 kg) updates that entry instead, so a sync
+    /// that delivers a reading twice doesn't create two rows.
     pub async fn log_weight(
         pool: &PgPool,
+        jobs: &JobManager,
+        config: &WeightConfig,
         user_id: Uuid,
         input: WeightEntryInput,
     ) -> Result<WeightLog, ApiError> {
-        // Validate weight range
-        if input.weight_kg < 20.0 || input.weight_kg > 500.0 {
-            return Err(ApiError::Validation(
-                "Weight must be between 20 and 500 kg".to_string(),
-            ));
+        let previous = WeightRepository::get_latest(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+        let recent_kg = previous.as_ref().map(|prev| decimal_to_f64(&prev.weight_kg));
+        let weight_kg = Self::resolve_weight_kg(input.weight, input.unit, recent_kg)?;
+        let source = resolve_source(input.source.as_deref())?;
+
+        if input.dedup {
+            let duplicate = WeightRepository::find_near_duplicate(
+                pool,
+                user_id,
+                weight_kg,
+                input.recorded_at,
+                config.dedup_window_secs as f64,
+                DEDUP_TOLERANCE_KG,
+            )
+            .await
+            .map_err(ApiError::Internal)?;
+
+            if let Some(duplicate) = duplicate {
+                let record = WeightRepository::update_reading(
+                    pool,
+                    duplicate.id,
+                    user_id,
+                    weight_kg,
+                    input.recorded_at,
+                    &source,
+                    input.notes.as_deref(),
+                )
+                .await
+                .map_err(ApiError::Internal)?
+                .ok_or_else(|| ApiError::NotFound("Weight entry not found".to_string()))?;
+
+                audit::record(
+                    jobs,
+                    pool,
+                    user_id,
+                    audit::ENTITY_WEIGHT_LOG,
+                    record.id,
+                    AuditAction::Update,
+                );
+
+                return Ok(Self::record_to_log(record));
+            }
         }
 
         // Check for anomaly by comparing with previous entry
-        let is_anomaly = Self::detect_anomaly(pool, user_id, input.weight_kg).await?;
+        let anomaly = previous.and_then(|prev| {
+            Self::detect_anomaly(
+                decimal_to_f64(&prev.weight_kg),
+                prev.recorded_at,
+                weight_kg,
+                input.recorded_at,
+            )
+        });
 
         let create_input = CreateWeightLog {
             user_id,
-            weight_kg: input.weight_kg,
+            weight_kg,
             recorded_at: input.recorded_at,
-            source: input.source.unwrap_or_else(|| "manual".to_string()),
+            source,
             notes: input.notes,
-            is_anomaly,
+            is_anomaly: anomaly.is_some(),
+            anomaly_cause: anomaly.map(|cause| cause.as_str().to_string()),
         };
 
         let record = WeightRepository::create(pool, create_input)
             .await
             .map_err(ApiError::Internal)?;
 
-        Ok(WeightLog {
+        audit::record(
+            jobs,
+            pool,
+            user_id,
+            audit::ENTITY_WEIGHT_LOG,
+            record.id,
+            AuditAction::Create,
+        );
+
+        Ok(Self::record_to_log(record))
+    }
+
+    fn record_to_log(record: WeightLogRecord) -> WeightLog {
+        WeightLog {
             id: record.id,
             weight_kg: decimal_to_f64(&record.weight_kg),
             recorded_at: record.recorded_at,
             source: record.source,
             notes: record.notes,
             is_anomaly: record.is_anomaly,
-        })
+            likely_cause: record.anomaly_cause.as_deref().and_then(|cause| cause.parse().ok()),
+        }
     }
 
     /// Detect if a weight entry is anomalous (>2% change from previous)
-    async fn detect_anomaly(pool: &PgPool, user_id: Uuid, new_weight: f64) -> Result<bool, ApiError> {
-        let previous = WeightRepository::get_latest(pool, user_id)
-            .await
-            .map_err(ApiError::Internal)?;
+    ///
+    /// Returns the likely cause for an anomalous entry and `None` otherwise.
+    fn detect_anomaly(
+        prev_weight: f64,
+        prev_recorded_at: DateTime<Utc>,
+        new_weight: f64,
+        recorded_at: DateTime<Utc>,
+    ) -> Option<AnomalyCause> {
+        let percent_change = ((new_weight - prev_weight) / prev_weight).abs() * 100.0;
+        if percent_change <= ANOMALY_THRESHOLD_PERCENT {
+            return None;
+        }
+        Some(Self::classify_anomaly(
+            prev_weight,
+            prev_recorded_at,
+            new_weight,
+            recorded_at,
+        ))
+    }
 
-        match previous {
-            Some(prev) => {
-                let prev_weight = decimal_to_f64(&prev.weight_kg);
-                let percent_change = ((new_weight - prev_weight) / prev_weight).abs() * 100.0;
-                Ok(percent_change > ANOMALY_THRESHOLD_PERCENT)
+    /// Convert a submitted weight to kg and check it is plausible
+    ///
+    /// A weight sent without a unit that is implausibly far from the user's
+    /// recent weight, but close once read as lbs or stone, is rejected with
+    /// `ApiError::ProbableUnitMismatch`; sending the same weight with a unit
+    /// accepts it.
+    pub fn resolve_weight_kg(
+        weight: f64,
+        unit: Option<WeightUnit>,
+        recent_kg: Option<f64>,
+    ) -> Result<f64, ApiError> {
+        let weight_kg = unit.unwrap_or(WeightUnit::Kg).to_kg(weight);
+        validate_range(weight_kg, 20.0, 500.0, "weight_kg")?;
+
+        if let (None, Some(recent_kg)) = (unit, recent_kg) {
+            if let Some(likely) = Self::likely_unit(weight, recent_kg) {
+                let converted_kg = likely.to_kg(weight);
+                return Err(ApiError::ProbableUnitMismatch {
+                    message: format!(
+                        "No unit was given and {} kg is far from your recent weight of {:.1} kg; \
+                         it may be in {} ({:.1} kg). Include a unit to confirm",
+                        weight,
+                        recent_kg,
+                        likely.abbreviation(),
+                        converted_kg
+                    ),
+                    likely_unit: likely,
+                    converted_kg,
+                });
             }
-            None => Ok(false), // First entry is never anomalous
+        }
+
+        Ok(weight_kg)
+    }
+
+    /// Unit other than kg that brings an implausible `weight` closest to `recent_kg`
+    fn likely_unit(weight: f64, recent_kg: f64) -> Option<WeightUnit> {
+        let distance = |kg: f64| (kg - recent_kg).abs();
+        if distance(weight) / recent_kg * 100.0 <= MAX_PLAUSIBLE_CHANGE_PERCENT {
+            return None;
+        }
+
+        [WeightUnit::Lbs, WeightUnit::Stone]
+            .into_iter()
+            .filter(|unit| distance(unit.to_kg(weight)) < distance(weight))
+            .min_by(|a, b| distance(a.to_kg(weight)).total_cmp(&distance(b.to_kg(weight))))
+    }
+
+    /// Guess why a weight moved more than expected since the previous entry
+    ///
+    /// A change faster than body water can explain (over 5% a day), or over
+    /// 25% in total, points to a measurement error. Otherwise readings taken
+    /// at very different times of day are put down to food and drink, and the
+    /// rest to water retained or lost depending on direction.
+    pub fn classify_anomaly(
+        prev_weight: f64,
+        prev_recorded_at: DateTime<Utc>,
+        new_weight: f64,
+        recorded_at: DateTime<Utc>,
+    ) -> AnomalyCause {
+        let percent_change = (new_weight - prev_weight) / prev_weight * 100.0;
+        let days = ((recorded_at - prev_recorded_at).num_minutes().abs() as f64 / 1440.0).max(1.0);
+
+        if percent_change.abs() > MAX_PLAUSIBLE_CHANGE_PERCENT
+            || percent_change.abs() / days > MAX_PLAUSIBLE_DAILY_CHANGE_PERCENT
+        {
+            return AnomalyCause::MeasurementError;
+        }
+
+        let hour_gap = (recorded_at.hour() as i64 - prev_recorded_at.hour() as i64).abs();
+        if hour_gap.min(24 - hour_gap) >= TIME_OF_DAY_GAP_HOURS {
+            AnomalyCause::TimeOfDay
+        } else if percent_change > 0.0 {
+            AnomalyCause::WaterRetention
+        } else {
+            AnomalyCause::FluidLoss
         }
     }
 
@@ -163,14 +423,7 @@ impl WeightService {
 
         Ok(records
             .into_iter()
-            .map(|r| WeightLog {
-                id: r.id,
-                weight_kg: decimal_to_f64(&r.weight_kg),
-                recorded_at: r.recorded_at,
-                source: r.source,
-                notes: r.notes,
-                is_anomaly: r.is_anomaly,
-            })
+            .map(Self::record_to_log)
             .collect())
     }
 
@@ -193,29 +446,85 @@ impl WeightService {
 
         let logs = records
             .into_iter()
-            .map(|r| WeightLog {
-                id: r.id,
-                weight_kg: decimal_to_f64(&r.weight_kg),
-                recorded_at: r.recorded_at,
-                source: r.source,
-                notes: r.notes,
-                is_anomaly: r.is_anomaly,
-            })
+            .map(Self::record_to_log)
             .collect();
 
         Ok((logs, total_count))
     }
 
+    /// Delete a weight entry
+    ///
+    /// Entries are soft-deleted and can be restored until they are purged.
+    pub async fn delete_weight(
+        pool: &PgPool,
+        jobs: &JobManager,
+        user_id: Uuid,
+        log_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let deleted = WeightRepository::delete(pool, log_id, user_id)
+            .await
+            .map_err(ApiError::Internal)?;
+
+        if !deleted {
+            return Err(ApiError::NotFound("Weight entry not found".to_string()));
+        }
+
+        audit::record(
+            jobs,
+            pool,
+            user_id,
+            audit::ENTITY_WEIGHT_LOG,
+            log_id,
+            AuditAction::Delete,
+        );
+
+        Ok(())
+    }
+
+    /// Restore a previously deleted weight entry
+    pub async fn restore_weight(
+        pool: &PgPool,
+        jobs: &JobManager,
+        user_id: Uuid,
+        log_id: Uuid,
+    ) -> Result<WeightLog, ApiError> {
+        let record = WeightRepository::restore(pool, log_id, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+            .ok_or_else(|| ApiError::NotFound("Deleted weight entry not found".to_string()))?;
+
+        audit::record(
+            jobs,
+            pool,
+            user_id,
+            audit::ENTITY_WEIGHT_LOG,
+            record.id,
+            AuditAction::Restore,
+        );
+
+        Ok(Self::record_to_log(record))
+    }
+
     /// Calculate weight trend analysis
     ///
     /// # Property 3: Moving Average Calculation
     /// The N-day moving average equals the arithmetic mean of the N most recent entries.
+    ///
+    /// `windows` selects the moving averages reported in `moving_averages`;
+    /// an empty slice reports the 7 and 30 entry averages.
     pub async fn get_weight_trend(
         pool: &PgPool,
         user_id: Uuid,
         start: Option<DateTime<Utc>>,
         end: Option<DateTime<Utc>>,
+        windows: &[usize],
     ) -> Result<WeightTrend, ApiError> {
+        if windows.contains(&0) {
+            return Err(ApiError::Validation(
+                "Moving average windows must be positive".to_string(),
+            ));
+        }
+
         let records = WeightRepository::get_by_date_range(pool, user_id, start, end)
             .await
             .map_err(ApiError::Internal)?;
@@ -247,6 +556,7 @@ impl WeightService {
         // Calculate moving averages
         let moving_average_7d = Self::calculate_moving_average(&weights, 7);
         let moving_average_30d = Self::calculate_moving_average(&weights, 30);
+        let moving_averages = Self::calculate_moving_averages(&weights, windows);
 
         Ok(WeightTrend {
             current_weight,
@@ -255,10 +565,41 @@ impl WeightService {
             average_daily_change,
             moving_average_7d,
             moving_average_30d,
+            moving_averages,
             entries_count: records.len(),
         })
     }
 
+    /// Whole days since the user's most recent weight log, in their timezone
+    ///
+    /// Returns `None` when the user has never logged their weight.
+    pub async fn days_since_last_log(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Option<i64>, ApiError> {
+        let Some(latest) = WeightRepository::get_latest(pool, user_id)
+            .await
+            .map_err(ApiError::Internal)?
+        else {
+            return Ok(None);
+        };
+
+        let tz = timezone::user_timezone(pool, user_id).await;
+        let last_date = latest.recorded_at.with_timezone(&tz).date_naive();
+        let days = (timezone::local_today(tz) - last_date).num_days();
+
+        // Entries dated in the future count as logged today
+        Ok(Some(days.max(0)))
+    }
+
+    /// Whether a user has missed a check-in for their cadence
+    ///
+    /// A daily logger is reminded once a full day has been skipped, a weekly
+    /// logger once more than a week has passed.
+    pub fn should_remind(days_since: i64, cadence: LogCadence) -> bool {
+        days_since > cadence.interval_days()
+    }
+
     /// Calculate N-day moving average from weight entries
     ///
     /// # Property 3: Moving Average Calculation
@@ -273,6 +614,118 @@ impl WeightService {
         Some(sum / count as f64)
     }
 
+    /// Moving averages for each requested window, keyed by window size
+    ///
+    /// An empty `windows` slice falls back to the 7 and 30 entry windows.
+    pub fn calculate_moving_averages(weights: &[f64], windows: &[usize]) -> HashMap<usize, f64> {
+        let windows = if windows.is_empty() {
+            &DEFAULT_TREND_WINDOWS[..]
+        } else {
+            windows
+        };
+
+        windows
+            .iter()
+            .filter_map(|&n| Some((n, Self::calculate_moving_average(weights, n)?)))
+            .collect()
+    }
+
+    /// Parse a comma-separated list of moving-average windows such as "14,90"
+    pub fn parse_trend_windows(s: &str) -> Result<Vec<usize>, ApiError> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|window| !window.is_empty())
+            .map(|window| match window.parse::<usize>() {
+                Ok(n) if n > 0 => Ok(n),
+                _ => Err(ApiError::Validation(format!(
+                    "Invalid moving average window: {} (must be a positive integer)",
+                    window
+                ))),
+            })
+            .collect()
+    }
+
+    /// Calculate N-day moving average, ignoring outliers
+    ///
+    /// Points in the window more than `z_threshold` standard deviations from
+    /// the window mean are dropped before averaging, so a single mis-entered
+    /// weight does not skew the trend. Returns `None` for an empty window or
+    /// a non-positive threshold.
+    pub fn calculate_moving_average_robust(
+        weights: &[f64],
+        n: usize,
+        z_threshold: f64,
+    ) -> Option<f64> {
+        if z_threshold.is_nan() || z_threshold <= 0.0 {
+            return None;
+        }
+        let mean = Self::calculate_moving_average(weights, n)?;
+        let window = &weights[..weights.len().min(n)];
+
+        let variance =
+            window.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / window.len() as f64;
+        let std_dev = variance.sqrt();
+        if std_dev == 0.0 {
+            return Some(mean);
+        }
+
+        let kept: Vec<f64> = window
+            .iter()
+            .copied()
+            .filter(|w| ((w - mean) / std_dev).abs() <= z_threshold)
+            .collect();
+        Self::calculate_moving_average(&kept, kept.len())
+    }
+
+    /// Produce one weight per local day between `start` and `end` (inclusive)
+    ///
+    /// Entries are bucketed by their date in `tz`. Days with entries use
+    /// their mean weight; gaps between two logged days
+    /// are linearly interpolated and flagged `true`. Days before the first or
+    /// after the last logged day in the range are omitted rather than
+    /// extrapolated.
+    pub fn interpolate_daily(
+        records: &[WeightLogRecord],
+        start: NaiveDate,
+        end: NaiveDate,
+        tz: Tz,
+    ) -> Vec<(NaiveDate, f64, bool)> {
+        let mut daily: BTreeMap<NaiveDate, (f64, u32)> = BTreeMap::new();
+        for record in records {
+            let date = timezone::local_date(record.recorded_at, tz);
+            if date < start || date > end {
+                continue;
+            }
+            let entry = daily.entry(date).or_insert((0.0, 0));
+            entry.0 += decimal_to_f64(&record.weight_kg);
+            entry.1 += 1;
+        }
+
+        let actual: Vec<(NaiveDate, f64)> = daily
+            .into_iter()
+            .map(|(date, (sum, count))| (date, sum / count as f64))
+            .collect();
+
+        let mut points = Vec::new();
+        for (i, &(date, weight)) in actual.iter().enumerate() {
+            points.push((date, weight, false));
+
+            if let Some(&(next_date, next_weight)) = actual.get(i + 1) {
+                let span = (next_date - date).num_days();
+                for offset in 1..span {
+                    let fraction = offset as f64 / span as f64;
+                    points.push((
+                        date + chrono::Duration::days(offset),
+                        weight + (next_weight - weight) * fraction,
+                        true,
+                    ));
+                }
+            }
+        }
+
+        points
+    }
+
     /// Project goal completion date
     ///
     /// # Property 4: Weight Goal Projection
@@ -348,16 +801,12 @@ impl WeightService {
     /// Log body composition entry
     pub async fn log_body_composition(
         pool: &PgPool,
+        jobs: &JobManager,
         user_id: Uuid,
         input: BodyCompositionInput,
     ) -> Result<BodyCompositionLog, ApiError> {
-        // Validate ranges
         if let Some(bf) = input.body_fat_percent {
-            if bf < 0.0 || bf > 100.0 {
-                return Err(ApiError::Validation(
-                    "Body fat percent must be between 0 and 100".to_string(),
-                ));
-            }
+            validate_range(bf, 0.0, 100.0, "body_fat_percent")?;
         }
 
         let create_input = CreateBodyCompositionLog {
@@ -368,13 +817,22 @@ impl WeightService {
             water_percent: input.water_percent,
             bone_mass_kg: input.bone_mass_kg,
             visceral_fat: input.visceral_fat,
-            source: input.source.unwrap_or_else(|| "manual".to_string()),
+            source: resolve_source(input.source.as_deref())?,
         };
 
         let record = BodyCompositionRepository::create(pool, create_input)
             .await
             .map_err(ApiError::Internal)?;
 
+        audit::record(
+            jobs,
+            pool,
+            user_id,
+            audit::ENTITY_BODY_COMPOSITION,
+            record.id,
+            AuditAction::Create,
+        );
+
         Ok(BodyCompositionLog {
             id: record.id,
             recorded_at: record.recorded_at,
@@ -424,6 +882,61 @@ mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    #[test]
+    fn test_declared_lbs_converted_before_validation() {
+        let weight_kg =
+            WeightService::resolve_weight_kg(330.0, Some(WeightUnit::Lbs), Some(70.0)).unwrap();
+        assert!((weight_kg - 149.7).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_undeclared_unit_far_from_history_flagged() {
+        let result = WeightService::resolve_weight_kg(330.0, None, Some(70.0));
+        match result {
+            Err(ApiError::ProbableUnitMismatch {
+                likely_unit,
+                converted_kg,
+                ..
+            }) => {
+                assert_eq!(likely_unit, WeightUnit::Lbs);
+                assert!((converted_kg - 149.7).abs() < 0.1);
+            }
+            other => panic!("expected a unit mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_undeclared_unit_accepted_near_history_or_without_one() {
+        assert_eq!(WeightService::resolve_weight_kg(72.0, None, Some(70.0)).unwrap(), 72.0);
+        assert_eq!(WeightService::resolve_weight_kg(330.0, None, None).unwrap(), 330.0);
+        // Declaring kg confirms an unusual reading
+        assert_eq!(
+            WeightService::resolve_weight_kg(330.0, Some(WeightUnit::Kg), Some(70.0)).unwrap(),
+            330.0
+        );
+    }
+
+    #[test]
+    fn test_weekly_cadence_reminds_after_a_week() {
+        assert!(!WeightService::should_remind(5, LogCadence::Weekly));
+        assert!(!WeightService::should_remind(7, LogCadence::Weekly));
+        assert!(WeightService::should_remind(8, LogCadence::Weekly));
+    }
+
+    #[test]
+    fn test_daily_cadence_reminds_after_a_skipped_day() {
+        assert!(!WeightService::should_remind(0, LogCadence::Daily));
+        assert!(!WeightService::should_remind(1, LogCadence::Daily));
+        assert!(WeightService::should_remind(2, LogCadence::Daily));
+    }
+
+    #[test]
+    fn test_parse_log_cadence() {
+        assert_eq!("Weekly".parse::<LogCadence>(), Ok(LogCadence::Weekly));
+        assert_eq!("daily".parse::<LogCadence>(), Ok(LogCadence::Daily));
+        assert!("monthly".parse::<LogCadence>().is_err());
+    }
+
     // Feature: fitness-assistant-ai, Property 3: Moving Average Calculation
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(100))]
@@ -463,6 +976,184 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_moving_averages_for_custom_windows() {
+        let weights: Vec<f64> = (0..100).map(|i| 70.0 + i as f64 * 0.1).collect();
+        let averages = WeightService::calculate_moving_averages(&weights, &[14, 90]);
+
+        assert_eq!(averages.len(), 2);
+        let expected_14 = weights[..14].iter().sum::<f64>() / 14.0;
+        let expected_90 = weights[..90].iter().sum::<f64>() / 90.0;
+        assert!((averages[&14] - expected_14).abs() < 1e-9);
+        assert!((averages[&90] - expected_90).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_moving_averages_default_to_7_and_30() {
+        let weights: Vec<f64> = (0..40).map(|i| 80.0 - i as f64 * 0.05).collect();
+        let averages = WeightService::calculate_moving_averages(&weights, &[]);
+
+        let mut windows: Vec<usize> = averages.keys().copied().collect();
+        windows.sort_unstable();
+        assert_eq!(windows, vec![7, 30]);
+        assert_eq!(
+            Some(averages[&7]),
+            WeightService::calculate_moving_average(&weights, 7)
+        );
+        assert_eq!(
+            Some(averages[&30]),
+            WeightService::calculate_moving_average(&weights, 30)
+        );
+    }
+
+    #[test]
+    fn test_parse_trend_windows() {
+        assert_eq!(WeightService::parse_trend_windows("14, 90").unwrap(), vec![14, 90]);
+        assert!(WeightService::parse_trend_windows("").unwrap().is_empty());
+        assert!(WeightService::parse_trend_windows("0").is_err());
+        assert!(WeightService::parse_trend_windows("-7").is_err());
+        assert!(WeightService::parse_trend_windows("week").is_err());
+    }
+
+    #[test]
+    fn test_robust_moving_average_excludes_outlier() {
+        let clean = [80.0, 80.2, 79.8, 80.1, 79.9, 80.0];
+        let mut weights = clean.to_vec();
+        weights.insert(3, 700.0); // typo for 70.0
+
+        let naive = WeightService::calculate_moving_average(&weights, 7).unwrap();
+        let robust = WeightService::calculate_moving_average_robust(&weights, 7, 2.0).unwrap();
+        let clean_mean = clean.iter().sum::<f64>() / clean.len() as f64;
+
+        assert!(naive > 150.0);
+        assert!((robust - clean_mean).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_robust_moving_average_keeps_clean_window() {
+        let weights = [80.0, 80.0, 80.0, 81.0, 79.0];
+
+        assert_eq!(
+            WeightService::calculate_moving_average_robust(&weights, 3, 2.0),
+            Some(80.0)
+        );
+        assert_eq!(
+            WeightService::calculate_moving_average_robust(&weights, 5, 3.0),
+            WeightService::calculate_moving_average(&weights, 5)
+        );
+        assert_eq!(WeightService::calculate_moving_average_robust(&[], 7, 2.0), None);
+        assert_eq!(WeightService::calculate_moving_average_robust(&weights, 5, 0.0), None);
+    }
+
+    fn weight_record(recorded_at: &str, weight_kg: f64) -> WeightLogRecord {
+        let recorded_at = DateTime::parse_from_rfc3339(recorded_at)
+            .unwrap()
+            .with_timezone(&Utc);
+        WeightLogRecord {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            weight_kg: Decimal::from_f64_retain(weight_kg).unwrap(),
+            recorded_at,
+            source: "manual".to_string(),
+            notes: None,
+            is_anomaly: false,
+            anomaly_cause: None,
+            created_at: recorded_at,
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_interpolate_daily_fills_gap_with_synthetic_points() {
+        // Logged on the 1st and 5th, leaving a 3-day gap
+        let records = vec![
+            weight_record("2024-03-05T07:00:00Z", 78.0),
+            weight_record("2024-03-01T07:00:00Z", 80.0),
+        ];
+
+        let points = WeightService::interpolate_daily(
+            &records,
+            date("2024-03-01"),
+            date("2024-03-05"),
+            Tz::UTC,
+        );
+
+        let expected = [
+            ("2024-03-01", 80.0, false),
+            ("2024-03-02", 79.5, true),
+            ("2024-03-03", 79.0, true),
+            ("2024-03-04", 78.5, true),
+            ("2024-03-05", 78.0, false),
+        ];
+        assert_eq!(points.len(), expected.len());
+        for ((day, weight, synthetic), (exp_day, exp_weight, exp_synthetic)) in
+            points.iter().zip(expected)
+        {
+            assert_eq!(*day, date(exp_day));
+            assert!((weight - exp_weight).abs() < 1e-9);
+            assert_eq!(*synthetic, exp_synthetic);
+        }
+    }
+
+    #[test]
+    fn test_interpolate_daily_averages_same_day_and_skips_out_of_range() {
+        let records = vec![
+            weight_record("2024-03-02T07:00:00Z", 80.0),
+            weight_record("2024-03-02T20:00:00Z", 81.0),
+            weight_record("2024-03-03T07:00:00Z", 80.0),
+            weight_record("2024-03-10T07:00:00Z", 70.0),
+        ];
+
+        let points = WeightService::interpolate_daily(
+            &records,
+            date("2024-03-01"),
+            date("2024-03-05"),
+            Tz::UTC,
+        );
+
+        assert_eq!(
+            points,
+            vec![
+                (date("2024-03-02"), 80.5, false),
+                (date("2024-03-03"), 80.0, false),
+            ]
+        );
+        assert!(WeightService::interpolate_daily(
+            &[],
+            date("2024-03-01"),
+            date("2024-03-05"),
+            Tz::UTC
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_interpolate_daily_buckets_by_local_date() {
+        // 23:30 on Mar 2 in New York is 04:30 on Mar 3 in UTC
+        let records = vec![
+            weight_record("2024-03-03T04:30:00Z", 80.0),
+            weight_record("2024-03-03T13:00:00Z", 79.0),
+        ];
+
+        let points = WeightService::interpolate_daily(
+            &records,
+            date("2024-03-01"),
+            date("2024-03-05"),
+            Tz::America__New_York,
+        );
+
+        assert_eq!(
+            points,
+            vec![
+                (date("2024-03-02"), 80.0, false),
+                (date("2024-03-03"), 79.0, false),
+            ]
+        );
+    }
+
     // Feature: fitness-assistant-ai, Property 5: Anomaly Detection Threshold
     #[test]
     fn test_anomaly_threshold_exactly_2_percent() {
@@ -492,6 +1183,80 @@ mod tests {
         assert!(percent_change <= ANOMALY_THRESHOLD_PERCENT);
     }
 
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_large_overnight_gain_is_water_retention() {
+        let cause = WeightService::classify_anomaly(
+            80.0,
+            at("2024-05-01T07:00:00Z"),
+            82.4,
+            at("2024-05-02T07:30:00Z"),
+        );
+        assert_eq!(cause, AnomalyCause::WaterRetention);
+        assert_eq!(cause.hint(), "possible water retention");
+    }
+
+    #[test]
+    fn test_implausible_change_is_measurement_error() {
+        let cause = WeightService::classify_anomaly(
+            80.0,
+            at("2024-05-01T07:00:00Z"),
+            120.0,
+            at("2024-05-02T07:00:00Z"),
+        );
+        assert_eq!(cause, AnomalyCause::MeasurementError);
+        assert_eq!(cause.hint(), "possible measurement error");
+
+        // Halving is implausible even spread over a fortnight
+        let cause = WeightService::classify_anomaly(
+            80.0,
+            at("2024-05-01T07:00:00Z"),
+            40.0,
+            at("2024-05-15T07:00:00Z"),
+        );
+        assert_eq!(cause, AnomalyCause::MeasurementError);
+    }
+
+    #[test]
+    fn test_anomaly_cause_uses_direction_and_time_of_day() {
+        let morning = at("2024-05-01T07:00:00Z");
+
+        assert_eq!(
+            WeightService::classify_anomaly(80.0, morning, 78.0, at("2024-05-02T08:00:00Z")),
+            AnomalyCause::FluidLoss
+        );
+        assert_eq!(
+            WeightService::classify_anomaly(80.0, morning, 82.0, at("2024-05-01T21:00:00Z")),
+            AnomalyCause::TimeOfDay
+        );
+        // Hours wrap around midnight
+        assert_eq!(
+            WeightService::classify_anomaly(
+                80.0,
+                at("2024-05-01T23:00:00Z"),
+                82.0,
+                at("2024-05-03T01:00:00Z")
+            ),
+            AnomalyCause::WaterRetention
+        );
+    }
+
+    #[test]
+    fn test_parse_anomaly_cause() {
+        for cause in [
+            AnomalyCause::WaterRetention,
+            AnomalyCause::FluidLoss,
+            AnomalyCause::TimeOfDay,
+            AnomalyCause::MeasurementError,
+        ] {
+            assert_eq!(cause.as_str().parse::<AnomalyCause>(), Ok(cause));
+        }
+        assert!("gremlins".parse::<AnomalyCause>().is_err());
+    }
+
     // Feature: fitness-assistant-ai, Property 4: Weight Goal Projection
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(100))]
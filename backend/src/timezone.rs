@@ -0,0 +1,138 @@
+//! Timezone helpers for bucketing entries by the user's local day
+//!
+//! Timestamps are stored in UTC. Daily summaries convert them to the
+//! user's configured IANA timezone (e.g. "Australia/Sydney") so that an
+//! entry logged late in the evening counts towards the local day.
+
+use crate::error::ApiError;
+use crate::repositories::UserRepository;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use sqlx::PgPool;
+
+/// Parse an IANA timezone name
+pub fn parse_timezone(name: &str) -> Result<Tz, ApiError> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| ApiError::Validation(format!("Invalid timezone: {}", name)))
+}
+
+/// Get the user's configured timezone
+///
+/// Falls back to UTC when settings are missing or hold an unknown name.
+pub async fn user_timezone(pool: &PgPool, user_id: uuid::Uuid) -> Tz {
+    UserRepository::get_settings(pool, user_id)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|s| s.timezone.parse::<Tz>().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+/// Local calendar date of a UTC timestamp
+pub fn local_date(timestamp: DateTime<Utc>, tz: Tz) -> NaiveDate {
+    timestamp.with_timezone(&tz).date_naive()
+}
+
+/// Today's date in the given timezone
+pub fn local_today(tz: Tz) -> NaiveDate {
+    local_date(Utc::now(), tz)
+}
+
+/// UTC instants bounding a local day, as a half-open `[start, end)` range
+pub fn local_day_bounds(date: NaiveDate, tz: Tz) -> (DateTime<Utc>, DateTime<Utc>) {
+    let next = date.succ_opt().unwrap_or(date);
+    (start_of_local_day(date, tz), start_of_local_day(next, tz))
+}
+
+/// First instant of a local day
+///
+/// Midnight can be skipped by a DST transition in a few zones; the day then
+/// starts at the first valid local time after it.
+fn start_of_local_day(date: NaiveDate, tz: Tz) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+
+    (0..=2)
+        .find_map(|hours| {
+            tz.from_local_datetime(&(midnight + Duration::hours(hours)))
+                .earliest()
+        })
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&midnight))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(
+            parse_timezone("Australia/Sydney").unwrap(),
+            Tz::Australia__Sydney
+        );
+        assert_eq!(parse_timezone("UTC").unwrap(), Tz::UTC);
+        assert!(matches!(
+            parse_timezone("Mars/Olympus_Mons"),
+            Err(ApiError::Validation(_))
+        ));
+        assert!(matches!(parse_timezone(""), Err(ApiError::Validation(_))));
+    }
+
+    #[test]
+    fn test_late_evening_entry_behind_utc_stays_on_local_day() {
+        // 23:00 on Jan 15 in New York is 04:00 on Jan 16 in UTC
+        let tz = parse_timezone("America/New_York").unwrap();
+        let consumed_at = utc("2024-01-16T04:00:00Z");
+
+        let local = local_date(consumed_at, tz);
+        assert_eq!(local, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+
+        let (start, end) = local_day_bounds(local, tz);
+        assert!(start <= consumed_at && consumed_at < end);
+    }
+
+    #[test]
+    fn test_late_evening_entry_ahead_of_utc_stays_on_local_day() {
+        // 23:00 on Jan 15 in Sydney (UTC+11 in summer) is 12:00 on Jan 15 UTC,
+        // and 08:00 on Jan 16 local is still Jan 15 in UTC
+        let tz = parse_timezone("Australia/Sydney").unwrap();
+        let late = utc("2024-01-15T12:00:00Z");
+        let morning = utc("2024-01-15T21:00:00Z");
+
+        assert_eq!(
+            local_date(late, tz),
+            NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+        );
+        assert_eq!(
+            local_date(morning, tz),
+            NaiveDate::from_ymd_opt(2024, 1, 16).unwrap()
+        );
+
+        let (start, end) = local_day_bounds(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), tz);
+        assert!(start <= late && late < end);
+        assert!(morning >= end);
+    }
+
+    #[test]
+    fn test_day_bounds_in_utc_match_calendar_day() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let (start, end) = local_day_bounds(date, Tz::UTC);
+
+        assert_eq!(start, utc("2024-03-01T00:00:00Z"));
+        assert_eq!(end, utc("2024-03-02T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_day_bounds_span_dst_transition() {
+        // New York springs forward on 2024-03-10, so that day is 23 hours long
+        let tz = parse_timezone("America/New_York").unwrap();
+        let (start, end) = local_day_bounds(NaiveDate::from_ymd_opt(2024, 3, 10).unwrap(), tz);
+
+        assert_eq!(end - start, Duration::hours(23));
+    }
+}
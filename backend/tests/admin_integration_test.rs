@@ -0,0 +1,164 @@
+//! Integration tests for admin-only endpoints
+
+mod common;
+
+use axum::http::StatusCode;
+use fitness_assistant_backend::services::UserService;
+use serde_json::json;
+
+async fn admin_token(app: &common::TestApp) -> String {
+    let email = format!("admin_{}@example.com", uuid::Uuid::new_v4());
+    let password = "SecurePassword123!";
+    UserService::create_admin(&app.pool, &email, password).await.unwrap();
+    app.verify_email(&email).await;
+
+    app.login(&email, password).await.unwrap().access_token
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_admin_can_add_library_exercise() {
+    let app = common::TestApp::new().await;
+    let token = admin_token(&app).await;
+
+    let (_, response) = app.get_auth("/api/v1/auth/me", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(profile["role"], "admin");
+
+    let body = json!({
+        "name": format!("Admin Press {}", uuid::Uuid::new_v4()),
+        "category": "strength",
+        "muscle_groups": ["chest"]
+    });
+    let (status, response) = app
+        .post_auth("/api/v1/admin/exercises", &body.to_string(), &token)
+        .await;
+
+    assert_eq!(status, StatusCode::OK);
+    let exercise: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(exercise["is_custom"], false);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_admin_routes_forbid_regular_users() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!([{ "name": "Sneaky Squat", "category": "strength", "muscle_groups": [] }]);
+    let (status, _) = app
+        .post_auth("/api/v1/admin/exercises/seed", &body.to_string(), &token)
+        .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_admin_routes_forbid_unverified_admins() {
+    let app = common::TestApp::new().await;
+    let email = format!("admin_{}@example.com", uuid::Uuid::new_v4());
+    let password = "SecurePassword123!";
+    UserService::create_admin(&app.pool, &email, password).await.unwrap();
+    let token = app.login(&email, password).await.unwrap().access_token;
+
+    let (status, _) = app.post_auth("/api/v1/admin/exercises/seed", "[]", &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    app.verify_email(&email).await;
+    let (status, _) = app.post_auth("/api/v1/admin/exercises/seed", "[]", &token).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_admin_routes_require_auth() {
+    let app = common::TestApp::new().await;
+
+    let (status, _) = app.post("/api/v1/admin/exercises/seed", "[]").await;
+
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_admin_food_import_skips_duplicate_barcodes() {
+    let app = common::TestApp::new().await;
+    let token = admin_token(&app).await;
+    let run = uuid::Uuid::new_v4().simple().to_string();
+
+    let mut items: Vec<serde_json::Value> = (0..100)
+        .map(|i| {
+            json!({
+                "name": format!("Imported Food {} {}", i, run),
+                "barcode": format!("{}-{}", &run[..16], i),
+                "serving_size": 100.0,
+                "serving_unit": "g",
+                "calories": 120.0,
+                "protein_g": 5.0,
+                "carbohydrates_g": 20.0,
+                "fat_g": 2.0
+            })
+        })
+        .collect();
+    // Repeats the first item's barcode within the batch
+    let mut duplicate = items[0].clone();
+    duplicate["name"] = json!(format!("Duplicate Food {}", run));
+    items.push(duplicate);
+
+    let body = serde_json::Value::Array(items).to_string();
+    let (status, response) = app.post_auth("/api/v1/admin/foods/import", &body, &token).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let result: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(result["inserted"], 100);
+
+    // Every barcode is now stored, so a second import adds nothing
+    let (_, response) = app.post_auth("/api/v1/admin/foods/import", &body, &token).await;
+    let result: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(result["inserted"], 0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_admin_food_import_rejects_invalid_rows() {
+    let app = common::TestApp::new().await;
+    let token = admin_token(&app).await;
+    let run = uuid::Uuid::new_v4().simple().to_string();
+
+    let valid = json!({
+        "name": format!("Imported Food {}", run),
+        "barcode": format!("{}-ok", &run[..16]),
+        "serving_size": 100.0,
+        "serving_unit": "g",
+        "calories": 120.0,
+        "protein_g": 5.0,
+        "carbohydrates_g": 20.0,
+        "fat_g": 2.0
+    });
+    let invalid = json!({
+        "name": "x".repeat(300),
+        "serving_size": 100.0,
+        "serving_unit": "g",
+        "calories": 1.0e12,
+        "protein_g": 5.0,
+        "carbohydrates_g": 20.0,
+        "fat_g": 2.0,
+        "sodium_mg": -10.0
+    });
+
+    let body = json!([valid, invalid]).to_string();
+    let (status, response) = app.post_auth("/api/v1/admin/foods/import", &body, &token).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(response.contains("items[1].name"));
+    assert!(response.contains("items[1].calories"));
+    assert!(response.contains("items[1].sodium_mg"));
+
+    // The whole batch is rejected, including the valid row
+    let path = format!("/api/v1/nutrition/barcode/{}-ok", &run[..16]);
+    let (status, response) = app.get_auth(&path, &token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(response, "null");
+}
@@ -3,6 +3,11 @@
 mod common;
 
 use axum::http::StatusCode;
+use fitness_assistant_backend::services::{
+    nutrition::CreateFoodItemInput, DataService, NutritionService, UserService,
+};
+use fitness_assistant_backend::{routes, state::AppState};
+use rust_decimal::Decimal;
 use serde_json::json;
 
 #[tokio::test]
@@ -71,9 +76,28 @@ async fn test_register_weak_password() {
         "password": "123"
     });
     
-    let (status, _) = app.post("/api/v1/auth/register", &body.to_string()).await;
+    let (status, response) = app.post("/api/v1/auth/register", &body.to_string()).await;
     
     assert_eq!(status, StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let failures = error["error"]["fields"]["password"].as_array().unwrap();
+    assert_eq!(failures.len(), 3, "length, lowercase and uppercase rules: {:?}", failures);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_password_reset_rejects_weak_password() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+
+    let token = request_password_reset(&app, &user.email).await.unwrap();
+    let body = json!({ "token": token, "new_password": "password1234" }).to_string();
+    let (status, response) = app.post("/api/v1/auth/password-reset", &body).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert!(error["error"]["fields"]["new_password"].is_array());
+    assert!(app.login(&user.email, &user.password).await.is_ok());
 }
 
 #[tokio::test]
@@ -188,3 +212,359 @@ async fn test_protected_endpoint_with_expired_token() {
     
     assert_eq!(status, StatusCode::UNAUTHORIZED);
 }
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_delete_account_requires_password() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "password": "WrongPassword123!" });
+    let (status, _) = app.delete_auth("/api/v1/auth/me", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = app.get_auth("/api/v1/auth/me", &token).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_delete_account_removes_all_data() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, response) = app.get_auth("/api/v1/auth/me", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let user_id = uuid::Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap();
+
+    // Seed data across several tables
+    let seeds = [
+        ("/api/v1/weight", json!({ "weight": 75.0 })),
+        ("/api/v1/hydration", json!({ "amount_ml": 500 })),
+        (
+            "/api/v1/sleep",
+            json!({
+                "sleep_start": "2024-01-01T23:00:00Z",
+                "sleep_end": "2024-01-02T07:00:00Z"
+            }),
+        ),
+        (
+            "/api/v1/exercise/custom",
+            json!({
+                "name": "Erase Test Lift",
+                "category": "strength",
+                "muscle_groups": ["chest"]
+            }),
+        ),
+    ];
+    for (path, body) in seeds {
+        let (status, response) = app.post_auth(path, &body.to_string(), &token).await;
+        assert!(status.is_success(), "seeding {} failed: {}", path, response);
+    }
+
+    let body = json!({ "password": user.password });
+    let (status, response) = app.delete_auth("/api/v1/auth/me", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let summary: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(summary["users"], 1);
+    assert_eq!(summary["weight_logs"], 1);
+    assert_eq!(summary["hydration_logs"], 1);
+    assert_eq!(summary["sleep_logs"], 1);
+    assert_eq!(summary["custom_exercises"], 1);
+
+    let erased = DataService::verify_deletion(&app.pool, user_id).await.unwrap();
+    assert!(erased);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_delete_account_keeps_foods_other_users_rely_on() {
+    let app = common::TestApp::new().await;
+    let owner = app.create_test_user().await;
+    let owner_token = owner.tokens.as_ref().unwrap().access_token.clone();
+    let other = app.create_test_user().await;
+    let other_token = other.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, response) = app.get_auth("/api/v1/auth/me", &owner_token).await;
+    let profile: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let owner_id = uuid::Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap();
+
+    let create_food = |name: &str| {
+        NutritionService::create_food_item(
+            &app.pool,
+            owner_id,
+            CreateFoodItemInput {
+                name: format!("{} {}", name, uuid::Uuid::new_v4()),
+                serving_size: Decimal::new(100, 0),
+                serving_unit: "g".to_string(),
+                calories: Decimal::new(150, 0),
+                ..Default::default()
+            },
+        )
+    };
+    let shared_id = create_food("Shared Oats").await.unwrap().id;
+    let private_id = create_food("Private Oats").await.unwrap().id;
+
+    // The other user favorites, templates and logs the owner's food
+    let path = format!("/api/v1/nutrition/favorites/{}", shared_id);
+    let (status, _) = app.post_auth(&path, "", &other_token).await;
+    assert_eq!(status, StatusCode::OK);
+    let body = json!({
+        "name": "Breakfast",
+        "items": [{ "food_item_id": shared_id, "servings": 1.0 }]
+    });
+    let (status, _) = app.post_auth("/api/v1/nutrition/templates", &body.to_string(), &other_token).await;
+    assert_eq!(status, StatusCode::OK);
+    let body = json!({ "food_item_id": shared_id, "servings": 1.0, "meal_type": "breakfast" });
+    let (status, _) = app.post_auth("/api/v1/nutrition/log", &body.to_string(), &other_token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let body = json!({ "password": owner.password });
+    let (status, response) = app.delete_auth("/api/v1/auth/me", &body.to_string(), &owner_token).await;
+    assert_eq!(status, StatusCode::OK);
+    let summary: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(summary["custom_food_items"], 1);
+
+    let (_, response) = app.get_auth("/api/v1/nutrition/favorites", &other_token).await;
+    let favorites: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(favorites[0]["id"], shared_id.to_string());
+
+    let (_, response) = app.get_auth("/api/v1/nutrition/templates", &other_token).await;
+    let templates: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(templates[0]["items"].as_array().unwrap().len(), 1);
+
+    let remaining: Vec<(uuid::Uuid, Option<uuid::Uuid>)> =
+        sqlx::query_as("SELECT id, created_by FROM food_items WHERE id = ANY($1)")
+            .bind(vec![shared_id, private_id])
+            .fetch_all(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(remaining, vec![(shared_id, None)]);
+
+    let logged: Option<uuid::Uuid> =
+        sqlx::query_scalar("SELECT food_item_id FROM food_logs WHERE food_item_id = $1")
+            .bind(shared_id)
+            .fetch_optional(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(logged, Some(shared_id));
+
+    assert!(DataService::verify_deletion(&app.pool, owner_id).await.unwrap());
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_create_admin_can_log_in() {
+    let app = common::TestApp::new().await;
+
+    let email = format!("admin_test_{}@example.com", uuid::Uuid::new_v4());
+    let password = "SecurePassword123!";
+
+    let user_id = UserService::create_admin(&app.pool, &email, password).await.unwrap();
+
+    let role: String = sqlx::query_scalar("SELECT role FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(role, "admin");
+
+    let login_body = json!({ "email": email, "password": password });
+    let (status, _) = app.post("/api/v1/auth/login", &login_body.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_logout_requires_auth() {
+    let app = common::TestApp::new().await;
+
+    let (status, _) = app.post("/api/v1/auth/logout", "").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+    let (status, _) = app.post_auth("/api/v1/auth/logout", "", &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_verify_email_with_valid_token() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let access_token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, response) = app.get_auth("/api/v1/auth/me", &access_token).await;
+    let profile: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(profile["email_verified"], false);
+
+    let token = app.issue_verification_token(&user.email).await;
+    let body = json!({ "token": token }).to_string();
+    let (status, _) = app.post("/api/v1/auth/verify-email", &body).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (_, response) = app.get_auth("/api/v1/auth/me", &access_token).await;
+    let profile: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(profile["email_verified"], true);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_verify_email_with_expired_token_fails() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+
+    let token = app.issue_verification_token(&user.email).await;
+    sqlx::query(
+        "UPDATE email_verification_tokens SET expires_at = NOW() - INTERVAL '1 minute'
+         WHERE user_id = (SELECT id FROM users WHERE email = $1)",
+    )
+    .bind(&user.email)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let body = json!({ "token": token }).to_string();
+    let (status, _) = app.post("/api/v1/auth/verify-email", &body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_verification_token_cannot_be_reused() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+
+    let token = app.issue_verification_token(&user.email).await;
+    let body = json!({ "token": token }).to_string();
+
+    let (status, _) = app.post("/api/v1/auth/verify-email", &body).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = app.post("/api/v1/auth/verify-email", &body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_access_token_cannot_verify_email() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let access_token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "token": access_token }).to_string();
+    let (status, _) = app.post("/api/v1/auth/verify-email", &body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+async fn request_password_reset(app: &common::TestApp, email: &str) -> Option<String> {
+    UserService::request_password_reset(&app.pool, app.state.jwt(), email)
+        .await
+        .expect("Failed to request password reset")
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_password_reset_success() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let old_refresh_token = user.tokens.as_ref().unwrap().refresh_token.clone();
+
+    // Token issue times have one-second resolution
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+    let body = json!({ "email": user.email }).to_string();
+    let (status, _) = app.post("/api/v1/auth/password-reset/request", &body).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+
+    // The token is emailed in the background
+    app.state.jobs().shutdown(std::time::Duration::from_secs(5)).await;
+    let sent = app.mailer.password_resets();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].email, user.email);
+
+    let body = json!({ "token": sent[0].token, "new_password": "NewPassword456!" }).to_string();
+    let (status, _) = app.post("/api/v1/auth/password-reset", &body).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    assert!(app.login(&user.email, &user.password).await.is_err());
+    assert!(app.login(&user.email, "NewPassword456!").await.is_ok());
+
+    // Existing sessions are logged out
+    let body = json!({ "refresh_token": old_refresh_token }).to_string();
+    let (status, _) = app.post("/api/v1/auth/refresh", &body).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_password_reset_token_cannot_be_reused() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+
+    let token = request_password_reset(&app, &user.email).await.unwrap();
+
+    let body = json!({ "token": token, "new_password": "NewPassword456!" }).to_string();
+    let (status, _) = app.post("/api/v1/auth/password-reset", &body).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let body = json!({ "token": token, "new_password": "OtherPassword789!" }).to_string();
+    let (status, _) = app.post("/api/v1/auth/password-reset", &body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    assert!(app.login(&user.email, "NewPassword456!").await.is_ok());
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_password_reset_for_unknown_email_succeeds_without_side_effects() {
+    let app = common::TestApp::new().await;
+    let email = format!("nobody_{}@example.com", uuid::Uuid::new_v4());
+
+    let body = json!({ "email": email }).to_string();
+    let (status, _) = app.post("/api/v1/auth/password-reset/request", &body).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+
+    app.state.jobs().shutdown(std::time::Duration::from_secs(5)).await;
+    assert!(app.mailer.password_resets().is_empty());
+    assert!(request_password_reset(&app, &email).await.is_none());
+
+    let users: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE email = $1")
+        .bind(&email)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(users, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_password_reset_unavailable_without_mailer() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let state = AppState {
+        mailer: None,
+        ..app.state.clone()
+    };
+    let app = common::TestApp {
+        app: routes::create_router(state),
+        ..app
+    };
+
+    let body = json!({ "email": user.email }).to_string();
+    let (status, _) = app.post("/api/v1/auth/password-reset/request", &body).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+    let tokens: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM password_reset_tokens t JOIN users u ON u.id = t.user_id WHERE u.email = $1",
+    )
+    .bind(&user.email)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(tokens, 0);
+}
@@ -0,0 +1,251 @@
+//! Integration tests for heart rate and HRV log management
+
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+/// Log a reading and return its id
+async fn log_entry(app: &common::TestApp, token: &str, path: &str, body: serde_json::Value) -> String {
+    let (status, response) = app.post_auth(path, &body.to_string(), token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    response["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_delete_heart_rate_and_hrv_logs() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let hr_id = log_entry(&app, &token, "/api/v1/biometrics/heart-rate", json!({ "bpm": 58 })).await;
+    let hrv_id = log_entry(&app, &token, "/api/v1/biometrics/hrv", json!({ "rmssd": 45.0 })).await;
+
+    let hr_path = format!("/api/v1/biometrics/heart-rate/{}", hr_id);
+    let (status, _) = app.delete_auth(&hr_path, "", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let hrv_path = format!("/api/v1/biometrics/hrv/{}", hrv_id);
+    let (status, _) = app.delete_auth(&hrv_path, "", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Deleting again reports the entries as missing
+    let (status, _) = app.delete_auth(&hr_path, "", &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = app.delete_auth(&hrv_path, "", &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_cannot_delete_another_users_logs() {
+    let app = common::TestApp::new().await;
+    let owner = app.create_test_user().await;
+    let owner_token = owner.tokens.as_ref().unwrap().access_token.clone();
+    let other = app.create_test_user().await;
+    let other_token = other.tokens.as_ref().unwrap().access_token.clone();
+
+    let hr_id = log_entry(&app, &owner_token, "/api/v1/biometrics/heart-rate", json!({ "bpm": 58 })).await;
+    let hrv_id = log_entry(&app, &owner_token, "/api/v1/biometrics/hrv", json!({ "rmssd": 45.0 })).await;
+
+    let (status, _) = app
+        .delete_auth(&format!("/api/v1/biometrics/heart-rate/{}", hr_id), "", &other_token)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let hrv_path = format!("/api/v1/biometrics/hrv/{}", hrv_id);
+    let (status, _) = app.delete_auth(&hrv_path, "", &other_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // The owner can still delete them
+    let (status, _) = app.delete_auth(&hrv_path, "", &owner_token).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_update_hrv_log_corrects_rmssd() {
+    let app = common::TestApp::new().await;
+    let owner = app.create_test_user().await;
+    let owner_token = owner.tokens.as_ref().unwrap().access_token.clone();
+    let other = app.create_test_user().await;
+    let other_token = other.tokens.as_ref().unwrap().access_token.clone();
+
+    let hrv_id = log_entry(&app, &owner_token, "/api/v1/biometrics/hrv", json!({ "rmssd": 450.0 })).await;
+    let path = format!("/api/v1/biometrics/hrv/{}", hrv_id);
+    let body = json!({ "rmssd": 45.0 }).to_string();
+
+    let (status, _) = app.put_auth(&path, &body, &other_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, response) = app.put_auth(&path, &body, &owner_token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["rmssd"], 45.0);
+    assert_eq!(response["context"], "morning");
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_ingest_heart_rate_stream_updates_workout() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "workout_type": "cardio" });
+    let (status, response) = app
+        .post_auth("/api/v1/exercise/workout", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let workout_id = response["workout"]["id"].as_str().unwrap().to_string();
+
+    let start = chrono::Utc::now() - chrono::Duration::hours(1);
+    let samples: Vec<serde_json::Value> = (0..60)
+        .map(|i| {
+            json!({
+                "recorded_at": start + chrono::Duration::seconds(i),
+                "bpm": 120 + i,
+            })
+        })
+        .collect();
+    let body = json!({ "samples": samples });
+    let path = format!("/api/v1/biometrics/workouts/{}/heart-rate-stream", workout_id);
+    let (status, response) = app.post_auth(&path, &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["sample_count"], 60);
+    assert_eq!(response["avg_bpm"], 150);
+    assert_eq!(response["max_bpm"], 179);
+
+    let (status, response) = app
+        .get_auth(&format!("/api/v1/exercise/workout/{}", workout_id), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["workout"]["avg_heart_rate"], 150);
+    assert_eq!(response["workout"]["max_heart_rate"], 179);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_heart_rate_ranges_depend_on_source() {
+    let app = common::TestApp::with_config(|config| {
+        config.biometrics.ranges.min_bpm = 40;
+        config.biometrics.sources.insert(
+            "chest_strap".to_string(),
+            fitness_assistant_backend::config::BiometricRanges::default(),
+        );
+    })
+    .await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let path = "/api/v1/biometrics/heart-rate";
+    let body = json!({ "bpm": 35, "context": "resting", "source": "chest_strap" });
+    let (status, _) = app.post_auth(path, &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let body = json!({ "bpm": 35, "context": "resting", "source": "garmin" });
+    let (status, _) = app.post_auth(path, &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let body = json!({ "bpm": 350, "source": "chest_strap" });
+    let (status, _) = app.post_auth(path, &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_zone_distribution_includes_heart_rate_stream() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "workout_type": "cardio" });
+    let (status, response) = app
+        .post_auth("/api/v1/exercise/workout", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let workout_id = response["workout"]["id"].as_str().unwrap().to_string();
+
+    let start = chrono::Utc::now() - chrono::Duration::hours(1);
+    let samples: Vec<serde_json::Value> = (0..60)
+        .map(|i| json!({ "recorded_at": start + chrono::Duration::seconds(i), "bpm": 150 }))
+        .collect();
+    let body = json!({ "samples": samples });
+    let path = format!("/api/v1/biometrics/workouts/{}/heart-rate-stream", workout_id);
+    let (status, _) = app.post_auth(&path, &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let today = chrono::Utc::now().date_naive();
+    let path = format!(
+        "/api/v1/biometrics/zones/distribution?start_date={}&end_date={}",
+        today - chrono::Duration::days(1),
+        today
+    );
+    let (status, response) = app.get_auth(&path, &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let zones: Vec<serde_json::Value> = serde_json::from_str(&response).unwrap();
+    let total: i64 = zones
+        .iter()
+        .map(|z| z["duration_seconds"].as_i64().unwrap())
+        .sum();
+    assert_eq!(total, 60);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_resting_hr_analysis_median_baseline() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    // Three readings in the previous week, one of them an outlier
+    let recorded_at = chrono::Utc::now() - chrono::Duration::days(10);
+    for bpm in [58, 60, 95] {
+        let body = json!({ "bpm": bpm, "context": "resting", "recorded_at": recorded_at });
+        log_entry(&app, &token, "/api/v1/biometrics/heart-rate", body).await;
+    }
+
+    let path = "/api/v1/biometrics/heart-rate/analysis";
+    let (status, response) = app.get_auth(path, &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["baseline_avg"], 71.0);
+
+    let (status, response) = app.get_auth(&format!("{}?baseline=median", path), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["baseline_avg"], 60.0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_recovery_score_accounts_for_elevated_resting_hr() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    log_entry(&app, &token, "/api/v1/biometrics/hrv", json!({ "rmssd": 50.0 })).await;
+
+    // Latest resting reading sits well above the earlier ones
+    let now = chrono::Utc::now();
+    for (hours_ago, bpm) in [(3, 55), (2, 55), (1, 75)] {
+        let recorded_at = now - chrono::Duration::hours(hours_ago);
+        let body = json!({ "bpm": bpm, "context": "resting", "recorded_at": recorded_at });
+        log_entry(&app, &token, "/api/v1/biometrics/heart-rate", body).await;
+    }
+
+    let (status, response) = app.get_auth("/api/v1/biometrics/recovery", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["resting_hr_current"], 75);
+    assert!(response["score"].as_f64().unwrap() < 100.0);
+}
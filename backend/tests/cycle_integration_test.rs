@@ -0,0 +1,93 @@
+//! Integration tests for cycle tracking endpoints
+
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+/// Opt the user in to cycle tracking with the default lengths
+async fn enable_cycle_tracking(app: &common::TestApp, token: &str) {
+    let body = json!({ "enabled": true });
+    let (status, _) = app.put_auth("/api/v1/cycle/settings", &body.to_string(), token).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_cycle_tracking_requires_opt_in() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (status, response) = app.get_auth("/api/v1/cycle/settings", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["enabled"], false);
+    assert_eq!(response["cycle_length_days"], 28);
+
+    let body = json!({ "started_on": "2024-03-01" });
+    let (status, _) = app.post_auth("/api/v1/cycle/starts", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app.get_auth("/api/v1/cycle/phase?date=2024-03-02", &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_cycle_phase_from_logged_start() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+    enable_cycle_tracking(&app, &token).await;
+
+    let body = json!({ "started_on": "2024-03-01" });
+    let (status, _) = app.post_auth("/api/v1/cycle/starts", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, response) = app.get_auth("/api/v1/cycle/phase?date=2024-03-14", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["phase"], "ovulatory");
+    assert_eq!(response["cycle_day"], 14);
+    assert_eq!(response["cycle_started_on"], "2024-03-01");
+
+    let (status, _) = app.get_auth("/api/v1/cycle/phase?date=2024-02-20", &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_cycle_settings_validate_lengths() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "enabled": true, "cycle_length_days": 60 });
+    let (status, _) = app.put_auth("/api/v1/cycle/settings", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_weight_history_annotated_with_cycle_phase() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "weight": 62.0, "recorded_at": "2024-03-02T12:00:00Z" });
+    app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+
+    // Not tracking yet, so no phase is reported
+    let (_, response) = app.get_auth("/api/v1/weight", &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert!(response["items"][0].get("cycle_phase").is_none());
+
+    enable_cycle_tracking(&app, &token).await;
+    let body = json!({ "started_on": "2024-03-01" });
+    app.post_auth("/api/v1/cycle/starts", &body.to_string(), &token).await;
+
+    let (_, response) = app.get_auth("/api/v1/weight", &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["items"][0]["cycle_phase"], "menstrual");
+}
@@ -0,0 +1,272 @@
+//! Integration tests for exercise endpoints
+
+mod common;
+
+use axum::http::StatusCode;
+use fitness_assistant_backend::db;
+use fitness_assistant_backend::services::ExerciseService;
+use serde_json::json;
+use std::path::Path;
+use uuid::Uuid;
+
+async fn user_id(app: &common::TestApp, token: &str) -> Uuid {
+    let (_, profile) = app.get_auth("/api/v1/profile", token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap()
+}
+
+async fn library_exercise_id(app: &common::TestApp, name: &str) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM exercises WHERE name = $1 AND NOT is_custom")
+        .bind(name)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap()
+}
+
+async fn workout_count(app: &common::TestApp, user_id: Uuid) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM workouts WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap()
+}
+
+fn workout_body(exercise_ids: &[Uuid]) -> serde_json::Value {
+    let exercises: Vec<_> = exercise_ids
+        .iter()
+        .map(|id| {
+            json!({
+                "exercise_id": id.to_string(),
+                "sets": [{ "reps": 5, "weight_kg": 100.0 }, { "reps": 5, "weight_kg": 100.0 }]
+            })
+        })
+        .collect();
+
+    json!({ "workout_type": "strength", "exercises": exercises })
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_log_workout_persists_exercises_and_sets() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+    let user_id = user_id(&app, &token).await;
+
+    let bench = library_exercise_id(&app, "Bench Press").await;
+    let row = library_exercise_id(&app, "Barbell Row").await;
+
+    let body = workout_body(&[bench, row]);
+    let (status, response) = app
+        .post_auth("/api/v1/exercise/workout", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let exercises = response["exercises"].as_array().unwrap();
+    assert_eq!(exercises.len(), 2);
+    assert!(exercises.iter().all(|e| e["sets"].as_array().unwrap().len() == 2));
+    assert_eq!(workout_count(&app, user_id).await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_log_workout_failure_on_second_exercise_rolls_back() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+    let user_id = user_id(&app, &token).await;
+
+    // The first exercise and its sets are written before the second fails
+    let bench = library_exercise_id(&app, "Bench Press").await;
+    let body = workout_body(&[bench, Uuid::new_v4()]);
+    let (status, _) = app
+        .post_auth("/api/v1/exercise/workout", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    assert_eq!(workout_count(&app, user_id).await, 0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_log_workout_stores_heart_rate_recovery() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({
+        "workout_type": "cardio",
+        "max_heart_rate": 175,
+        "recovery_heart_rate": 145,
+    });
+    let (status, response) = app
+        .post_auth("/api/v1/exercise/workout", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["workout"]["heart_rate_recovery"], 30);
+    assert_eq!(response["workout"]["heart_rate_recovery_class"], "excellent");
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_log_workout_rejects_implausible_recovery_heart_rate() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    for recovery_heart_rate in [-5, 400] {
+        let body = json!({
+            "workout_type": "cardio",
+            "max_heart_rate": 175,
+            "recovery_heart_rate": recovery_heart_rate,
+        });
+        let (status, _) = app
+            .post_auth("/api/v1/exercise/workout", &body.to_string(), &token)
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_seed_exercises_is_idempotent() {
+    let app = common::TestApp::new().await;
+    let path = Path::new(db::seed::DEFAULT_EXERCISE_SEED_PATH);
+
+    db::seed_exercises(&app.pool, None, path).await.unwrap();
+    let inserted = db::seed_exercises(&app.pool, None, path).await.unwrap();
+    assert_eq!(inserted, 0);
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM exercises WHERE LOWER(name) = 'hip thrust'")
+            .fetch_one(&app.pool)
+            .await
+            .unwrap();
+    assert_eq!(count, 1);
+
+    library_exercise_id(&app, "Bench Press").await;
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_seed_ignores_custom_exercises_with_the_same_name() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let name = format!("Seed Lift {}", Uuid::new_v4());
+    let body = json!({ "name": name, "category": "strength", "muscle_groups": ["glutes"] });
+    let (status, _) = app
+        .post_auth("/api/v1/exercise/custom", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let path = std::env::temp_dir().join(format!("{}.json", Uuid::new_v4()));
+    let seed = json!([{ "name": name, "category": "strength", "muscle_groups": ["glutes"] }]);
+    std::fs::write(&path, seed.to_string()).unwrap();
+
+    let inserted = db::seed_exercises(&app.pool, None, &path).await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(inserted, 1);
+
+    library_exercise_id(&app, &name).await;
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_instantiate_template_prepopulates_workout() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let bench = library_exercise_id(&app, "Bench Press").await;
+    let squat = library_exercise_id(&app, "Squat").await;
+    let row = library_exercise_id(&app, "Barbell Row").await;
+    let body = json!({
+        "name": "Full Body A",
+        "exercises": [
+            { "exercise_id": bench, "target_sets": 3, "target_reps": 5, "target_weight_kg": 80.0 },
+            { "exercise_id": squat, "target_sets": 3, "target_reps": 5, "target_weight_kg": 100.0 },
+            { "exercise_id": row, "target_sets": 2, "target_reps": 8 },
+        ],
+    });
+    let (status, response) = app
+        .post_auth("/api/v1/exercise/templates", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let template_id = response["id"].as_str().unwrap().to_string();
+
+    let body = json!({ "date": "2024-06-03" });
+    let (status, response) = app
+        .post_auth(
+            &format!("/api/v1/exercise/templates/{}/instantiate", template_id),
+            &body.to_string(),
+            &token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["workout"]["name"], "Full Body A");
+    let exercises = response["exercises"].as_array().unwrap();
+    let ids: Vec<String> = exercises
+        .iter()
+        .map(|e| e["exercise"]["id"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(ids, vec![bench.to_string(), squat.to_string(), row.to_string()]);
+
+    let set_counts: Vec<usize> = exercises
+        .iter()
+        .map(|e| e["sets"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(set_counts, vec![3, 3, 2]);
+    assert_eq!(exercises[0]["sets"][0]["reps"], 5);
+    assert_eq!(exercises[0]["sets"][0]["weight_kg"], 80.0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_search_ranks_prefix_then_word_then_substring() {
+    let app = common::TestApp::new().await;
+
+    // A made-up word keeps other tests' exercises out of the results
+    let word = format!("zq{}", &Uuid::new_v4().simple().to_string()[..8]);
+    let names = [
+        format!("Arnold {}", word),
+        format!("Com{}ion Sprint", word),
+        format!("Bench {}", word),
+        format!("{} Up", word),
+        format!("Leg {} Machine", word),
+        format!("Ex{}ive Dance", word),
+    ];
+    for name in &names {
+        sqlx::query("INSERT INTO exercises (name, category) VALUES ($1, 'strength')")
+            .bind(name)
+            .execute(&app.pool)
+            .await
+            .unwrap();
+    }
+
+    let query = format!(" {} ", word.to_uppercase());
+    let ranked = ExerciseService::search_exercises(&app.pool, &query, 10).await.unwrap();
+    let ranked: Vec<&str> = ranked.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        ranked,
+        vec![&names[3], &names[0], &names[2], &names[4], &names[1], &names[5]]
+    );
+
+    // The limit applies after ranking
+    let limited = ExerciseService::search_exercises(&app.pool, &word, 2).await.unwrap();
+    let limited: Vec<&str> = limited.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(limited, vec![&names[3], &names[0]]);
+
+    // Wildcards in the query match literally
+    let literal = ExerciseService::search_exercises(&app.pool, &format!("{}%", word), 10)
+        .await
+        .unwrap();
+    assert!(literal.is_empty());
+}
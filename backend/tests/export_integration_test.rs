@@ -0,0 +1,264 @@
+//! Integration tests for data export endpoints
+
+mod common;
+
+use axum::http::StatusCode;
+use fitness_assistant_backend::services::ExportJobService;
+use serde_json::json;
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_json_export_date_range_excludes_logs_outside_window() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    for (weight, recorded_at) in [(80.0, "2023-12-31T12:00:00Z"), (79.0, "2024-06-15T08:00:00Z")] {
+        let body = json!({ "weight": weight, "recorded_at": recorded_at });
+        let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    for (start, end) in [
+        ("2024-06-14T23:00:00Z", "2024-06-15T07:00:00Z"),
+        ("2025-01-01T23:00:00Z", "2025-01-02T07:00:00Z"),
+    ] {
+        let body = json!({ "sleep_start": start, "sleep_end": end });
+        let (status, _) = app.post_auth("/api/v1/sleep", &body.to_string(), &token).await;
+        assert!(status.is_success());
+    }
+
+    let (status, response) = app
+        .get_auth(
+            "/api/v1/export/json?start=2024-01-01T00:00:00Z&end=2024-12-31T23:59:59Z",
+            &token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let export: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+    let weights = export["weight_logs"].as_array().unwrap();
+    assert_eq!(weights.len(), 1);
+    assert_eq!(weights[0]["weight_kg"], 79.0);
+
+    let sleep = export["sleep_logs"].as_array().unwrap();
+    assert_eq!(sleep.len(), 1);
+    assert_eq!(sleep[0]["sleep_end"], "2024-06-15T07:00:00Z");
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_json_export_rejects_start_after_end() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (status, _) = app
+        .get_auth(
+            "/api/v1/export/json?start=2024-12-31T00:00:00Z&end=2024-01-01T00:00:00Z",
+            &token,
+        )
+        .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_unverified_user_can_export() {
+    // Verification emails aren't delivered yet, so exports stay open to unverified accounts
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (status, _) = app.get_auth("/api/v1/export/json", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = app.post_auth("/api/v1/export", "", &token).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_queued_export_completes_and_can_be_downloaded() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "weight": 81.5, "recorded_at": "2024-06-15T08:00:00Z" });
+    let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, response) = app.post_auth("/api/v1/export?include=weight", "", &token).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(job["status"], "pending");
+    assert!(job.get("download_url").is_none());
+    let job_id = job["id"].as_str().unwrap().to_string();
+
+    // Run the worker until it reaches this job; other tests may queue jobs too
+    let dir = std::env::temp_dir().join(format!("exports-{}", uuid::Uuid::new_v4()));
+    loop {
+        let processed = ExportJobService::process_next(&app.pool, &dir).await.unwrap();
+        match processed {
+            Some(id) if id.to_string() == job_id => break,
+            Some(_) => continue,
+            None => panic!("export job was never processed"),
+        }
+    }
+
+    let (status, response) = app.get_auth(&format!("/api/v1/export/{}", job_id), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let job: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(job["status"], "complete");
+    let download_url = job["download_url"].as_str().unwrap();
+    assert_eq!(download_url, format!("/api/v1/export/{}/download", job_id));
+
+    let (status, response) = app.get_auth(download_url, &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let export: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(export["weight_logs"].as_array().unwrap().len(), 1);
+    assert!(export["sleep_logs"].as_array().unwrap().is_empty());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_export_jobs_are_private() {
+    let app = common::TestApp::new().await;
+    let owner = app.create_test_user().await;
+    let other = app.create_test_user().await;
+
+    let owner_token = owner.tokens.as_ref().unwrap().access_token.clone();
+    let (_, response) = app.post_auth("/api/v1/export", "", &owner_token).await;
+    let job: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+    let other_token = other.tokens.as_ref().unwrap().access_token.clone();
+    let path = format!("/api/v1/export/{}", job["id"].as_str().unwrap());
+    let (status, _) = app.get_auth(&path, &other_token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_abandoned_running_export_is_claimed_again() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (status, response) = app.post_auth("/api/v1/export?include=weight", "", &token).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let job_id = uuid::Uuid::parse_str(job["id"].as_str().unwrap()).unwrap();
+
+    // As if a worker claimed the job an hour ago and then stopped
+    sqlx::query(
+        "UPDATE export_jobs SET status = 'running', started_at = NOW() - INTERVAL '1 hour' WHERE id = $1",
+    )
+    .bind(job_id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let dir = std::env::temp_dir().join(format!("exports-{}", uuid::Uuid::new_v4()));
+    loop {
+        match ExportJobService::process_next(&app.pool, &dir).await.unwrap() {
+            Some(id) if id == job_id => break,
+            Some(_) => continue,
+            None => panic!("abandoned export job was never reclaimed"),
+        }
+    }
+
+    let (_, response) = app.get_auth(&format!("/api/v1/export/{}", job_id), &token).await;
+    let job: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(job["status"], "complete");
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+/// Queue an export for `token` and run the worker into `dir` until it finishes, returning the file path
+async fn complete_export(
+    app: &common::TestApp,
+    token: &str,
+    dir: &std::path::Path,
+) -> (uuid::Uuid, std::path::PathBuf) {
+    let (status, response) = app.post_auth("/api/v1/export", "", token).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let job: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let job_id = uuid::Uuid::parse_str(job["id"].as_str().unwrap()).unwrap();
+
+    loop {
+        match ExportJobService::process_next(&app.pool, dir).await.unwrap() {
+            Some(id) if id == job_id => break,
+            Some(_) => continue,
+            None => panic!("export job was never processed"),
+        }
+    }
+
+    let file_path: String = sqlx::query_scalar("SELECT file_path FROM export_jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    let path = std::path::PathBuf::from(file_path);
+    assert!(path.exists());
+    (job_id, path)
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_account_deletion_removes_export_files() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let dir = std::env::temp_dir().join(format!("exports-{}", uuid::Uuid::new_v4()));
+    let (job_id, path) = complete_export(&app, &token, &dir).await;
+
+    let body = json!({ "password": user.password });
+    let (status, _) = app.delete_auth("/api/v1/auth/me", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert!(!path.exists());
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM export_jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_expired_exports_are_deleted() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let dir = std::env::temp_dir().join(format!("exports-{}", uuid::Uuid::new_v4()));
+    let (stale_id, stale_path) = complete_export(&app, &token, &dir).await;
+    let (fresh_id, fresh_path) = complete_export(&app, &token, &dir).await;
+
+    sqlx::query("UPDATE export_jobs SET completed_at = NOW() - INTERVAL '2 days' WHERE id = $1")
+        .bind(stale_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let ttl = std::time::Duration::from_secs(24 * 60 * 60);
+    let expired = ExportJobService::expire_finished(&app.pool, ttl).await.unwrap();
+    assert!(expired >= 1);
+
+    assert!(!stale_path.exists());
+    let (status, _) = app.get_auth(&format!("/api/v1/export/{}", stale_id), &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    assert!(fresh_path.exists());
+    let (status, _) = app.get_auth(&format!("/api/v1/export/{}", fresh_id), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,104 @@
+//! Integration tests for per-user feature flags
+
+mod common;
+
+use axum::http::StatusCode;
+use fitness_assistant_backend::services::UserService;
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const SUGGESTIONS_PATH: &str = "/api/v1/nutrition/suggestions";
+
+/// Test app with AI enabled against a mock model
+async fn app_with_mock_model() -> (common::TestApp, MockServer) {
+    let server = MockServer::start().await;
+    let completion = r#"{"suggestions": [
+        {"name": "Lentil soup", "calories": 400, "protein_g": 25, "carbohydrates_g": 55, "fat_g": 8}
+    ]}"#;
+    Mock::given(method("POST"))
+        .and(path("/api/generate"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "model": "llama3.2",
+            "response": completion,
+            "done": true
+        })))
+        .mount(&server)
+        .await;
+
+    let url = server.uri();
+    let app = common::TestApp::with_config(|config| {
+        config.ai.enabled = true;
+        config.ai.ollama_url = url;
+    })
+    .await;
+
+    (app, server)
+}
+
+fn remaining_macros() -> String {
+    json!({ "calories": 800, "protein_g": 60, "carbohydrates_g": 80, "fat_g": 25 }).to_string()
+}
+
+async fn set_flag(app: &common::TestApp, email: &str, flag: &str, enabled: bool) -> StatusCode {
+    let admin_email = format!("admin_{}@example.com", uuid::Uuid::new_v4());
+    let password = "SecurePassword123!";
+    UserService::create_admin(&app.pool, &admin_email, password).await.unwrap();
+    app.verify_email(&admin_email).await;
+    let admin_token = app.login(&admin_email, password).await.unwrap().access_token;
+
+    let user_id: uuid::Uuid = sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+        .bind(email)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+
+    let body = json!({ "enabled": enabled }).to_string();
+    let (status, _) = app
+        .put_auth(&format!("/api/v1/admin/users/{}/features/{}", user_id, flag), &body, &admin_token)
+        .await;
+    status
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_ai_suggestions_not_available_without_flag() {
+    let (app, _server) = app_with_mock_model().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (status, response) = app.post_auth(SUGGESTIONS_PATH, &remaining_macros(), &token).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(response.contains("not available"), "{}", response);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_ai_suggestions_available_with_flag() {
+    let (app, _server) = app_with_mock_model().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    assert_eq!(set_flag(&app, &user.email, "ai_meal_suggestions", true).await, StatusCode::OK);
+
+    let (status, response) = app.post_auth(SUGGESTIONS_PATH, &remaining_macros(), &token).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let suggestions: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(suggestions[0]["name"], "Lentil soup");
+
+    // Turning the flag back off withdraws the feature
+    assert_eq!(set_flag(&app, &user.email, "ai_meal_suggestions", false).await, StatusCode::OK);
+    let (status, _) = app.post_auth(SUGGESTIONS_PATH, &remaining_macros(), &token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_unknown_feature_flag_rejected() {
+    let (app, _server) = app_with_mock_model().await;
+    let user = app.create_test_user().await;
+
+    assert_eq!(set_flag(&app, &user.email, "time_travel", true).await, StatusCode::BAD_REQUEST);
+}
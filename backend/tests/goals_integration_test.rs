@@ -0,0 +1,268 @@
+//! Integration tests for goal endpoints
+
+mod common;
+
+use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn create_goal(app: &common::TestApp, token: &str, metric: &str, target: f64) -> String {
+    let body = json!({
+        "name": format!("Reach {} {}", metric, target),
+        "goal_type": "custom",
+        "metric": metric,
+        "target_value": target,
+        "direction": "decreasing"
+    });
+
+    let (status, response) = app
+        .post_auth("/api/v1/goals", &body.to_string(), token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    response["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_body_fat_goal_projection() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    // Body fat dropping 0.1% per day over three weeks
+    for (days_ago, body_fat) in [(21, 24.0), (14, 23.3), (7, 22.6), (0, 21.9)] {
+        let body = json!({
+            "recorded_at": Utc::now() - Duration::days(days_ago),
+            "body_fat_percent": body_fat
+        });
+        let (status, _) = app
+            .post_auth("/api/v1/weight/body-composition", &body.to_string(), &token)
+            .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let goal_id = create_goal(&app, &token, "body_fat_percent", 20.0).await;
+
+    let (status, response) = app
+        .get_auth(&format!("/api/v1/goals/{}/projection", goal_id), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["metric"], "body_fat_percent");
+    assert!((response["current_value"].as_f64().unwrap() - 21.9).abs() < 0.01);
+    assert!((response["average_daily_change"].as_f64().unwrap() + 0.1).abs() < 0.01);
+    assert_eq!(response["on_track"], true);
+    // 1.9% remaining at 0.1% per day
+    let days = response["projected_days"].as_i64().unwrap();
+    assert!((19..=20).contains(&days));
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_five_k_pace_projection_ignores_rides() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let exercise_id = |name: &'static str| {
+        sqlx::query_scalar::<_, uuid::Uuid>("SELECT id FROM exercises WHERE name = $1 AND NOT is_custom")
+            .bind(name)
+            .fetch_one(&app.pool)
+    };
+    let running = exercise_id("Running").await.unwrap();
+    let cycling = exercise_id("Cycling").await.unwrap();
+
+    // Three 5 km runs, the last logged as cardio with a running exercise,
+    // then a 30 km ride at 120 s/km
+    let workouts = [
+        (7, "running", None, 5000.0, 26),
+        (5, "running", None, 5000.0, 25),
+        (3, "cardio", Some(running), 5000.0, 24),
+        (1, "cardio", Some(cycling), 30000.0, 60),
+    ];
+    for (days_ago, workout_type, exercise, distance_meters, duration_minutes) in workouts {
+        let exercises: Vec<_> = exercise.map(|id| json!({ "exercise_id": id })).into_iter().collect();
+        let body = json!({
+            "workout_type": workout_type,
+            "started_at": Utc::now() - Duration::days(days_ago),
+            "distance_meters": distance_meters,
+            "duration_minutes": duration_minutes,
+            "exercises": exercises
+        });
+        let (status, response) = app.post_auth("/api/v1/exercise/workout", &body.to_string(), &token).await;
+        assert_eq!(status, StatusCode::OK, "{}", response);
+    }
+
+    let goal_id = create_goal(&app, &token, "5k_pace", 270.0).await;
+
+    let (status, response) = app
+        .get_auth(&format!("/api/v1/goals/{}/projection", goal_id), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", response);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    // 24 minutes over 5 km, not the ride's 120 s/km
+    assert_eq!(response["current_value"], 288.0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_goal_projection_unsupported_metric() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let goal_id = create_goal(&app, &token, "sleep_hours", 8.0).await;
+
+    let (status, _) = app
+        .get_auth(&format!("/api/v1/goals/{}/projection", goal_id), &token)
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_weight_loss_and_gain_goals_conflict() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let cut_id = create_goal(&app, &token, "weight_kg", 70.0).await;
+    let body = json!({
+        "name": "Bulk",
+        "goal_type": "weight",
+        "metric": "weight_kg",
+        "target_value": 85.0,
+        "start_value": 78.0,
+        "direction": "increasing"
+    });
+    let (status, _) = app.post_auth("/api/v1/goals", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, response) = app.get_auth("/api/v1/goals/conflicts", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let conflicts = response.as_array().unwrap();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0]["kind"], "opposite_energy_balance");
+    assert!(conflicts[0]["goal_ids"].as_array().unwrap().contains(&json!(cut_id)));
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_achieving_milestone_posts_webhook() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hooks/milestones"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let webhook_url = format!("{}/hooks/milestones", server.uri());
+    let app = common::TestApp::with_config(|config| {
+        config.notifications.milestone_webhook_url = Some(webhook_url);
+    })
+    .await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+    let (_, profile) = app.get_auth("/api/v1/profile", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+
+    let body = json!({
+        "name": "Run 100 km",
+        "goal_type": "custom",
+        "metric": "distance_km",
+        "target_value": 100.0,
+        "start_value": 0.0,
+        "direction": "increasing"
+    });
+    let (status, response) = app.post_auth("/api/v1/goals", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let goal: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let goal_id = goal["id"].as_str().unwrap();
+
+    // Passes the 25% milestone only
+    let body = json!({ "current_value": 30.0 });
+    let (status, _) = app
+        .put_auth(&format!("/api/v1/goals/{}", goal_id), &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Delivery happens in the background
+    let mut requests = Vec::new();
+    for _ in 0..50 {
+        requests = server.received_requests().await.unwrap();
+        if !requests.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(requests.len(), 1);
+
+    let payload: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(payload["user_id"], profile["id"]);
+    assert_eq!(payload["goal_id"], goal_id);
+    assert_eq!(payload["milestone"]["percentage"], 25);
+    assert_eq!(payload["milestone"]["target_value"], 25.0);
+    assert_eq!(payload["milestone"]["achieved_value"], 30.0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_goal_conflicts_use_local_date() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    // UTC+14, so the local date is usually ahead of the UTC date
+    let body = json!({ "timezone": "Pacific/Kiritimati" });
+    let (status, _) = app.put_auth("/api/v1/profile/settings", &body.to_string(), &token).await;
+    assert!(status.is_success());
+    let local_today = fitness_assistant_backend::timezone::local_today(chrono_tz::Pacific::Kiritimati);
+
+    let strength = json!({
+        "name": "Bench PR",
+        "goal_type": "custom",
+        "metric": format!("one_rep_max:{}", uuid::Uuid::new_v4()),
+        "target_value": 100.0,
+        "direction": "increasing"
+    });
+    let (status, _) = app.post_auth("/api/v1/goals", &strength.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let cut = |target_date: chrono::NaiveDate| {
+        json!({
+            "name": format!("Cut by {}", target_date),
+            "goal_type": "weight",
+            "metric": "weight_kg",
+            "target_value": 79.0,
+            "start_value": 80.0,
+            "target_date": target_date
+        })
+    };
+
+    // Due today locally, so there is no timeline left to be aggressive
+    let (status, _) = app.post_auth("/api/v1/goals", &cut(local_today).to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, response) = app.get_auth("/api/v1/goals/conflicts", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response.as_array().unwrap().len(), 0);
+
+    // Losing 1 kg by tomorrow is far beyond 1% a week
+    let tomorrow = local_today + Duration::days(1);
+    let (status, _) = app.post_auth("/api/v1/goals", &cut(tomorrow).to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, response) = app.get_auth("/api/v1/goals/conflicts", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let conflicts = response.as_array().unwrap();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0]["kind"], "aggressive_deficit_vs_strength");
+}
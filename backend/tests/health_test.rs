@@ -35,6 +35,13 @@ async fn test_readiness_endpoint() {
     
     assert_eq!(status, StatusCode::OK);
     assert!(body.contains("ready"));
+
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let pool = &body["checks"]["pool"];
+    assert!(pool["size"].as_u64().unwrap() >= 1);
+    assert!(pool["idle"].is_u64());
+    assert!(pool["active"].is_u64());
+    assert_eq!(pool["max_connections"], 5);
 }
 
 #[tokio::test]
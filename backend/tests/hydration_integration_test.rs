@@ -0,0 +1,98 @@
+//! Integration tests for hydration goals
+
+mod common;
+
+use axum::http::StatusCode;
+use fitness_assistant_backend::config::HydrationConfig;
+use fitness_assistant_backend::services::jobs;
+use serde_json::json;
+
+async fn log_weight(app: &common::TestApp, token: &str, weight: f64) {
+    let body = json!({ "weight": weight, "unit": "kg" });
+    let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), token).await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
+async fn daily_goal_ml(app: &common::TestApp, token: &str) -> i64 {
+    let (status, response) = app.get_auth("/api/v1/hydration/goal", token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    response["daily_goal_ml"].as_i64().unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_recompute_updates_auto_goal_after_weight_change() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    log_weight(&app, &token, 60.0).await;
+    let body = json!({ "auto_calculate": true });
+    let (status, _) = app
+        .post_auth("/api/v1/hydration/goal", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let before = daily_goal_ml(&app, &token).await;
+
+    log_weight(&app, &token, 90.0).await;
+    assert_eq!(daily_goal_ml(&app, &token).await, before);
+
+    let updated = jobs::recompute_hydration_goals(&app.pool, &HydrationConfig::default()).await.unwrap();
+    assert!(updated >= 1);
+    assert!(daily_goal_ml(&app, &token).await > before);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_recompute_leaves_manual_goal_alone() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "auto_calculate": false, "daily_goal_ml": 1500 });
+    let (status, _) = app
+        .post_auth("/api/v1/hydration/goal", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    log_weight(&app, &token, 90.0).await;
+    jobs::recompute_hydration_goals(&app.pool, &HydrationConfig::default()).await.unwrap();
+
+    assert_eq!(daily_goal_ml(&app, &token).await, 1500);
+}
+
+async fn log_drink(app: &common::TestApp, token: &str, body: serde_json::Value) -> serde_json::Value {
+    let (status, response) = app.post_auth("/api/v1/hydration", &body.to_string(), token).await;
+    assert_eq!(status, StatusCode::OK);
+    serde_json::from_str(&response).unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_daily_caffeine_total_and_warning() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    // Explicit caffeine overrides the coffee estimate
+    let body = json!({ "amount_ml": 240, "beverage_type": "coffee", "caffeine_mg": 5 });
+    let decaf = log_drink(&app, &token, body).await;
+    assert_eq!(decaf["caffeine_mg"], 5);
+
+    for _ in 0..3 {
+        let mug = log_drink(&app, &token, json!({ "amount_ml": 350, "beverage_type": "coffee" })).await;
+        assert_eq!(mug["caffeine_mg"], 139);
+    }
+    log_drink(&app, &token, json!({ "amount_ml": 500 })).await;
+
+    let today = chrono::Utc::now().date_naive();
+    let (status, response) = app
+        .get_auth(&format!("/api/v1/hydration/daily/{}", today), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["total_caffeine_mg"], 422);
+    assert!(response["caffeine_warning"].is_string());
+}
@@ -0,0 +1,188 @@
+//! Integration tests for health insights
+
+mod common;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use fitness_assistant_backend::config::HydrationConfig;
+use fitness_assistant_backend::services::HealthInsightsService;
+use serde_json::json;
+use uuid::Uuid;
+
+async fn user_id(app: &common::TestApp, token: &str) -> Uuid {
+    let (_, profile) = app.get_auth("/api/v1/profile", token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap()
+}
+
+async fn post(app: &common::TestApp, token: &str, path: &str, body: serde_json::Value) {
+    let (status, response) = app.post_auth(path, &body.to_string(), token).await;
+    assert!(status.is_success(), "POST {} failed: {}", path, response);
+}
+
+fn at(timestamp: &str) -> DateTime<Utc> {
+    timestamp.parse().unwrap()
+}
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_weekly_digest_summarizes_each_logged_area() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+    let user_id = user_id(&app, &token).await;
+
+    // Monday 2024-03-11 to Sunday 2024-03-17, plus one entry outside it
+    for (started_at, minutes, calories) in [
+        ("2024-03-11T07:00:00Z", 45, 400),
+        ("2024-03-13T07:00:00Z", 30, 250),
+        ("2024-03-19T07:00:00Z", 60, 600),
+    ] {
+        post(&app, &token, "/api/v1/exercise/workout", json!({
+            "workout_type": "cardio",
+            "started_at": at(started_at),
+            "duration_minutes": minutes,
+            "calories_burned": calories,
+            "exercises": []
+        }))
+        .await;
+    }
+
+    for (start, end) in [
+        ("2024-03-11T23:00:00Z", "2024-03-12T06:00:00Z"),
+        ("2024-03-12T22:00:00Z", "2024-03-13T07:00:00Z"),
+    ] {
+        post(&app, &token, "/api/v1/sleep", json!({
+            "sleep_start": at(start),
+            "sleep_end": at(end)
+        }))
+        .await;
+    }
+
+    post(&app, &token, "/api/v1/hydration/goal", json!({
+        "auto_calculate": false,
+        "daily_goal_ml": 2000
+    }))
+    .await;
+    for (consumed_at, amount_ml) in [
+        ("2024-03-11T12:00:00Z", 2200),
+        ("2024-03-12T12:00:00Z", 1200),
+        ("2024-03-14T12:00:00Z", 2000),
+    ] {
+        post(&app, &token, "/api/v1/hydration", json!({
+            "amount_ml": amount_ml,
+            "consumed_at": at(consumed_at)
+        }))
+        .await;
+    }
+
+    for (recorded_at, weight) in [("2024-03-11T07:00:00Z", 80.0), ("2024-03-17T07:00:00Z", 79.2)] {
+        post(&app, &token, "/api/v1/weight", json!({
+            "weight": weight,
+            "recorded_at": at(recorded_at)
+        }))
+        .await;
+    }
+
+    sqlx::query("UPDATE user_settings SET daily_calorie_goal = 2000 WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    for (consumed_at, calories) in [("2024-03-11T12:00:00Z", 1950), ("2024-03-12T12:00:00Z", 2600)] {
+        sqlx::query(
+            "INSERT INTO food_logs (user_id, custom_name, calories, consumed_at) VALUES ($1, 'Meals', $2, $3)",
+        )
+        .bind(user_id)
+        .bind(rust_decimal::Decimal::from(calories))
+        .bind(at(consumed_at))
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    }
+
+    let digest = HealthInsightsService::generate_weekly_digest(&app.pool, &HydrationConfig::default(), user_id, date("2024-03-14"))
+        .await
+        .unwrap();
+
+    assert_eq!(digest.week_start, date("2024-03-11"));
+    assert_eq!(digest.week_end, date("2024-03-17"));
+
+    let exercise = digest.exercise.unwrap();
+    assert_eq!(exercise.workouts, 2);
+    assert_eq!(exercise.total_duration_minutes, 75);
+    assert_eq!(exercise.total_calories_burned, 650);
+
+    let sleep = digest.sleep.unwrap();
+    assert_eq!(sleep.nights_logged, 2);
+    assert!((sleep.average_duration_minutes - 480.0).abs() < 0.01);
+
+    let hydration = digest.hydration.unwrap();
+    assert_eq!(hydration.daily_goal_ml, 2000);
+    assert_eq!(hydration.days_logged, 3);
+    assert_eq!(hydration.days_goal_met, 2);
+
+    let weight = digest.weight.unwrap();
+    assert!((weight.change_kg + 0.8).abs() < 0.01);
+
+    let nutrition = digest.nutrition.unwrap();
+    assert_eq!(nutrition.days_logged, 2);
+    assert_eq!(nutrition.days_on_target, Some(1));
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_weekly_digest_omits_empty_sections() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+    let user_id = user_id(&app, &token).await;
+
+    let digest = HealthInsightsService::generate_weekly_digest(&app.pool, &HydrationConfig::default(), user_id, date("2024-03-14"))
+        .await
+        .unwrap();
+
+    assert!(digest.exercise.is_none());
+    assert!(digest.sleep.is_none());
+    assert!(digest.hydration.is_none());
+    assert!(digest.weight.is_none());
+    assert!(digest.nutrition.is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_weekly_digest_groups_food_by_local_day() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+    let user_id = user_id(&app, &token).await;
+
+    let body = json!({ "timezone": "America/Los_Angeles" });
+    let (status, _) = app.put_auth("/api/v1/profile/settings", &body.to_string(), &token).await;
+    assert!(status.is_success());
+
+    // Late Sunday evening in Los Angeles is already Monday in UTC, and the
+    // other way round for the Monday before the week starts
+    for (consumed_at, calories) in [("2024-03-18T03:00:00Z", 2000), ("2024-03-11T03:00:00Z", 1500)] {
+        sqlx::query(
+            "INSERT INTO food_logs (user_id, custom_name, calories, consumed_at) VALUES ($1, 'Meals', $2, $3)",
+        )
+        .bind(user_id)
+        .bind(rust_decimal::Decimal::from(calories))
+        .bind(at(consumed_at))
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    }
+
+    let digest = HealthInsightsService::generate_weekly_digest(&app.pool, &HydrationConfig::default(), user_id, date("2024-03-14"))
+        .await
+        .unwrap();
+
+    let nutrition = digest.nutrition.unwrap();
+    assert_eq!(nutrition.days_logged, 1);
+    assert_eq!(nutrition.average_daily_calories, 2000.0);
+}
@@ -2,8 +2,42 @@
 
 mod common;
 
-use axum::http::StatusCode;
+use axum::{http::StatusCode, response::IntoResponse};
+use fitness_assistant_backend::{
+    error::ApiError,
+    services::{nutrition::CreateFoodItemInput, NutritionService},
+};
+use rust_decimal::Decimal;
 use serde_json::json;
+use uuid::Uuid;
+
+/// Create a food item owned by the user and return its ID
+async fn create_food(app: &common::TestApp, token: &str, name: &str) -> String {
+    let (_, profile) = app.get_auth("/api/v1/profile", token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    let user_id = Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap();
+
+    let item = NutritionService::create_food_item(
+        &app.pool,
+        user_id,
+        CreateFoodItemInput {
+            name: format!("{} {}", name, Uuid::new_v4()),
+            serving_size: Decimal::new(100, 0),
+            serving_unit: "g".to_string(),
+            calories: Decimal::new(150, 0),
+            protein_g: Decimal::new(10, 0),
+            carbohydrates_g: Decimal::new(20, 0),
+            fat_g: Decimal::new(5, 0),
+            fiber_g: Decimal::new(2, 0),
+            sugar_g: Decimal::new(1, 0),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    item.id.to_string()
+}
 
 #[tokio::test]
 #[ignore = "requires database"]
@@ -43,6 +77,19 @@ async fn test_barcode_lookup_not_found() {
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_nutrition_preview_unknown_food_not_found() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let path = format!("/api/v1/nutrition/foods/{}/preview?servings=0.5", Uuid::new_v4());
+    let (status, _) = app.get_auth(&path, &token).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
 #[tokio::test]
 #[ignore = "requires database"]
 async fn test_get_daily_summary_empty() {
@@ -162,3 +209,392 @@ async fn test_get_recipe_not_found() {
     
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_logged_food_appears_in_recents() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let oats = create_food(&app, &token, "Oats").await;
+    let eggs = create_food(&app, &token, "Eggs").await;
+
+    // Oats twice, then eggs: eggs is newest and oats appears once
+    for food_id in [&oats, &oats, &eggs] {
+        let body = json!({ "food_item_id": food_id, "servings": 1.0, "meal_type": "breakfast" });
+        let (status, _) = app.post_auth("/api/v1/nutrition/log", &body.to_string(), &token).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, response) = app.get_auth("/api/v1/nutrition/recent", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let ids: Vec<&str> = response
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec![eggs.as_str(), oats.as_str()]);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_toggle_favorite_flips_per_food() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let food_id = create_food(&app, &token, "Greek Yogurt").await;
+    let path = format!("/api/v1/nutrition/favorites/{}", food_id);
+
+    for expected in [true, false, true] {
+        let (status, response) = app.post_auth(&path, "", &token).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["is_favorite"], expected);
+    }
+
+    // Toggling never duplicates the food in the favorites list
+    let (status, response) = app.get_auth("/api/v1/nutrition/favorites", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let favorites = response.as_array().unwrap();
+    assert_eq!(favorites.len(), 1);
+    assert_eq!(favorites[0]["id"], food_id);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_toggle_favorite_unknown_food() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let fake_id = "00000000-0000-0000-0000-000000000000";
+    let (status, _) = app
+        .post_auth(&format!("/api/v1/nutrition/favorites/{}", fake_id), "", &token)
+        .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_log_meal_template_creates_log_per_item() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    // Each food is 150 kcal, 10g protein per serving
+    let oats = create_food(&app, &token, "Oats").await;
+    let milk = create_food(&app, &token, "Milk").await;
+    let berries = create_food(&app, &token, "Berries").await;
+
+    let body = json!({
+        "name": "Usual Breakfast",
+        "items": [
+            { "food_item_id": oats, "servings": 1.0 },
+            { "food_item_id": milk, "servings": 2.0 },
+            { "food_item_id": berries, "servings": 0.5 }
+        ]
+    });
+    let (status, response) = app
+        .post_auth("/api/v1/nutrition/templates", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["items"].as_array().unwrap().len(), 3);
+    let template_id = response["id"].as_str().unwrap();
+
+    let body = json!({ "meal_type": "breakfast" });
+    let (status, response) = app
+        .post_auth(
+            &format!("/api/v1/nutrition/templates/{}/log", template_id),
+            &body.to_string(),
+            &token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let logs = response.as_array().unwrap();
+    assert_eq!(logs.len(), 3);
+    assert!(logs.iter().all(|log| log["meal_type"] == "breakfast"));
+
+    let total = |field: &str| logs.iter().map(|log| log[field].as_f64().unwrap()).sum::<f64>();
+    assert!((total("calories") - 525.0).abs() < 0.01);
+    assert!((total("protein_g") - 35.0).abs() < 0.01);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_save_meal_template_unknown_food() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({
+        "name": "Ghost Meal",
+        "items": [{ "food_item_id": "00000000-0000-0000-0000-000000000000", "servings": 1.0 }]
+    });
+    let (status, _) = app
+        .post_auth("/api/v1/nutrition/templates", &body.to_string(), &token)
+        .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_copy_day_duplicates_entries_onto_target_date() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let food_id = create_food(&app, &token, "Rice").await;
+    for (hour, servings) in [(8, 1.0), (12, 2.0), (15, 0.5), (19, 1.5)] {
+        let body = json!({
+            "food_item_id": food_id,
+            "servings": servings,
+            "meal_type": "lunch",
+            "consumed_at": format!("2024-06-10T{:02}:00:00Z", hour)
+        });
+        let (status, _) = app.post_auth("/api/v1/nutrition/log", &body.to_string(), &token).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let body = json!({ "from_date": "2024-06-10", "to_date": "2024-06-11" });
+    let (status, response) = app
+        .post_auth("/api/v1/nutrition/copy-day", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["copied"], 4);
+
+    let (_, source) = app.get_auth("/api/v1/nutrition/daily?date=2024-06-10", &token).await;
+    let (_, target) = app.get_auth("/api/v1/nutrition/daily?date=2024-06-11", &token).await;
+    let source: serde_json::Value = serde_json::from_str(&source).unwrap();
+    let target: serde_json::Value = serde_json::from_str(&target).unwrap();
+
+    assert_eq!(target["meal_count"], 4);
+    assert_eq!(target["total_calories"], source["total_calories"]);
+    assert_eq!(target["total_protein_g"], source["total_protein_g"]);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_copy_day_onto_itself_rejected() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "from_date": "2024-06-10", "to_date": "2024-06-10" });
+    let (status, _) = app
+        .post_auth("/api/v1/nutrition/copy-day", &body.to_string(), &token)
+        .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_custom_meal_type_requires_configuration() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let food_id = create_food(&app, &token, "Banana").await;
+    let log = json!({ "food_item_id": food_id, "servings": 1.0, "meal_type": "pre-workout" });
+
+    // Not one of the defaults
+    let (status, _) = app.post_auth("/api/v1/nutrition/log", &log.to_string(), &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let settings = json!({
+        "meal_types": ["breakfast", "lunch", "dinner", "snack", "Pre-Workout"]
+    });
+    let (status, response) = app
+        .put_auth("/api/v1/profile/settings", &settings.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(
+        response["meal_types"],
+        json!(["breakfast", "lunch", "dinner", "snack", "pre-workout"])
+    );
+
+    let (status, response) = app.post_auth("/api/v1/nutrition/log", &log.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["meal_type"], "pre-workout");
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_daily_summary_includes_alcohol() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, profile) = app.get_auth("/api/v1/profile", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    let user_id = Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap();
+
+    // 13g carbs and 14g alcohol reconcile to 150 kcal
+    let beer = NutritionService::create_food_item(
+        &app.pool,
+        user_id,
+        CreateFoodItemInput {
+            name: format!("Lager {}", Uuid::new_v4()),
+            serving_size: Decimal::new(355, 0),
+            serving_unit: "ml".to_string(),
+            calories: Decimal::new(150, 0),
+            carbohydrates_g: Decimal::new(13, 0),
+            alcohol_g: Decimal::new(14, 0),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let log = json!({
+        "food_item_id": beer.id.to_string(),
+        "servings": 2.0,
+        "meal_type": "dinner",
+        "consumed_at": "2024-06-10T19:00:00Z"
+    });
+    let (status, response) = app.post_auth("/api/v1/nutrition/log", &log.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["alcohol_g"].as_f64().unwrap(), 28.0);
+
+    let (status, summary) = app.get_auth("/api/v1/nutrition/daily?date=2024-06-10", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let summary: serde_json::Value = serde_json::from_str(&summary).unwrap();
+    assert_eq!(summary["total_alcohol_g"].as_f64().unwrap(), 28.0);
+    assert_eq!(summary["total_calories"].as_f64().unwrap(), 300.0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_duplicate_barcode_conflicts() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, profile) = app.get_auth("/api/v1/profile", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    let user_id = Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap();
+
+    let barcode = Uuid::new_v4().simple().to_string();
+    let create = |name: &'static str| {
+        NutritionService::create_food_item(
+            &app.pool,
+            user_id,
+            CreateFoodItemInput {
+                name: name.to_string(),
+                serving_size: Decimal::new(100, 0),
+                serving_unit: "g".to_string(),
+                calories: Decimal::new(380, 0),
+                protein_g: Decimal::new(13, 0),
+                carbohydrates_g: Decimal::new(68, 0),
+                fat_g: Decimal::new(7, 0),
+                fiber_g: Decimal::new(10, 0),
+                sugar_g: Decimal::new(1, 0),
+                barcode: Some(barcode.clone()),
+                ..Default::default()
+            },
+        )
+    };
+
+    create("Rolled Oats").await.unwrap();
+
+    let result = create("Rolled Oats (copy)").await;
+    let Err(err) = result else {
+        panic!("duplicate barcode was accepted");
+    };
+    assert!(matches!(&err, ApiError::Conflict(msg) if msg == "A food with this barcode already exists"));
+    assert_eq!(err.into_response().status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_food_edit_keeps_logged_nutrition() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let food_id = create_food(&app, &token, "Granola").await;
+    let body = json!({
+        "food_item_id": food_id,
+        "servings": 1.0,
+        "meal_type": "breakfast",
+        "consumed_at": "2024-06-10T08:00:00Z"
+    });
+    let (status, _) = app.post_auth("/api/v1/nutrition/log", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let body = json!({
+        "calories": 200.0,
+        "protein_g": 12.0,
+        "carbohydrates_g": 27.0,
+        "sodium_mg": 140.0
+    });
+    let (status, response) = app
+        .put_auth(&format!("/api/v1/nutrition/foods/{}", food_id), &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let item: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(item["calories"], 200.0);
+    assert_eq!(item["fat_g"], 5.0);
+    assert_eq!(item["sodium_mg"], 140.0);
+
+    // The earlier log keeps the nutrition computed when it was logged
+    let (_, response) = app.get_auth("/api/v1/nutrition/daily?date=2024-06-10", &token).await;
+    let daily: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(daily["logs"][0]["calories"], 150.0);
+    assert_eq!(daily["logs"][0]["protein_g"], 10.0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_food_edit_rejects_calories_that_contradict_macros() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    // The food's 10g protein, 20g carbs and 5g fat come to about 165 kcal
+    let food_id = create_food(&app, &token, "Granola").await;
+    let body = json!({ "calories": 600.0 });
+    let (status, response) = app
+        .put_auth(&format!("/api/v1/nutrition/foods/{}", food_id), &body.to_string(), &token)
+        .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(response.contains("expected about 165 kcal"));
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_food_edit_by_other_user_forbidden() {
+    let app = common::TestApp::new().await;
+    let owner = app.create_test_user().await;
+    let owner_token = owner.tokens.as_ref().unwrap().access_token.clone();
+    let other = app.create_test_user().await;
+    let other_token = other.tokens.as_ref().unwrap().access_token.clone();
+
+    let food_id = create_food(&app, &owner_token, "Granola").await;
+    let body = json!({ "calories": 1.0 });
+    let (status, _) = app
+        .put_auth(&format!("/api/v1/nutrition/foods/{}", food_id), &body.to_string(), &other_token)
+        .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
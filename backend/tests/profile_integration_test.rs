@@ -3,7 +3,13 @@
 mod common;
 
 use axum::http::StatusCode;
+use chrono::{Duration, Utc};
+use fitness_assistant_backend::error::ApiError;
+use fitness_assistant_backend::services::ProfileService;
+use fitness_assistant_shared::health_metrics::{calculate_bmi, calculate_ffmi};
+use fitness_assistant_shared::units::UnitPreferences;
 use serde_json::json;
+use uuid::Uuid;
 
 #[tokio::test]
 #[ignore = "requires database"]
@@ -49,6 +55,7 @@ async fn test_update_profile_height() {
     
     let response: serde_json::Value = serde_json::from_str(&response).unwrap();
     assert_eq!(response["height"], 175.0);
+    assert_eq!(response["height_display"], "175.0 cm");
 }
 
 #[tokio::test]
@@ -129,6 +136,112 @@ async fn test_update_settings() {
     assert_eq!(response["daily_step_goal"], 10000);
 }
 
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_update_settings_with_current_version_bumps_it() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, settings) = app.get_auth("/api/v1/profile/settings", &token).await;
+    let settings: serde_json::Value = serde_json::from_str(&settings).unwrap();
+    let version = settings["version"].as_i64().unwrap();
+
+    let body = json!({ "daily_step_goal": 8000, "expected_version": version });
+    let (status, response) = app.put_auth("/api/v1/profile/settings", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["daily_step_goal"], 8000);
+    assert_eq!(response["version"], version + 1);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_update_settings_with_stale_version_conflicts() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, settings) = app.get_auth("/api/v1/profile/settings", &token).await;
+    let settings: serde_json::Value = serde_json::from_str(&settings).unwrap();
+    let version = settings["version"].as_i64().unwrap();
+
+    // Another device saves first
+    let body = json!({ "daily_step_goal": 8000, "expected_version": version });
+    let (status, _) = app.put_auth("/api/v1/profile/settings", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // This device still holds the old version
+    let body = json!({ "daily_step_goal": 12000, "expected_version": version });
+    let (status, _) = app.put_auth("/api/v1/profile/settings", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (_, settings) = app.get_auth("/api/v1/profile/settings", &token).await;
+    let settings: serde_json::Value = serde_json::from_str(&settings).unwrap();
+    assert_eq!(settings["daily_step_goal"], 8000);
+    assert_eq!(settings["version"], version + 1);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_update_profile_with_stale_version_conflicts() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, profile) = app.get_auth("/api/v1/profile", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    let version = profile["version"].as_i64().unwrap();
+
+    let body = json!({ "activity_level": "very_active", "expected_version": version - 1 });
+    let (status, _) = app.put_auth("/api/v1/profile", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_update_settings_rejects_invalid_timezone() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "timezone": "Mars/Olympus_Mons" });
+    let (status, _) = app.put_auth("/api/v1/profile/settings", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let body = json!({ "timezone": "Australia/Sydney" });
+    let (status, response) = app.put_auth("/api/v1/profile/settings", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["timezone"], "Australia/Sydney");
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_daily_hydration_uses_local_day() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "timezone": "America/New_York" });
+    app.put_auth("/api/v1/profile/settings", &body.to_string(), &token).await;
+
+    // 23:00 on Jan 15 in New York is already Jan 16 in UTC
+    let body = json!({ "amount_ml": 500, "consumed_at": "2024-01-16T04:00:00Z" });
+    let (status, _) = app.post_auth("/api/v1/hydration", &body.to_string(), &token).await;
+    assert!(status.is_success());
+
+    let (_, response) = app.get_auth("/api/v1/hydration/daily/2024-01-15", &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["total_ml"], 500);
+
+    let (_, response) = app.get_auth("/api/v1/hydration/daily/2024-01-16", &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["total_ml"], 0);
+}
+
 #[tokio::test]
 #[ignore = "requires database"]
 async fn test_get_health_insights_incomplete_profile() {
@@ -188,3 +301,274 @@ async fn test_get_health_insights_complete_profile() {
     let missing = response["missing_fields"].as_array().unwrap();
     assert!(missing.is_empty());
 }
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_profile_completeness() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (status, response) = app.get_auth("/api/v1/profile/completeness", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["score"], 0);
+    assert_eq!(
+        response["missing_fields"],
+        json!([
+            "Date of Birth",
+            "Height",
+            "Biological Sex",
+            "Activity Level",
+            "Goals",
+            "Unit Preferences"
+        ])
+    );
+
+    let profile = json!({
+        "height": 175.0,
+        "height_unit": "cm",
+        "date_of_birth": "1990-05-01",
+        "biological_sex": "female",
+        "activity_level": "lightly_active"
+    });
+    let (status, _) = app.put_auth("/api/v1/profile", &profile.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let settings = json!({
+        "weight_unit": "kg",
+        "daily_water_goal_ml": 2500
+    });
+    let (status, _) = app.put_auth("/api/v1/profile/settings", &settings.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, response) = app.get_auth("/api/v1/profile/completeness", &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["score"], 100);
+    assert_eq!(response["missing_fields"], json!([]));
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_profile_as_of_last_month_uses_that_months_weight() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let profile = json!({
+        "height": 180.0,
+        "height_unit": "cm",
+        "date_of_birth": "1990-05-01",
+        "biological_sex": "male"
+    });
+    let (status, _) = app.put_auth("/api/v1/profile", &profile.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let now = Utc::now();
+    for (days_ago, weight) in [(40, 90.0), (0, 80.0)] {
+        let body = json!({
+            "weight": weight,
+            "recorded_at": now - Duration::days(days_ago)
+        });
+        let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let (_, profile) = app.get_auth("/api/v1/profile", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    let user_id = Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap();
+
+    let last_month = (now - Duration::days(30)).date_naive();
+    let past = ProfileService::get_profile_at(&app.pool, user_id, last_month)
+        .await
+        .unwrap();
+    let current = ProfileService::get_profile_at(&app.pool, user_id, now.date_naive())
+        .await
+        .unwrap();
+
+    assert_eq!(past.weight_kg, 90.0);
+    assert_eq!(current.weight_kg, 80.0);
+    assert_eq!(
+        calculate_bmi(past.weight_kg, past.height_cm),
+        calculate_bmi(90.0, 180.0)
+    );
+    assert!(
+        calculate_bmi(past.weight_kg, past.height_cm)
+            > calculate_bmi(current.weight_kg, current.height_cm)
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_derived_metrics_for_complete_profile() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let profile = json!({
+        "height": 175.0,
+        "height_unit": "cm",
+        "date_of_birth": "1990-05-01",
+        "biological_sex": "male",
+        "activity_level": "moderately_active"
+    });
+    let (status, _) = app.put_auth("/api/v1/profile", &profile.to_string(), &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let body = json!({ "weight": 78.0 });
+    let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, response) = app.get_auth("/api/v1/profile/derived-metrics", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let bmi = response["bmi"]["value"].as_f64().unwrap();
+    assert!((bmi - calculate_bmi(78.0, 175.0)).abs() < 1e-9);
+    assert!(response["bmi"]["category"].is_string());
+    assert!(response["tdee"]["tdee"].as_f64().unwrap() > response["tdee"]["bmr"].as_f64().unwrap());
+    assert!(response["ideal_weight"]["average"].as_f64().unwrap() > 0.0);
+    assert_eq!(response["healthy_weight_range_kg"].as_array().unwrap().len(), 2);
+    assert!(response["body_fat"]["percent"].as_f64().unwrap() > 0.0);
+    assert!(response["body_fat"]["category"].is_string());
+    assert!(response["daily_water_ml"].as_i64().unwrap() > 0);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_derived_metrics_include_ffmi_with_measured_body_fat() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let profile = json!({
+        "height": 180.0,
+        "height_unit": "cm",
+        "date_of_birth": "1990-05-01",
+        "biological_sex": "male"
+    });
+    app.put_auth("/api/v1/profile", &profile.to_string(), &token).await;
+    app.post_auth("/api/v1/weight", &json!({ "weight": 90.0 }).to_string(), &token)
+        .await;
+
+    let (_, response) = app.get_auth("/api/v1/profile/derived-metrics", &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert!(response.get("ffmi").is_none());
+
+    let body = json!({ "body_fat_percent": 12.0 });
+    let (status, _) = app
+        .post_auth("/api/v1/weight/body-composition", &body.to_string(), &token)
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, response) = app.get_auth("/api/v1/profile/derived-metrics", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let ffmi = response["ffmi"]["value"].as_f64().unwrap();
+    assert!((ffmi - calculate_ffmi(90.0, 180.0, 12.0)).abs() < 1e-9);
+    assert_eq!(response["ffmi"]["category"], "superior");
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_derived_metrics_lists_missing_fields() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (status, response) = app.get_auth("/api/v1/profile/derived-metrics", &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(response.contains("Height"));
+    assert!(response.contains("Current Weight"));
+    assert!(response.contains("Date of Birth"));
+    assert!(response.contains("Biological Sex"));
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_unit_switch_leaves_stored_weights_untouched() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "weight": 72.35 });
+    let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (_, profile) = app.get_auth("/api/v1/profile", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    let user_id = Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap();
+
+    let stored = || async {
+        sqlx::query_scalar::<_, String>(
+            "SELECT weight_kg::text FROM weight_logs WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_all(&app.pool)
+        .await
+        .unwrap()
+    };
+    let before = stored().await;
+
+    let settings =
+        ProfileService::update_unit_preferences(&app.pool, app.state.jobs(), None, user_id, UnitPreferences::imperial())
+            .await
+            .unwrap();
+    assert_eq!(settings.weight_unit, "lbs");
+    assert_eq!(stored().await, before);
+
+    let (_, history) = app.get_auth("/api/v1/weight", &token).await;
+    let history: serde_json::Value = serde_json::from_str(&history).unwrap();
+    assert_eq!(history["items"][0]["display"], "159.5 lbs");
+
+    let settings =
+        ProfileService::update_unit_preferences(&app.pool, app.state.jobs(), None, user_id, UnitPreferences::metric())
+            .await
+            .unwrap();
+    assert_eq!(settings.weight_unit, "kg");
+    assert_eq!(stored().await, before);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_profile_at_rejects_invalid_stored_timezone() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, profile) = app.get_auth("/api/v1/profile", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    let user_id = Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap();
+
+    sqlx::query("UPDATE user_settings SET timezone = 'Mars/Olympus_Mons' WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let result = ProfileService::get_profile_at(&app.pool, user_id, Utc::now().date_naive()).await;
+    assert!(matches!(result, Err(ApiError::Validation(_))));
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_update_settings_without_settings_row_is_not_found() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (_, profile) = app.get_auth("/api/v1/profile", &token).await;
+    let profile: serde_json::Value = serde_json::from_str(&profile).unwrap();
+    let user_id = Uuid::parse_str(profile["id"].as_str().unwrap()).unwrap();
+
+    sqlx::query("DELETE FROM user_settings WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let body = json!({ "daily_step_goal": 8000 });
+    let (status, _) = app.put_auth("/api/v1/profile/settings", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,63 @@
+//! Integration tests for sleep logging and analysis
+
+mod common;
+
+use axum::http::StatusCode;
+use serde_json::json;
+
+async fn log_sleep(app: &common::TestApp, token: &str, body: serde_json::Value) -> StatusCode {
+    let (status, _) = app.post_auth("/api/v1/sleep", &body.to_string(), token).await;
+    status
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_analysis_averages_onset_latency_over_nights_that_recorded_it() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    for (start, end, onset) in [
+        ("2024-03-11T23:00:00Z", "2024-03-12T07:00:00Z", Some(40)),
+        ("2024-03-12T23:00:00Z", "2024-03-13T07:00:00Z", Some(50)),
+        ("2024-03-13T23:00:00Z", "2024-03-14T07:00:00Z", None),
+    ] {
+        let status = log_sleep(&app, &token, json!({
+            "sleep_start": start,
+            "sleep_end": end,
+            "sleep_onset_minutes": onset
+        }))
+        .await;
+        assert!(status.is_success());
+    }
+
+    let (status, response) = app
+        .get_auth(
+            "/api/v1/sleep/analysis?start_date=2024-03-12&end_date=2024-03-14",
+            &token,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let analysis: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+    assert_eq!(analysis["total_nights"], 3);
+    assert_eq!(analysis["avg_onset_latency_minutes"], 45.0);
+    assert_eq!(analysis["prolonged_onset_latency"], true);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_onset_latency_longer_than_sleep_is_rejected() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let status = log_sleep(&app, &token, json!({
+        "sleep_start": "2024-03-11T23:00:00Z",
+        "sleep_end": "2024-03-12T00:00:00Z",
+        "sleep_onset_minutes": 90
+    }))
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
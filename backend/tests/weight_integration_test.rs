@@ -3,6 +3,9 @@
 mod common;
 
 use axum::http::StatusCode;
+use chrono::Utc;
+use fitness_assistant_backend::repositories::WeightRepository;
+use fitness_assistant_backend::services::DataService;
 use serde_json::json;
 
 #[tokio::test]
@@ -62,6 +65,23 @@ async fn test_log_weight_with_unit_conversion() {
     assert!(weight_kg > 74.0 && weight_kg < 76.0);
 }
 
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_log_weight_rejects_unknown_unit() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "weight": 165.0, "unit": "pnds" });
+    let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Common spellings of a known unit are still accepted
+    let body = json!({ "weight": 165.0, "unit": "lb" });
+    let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CREATED);
+}
+
 #[tokio::test]
 #[ignore = "requires database"]
 async fn test_get_weight_history_empty() {
@@ -165,6 +185,33 @@ async fn test_get_weight_trend() {
     assert!(response["total_change"].as_f64().unwrap() < 0.0); // Weight decreased
 }
 
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_get_weight_trend_custom_windows() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    for weight in [76.0, 75.0, 74.0] {
+        log_weight_entry(&app, &token, weight).await;
+    }
+
+    let (status, response) = app
+        .get_auth("/api/v1/weight/trend?windows=2,14", &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["moving_averages"]["2"], 74.5);
+    assert_eq!(response["moving_averages"]["14"], 75.0);
+    assert!(response["moving_averages"].get("7").is_none());
+
+    let (status, _) = app
+        .get_auth("/api/v1/weight/trend?windows=0", &token)
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 #[ignore = "requires database"]
 async fn test_log_body_composition() {
@@ -206,4 +253,261 @@ async fn test_weight_anomaly_detection() {
     
     let response: serde_json::Value = serde_json::from_str(&response).unwrap();
     assert_eq!(response["is_anomaly"], true);
+    // Seconds apart, so far faster than water weight can change
+    assert_eq!(response["likely_cause"], "possible measurement error");
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_overnight_gain_tagged_as_water_retention() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "weight": 75.0, "recorded_at": "2024-05-01T07:00:00Z" });
+    app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+
+    let body = json!({ "weight": 77.0, "recorded_at": "2024-05-02T07:15:00Z" });
+    let (_, response) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["is_anomaly"], true);
+    assert_eq!(response["likely_cause"], "possible water retention");
+
+    let (_, response) = app.get_auth("/api/v1/weight", &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["items"][0]["likely_cause"], "possible water retention");
+    assert!(response["items"][1].get("likely_cause").is_none());
+}
+
+/// Log a weight entry and return its id
+async fn log_weight_entry(app: &common::TestApp, token: &str, weight: f64) -> String {
+    let body = json!({ "weight": weight });
+    let (status, response) = app.post_auth("/api/v1/weight", &body.to_string(), token).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    response["id"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_deleted_weight_disappears_from_history() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let id = log_weight_entry(&app, &token, 75.0).await;
+
+    let (status, _) = app.delete_auth(&format!("/api/v1/weight/{}", id), "", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, response) = app.get_auth("/api/v1/weight", &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["total_count"], 0);
+
+    // Deleting again reports the entry as missing
+    let (status, _) = app.delete_auth(&format!("/api/v1/weight/{}", id), "", &token).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_deleted_weight_can_be_restored() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let id = log_weight_entry(&app, &token, 75.0).await;
+    app.delete_auth(&format!("/api/v1/weight/{}", id), "", &token).await;
+
+    let (status, response) = app
+        .post_auth(&format!("/api/v1/weight/{}/restore", id), "", &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["id"], id);
+
+    let (_, response) = app.get_auth("/api/v1/weight", &token).await;
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["total_count"], 1);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_soft_deleted_weight_purged_after_retention() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let recent = log_weight_entry(&app, &token, 75.0).await;
+    let expired = log_weight_entry(&app, &token, 76.0).await;
+    app.delete_auth(&format!("/api/v1/weight/{}", recent), "", &token).await;
+    app.delete_auth(&format!("/api/v1/weight/{}", expired), "", &token).await;
+
+    // Backdate one deletion past the retention window
+    sqlx::query("UPDATE weight_logs SET deleted_at = NOW() - INTERVAL '31 days' WHERE id = $1")
+        .bind(uuid::Uuid::parse_str(&expired).unwrap())
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let summary = DataService::purge_soft_deleted(&app.pool, 30).await.unwrap();
+    assert!(summary.weight_logs >= 1);
+
+    // The purged entry is gone for good; the recent one is still restorable
+    let (status, _) = app
+        .post_auth(&format!("/api/v1/weight/{}/restore", expired), "", &token)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = app
+        .post_auth(&format!("/api/v1/weight/{}/restore", recent), "", &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_log_weight_records_audit_entry() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let id = log_weight_entry(&app, &token, 75.0).await;
+
+    // Audit entries are written in the background; wait for them
+    app.state.jobs().shutdown(std::time::Duration::from_secs(5)).await;
+
+    let (status, response) = app.get_auth("/api/v1/audit", &token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let items = response["items"].as_array().unwrap();
+    assert!(items.iter().any(|item| {
+        item["entity"] == "weight_log" && item["entity_id"] == id && item["action"] == "create"
+    }));
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_weight_reminder_tracks_days_since_last_log() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let (status, response) = app.get_auth("/api/v1/weight/reminder", &token).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert!(response.get("days_since_last_log").is_none());
+    assert_eq!(response["should_remind"], false);
+
+    let body = json!({
+        "weight": 75.0,
+        "recorded_at": chrono::Utc::now() - chrono::Duration::days(10)
+    });
+    let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, response) = app
+        .get_auth("/api/v1/weight/reminder?cadence=weekly", &token)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["days_since_last_log"], 10);
+    assert_eq!(response["should_remind"], true);
+}
+
+/// Count a user's live weight entries
+async fn user_id(app: &common::TestApp, email: &str) -> uuid::Uuid {
+    sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+        .bind(email)
+        .fetch_one(&app.pool)
+        .await
+        .unwrap()
+}
+
+async fn count_weight_entries(app: &common::TestApp, email: &str) -> i64 {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM weight_logs
+         WHERE user_id = (SELECT id FROM users WHERE email = $1) AND deleted_at IS NULL",
+    )
+    .bind(email)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_dedup_merges_near_simultaneous_identical_logs() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let first = json!({
+        "weight": 75.0,
+        "recorded_at": "2024-06-01T07:00:00Z",
+        "dedup": true
+    });
+    let second = json!({
+        "weight": 75.05,
+        "recorded_at": "2024-06-01T07:00:20Z",
+        "dedup": true
+    });
+
+    let (status, response) = app.post_auth("/api/v1/weight", &first.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let first: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+    let (status, response) = app.post_auth("/api/v1/weight", &second.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let second: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+    assert_eq!(first["id"], second["id"]);
+    assert_eq!(count_weight_entries(&app, &user.email).await, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_update_reading_skips_foreign_and_deleted_entries() {
+    let app = common::TestApp::new().await;
+    let owner = app.create_test_user().await;
+    let other = app.create_test_user().await;
+    let token = owner.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({ "weight": 75.0 });
+    let (status, response) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let log: serde_json::Value = serde_json::from_str(&response).unwrap();
+    let id = uuid::Uuid::parse_str(log["id"].as_str().unwrap()).unwrap();
+
+    let update = |user_id| {
+        WeightRepository::update_reading(&app.pool, id, user_id, 80.0, Utc::now(), "manual", None)
+    };
+    let other_id = user_id(&app, &other.email).await;
+    assert!(update(other_id).await.unwrap().is_none());
+
+    app.delete_auth(&format!("/api/v1/weight/{}", id), "", &token).await;
+    let owner_id = user_id(&app, &owner.email).await;
+    assert!(update(owner_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+#[ignore = "requires database"]
+async fn test_identical_logs_kept_without_dedup() {
+    let app = common::TestApp::new().await;
+    let user = app.create_test_user().await;
+    let token = user.tokens.as_ref().unwrap().access_token.clone();
+
+    let body = json!({
+        "weight": 75.0,
+        "recorded_at": "2024-06-01T07:00:00Z"
+    });
+
+    for _ in 0..2 {
+        let (status, _) = app.post_auth("/api/v1/weight", &body.to_string(), &token).await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    assert_eq!(count_weight_entries(&app, &user.email).await, 2);
 }
@@ -1,7 +1,7 @@
 //! Health metrics calculations module
 //!
-//! Provides calculations for BMI, TDEE, healthy weight ranges, and other
-//! health-related metrics based on user profile data.
+//! Provides calculations for BMI, FFMI, TDEE, healthy weight ranges, population
+//! percentiles, and other health-related metrics based on user profile data.
 //!
 //! # Design Principles
 //!
@@ -25,6 +25,18 @@ pub enum BiologicalSex {
     Female,
 }
 
+impl std::str::FromStr for BiologicalSex {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "male" => Ok(BiologicalSex::Male),
+            "female" => Ok(BiologicalSex::Female),
+            _ => Err(format!("Unknown biological sex: {}", s)),
+        }
+    }
+}
+
 /// Activity level for TDEE calculation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -64,6 +76,89 @@ impl ActivityLevel {
             ActivityLevel::ExtraActive => "Very hard exercise or physical job",
         }
     }
+
+    /// Get the activity multiplier from a custom configuration
+    pub fn multiplier_with(&self, config: &ActivityMultiplierConfig) -> f64 {
+        match self {
+            ActivityLevel::Sedentary => config.sedentary,
+            ActivityLevel::LightlyActive => config.lightly_active,
+            ActivityLevel::ModeratelyActive => config.moderately_active,
+            ActivityLevel::VeryActive => config.very_active,
+            ActivityLevel::ExtraActive => config.extra_active,
+        }
+    }
+}
+
+impl std::str::FromStr for ActivityLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sedentary" => Ok(ActivityLevel::Sedentary),
+            "lightly_active" => Ok(ActivityLevel::LightlyActive),
+            "moderately_active" => Ok(ActivityLevel::ModeratelyActive),
+            "very_active" => Ok(ActivityLevel::VeryActive),
+            "extra_active" => Ok(ActivityLevel::ExtraActive),
+            _ => Err(format!("Unknown activity level: {}", s)),
+        }
+    }
+}
+
+/// Default minimum daily calories recommended for weight loss
+pub const DEFAULT_CALORIE_FLOOR: f64 = 1200.0;
+
+/// Overrides for the TDEE activity multipliers and weight-loss calorie floor
+///
+/// Some protocols use different multipliers (e.g. 1.465 for lightly active).
+/// The default matches [`ActivityLevel::multiplier`] and a 1200 kcal floor.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActivityMultiplierConfig {
+    pub sedentary: f64,
+    pub lightly_active: f64,
+    pub moderately_active: f64,
+    pub very_active: f64,
+    pub extra_active: f64,
+    /// Minimum calories suggested for weight loss
+    pub calorie_floor: f64,
+}
+
+impl Default for ActivityMultiplierConfig {
+    fn default() -> Self {
+        Self {
+            sedentary: ActivityLevel::Sedentary.multiplier(),
+            lightly_active: ActivityLevel::LightlyActive.multiplier(),
+            moderately_active: ActivityLevel::ModeratelyActive.multiplier(),
+            very_active: ActivityLevel::VeryActive.multiplier(),
+            extra_active: ActivityLevel::ExtraActive.multiplier(),
+            calorie_floor: DEFAULT_CALORIE_FLOOR,
+        }
+    }
+}
+
+impl ActivityMultiplierConfig {
+    /// Validate that every multiplier is at least 1.0 and the floor is positive
+    pub fn validate(&self) -> Result<(), String> {
+        let multipliers = [
+            ("sedentary", self.sedentary),
+            ("lightly_active", self.lightly_active),
+            ("moderately_active", self.moderately_active),
+            ("very_active", self.very_active),
+            ("extra_active", self.extra_active),
+        ];
+
+        for (name, value) in multipliers {
+            if !value.is_finite() || value < 1.0 {
+                return Err(format!("Activity multiplier '{}' must be at least 1.0", name));
+            }
+        }
+
+        if !self.calorie_floor.is_finite() || self.calorie_floor <= 0.0 {
+            return Err("Calorie floor must be positive".to_string());
+        }
+
+        Ok(())
+    }
 }
 
 /// User profile data needed for health calculations
@@ -199,6 +294,64 @@ pub fn calculate_bmi_result(weight_kg: f64, height_cm: f64) -> BmiResult {
     }
 }
 
+// ============================================================================
+// Max Heart Rate
+// ============================================================================
+
+/// Age-based max heart rate formula
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MaxHrFormula {
+    /// Fox: 220 - age
+    #[default]
+    Fox,
+    /// Tanaka: 208 - 0.7 × age
+    Tanaka,
+    /// Gulati: 206 - 0.88 × age, derived from women (Fox is used for others)
+    Gulati,
+}
+
+impl MaxHrFormula {
+    /// Name under which the formula is stored
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MaxHrFormula::Fox => "fox",
+            MaxHrFormula::Tanaka => "tanaka",
+            MaxHrFormula::Gulati => "gulati",
+        }
+    }
+}
+
+impl std::str::FromStr for MaxHrFormula {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "fox" => Ok(MaxHrFormula::Fox),
+            "tanaka" => Ok(MaxHrFormula::Tanaka),
+            "gulati" => Ok(MaxHrFormula::Gulati),
+            _ => Err("Invalid max HR formula. Must be one of: fox, tanaka, gulati".to_string()),
+        }
+    }
+}
+
+/// Estimate max heart rate in bpm from age
+///
+/// Gulati only applies to women; for anyone else it falls back to Fox.
+pub fn calculate_max_heart_rate(
+    age_years: i32,
+    sex: Option<BiologicalSex>,
+    formula: MaxHrFormula,
+) -> i32 {
+    let age = age_years as f64;
+    let max_hr = match (formula, sex) {
+        (MaxHrFormula::Tanaka, _) => 208.0 - 0.7 * age,
+        (MaxHrFormula::Gulati, Some(BiologicalSex::Female)) => 206.0 - 0.88 * age,
+        _ => 220.0 - age,
+    };
+    max_hr.round() as i32
+}
+
 // ============================================================================
 // BMR and TDEE Calculations
 // ============================================================================
@@ -270,9 +423,15 @@ pub fn calculate_bmr(profile: &HealthProfile, method: BmrMethod) -> f64 {
 /// Calculate Total Daily Energy Expenditure
 ///
 /// TDEE = BMR × Activity Multiplier
-pub fn calculate_tdee(profile: &HealthProfile) -> f64 {
+///
+/// Uses the standard multipliers unless a custom configuration is given.
+pub fn calculate_tdee(profile: &HealthProfile, config: Option<&ActivityMultiplierConfig>) -> f64 {
     let bmr = calculate_bmr(profile, BmrMethod::MifflinStJeor);
-    bmr * profile.activity_level.multiplier()
+    bmr * activity_multiplier(profile.activity_level, config)
+}
+
+fn activity_multiplier(level: ActivityLevel, config: Option<&ActivityMultiplierConfig>) -> f64 {
+    config.map_or_else(|| level.multiplier(), |c| level.multiplier_with(c))
 }
 
 /// TDEE calculation result with breakdown
@@ -293,20 +452,54 @@ pub struct TdeeResult {
 }
 
 /// Calculate complete TDEE result
-pub fn calculate_tdee_result(profile: &HealthProfile) -> TdeeResult {
+pub fn calculate_tdee_result(
+    profile: &HealthProfile,
+    config: Option<&ActivityMultiplierConfig>,
+) -> TdeeResult {
     let bmr = calculate_bmr(profile, BmrMethod::MifflinStJeor);
-    let tdee = bmr * profile.activity_level.multiplier();
+    let multiplier = activity_multiplier(profile.activity_level, config);
+    let tdee = bmr * multiplier;
+    let floor = config.map_or(DEFAULT_CALORIE_FLOOR, |c| c.calorie_floor);
     
     TdeeResult {
         bmr,
         tdee,
-        activity_multiplier: profile.activity_level.multiplier(),
-        calories_for_loss: (tdee - 500.0).max(1200.0), // Never below 1200
+        activity_multiplier: multiplier,
+        calories_for_loss: (tdee - 500.0).max(floor), // Never below the floor
         calories_for_gain: tdee + 500.0,
         calories_for_maintenance: tdee,
     }
 }
 
+/// Maximum fractional TDEE reduction from adaptive thermogenesis
+pub const MAX_METABOLIC_ADAPTATION: f64 = 0.15;
+
+/// Fractional TDEE reduction after a sustained calorie deficit
+///
+/// Adaptation grows by ~1% per week at a 500 kcal/day deficit and scales
+/// with deficit size (up to 2× for deficits of 1000 kcal or more), capped at
+/// [`MAX_METABOLIC_ADAPTATION`].
+pub fn metabolic_adaptation_factor(weeks_dieting: u32, avg_deficit_kcal: f64) -> f64 {
+    if !avg_deficit_kcal.is_finite() || avg_deficit_kcal <= 0.0 {
+        return 0.0;
+    }
+    let deficit_scale = (avg_deficit_kcal / 500.0).min(2.0);
+    (0.01 * weeks_dieting as f64 * deficit_scale).min(MAX_METABOLIC_ADAPTATION)
+}
+
+/// Calculate TDEE adjusted for metabolic adaptation
+///
+/// Long-term dieters burn less than the predicted TDEE; this applies the
+/// reduction from [`metabolic_adaptation_factor`] to the standard estimate.
+pub fn calculate_adapted_tdee(
+    profile: &HealthProfile,
+    config: Option<&ActivityMultiplierConfig>,
+    weeks_dieting: u32,
+    avg_deficit_kcal: f64,
+) -> f64 {
+    calculate_tdee(profile, config) * (1.0 - metabolic_adaptation_factor(weeks_dieting, avg_deficit_kcal))
+}
+
 // ============================================================================
 // Hydration Calculations
 // ============================================================================
@@ -406,6 +599,103 @@ pub fn classify_body_fat(body_fat_percent: f64, sex: BiologicalSex) -> BodyFatCa
     }
 }
 
+// ============================================================================
+// Fat-Free Mass Index
+// ============================================================================
+
+/// Height (m) that normalized FFMI is adjusted to
+const FFMI_REFERENCE_HEIGHT_M: f64 = 1.8;
+
+/// FFMI points per meter of height used by the normalization
+const FFMI_HEIGHT_ADJUSTMENT: f64 = 6.1;
+
+/// Lowest FFMI accepted as a real body; anything below points to a wrong
+/// weight, height or body fat reading
+pub const MIN_PLAUSIBLE_FFMI: f64 = 8.0;
+
+/// Fat-free mass index category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FfmiCategory {
+    BelowAverage,
+    Average,
+    AboveAverage,
+    Excellent,
+    Superior,
+}
+
+/// FFMI calculation result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FfmiResult {
+    /// Fat-free mass index
+    pub value: f64,
+    /// FFMI adjusted to a height of 1.8 m
+    pub normalized: f64,
+    /// Category of the normalized FFMI for the user's sex
+    pub category: FfmiCategory,
+}
+
+/// Calculate fat-free mass index
+///
+/// Unlike BMI, FFMI only counts lean mass, so muscular users aren't
+/// classified as overweight.
+/// Formula: FFMI = weight(kg) × (1 - BF% / 100) / height(m)²
+pub fn calculate_ffmi(weight_kg: f64, height_cm: f64, body_fat_percent: f64) -> f64 {
+    let height_m = height_cm / 100.0;
+    let lean_mass_kg = weight_kg * (1.0 - body_fat_percent / 100.0);
+    lean_mass_kg / (height_m * height_m)
+}
+
+/// Adjust FFMI to a height of 1.8 m so users of different heights compare
+///
+/// Formula (Kouri et al., 1995): FFMI + 6.1 × (1.8 - height(m))
+pub fn normalize_ffmi(ffmi: f64, height_cm: f64) -> f64 {
+    ffmi + FFMI_HEIGHT_ADJUSTMENT * (FFMI_REFERENCE_HEIGHT_M - height_cm / 100.0)
+}
+
+/// Classify a normalized FFMI
+pub fn classify_ffmi(normalized_ffmi: f64, sex: BiologicalSex) -> FfmiCategory {
+    let thresholds = match sex {
+        BiologicalSex::Male => [18.0, 20.0, 22.0, 23.0],
+        BiologicalSex::Female => [15.0, 17.0, 18.0, 19.0],
+    };
+
+    if normalized_ffmi < thresholds[0] {
+        FfmiCategory::BelowAverage
+    } else if normalized_ffmi < thresholds[1] {
+        FfmiCategory::Average
+    } else if normalized_ffmi < thresholds[2] {
+        FfmiCategory::AboveAverage
+    } else if normalized_ffmi < thresholds[3] {
+        FfmiCategory::Excellent
+    } else {
+        FfmiCategory::Superior
+    }
+}
+
+/// Calculate complete FFMI result
+///
+/// Returns None when the FFMI is below `MIN_PLAUSIBLE_FFMI`, where the
+/// height normalization would produce a meaningless (even negative) value.
+pub fn calculate_ffmi_result(
+    weight_kg: f64,
+    height_cm: f64,
+    body_fat_percent: f64,
+    sex: BiologicalSex,
+) -> Option<FfmiResult> {
+    let value = calculate_ffmi(weight_kg, height_cm, body_fat_percent);
+    if value.is_nan() || value < MIN_PLAUSIBLE_FFMI {
+        return None;
+    }
+    let normalized = normalize_ffmi(value, height_cm);
+
+    Some(FfmiResult {
+        value,
+        normalized,
+        category: classify_ffmi(normalized, sex),
+    })
+}
+
 // ============================================================================
 // Ideal Weight Calculations
 // ============================================================================
@@ -458,11 +748,236 @@ pub fn calculate_ideal_weight(height_cm: f64, sex: BiologicalSex) -> IdealWeight
     }
 }
 
+// ============================================================================
+// Population Percentiles
+// ============================================================================
+
+/// Percentiles that each row of a normative table gives values for
+const NORM_PERCENTILES: [f64; 5] = [10.0, 25.0, 50.0, 75.0, 90.0];
+
+/// One age band of a normative table: the band's upper age (inclusive) and
+/// the male and female values at [`NORM_PERCENTILES`], from least to most fit
+type NormBand = (i32, [f64; 5], [f64; 5]);
+
+/// VO2max (ml/kg/min), approximated from the ACSM / Cooper Institute norms
+const VO2MAX_NORMS: &[NormBand] = &[
+    (29, [33.0, 38.0, 43.0, 48.0, 53.0], [28.5, 33.0, 37.0, 41.0, 46.0]),
+    (39, [31.5, 36.0, 41.0, 45.5, 50.5], [27.0, 31.0, 35.0, 39.0, 43.5]),
+    (49, [29.5, 34.0, 38.5, 43.0, 48.0], [25.0, 29.0, 32.5, 36.5, 41.0]),
+    (59, [26.5, 31.0, 35.5, 40.0, 44.5], [22.5, 26.0, 29.5, 33.0, 37.0]),
+    (69, [23.5, 27.5, 32.0, 36.0, 40.5], [20.0, 23.0, 26.5, 30.0, 33.5]),
+    (i32::MAX, [20.5, 24.0, 28.0, 32.5, 37.0], [18.0, 20.5, 24.0, 27.0, 30.5]),
+];
+
+/// Resting heart rate (bpm), approximated from the YMCA norms
+const RESTING_HR_NORMS: &[NormBand] = &[
+    (25, [81.0, 74.0, 68.0, 62.0, 56.0], [85.0, 78.0, 72.0, 66.0, 61.0]),
+    (35, [82.0, 75.0, 69.0, 63.0, 57.0], [84.0, 77.0, 71.0, 65.0, 60.0]),
+    (45, [83.0, 76.0, 70.0, 64.0, 58.0], [85.0, 78.0, 72.0, 66.0, 60.0]),
+    (55, [84.0, 77.0, 71.0, 65.0, 59.0], [86.0, 79.0, 73.0, 67.0, 61.0]),
+    (65, [84.0, 77.0, 71.0, 65.0, 59.0], [85.0, 78.0, 72.0, 66.0, 60.0]),
+    (i32::MAX, [83.0, 76.0, 70.0, 64.0, 58.0], [84.0, 77.0, 71.0, 65.0, 60.0]),
+];
+
+/// Body fat (%), approximated from the ACSM norms
+const BODY_FAT_NORMS: &[NormBand] = &[
+    (29, [25.9, 20.9, 15.9, 11.5, 7.9], [31.8, 27.0, 22.1, 18.4, 14.5]),
+    (39, [27.3, 22.9, 19.0, 15.0, 11.9], [32.8, 28.3, 24.0, 19.9, 16.0]),
+    (49, [28.5, 24.6, 21.1, 17.5, 14.9], [34.2, 30.3, 26.4, 22.5, 18.6]),
+    (59, [29.6, 25.7, 22.5, 19.4, 16.7], [36.3, 32.8, 29.2, 25.5, 21.8]),
+    (i32::MAX, [29.9, 26.1, 22.8, 19.8, 17.6], [37.0, 33.5, 30.2, 26.4, 22.6]),
+];
+
+/// Norms for the user's age band and sex
+fn norms_for(table: &[NormBand], age_years: i32, sex: BiologicalSex) -> [f64; 5] {
+    let (_, male, female) = table
+        .iter()
+        .find(|(max_age, _, _)| age_years <= *max_age)
+        .unwrap_or(&table[table.len() - 1]);
+    match sex {
+        BiologicalSex::Male => *male,
+        BiologicalSex::Female => *female,
+    }
+}
+
+/// Percentile of `value` given the values at [`NORM_PERCENTILES`] in ascending order
+///
+/// Interpolates linearly between reference points and extrapolates the
+/// outer segments, clamped to 0–100.
+fn interpolate_percentile(value: f64, norms: [f64; 5]) -> f64 {
+    let segment = (1..norms.len() - 1).take_while(|&i| value > norms[i]).count();
+    let (v0, v1) = (norms[segment], norms[segment + 1]);
+    let (p0, p1) = (NORM_PERCENTILES[segment], NORM_PERCENTILES[segment + 1]);
+    (p0 + (value - v0) * (p1 - p0) / (v1 - v0)).clamp(0.0, 100.0)
+}
+
+/// Percentile of a lower-is-fitter metric, so higher percentiles stay fitter
+fn interpolate_inverse_percentile(value: f64, norms: [f64; 5]) -> f64 {
+    interpolate_percentile(-value, norms.map(|v| -v))
+}
+
+/// VO2max percentile (0–100) among people of the same age band and sex
+pub fn percentile_vo2max(vo2max: f64, age_years: i32, sex: BiologicalSex) -> f64 {
+    interpolate_percentile(vo2max, norms_for(VO2MAX_NORMS, age_years, sex))
+}
+
+/// Resting heart rate percentile (0–100) among people of the same age band and sex
+///
+/// Lower heart rates rank higher, so the 90th percentile is fitter than 90%
+/// of the population.
+pub fn percentile_resting_hr(bpm: f64, age_years: i32, sex: BiologicalSex) -> f64 {
+    interpolate_inverse_percentile(bpm, norms_for(RESTING_HR_NORMS, age_years, sex))
+}
+
+/// Body fat percentile (0–100) among people of the same age band and sex
+///
+/// Leaner ranks higher, so the 90th percentile is leaner than 90% of the
+/// population.
+pub fn percentile_body_fat(body_fat_percent: f64, age_years: i32, sex: BiologicalSex) -> f64 {
+    interpolate_inverse_percentile(body_fat_percent, norms_for(BODY_FAT_NORMS, age_years, sex))
+}
+
+// ============================================================================
+// Derived Metrics
+// ============================================================================
+
+/// Estimated body fat with its category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyFatEstimate {
+    /// Estimated body fat percentage (BMI-based)
+    pub percent: f64,
+    /// Body fat category for the user's sex
+    pub category: BodyFatCategory,
+}
+
+/// All metrics derived from a single health profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DerivedMetrics {
+    pub bmi: BmiResult,
+    pub tdee: TdeeResult,
+    pub ideal_weight: IdealWeightResult,
+    /// Healthy weight range in kg for the user's height
+    pub healthy_weight_range_kg: (f64, f64),
+    pub body_fat: BodyFatEstimate,
+    /// Recommended daily water intake in ml
+    pub daily_water_ml: i32,
+    /// Fat-free mass index, present when body fat has been measured and the
+    /// result is plausible
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ffmi: Option<FfmiResult>,
+}
+
+/// Calculate every derived metric for a profile
+///
+/// FFMI needs a real body fat measurement, so it is only reported when
+/// `measured_body_fat_percent` is given and gives a plausible result. TDEE uses the multipliers and
+/// calorie floor in `config`.
+pub fn calculate_derived_metrics(
+    profile: &HealthProfile,
+    config: &ActivityMultiplierConfig,
+    measured_body_fat_percent: Option<f64>,
+) -> DerivedMetrics {
+    let bmi = calculate_bmi_result(profile.weight_kg, profile.height_cm);
+    let body_fat_percent = estimate_body_fat_from_bmi(bmi.value, profile.age_years, profile.sex);
+
+    DerivedMetrics {
+        healthy_weight_range_kg: healthy_weight_range_kg(profile.height_cm),
+        tdee: calculate_tdee_result(profile, Some(config)),
+        ideal_weight: calculate_ideal_weight(profile.height_cm, profile.sex),
+        body_fat: BodyFatEstimate {
+            percent: body_fat_percent,
+            category: classify_body_fat(body_fat_percent, profile.sex),
+        },
+        daily_water_ml: calculate_daily_water_ml(profile.weight_kg, profile.activity_level),
+        ffmi: measured_body_fat_percent.and_then(|body_fat_percent| {
+            calculate_ffmi_result(profile.weight_kg, profile.height_cm, body_fat_percent, profile.sex)
+        }),
+        bmi,
+    }
+}
+
+// ============================================================================
+// Trends
+// ============================================================================
+
+/// Least-squares slope of (x, y) points
+///
+/// Returns None with fewer than two distinct x values.
+pub fn least_squares_slope(points: &[(f64, f64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let (num, den) = points.iter().fold((0.0, 0.0), |(num, den), (x, y)| {
+        (num + (x - mean_x) * (y - mean_y), den + (x - mean_x).powi(2))
+    });
+
+    if den == 0.0 {
+        None
+    } else {
+        Some(num / den)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use proptest::prelude::*;
 
+    // =========================================================================
+    // Derived Metrics Tests
+    // =========================================================================
+
+    #[test]
+    fn test_derived_metrics_compose_individual_calculations() {
+        let profile = HealthProfile {
+            height_cm: 170.0,
+            weight_kg: 72.0,
+            age_years: 28,
+            sex: BiologicalSex::Female,
+            activity_level: ActivityLevel::VeryActive,
+        };
+
+        let metrics = calculate_derived_metrics(&profile, &ActivityMultiplierConfig::default(), None);
+
+        assert_eq!(metrics.bmi.value, calculate_bmi(72.0, 170.0));
+        assert_eq!(metrics.tdee.tdee, calculate_tdee(&profile, None));
+        assert_eq!(metrics.ideal_weight.average, calculate_ideal_weight(170.0, BiologicalSex::Female).average);
+        assert_eq!(metrics.healthy_weight_range_kg, healthy_weight_range_kg(170.0));
+        assert_eq!(
+            metrics.body_fat.category,
+            classify_body_fat(metrics.body_fat.percent, BiologicalSex::Female)
+        );
+        assert_eq!(metrics.daily_water_ml, calculate_daily_water_ml(72.0, ActivityLevel::VeryActive));
+    }
+
+    #[test]
+    fn test_derived_metrics_use_configured_multipliers() {
+        let profile = HealthProfile {
+            height_cm: 170.0,
+            weight_kg: 72.0,
+            age_years: 28,
+            sex: BiologicalSex::Female,
+            activity_level: ActivityLevel::VeryActive,
+        };
+        let config = ActivityMultiplierConfig {
+            very_active: 1.8,
+            calorie_floor: 2500.0,
+            ..Default::default()
+        };
+
+        let metrics = calculate_derived_metrics(&profile, &config, None);
+
+        assert_eq!(metrics.tdee.activity_multiplier, 1.8);
+        assert_eq!(metrics.tdee.tdee, calculate_tdee(&profile, Some(&config)));
+        assert_ne!(metrics.tdee.tdee, calculate_tdee(&profile, None));
+        assert_eq!(metrics.tdee.calories_for_loss, 2500.0);
+    }
+
     // =========================================================================
     // BMI Tests
     // =========================================================================
@@ -543,6 +1058,41 @@ mod tests {
     // BMR/TDEE Tests
     // =========================================================================
 
+    #[test]
+    fn test_max_heart_rate_formulas() {
+        let fox = |age| calculate_max_heart_rate(age, None, MaxHrFormula::Fox);
+        let tanaka = |age| calculate_max_heart_rate(age, None, MaxHrFormula::Tanaka);
+
+        assert_eq!(fox(25), 195);
+        assert_eq!(tanaka(25), 191); // 190.5 rounds up
+        assert_eq!(fox(60), 160);
+        assert_eq!(tanaka(60), 166);
+
+        // The formulas cross at 40: Tanaka is lower for young users, higher for older ones
+        assert!(tanaka(25) < fox(25));
+        assert!(tanaka(60) > fox(60));
+        assert_eq!(tanaka(40), fox(40));
+    }
+
+    #[test]
+    fn test_gulati_applies_to_women_only() {
+        let female = calculate_max_heart_rate(50, Some(BiologicalSex::Female), MaxHrFormula::Gulati);
+        assert_eq!(female, 162); // 206 - 44
+        assert_eq!(
+            calculate_max_heart_rate(50, Some(BiologicalSex::Male), MaxHrFormula::Gulati),
+            calculate_max_heart_rate(50, None, MaxHrFormula::Fox)
+        );
+    }
+
+    #[test]
+    fn test_max_hr_formula_parsing() {
+        assert_eq!("tanaka".parse::<MaxHrFormula>(), Ok(MaxHrFormula::Tanaka));
+        assert_eq!("Gulati".parse::<MaxHrFormula>(), Ok(MaxHrFormula::Gulati));
+        assert!("karvonen".parse::<MaxHrFormula>().is_err());
+        assert_eq!(MaxHrFormula::default(), MaxHrFormula::Fox);
+        assert_eq!(MaxHrFormula::Fox.as_str().parse::<MaxHrFormula>(), Ok(MaxHrFormula::Fox));
+    }
+
     #[test]
     fn test_bmr_mifflin() {
         // 30yo male, 80kg, 180cm -> BMR ~1780
@@ -564,7 +1114,7 @@ mod tests {
             activity_level: ActivityLevel::ModeratelyActive,
         };
         
-        let result = calculate_tdee_result(&profile);
+        let result = calculate_tdee_result(&profile, None);
         
         // BMR ~1780, TDEE = BMR * 1.55 ~2760
         assert!(result.bmr > 1700.0 && result.bmr < 1900.0);
@@ -573,6 +1123,130 @@ mod tests {
         assert_eq!(result.calories_for_gain, result.tdee + 500.0);
     }
 
+    #[test]
+    fn test_tdee_default_config_matches_standard_multipliers() {
+        let config = ActivityMultiplierConfig::default();
+        assert!(config.validate().is_ok());
+
+        for level in [
+            ActivityLevel::Sedentary,
+            ActivityLevel::LightlyActive,
+            ActivityLevel::ModeratelyActive,
+            ActivityLevel::VeryActive,
+            ActivityLevel::ExtraActive,
+        ] {
+            let profile = HealthProfile {
+                height_cm: 165.0,
+                weight_kg: 60.0,
+                age_years: 40,
+                sex: BiologicalSex::Female,
+                activity_level: level,
+            };
+            let standard = calculate_tdee_result(&profile, None);
+            let configured = calculate_tdee_result(&profile, Some(&config));
+
+            assert_eq!(calculate_tdee(&profile, None), standard.tdee);
+            assert_eq!(configured.tdee, standard.tdee);
+            assert_eq!(configured.activity_multiplier, level.multiplier());
+            assert_eq!(configured.calories_for_loss, standard.calories_for_loss);
+        }
+    }
+
+    #[test]
+    fn test_tdee_custom_multiplier_and_floor() {
+        let profile = HealthProfile {
+            height_cm: 155.0,
+            weight_kg: 50.0,
+            age_years: 60,
+            sex: BiologicalSex::Female,
+            activity_level: ActivityLevel::LightlyActive,
+        };
+        let config = ActivityMultiplierConfig {
+            lightly_active: 1.465,
+            calorie_floor: 1400.0,
+            ..Default::default()
+        };
+
+        let standard = calculate_tdee_result(&profile, None);
+        let custom = calculate_tdee_result(&profile, Some(&config));
+
+        assert_eq!(custom.activity_multiplier, 1.465);
+        assert!((custom.tdee - custom.bmr * 1.465).abs() < 1e-9);
+        assert!(custom.tdee > standard.tdee);
+        assert_eq!(calculate_tdee(&profile, Some(&config)), custom.tdee);
+        // BMR ~1030, so the 500 kcal deficit lands under both floors
+        assert_eq!(standard.calories_for_loss, DEFAULT_CALORIE_FLOOR);
+        assert_eq!(custom.calories_for_loss, 1400.0);
+    }
+
+    #[test]
+    fn test_adapted_tdee_increases_with_diet_duration() {
+        let profile = HealthProfile {
+            height_cm: 175.0,
+            weight_kg: 85.0,
+            age_years: 35,
+            sex: BiologicalSex::Male,
+            activity_level: ActivityLevel::LightlyActive,
+        };
+        let predicted = calculate_tdee(&profile, None);
+
+        assert_eq!(calculate_adapted_tdee(&profile, None, 0, 500.0), predicted);
+        assert_eq!(calculate_adapted_tdee(&profile, None, 12, 0.0), predicted);
+
+        let four_weeks = calculate_adapted_tdee(&profile, None, 4, 500.0);
+        let eight_weeks = calculate_adapted_tdee(&profile, None, 8, 500.0);
+        assert!(four_weeks < predicted);
+        assert!(eight_weeks < four_weeks);
+        assert!((four_weeks - predicted * 0.96).abs() < 1e-9);
+
+        // A larger deficit adapts faster
+        assert!(calculate_adapted_tdee(&profile, None, 4, 1000.0) < four_weeks);
+    }
+
+    #[test]
+    fn test_adapted_tdee_clamped_at_maximum() {
+        let profile = HealthProfile {
+            height_cm: 165.0,
+            weight_kg: 70.0,
+            age_years: 45,
+            sex: BiologicalSex::Female,
+            activity_level: ActivityLevel::Sedentary,
+        };
+        let floor = calculate_tdee(&profile, None) * (1.0 - MAX_METABOLIC_ADAPTATION);
+
+        assert_eq!(metabolic_adaptation_factor(52, 750.0), MAX_METABOLIC_ADAPTATION);
+        assert_eq!(metabolic_adaptation_factor(500, 5000.0), MAX_METABOLIC_ADAPTATION);
+        assert!((calculate_adapted_tdee(&profile, None, 52, 750.0) - floor).abs() < 1e-9);
+        assert!((calculate_adapted_tdee(&profile, None, 104, 2000.0) - floor).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_activity_multiplier_config_validation() {
+        let below_one = ActivityMultiplierConfig {
+            sedentary: 0.9,
+            ..Default::default()
+        };
+        assert!(below_one.validate().is_err());
+
+        let nan = ActivityMultiplierConfig {
+            very_active: f64::NAN,
+            ..Default::default()
+        };
+        assert!(nan.validate().is_err());
+
+        let no_floor = ActivityMultiplierConfig {
+            calorie_floor: 0.0,
+            ..Default::default()
+        };
+        assert!(no_floor.validate().is_err());
+
+        let exactly_one = ActivityMultiplierConfig {
+            sedentary: 1.0,
+            ..Default::default()
+        };
+        assert!(exactly_one.validate().is_ok());
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(100))]
 
@@ -615,7 +1289,7 @@ mod tests {
                 sex: BiologicalSex::Male,
                 activity_level: ActivityLevel::ModeratelyActive,
             };
-            let result = calculate_tdee_result(&profile);
+            let result = calculate_tdee_result(&profile, None);
             prop_assert!(result.tdee > result.bmr);
         }
     }
@@ -670,6 +1344,131 @@ mod tests {
         assert_eq!(classify_body_fat(28.0, BiologicalSex::Female), BodyFatCategory::Average);
     }
 
+    // =========================================================================
+    // FFMI Tests
+    // =========================================================================
+
+    #[test]
+    fn test_ffmi_reference_values() {
+        // 90kg at 180cm and 12% body fat carries 79.2kg of lean mass
+        let ffmi = calculate_ffmi(90.0, 180.0, 12.0);
+        assert!((ffmi - 24.44).abs() < 0.01);
+        // Already at the reference height, so normalizing changes nothing
+        assert!((normalize_ffmi(ffmi, 180.0) - ffmi).abs() < 1e-9);
+
+        let result = calculate_ffmi_result(90.0, 180.0, 12.0, BiologicalSex::Male).unwrap();
+        assert_eq!(result.category, FfmiCategory::Superior);
+
+        // Shorter users are adjusted upwards, taller ones downwards
+        assert!(normalize_ffmi(20.0, 165.0) > 20.0);
+        assert!(normalize_ffmi(20.0, 195.0) < 20.0);
+    }
+
+    #[test]
+    fn test_implausible_ffmi_is_rejected() {
+        // Lean index of ~1.3, which normalizing at 224cm would push below zero
+        assert!(calculate_ffmi_result(30.0, 224.2, 67.0, BiologicalSex::Male).is_none());
+        assert!(calculate_ffmi_result(f64::NAN, 180.0, 12.0, BiologicalSex::Male).is_none());
+    }
+
+    #[test]
+    fn test_ffmi_classification() {
+        assert_eq!(classify_ffmi(17.0, BiologicalSex::Male), FfmiCategory::BelowAverage);
+        assert_eq!(classify_ffmi(19.0, BiologicalSex::Male), FfmiCategory::Average);
+        assert_eq!(classify_ffmi(21.0, BiologicalSex::Male), FfmiCategory::AboveAverage);
+        assert_eq!(classify_ffmi(19.0, BiologicalSex::Female), FfmiCategory::Superior);
+    }
+
+    #[test]
+    fn test_derived_metrics_include_ffmi_only_with_measured_body_fat() {
+        let profile = HealthProfile {
+            height_cm: 180.0,
+            weight_kg: 90.0,
+            age_years: 30,
+            sex: BiologicalSex::Male,
+            activity_level: ActivityLevel::VeryActive,
+        };
+
+        assert!(calculate_derived_metrics(&profile, &ActivityMultiplierConfig::default(), None).ffmi.is_none());
+        let ffmi = calculate_derived_metrics(&profile, &ActivityMultiplierConfig::default(), Some(12.0)).ffmi.unwrap();
+        assert_eq!(ffmi.value, calculate_ffmi(90.0, 180.0, 12.0));
+    }
+
+    proptest! {
+        /// Property: FFMI is positive for any realistic body
+        #[test]
+        fn prop_ffmi_is_positive(
+            weight in 30.0f64..250.0,
+            height in 120.0f64..230.0,
+            body_fat in 2.0f64..70.0
+        ) {
+            let ffmi = calculate_ffmi(weight, height, body_fat);
+            prop_assert!(ffmi > 0.0);
+
+            // Bodies too light for their height are rejected before normalizing
+            let result = calculate_ffmi_result(weight, height, body_fat, BiologicalSex::Male);
+            prop_assert_eq!(result.is_some(), ffmi >= MIN_PLAUSIBLE_FFMI);
+            prop_assume!(ffmi >= MIN_PLAUSIBLE_FFMI);
+            prop_assert!(normalize_ffmi(ffmi, height) > 0.0);
+        }
+    }
+
+    // =========================================================================
+    // Population Percentile Tests
+    // =========================================================================
+
+    #[test]
+    fn test_vo2max_percentile_reference_points() {
+        assert!((percentile_vo2max(43.0, 25, BiologicalSex::Male) - 50.0).abs() < 1e-9);
+        assert!((percentile_vo2max(41.0, 44, BiologicalSex::Female) - 90.0).abs() < 1e-9);
+        // Halfway between the 50th and 75th percentile values
+        assert!((percentile_vo2max(45.5, 25, BiologicalSex::Male) - 62.5).abs() < 1e-9);
+        assert_eq!(percentile_vo2max(80.0, 25, BiologicalSex::Male), 100.0);
+        assert_eq!(percentile_vo2max(10.0, 25, BiologicalSex::Male), 0.0);
+    }
+
+    #[test]
+    fn test_vo2max_percentile_is_age_adjusted() {
+        let young = percentile_vo2max(40.0, 25, BiologicalSex::Male);
+        let older = percentile_vo2max(40.0, 65, BiologicalSex::Male);
+        assert!(older > young);
+    }
+
+    #[test]
+    fn test_resting_hr_percentile_reference_points() {
+        assert!((percentile_resting_hr(68.0, 22, BiologicalSex::Male) - 50.0).abs() < 1e-9);
+        assert!((percentile_resting_hr(56.0, 22, BiologicalSex::Male) - 90.0).abs() < 1e-9);
+        assert!((percentile_resting_hr(79.0, 50, BiologicalSex::Female) - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_body_fat_percentile_reference_points() {
+        assert!((percentile_body_fat(15.9, 25, BiologicalSex::Male) - 50.0).abs() < 1e-9);
+        assert!((percentile_body_fat(16.0, 35, BiologicalSex::Female) - 90.0).abs() < 1e-9);
+        assert!((percentile_body_fat(30.2, 70, BiologicalSex::Female) - 50.0).abs() < 1e-9);
+    }
+
+    proptest! {
+        /// Property: percentiles only move one way as the input improves
+        #[test]
+        fn prop_percentiles_are_monotonic(
+            a in 1.0f64..100.0,
+            b in 1.0f64..100.0,
+            age in 18i32..90,
+            male in any::<bool>()
+        ) {
+            let sex = if male { BiologicalSex::Male } else { BiologicalSex::Female };
+            let (low, high) = if a <= b { (a, b) } else { (b, a) };
+
+            prop_assert!(percentile_vo2max(low, age, sex) <= percentile_vo2max(high, age, sex));
+            prop_assert!(percentile_resting_hr(low, age, sex) >= percentile_resting_hr(high, age, sex));
+            prop_assert!(percentile_body_fat(low, age, sex) >= percentile_body_fat(high, age, sex));
+
+            let p = percentile_vo2max(a, age, sex);
+            prop_assert!((0.0..=100.0).contains(&p));
+        }
+    }
+
     // =========================================================================
     // Ideal Weight Tests
     // =========================================================================
@@ -686,4 +1485,18 @@ mod tests {
         // Should be around 55-65kg
         assert!(result.average > 50.0 && result.average < 70.0);
     }
+
+    // =========================================================================
+    // Trend Tests
+    // =========================================================================
+
+    #[test]
+    fn test_least_squares_slope() {
+        let points = [(0.0, 50.0), (1.0, 52.0), (2.0, 54.0)];
+        let slope = least_squares_slope(&points).unwrap();
+        assert!((slope - 2.0).abs() < 1e-10);
+
+        assert_eq!(least_squares_slope(&[(0.0, 50.0)]), None);
+        assert_eq!(least_squares_slope(&[(1.0, 50.0), (1.0, 60.0)]), None);
+    }
 }
@@ -19,4 +19,4 @@ pub use types::*;
 pub use units::*;
 
 // Export models (excluding unit types which are re-exported from units)
-pub use models::{DataSource, Goal, GoalStatus, GoalType, User, UserSettings};
+pub use models::{DataSource, Goal, GoalStatus, GoalType, Role, User, UserSettings};
@@ -1,6 +1,8 @@
 //! Data models for the Fitness Assistant application
 
-use chrono::{DateTime, NaiveDate, Utc};
+use crate::health_metrics::MaxHrFormula;
+use crate::validation::DEFAULT_MEAL_TYPES;
+use chrono::{DateTime, NaiveDate, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -27,10 +29,43 @@ pub struct User {
     pub email: String,
     #[serde(skip_serializing)]
     pub password_hash: String,
+    #[serde(default)]
+    pub role: Role,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Access level of a user account
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    #[default]
+    User,
+    Admin,
+}
+
+impl Role {
+    /// Value stored in the `role` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(Role::User),
+            "admin" => Ok(Role::Admin),
+            other => Err(format!("Unknown role: {}", other)),
+        }
+    }
+}
+
 /// User settings and preferences
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserSettings {
@@ -39,6 +74,12 @@ pub struct UserSettings {
     pub distance_unit: DistanceUnit,
     pub energy_unit: EnergyUnit,
     pub timezone: String,
+    /// First day of the week for weekly summaries
+    pub week_start: Weekday,
+    /// Meal types food can be logged under
+    pub meal_types: Vec<String>,
+    /// Formula for default heart rate zones
+    pub max_hr_formula: MaxHrFormula,
     pub daily_calorie_goal: Option<i32>,
     pub daily_water_goal_ml: Option<i32>,
     pub daily_step_goal: Option<i32>,
@@ -53,6 +94,9 @@ impl Default for UserSettings {
             distance_unit: DistanceUnit::default(),
             energy_unit: EnergyUnit::default(),
             timezone: "UTC".to_string(),
+            week_start: Weekday::Mon,
+            meal_types: DEFAULT_MEAL_TYPES.iter().map(|m| m.to_string()).collect(),
+            max_hr_formula: MaxHrFormula::default(),
             daily_calorie_goal: None,
             daily_water_goal_ml: None,
             daily_step_goal: None,
@@ -1,7 +1,9 @@
 //! API request and response types
 
+use crate::models::Role;
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Date range for queries
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,14 +126,43 @@ pub struct RegisterRequest {
     pub password: String,
 }
 
+/// Account deletion request, confirmed with the account password
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteAccountRequest {
+    pub password: String,
+}
+
 /// User profile response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserProfile {
     pub id: String,
     pub email: String,
+    #[serde(default)]
+    pub email_verified: bool,
+    #[serde(default)]
+    pub role: Role,
     pub created_at: DateTime<Utc>,
 }
 
+/// Password reset request, answered the same whether or not the email is registered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordResetRequest {
+    pub email: String,
+}
+
+/// New password, authorized by a password reset token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+/// Email verification request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
 
 // ============================================================================
 // Weight and Body Composition Types
@@ -151,6 +182,9 @@ pub struct LogWeightRequest {
     pub source: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+    /// Update a matching entry logged moments earlier instead of adding one
+    #[serde(default)]
+    pub dedup: bool,
 }
 
 /// Weight log response (returns in user's preferred unit)
@@ -161,6 +195,8 @@ pub struct WeightLogResponse {
     pub weight: f64,
     /// The unit of the weight value
     pub unit: String,
+    /// Weight with its unit for display, e.g. `154.3 lbs`
+    pub display: String,
     /// Weight in kg (always included for consistency)
     pub weight_kg: f64,
     pub recorded_at: DateTime<Utc>,
@@ -168,6 +204,12 @@ pub struct WeightLogResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
     pub is_anomaly: bool,
+    /// Likely explanation for an anomaly, e.g. "possible water retention"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub likely_cause: Option<String>,
+    /// Cycle phase on the day of the reading, for users tracking their cycle
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle_phase: Option<String>,
 }
 
 /// Weight history query parameters
@@ -176,29 +218,13 @@ pub struct WeightHistoryQuery {
     pub start: Option<DateTime<Utc>>,
     pub end: Option<DateTime<Utc>>,
     /// Number of items to return (default: 50, max: 100)
-    #[serde(default = "default_weight_limit")]
-    pub limit: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
     /// Number of items to skip (default: 0)
     #[serde(default)]
     pub offset: i64,
 }
 
-fn default_weight_limit() -> i64 {
-    50
-}
-
-impl WeightHistoryQuery {
-    /// Normalize query parameters to valid ranges
-    pub fn normalize(&self) -> Self {
-        Self {
-            start: self.start,
-            end: self.end,
-            limit: self.limit.clamp(1, 100),
-            offset: self.offset.max(0),
-        }
-    }
-}
-
 /// Paginated weight history response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeightHistoryResponse {
@@ -220,9 +246,39 @@ pub struct WeightTrendResponse {
     pub moving_average_7d: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub moving_average_30d: Option<f64>,
+    /// Moving averages keyed by window size in entries
+    pub moving_averages: HashMap<usize, f64>,
     pub entries_count: usize,
 }
 
+/// Weight trend query parameters
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WeightTrendQuery {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    /// Comma-separated moving-average windows (e.g. "14,90"); 7 and 30 when absent
+    #[serde(default)]
+    pub windows: Option<String>,
+}
+
+/// Weight check-in reminder query parameters
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WeightReminderQuery {
+    /// How often the user means to weigh in: daily (default) or weekly
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cadence: Option<String>,
+}
+
+/// Weight check-in reminder response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightReminderResponse {
+    /// Days since the last weight log, absent if the user has never logged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_since_last_log: Option<i64>,
+    /// Whether the user has missed a check-in for their cadence
+    pub should_remind: bool,
+}
+
 /// Goal projection request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GoalProjectionRequest {
@@ -303,6 +359,9 @@ pub struct UpdateProfileRequest {
     /// Activity level (sedentary, lightly_active, moderately_active, very_active, extra_active)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub activity_level: Option<String>,
+    /// Version from the last read; stale updates are rejected with 409
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_version: Option<i32>,
 }
 
 /// User settings update request
@@ -326,6 +385,15 @@ pub struct UpdateSettingsRequest {
     /// Timezone (e.g., "America/New_York")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timezone: Option<String>,
+    /// First day of the week for weekly summaries (e.g., "monday", "sunday")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub week_start: Option<String>,
+    /// Meal types food can be logged under (e.g., ["breakfast", "pre-workout"])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meal_types: Option<Vec<String>>,
+    /// Max heart rate formula for default zones (fox, tanaka, gulati)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_hr_formula: Option<String>,
     /// Daily calorie goal
     #[serde(skip_serializing_if = "Option::is_none")]
     pub daily_calorie_goal: Option<i32>,
@@ -335,6 +403,9 @@ pub struct UpdateSettingsRequest {
     /// Daily step goal
     #[serde(skip_serializing_if = "Option::is_none")]
     pub daily_step_goal: Option<i32>,
+    /// Version from the last read; stale updates are rejected with 409
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_version: Option<i32>,
 }
 
 /// User profile response
@@ -346,6 +417,9 @@ pub struct UserProfileResponse {
     pub height: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub height_unit: Option<String>,
+    /// Height with its unit for display, e.g. `5'9"` or `175.0 cm`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height_display: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub date_of_birth: Option<NaiveDate>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -354,6 +428,8 @@ pub struct UserProfileResponse {
     pub biological_sex: Option<String>,
     pub activity_level: String,
     pub created_at: DateTime<Utc>,
+    /// Settings version, to send back as `expected_version`
+    pub version: i32,
 }
 
 /// User settings response
@@ -365,12 +441,26 @@ pub struct UserSettingsResponse {
     pub height_unit: String,
     pub temperature_unit: String,
     pub timezone: String,
+    pub week_start: String,
+    pub meal_types: Vec<String>,
+    pub max_hr_formula: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub daily_calorie_goal: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub daily_water_goal_ml: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub daily_step_goal: Option<i32>,
+    /// Settings version, to send back as `expected_version`
+    pub version: i32,
+}
+
+/// How much of the profile the user has filled in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileCompleteness {
+    /// Weighted score from 0 to 100
+    pub score: u8,
+    /// Labels of fields still to fill in, most important first
+    pub missing_fields: Vec<String>,
 }
 
 // ============================================================================
@@ -423,6 +513,12 @@ pub struct EnergyInfo {
     pub calories_for_gain: f64,
     /// Calories for maintenance
     pub calories_for_maintenance: f64,
+    /// TDEE adjusted for metabolic adaptation during a sustained deficit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adapted_tdee: Option<f64>,
+    /// Consecutive weeks of logged calorie deficit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weeks_in_deficit: Option<u32>,
     pub unit: String,
 }
 
@@ -459,6 +555,7 @@ pub struct BodyFatInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FoodSearchQuery {
     pub q: String,
+    /// Number of foods to return (default: 20, max: 100)
     #[serde(default)]
     pub limit: Option<i64>,
 }
@@ -480,10 +577,77 @@ pub struct FoodItemResponse {
     pub fat_g: f64,
     pub fiber_g: f64,
     pub sugar_g: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sodium_mg: Option<f64>,
+    pub alcohol_g: f64,
     pub source: String,
     pub verified: bool,
 }
 
+/// Recently used foods query parameters
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RecentFoodsQuery {
+    /// Number of foods to return (default: 10, max: 50)
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+/// Nutrition preview query parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NutritionPreviewQuery {
+    pub servings: f64,
+}
+
+/// Edit a food item; omitted fields are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateFoodItemRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brand: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serving_size: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serving_unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calories: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protein_g: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carbohydrates_g: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fat_g: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fiber_g: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sugar_g: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sodium_mg: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alcohol_g: Option<f64>,
+}
+
+/// Nutrition for some servings of a food, before it's logged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NutritionPreviewResponse {
+    pub food_item_id: String,
+    pub servings: f64,
+    pub calories: f64,
+    pub protein_g: f64,
+    pub carbohydrates_g: f64,
+    pub fat_g: f64,
+    pub fiber_g: f64,
+    pub sugar_g: f64,
+    pub alcohol_g: f64,
+}
+
+/// Favorite toggle response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavoriteFoodResponse {
+    pub food_item_id: String,
+    pub is_favorite: bool,
+}
+
 /// Log food request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogFoodRequest {
@@ -492,7 +656,7 @@ pub struct LogFoodRequest {
     pub food_item_id: Option<String>,
     /// Number of servings consumed
     pub servings: f64,
-    /// Meal type: breakfast, lunch, dinner, snack
+    /// Meal type from the user's configured list (default: breakfast, lunch, dinner, snack)
     pub meal_type: String,
     /// When the food was consumed (defaults to now)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -516,6 +680,11 @@ pub struct FoodLogResponse {
     pub carbohydrates_g: f64,
     pub fat_g: f64,
     pub fiber_g: f64,
+    /// Carbohydrates minus fiber, floored at zero
+    pub net_carbs_g: f64,
+    pub sugar_g: f64,
+    pub sodium_mg: f64,
+    pub alcohol_g: f64,
     pub meal_type: String,
     pub consumed_at: DateTime<Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -531,6 +700,11 @@ pub struct DailyNutritionResponse {
     pub total_carbs_g: f64,
     pub total_fat_g: f64,
     pub total_fiber_g: f64,
+    /// Sum of each entry's net carbs
+    pub total_net_carbs_g: f64,
+    pub total_sugar_g: f64,
+    pub total_sodium_mg: f64,
+    pub total_alcohol_g: f64,
     pub meal_count: i64,
     pub logs: Vec<FoodLogResponse>,
 }
@@ -560,6 +734,47 @@ pub struct RecipeIngredientInput {
     pub sort_order: i32,
 }
 
+/// Create meal template request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMealTemplateRequest {
+    pub name: String,
+    pub items: Vec<MealTemplateItemInput>,
+}
+
+/// Meal template item input
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MealTemplateItemInput {
+    pub food_item_id: String,
+    pub servings: f64,
+}
+
+/// Log meal template request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogMealTemplateRequest {
+    /// Meal type from the user's configured list (default: breakfast, lunch, dinner, snack)
+    pub meal_type: String,
+    /// When the meal was consumed (defaults to now)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consumed_at: Option<DateTime<Utc>>,
+}
+
+/// Meal template response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MealTemplateResponse {
+    pub id: String,
+    pub name: String,
+    pub items: Vec<MealTemplateItemResponse>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Meal template item response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MealTemplateItemResponse {
+    pub food_item_id: String,
+    pub food_name: String,
+    pub servings: f64,
+}
+
 /// Recipe response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecipeResponse {
@@ -609,6 +824,41 @@ pub struct DateQuery {
     pub date: NaiveDate,
 }
 
+/// Copy a day's food logs onto another date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyDayRequest {
+    pub from_date: NaiveDate,
+    pub to_date: NaiveDate,
+}
+
+/// Copy day response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyDayResponse {
+    /// Number of food logs created on the target date
+    pub copied: usize,
+}
+
+/// Macros still available today, for AI meal suggestions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MealSuggestionRequest {
+    pub calories: f64,
+    pub protein_g: f64,
+    pub carbohydrates_g: f64,
+    pub fat_g: f64,
+}
+
+/// AI-suggested meal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MealSuggestionResponse {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub calories: f64,
+    pub protein_g: f64,
+    pub carbohydrates_g: f64,
+    pub fat_g: f64,
+}
+
 
 // ============================================================================
 // Exercise and Workout Types
@@ -647,13 +897,110 @@ pub struct ExerciseLibraryQuery {
     /// Include user's custom exercises
     #[serde(default)]
     pub include_custom: bool,
-    /// Limit results
-    #[serde(default = "default_exercise_limit")]
-    pub limit: i64,
+    /// Limit search results (default: 50, max: 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
 }
 
-fn default_exercise_limit() -> i64 {
-    50
+/// Exercise prescription in a workout template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkoutTemplateExerciseInput {
+    pub exercise_id: String,
+    pub target_sets: i32,
+    pub target_reps: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_weight_kg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// Create or replace workout template request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkoutTemplateRequest {
+    pub name: String,
+    /// Workout type (default: strength)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workout_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Exercises in the order they are performed
+    pub exercises: Vec<WorkoutTemplateExerciseInput>,
+}
+
+/// Instantiate workout template request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstantiateTemplateRequest {
+    /// Day of the workout (defaults to today)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+}
+
+/// Workout template exercise response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkoutTemplateExerciseResponse {
+    pub exercise_id: String,
+    pub exercise_name: String,
+    pub target_sets: i32,
+    pub target_reps: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_weight_kg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// Workout template response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkoutTemplateResponse {
+    pub id: String,
+    pub name: String,
+    pub workout_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    pub exercises: Vec<WorkoutTemplateExerciseResponse>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Next-load recommendation response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadRecommendationResponse {
+    /// increase, maintain, deload, or none when there is no history
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_weight_kg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommended_weight_kg: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_reps: Option<i32>,
+    pub message: String,
+}
+
+/// Exercise history query parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExerciseHistoryQuery {
+    /// Most recent sessions to include (default: 20, max: 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+}
+
+/// One session of an exercise on a strength progress chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExerciseHistoryPointResponse {
+    pub workout_id: String,
+    pub performed_at: DateTime<Utc>,
+    pub date: NaiveDate,
+    pub top_set_weight_kg: f64,
+    pub top_set_reps: i32,
+    pub estimated_one_rep_max_kg: f64,
+    pub total_volume_kg: f64,
+}
+
+/// Exercise substitution query parameters
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ExerciseSubstitutionQuery {
+    /// Comma-separated equipment the user has (e.g., "dumbbells,kettlebell")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub equipment: Option<String>,
 }
 
 /// Create custom exercise request
@@ -672,6 +1019,57 @@ pub struct CreateExerciseRequest {
     pub instructions: Option<String>,
 }
 
+/// Turn a feature flag on or off for a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetFeatureFlagRequest {
+    pub enabled: bool,
+}
+
+/// A user's feature flags; flags that were never set are off
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureFlagsResponse {
+    pub flags: std::collections::BTreeMap<String, bool>,
+}
+
+/// Result of seeding the shared exercise library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedExercisesResponse {
+    /// Exercises added; ones already in the library are skipped
+    pub inserted: usize,
+}
+
+/// Shared food item in an admin import batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportFoodItemRequest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brand: Option<String>,
+    /// Items repeating a barcode in the batch or database are skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub barcode: Option<String>,
+    pub serving_size: f64,
+    pub serving_unit: String,
+    pub calories: f64,
+    pub protein_g: f64,
+    pub carbohydrates_g: f64,
+    pub fat_g: f64,
+    #[serde(default)]
+    pub fiber_g: f64,
+    #[serde(default)]
+    pub sugar_g: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sodium_mg: Option<f64>,
+    #[serde(default)]
+    pub alcohol_g: f64,
+}
+
+/// Result of importing a batch of food items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportFoodItemsResponse {
+    /// Items added; duplicates by barcode are skipped
+    pub inserted: u64,
+}
+
 /// Log workout request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogWorkoutRequest {
@@ -691,6 +1089,9 @@ pub struct LogWorkoutRequest {
     pub avg_heart_rate: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_heart_rate: Option<i32>,
+    /// Heart rate one minute after finishing, used for heart-rate recovery
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_heart_rate: Option<i32>,
     /// Distance in meters (for cardio)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub distance_meters: Option<f64>,
@@ -759,12 +1160,23 @@ pub struct WorkoutResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_heart_rate: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_heart_rate: Option<i32>,
+    /// One-minute heart-rate recovery in bpm (max minus recovery heart rate)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heart_rate_recovery: Option<i32>,
+    /// Heart-rate recovery class: poor, normal, excellent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heart_rate_recovery_class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub distance_meters: Option<f64>,
     /// Pace in seconds per kilometer (calculated for cardio)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pace_seconds_per_km: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub elevation_gain_meters: Option<f64>,
+    /// Pace adjusted for elevation gain, in seconds per kilometer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grade_adjusted_pace_seconds_per_km: Option<i32>,
     pub source: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
@@ -816,27 +1228,13 @@ pub struct ExerciseSetResponse {
 pub struct WorkoutHistoryQuery {
     pub start: Option<DateTime<Utc>>,
     pub end: Option<DateTime<Utc>>,
-    #[serde(default = "default_workout_limit")]
-    pub limit: i64,
+    /// Number of items to return (default: 50, max: 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
     #[serde(default)]
     pub offset: i64,
 }
 
-fn default_workout_limit() -> i64 {
-    50
-}
-
-impl WorkoutHistoryQuery {
-    pub fn normalize(&self) -> Self {
-        Self {
-            start: self.start,
-            end: self.end,
-            limit: self.limit.clamp(1, 100),
-            offset: self.offset.max(0),
-        }
-    }
-}
-
 /// Paginated workout history response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkoutHistoryResponse {
@@ -899,6 +1297,9 @@ pub struct LogHydrationRequest {
     /// Optional notes
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+    /// Caffeine in mg (estimated from the beverage type when omitted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caffeine_mg: Option<i32>,
 }
 
 /// Hydration log response
@@ -911,6 +1312,8 @@ pub struct HydrationLogResponse {
     pub source: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caffeine_mg: Option<i32>,
 }
 
 /// Daily hydration summary response
@@ -921,6 +1324,10 @@ pub struct DailyHydrationResponse {
     pub goal_ml: i32,
     pub progress_percent: f64,
     pub goal_met: bool,
+    pub total_caffeine_mg: i64,
+    /// Set when caffeine is over the daily warning threshold
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caffeine_warning: Option<String>,
     pub entry_count: i64,
     pub entries: Vec<HydrationLogResponse>,
 }
@@ -983,6 +1390,10 @@ pub struct DailyHydrationSummaryResponse {
     pub goal_ml: i32,
     pub progress_percent: f64,
     pub goal_met: bool,
+    pub total_caffeine_mg: i64,
+    /// Set when caffeine is over the daily warning threshold
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caffeine_warning: Option<String>,
     pub entry_count: i64,
 }
 
@@ -998,6 +1409,12 @@ pub struct LogSleepRequest {
     pub sleep_start: DateTime<Utc>,
     /// When sleep ended (woke up)
     pub sleep_end: DateTime<Utc>,
+    /// Total minutes in bed, if longer than the sleep period
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_bed_minutes: Option<i32>,
+    /// Minutes from lights-out to falling asleep
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sleep_onset_minutes: Option<i32>,
     /// Minutes spent awake during sleep
     #[serde(skip_serializing_if = "Option::is_none")]
     pub awake_minutes: Option<i32>,
@@ -1043,6 +1460,10 @@ pub struct SleepLogResponse {
     pub sleep_start: DateTime<Utc>,
     pub sleep_end: DateTime<Utc>,
     pub total_duration_minutes: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_bed_minutes: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sleep_onset_minutes: Option<i32>,
     pub awake_minutes: i32,
     pub light_minutes: i32,
     pub deep_minutes: i32,
@@ -1071,27 +1492,13 @@ pub struct SleepLogResponse {
 pub struct SleepHistoryQuery {
     pub start_date: NaiveDate,
     pub end_date: NaiveDate,
-    #[serde(default = "default_sleep_limit")]
-    pub limit: i64,
+    /// Number of items to return (default: 30, max: 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
     #[serde(default)]
     pub offset: i64,
 }
 
-fn default_sleep_limit() -> i64 {
-    30
-}
-
-impl SleepHistoryQuery {
-    pub fn normalize(&self) -> Self {
-        Self {
-            start_date: self.start_date,
-            end_date: self.end_date,
-            limit: self.limit.clamp(1, 100),
-            offset: self.offset.max(0),
-        }
-    }
-}
-
 /// Paginated sleep history response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SleepHistoryResponse {
@@ -1117,6 +1524,13 @@ pub struct SleepAnalysisResponse {
     pub avg_light_percent: f64,
     /// Average percentage of time awake
     pub avg_awake_percent: f64,
+    /// Average minutes to fall asleep, over nights that recorded it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_onset_latency_minutes: Option<f64>,
+    /// Average onset latency is over 30 minutes, a possible insomnia indicator
+    pub prolonged_onset_latency: bool,
+    /// Chronotype from the average sleep midpoint: lark, intermediate, or owl
+    pub chronotype: String,
     /// Total number of nights tracked
     pub total_nights: i64,
     /// Sleep debt in minutes (positive = under-slept)
@@ -1233,6 +1647,22 @@ pub struct LogHrvRequest {
     pub notes: Option<String>,
 }
 
+/// Update HRV log request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateHrvRequest {
+    /// Corrected RMSSD value in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rmssd: Option<f64>,
+    /// Corrected SDNN value in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdnn: Option<f64>,
+    /// Context: morning, sleep, recovery, workout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
 /// HRV log response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HrvLogResponse {
@@ -1264,6 +1694,25 @@ pub struct RecoveryScoreResponse {
     pub resting_hr_baseline: Option<f64>,
     /// Status: excellent, good, moderate, low, poor
     pub status: String,
+    /// Today's cycle phase, for users tracking their cycle
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle_phase: Option<String>,
+}
+
+/// Training readiness response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingReadinessResponse {
+    /// Recommendation: go hard, moderate, rest
+    pub recommendation: String,
+    /// Why this recommendation was made
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_score: Option<f64>,
+    /// HRV trend in ms/day
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hrv_trend_slope: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resting_hr_anomaly: Option<bool>,
 }
 
 /// Heart rate zone
@@ -1294,6 +1743,42 @@ pub struct ZoneDistributionResponse {
     pub percentage: f64,
 }
 
+/// One heart rate sample from a wearable stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartRateSample {
+    pub recorded_at: DateTime<Utc>,
+    pub bpm: i32,
+}
+
+/// Ingest a workout heart rate stream request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestHeartRateStreamRequest {
+    /// Samples in chronological order
+    pub samples: Vec<HeartRateSample>,
+    /// Device that recorded the stream, which decides the valid bpm range
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Heart rate stream summary response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartRateStreamSummaryResponse {
+    pub workout_id: String,
+    pub sample_count: usize,
+    pub duration_seconds: i32,
+    pub avg_bpm: i32,
+    pub max_bpm: i32,
+    pub min_bpm: i32,
+    pub zone_distribution: Vec<ZoneDistributionResponse>,
+}
+
+/// Zone distribution query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZoneDistributionQuery {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
 /// Resting HR analysis response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RestingHrAnalysisResponse {
@@ -1304,6 +1789,14 @@ pub struct RestingHrAnalysisResponse {
     pub trend: String,
 }
 
+/// Resting HR analysis query
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RestingHrAnalysisQuery {
+    /// Baseline method: mean (default) or median
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<String>,
+}
+
 /// Biometrics history query
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BiometricsHistoryQuery {
@@ -1421,6 +1914,8 @@ pub struct GoalResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub target_date: Option<NaiveDate>,
     pub status: String,
+    /// Percent complete, 0-100
+    pub progress_percent: f64,
 }
 
 /// Goal progress response
@@ -1437,6 +1932,30 @@ pub struct GoalProgressResponse {
     pub milestones: Vec<MilestoneResponse>,
 }
 
+/// Goal completion projection from the tracked metric's trend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalMetricProjectionResponse {
+    pub goal_id: String,
+    pub metric: String,
+    pub current_value: f64,
+    pub target_value: f64,
+    pub remaining: f64,
+    pub average_daily_change: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_days: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_date: Option<NaiveDate>,
+    pub on_track: bool,
+}
+
+/// Two active goals whose calorie implications contradict each other
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalConflictResponse {
+    pub goal_ids: [String; 2],
+    pub kind: String,
+    pub message: String,
+}
+
 /// Milestone response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MilestoneResponse {
@@ -1621,3 +2140,137 @@ pub struct SupplementsListQuery {
 fn default_active_only() -> bool {
     true
 }
+
+// ============================================================================
+// Audit Types
+// ============================================================================
+
+/// Audit log query with pagination
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogQuery {
+    /// Number of items to return (default: 50, max: 100)
+    #[serde(default = "default_audit_limit")]
+    pub limit: i64,
+    /// Number of items to skip (default: 0)
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_audit_limit() -> i64 {
+    50
+}
+
+impl AuditLogQuery {
+    /// Normalize query parameters to valid ranges
+    pub fn normalize(&self) -> Self {
+        Self {
+            limit: self.limit.clamp(1, 100),
+            offset: self.offset.max(0),
+        }
+    }
+}
+
+/// A single audited action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogResponse {
+    pub id: String,
+    pub entity: String,
+    pub entity_id: String,
+    pub action: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated audit log response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogPageResponse {
+    pub items: Vec<AuditLogResponse>,
+    pub total_count: i64,
+    pub limit: i64,
+    pub offset: i64,
+    pub has_more: bool,
+}
+
+// ============================================================================
+// Export Types
+// ============================================================================
+
+/// JSON export query parameters
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ExportQuery {
+    /// Comma-separated datasets to include (e.g. "weight,sleep"); all when absent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<String>,
+    /// Only export logs recorded at or after this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<DateTime<Utc>>,
+    /// Only export logs recorded at or before this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Utc>>,
+}
+
+/// Queued data export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportJobResponse {
+    pub id: String,
+    /// pending, running, complete or failed
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Where to fetch the export once it is complete
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// Cycle Tracking Types
+// ============================================================================
+
+/// Update cycle tracking settings request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateCycleSettingsRequest {
+    /// Opt in to (or out of) cycle tracking
+    pub enabled: bool,
+    /// Typical cycle length in days (20-45, default 28)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle_length_days: Option<i32>,
+    /// Typical period length in days (1-10, default 5)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period_length_days: Option<i32>,
+}
+
+/// Cycle tracking settings response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleSettingsResponse {
+    pub enabled: bool,
+    pub cycle_length_days: i32,
+    pub period_length_days: i32,
+}
+
+/// Log the first day of a cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogCycleStartRequest {
+    pub started_on: NaiveDate,
+}
+
+/// Cycle phase query parameters
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CyclePhaseQuery {
+    /// Day to look up (defaults to today in the user's timezone)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+}
+
+/// Cycle phase on a given day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CyclePhaseResponse {
+    pub date: NaiveDate,
+    /// menstrual, follicular, ovulatory or luteal
+    pub phase: String,
+    /// Day of the cycle, starting at 1
+    pub cycle_day: i32,
+    /// First day of the cycle the date falls in
+    pub cycle_started_on: NaiveDate,
+}
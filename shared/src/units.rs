@@ -263,6 +263,14 @@ impl TemperatureUnit {
             TemperatureUnit::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
         }
     }
+
+    /// Get the unit abbreviation
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "°C",
+            TemperatureUnit::Fahrenheit => "°F",
+        }
+    }
 }
 
 // ============================================================================
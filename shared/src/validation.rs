@@ -3,6 +3,11 @@
 //! This module provides validation utilities for user input.
 //! Uses both custom validators and the `validator` crate for derive macros.
 
+use chrono::{DateTime, Utc, Weekday};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
 /// Validate email format
 pub fn validate_email(email: &str) -> Result<(), String> {
     if email.is_empty() {
@@ -95,6 +100,35 @@ pub fn validate_duration_minutes(minutes: i32) -> Result<(), String> {
     Ok(())
 }
 
+/// Allowed disagreement between a supplied duration and its timestamps
+pub const DURATION_TOLERANCE_MINUTES: i64 = 1;
+
+/// Reconcile an activity's duration with its start and end times
+///
+/// Returns the supplied duration when it agrees with the timestamps to within
+/// a minute, or the duration derived from them when none was supplied.
+pub fn reconcile_duration_minutes(
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    duration_minutes: Option<i32>,
+) -> Result<i32, String> {
+    if ended_at <= started_at {
+        return Err("End time must be after start time".to_string());
+    }
+
+    let derived = (ended_at - started_at).num_minutes();
+    match duration_minutes {
+        Some(supplied) if (i64::from(supplied) - derived).abs() > DURATION_TOLERANCE_MINUTES => {
+            Err(format!(
+                "Duration ({} min) does not match start and end times ({} min)",
+                supplied, derived
+            ))
+        }
+        Some(supplied) => Ok(supplied),
+        None => i32::try_from(derived).map_err(|_| "Duration is too long".to_string()),
+    }
+}
+
 // ============================================================================
 // Profile Validation
 // ============================================================================
@@ -179,6 +213,198 @@ pub fn validate_biological_sex(sex: &str) -> Result<(), String> {
     }
 }
 
+/// Parse a week start preference such as "monday" or "Sun"
+pub fn parse_week_start(value: &str) -> Result<Weekday, String> {
+    value
+        .trim()
+        .parse::<Weekday>()
+        .map_err(|_| "Invalid week start. Must be a day of the week".to_string())
+}
+
+/// Name under which a week start preference is stored
+pub fn week_start_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// Meal types every user starts with
+pub const DEFAULT_MEAL_TYPES: &[&str] = &["breakfast", "lunch", "dinner", "snack"];
+
+/// Maximum number of configured meal types
+pub const MAX_MEAL_TYPES: usize = 12;
+
+/// Maximum length of a meal type name (matches the food log column)
+pub const MAX_MEAL_TYPE_LENGTH: usize = 20;
+
+/// Normalize a meal type name for storage and comparison
+///
+/// Lowercases, trims and collapses inner whitespace, so "Second  Breakfast"
+/// and "second breakfast" are the same meal type.
+pub fn normalize_meal_type(meal_type: &str) -> String {
+    meal_type
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Validate and normalize a user's meal type list
+///
+/// Duplicates after normalization are dropped, keeping the first occurrence.
+pub fn validate_meal_types(meal_types: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(meal_types.len());
+    for meal_type in meal_types {
+        let meal_type = normalize_meal_type(meal_type);
+        if meal_type.is_empty() {
+            return Err("Meal type names cannot be empty".to_string());
+        }
+        if meal_type.chars().count() > MAX_MEAL_TYPE_LENGTH {
+            return Err(format!(
+                "Meal type names must be at most {} characters",
+                MAX_MEAL_TYPE_LENGTH
+            ));
+        }
+        if !normalized.contains(&meal_type) {
+            normalized.push(meal_type);
+        }
+    }
+
+    if normalized.is_empty() {
+        return Err("At least one meal type is required".to_string());
+    }
+    if normalized.len() > MAX_MEAL_TYPES {
+        return Err(format!("At most {} meal types are allowed", MAX_MEAL_TYPES));
+    }
+
+    Ok(normalized)
+}
+
+/// Source recorded when a log entry does not name one
+pub const DEFAULT_SOURCE: &str = "manual";
+
+/// Prefix that lets clients record a source outside the known set
+pub const CUSTOM_SOURCE_PREFIX: &str = "custom:";
+
+/// Maximum length of a source (matches the log tables' source column)
+pub const MAX_SOURCE_LENGTH: usize = 50;
+
+/// Canonical names of the sources a log entry may come from
+pub const VALID_SOURCES: &[&str] = &[
+    "manual",
+    "template",
+    "import",
+    "apple_watch",
+    "apple_health",
+    "google_fit",
+    "fitbit",
+    "garmin",
+    "polar",
+    "oura",
+    "whoop",
+    "withings",
+    "strava",
+    "chest_strap",
+];
+
+/// Alternate spellings of known sources, keyed by their compacted form
+const SOURCE_ALIASES: &[(&str, &str)] = &[
+    ("healthkit", "apple_health"),
+    ("applehealthkit", "apple_health"),
+    ("iwatch", "apple_watch"),
+    ("googlehealthconnect", "google_fit"),
+    ("garminconnect", "garmin"),
+    ("ouraring", "oura"),
+];
+
+/// Lowercase alphanumerics only, so "Apple Watch" and "apple-watch" compare equal
+fn compact_source(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Normalize a log source for storage and comparison
+///
+/// Known device and app names are mapped to their canonical form regardless of
+/// case, spacing or separators, so "Apple Watch", "apple_watch" and
+/// "applewatch" all become "apple_watch". Custom sources keep their label with
+/// a lowercase prefix; anything else is lowercased with separators turned into
+/// underscores.
+pub fn normalize_source(raw: &str) -> String {
+    let trimmed = raw.trim();
+
+    let prefix_len = CUSTOM_SOURCE_PREFIX.len();
+    if trimmed.len() >= prefix_len
+        && trimmed.is_char_boundary(prefix_len)
+        && trimmed[..prefix_len].eq_ignore_ascii_case(CUSTOM_SOURCE_PREFIX)
+    {
+        return format!("{}{}", CUSTOM_SOURCE_PREFIX, trimmed[prefix_len..].trim());
+    }
+
+    let compact = compact_source(trimmed);
+    if let Some(canonical) = VALID_SOURCES
+        .iter()
+        .find(|source| compact_source(source) == compact)
+    {
+        return canonical.to_string();
+    }
+    if let Some((_, canonical)) = SOURCE_ALIASES.iter().find(|(alias, _)| *alias == compact) {
+        return canonical.to_string();
+    }
+
+    trimmed
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase()
+}
+
+/// Validate and normalize a log source
+///
+/// Unknown sources are rejected unless they carry the "custom:" prefix.
+pub fn validate_source(raw: &str) -> Result<String, String> {
+    let source = normalize_source(raw);
+
+    if let Some(label) = source.strip_prefix(CUSTOM_SOURCE_PREFIX) {
+        if label.is_empty() {
+            return Err("Custom sources need a name after \"custom:\"".to_string());
+        }
+    } else if !VALID_SOURCES.contains(&source.as_str()) {
+        return Err(format!(
+            "Unknown source. Must be one of: {}, or start with \"{}\"",
+            VALID_SOURCES.join(", "),
+            CUSTOM_SOURCE_PREFIX
+        ));
+    }
+
+    if source.chars().count() > MAX_SOURCE_LENGTH {
+        return Err(format!(
+            "Source must be at most {} characters",
+            MAX_SOURCE_LENGTH
+        ));
+    }
+
+    Ok(source)
+}
+
+/// Resolve the source of a new log entry, defaulting to "manual"
+pub fn resolve_source(source: Option<&str>) -> Result<String, ValidationError> {
+    match source {
+        Some(raw) => validate_source(raw).map_err(|msg| ValidationError::new("source", &msg)),
+        None => Ok(DEFAULT_SOURCE.to_string()),
+    }
+}
+
 // ============================================================================
 // User-Friendly Field Labels
 // ============================================================================
@@ -197,9 +423,22 @@ pub fn get_field_display_label(field_name: &str) -> &str {
         "energy_unit" => "Energy Unit",
         "temperature_unit" => "Temperature Unit",
         "timezone" => "Timezone",
+        "week_start" => "Week Start",
+        "meal_types" => "Meal Types",
+        "max_hr_formula" => "Max HR Formula",
         "daily_calorie_goal" => "Daily Calorie Goal",
         "daily_water_goal_ml" => "Daily Water Goal",
         "daily_step_goal" => "Daily Step Goal",
+        "unit_preferences" => "Unit Preferences",
+        "goals" => "Goals",
+        "weight_kg" => "Weight (kg)",
+        "body_fat_percent" => "Body Fat (%)",
+        "bpm" => "Heart Rate (BPM)",
+        "rmssd" => "RMSSD (ms)",
+        "sdnn" => "SDNN (ms)",
+        "amount_ml" => "Amount (ml)",
+        "daily_goal_ml" => "Daily Goal (ml)",
+        "source" => "Source",
         _ => field_name,
     }
 }
@@ -235,6 +474,97 @@ impl ValidationError {
     }
 }
 
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.display_label, self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Field errors collected across a whole input
+///
+/// Serializes as `{ "field": ["message", ...] }` so clients can show every
+/// problem at once instead of one per submission.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct ValidationErrors {
+    fields: BTreeMap<String, Vec<String>>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a failure for a field
+    pub fn add(&mut self, field: &str, message: &str) {
+        self.fields
+            .entry(field.to_string())
+            .or_default()
+            .push(message.to_string());
+    }
+
+    /// Record the failure from a single-field check, if any
+    pub fn check(&mut self, result: Result<(), ValidationError>) {
+        if let Err(err) = result {
+            self.add(&err.field, &err.message);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Messages recorded for a field
+    pub fn get(&self, field: &str) -> Option<&[String]> {
+        self.fields.get(field).map(Vec::as_slice)
+    }
+
+    /// `Ok` when nothing failed, otherwise every collected error
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self
+            .fields
+            .iter()
+            .flat_map(|(field, messages)| {
+                messages
+                    .iter()
+                    .map(move |msg| format!("{}: {}", get_field_display_label(field), msg))
+            })
+            .collect();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Validate that `value` lies within `min..=max` (inclusive)
+///
+/// NaN never lies within a range, so non-finite floats are rejected too.
+pub fn validate_range<T>(value: T, min: T, max: T, field: &str) -> Result<(), ValidationError>
+where
+    T: PartialOrd + Copy + fmt::Display,
+{
+    if (min..=max).contains(&value) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(
+            field,
+            &format!("must be between {} and {}", min, max),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -373,6 +703,98 @@ mod tests {
         assert!(validate_biological_sex("").is_err());
     }
 
+    #[test]
+    fn test_parse_week_start() {
+        assert_eq!(parse_week_start("monday"), Ok(Weekday::Mon));
+        assert_eq!(parse_week_start("Sunday"), Ok(Weekday::Sun));
+        assert_eq!(parse_week_start("sun"), Ok(Weekday::Sun));
+        assert!(parse_week_start("someday").is_err());
+        assert!(parse_week_start("").is_err());
+
+        // Stored names parse back to the same day
+        for day in [Weekday::Mon, Weekday::Wed, Weekday::Sun] {
+            assert_eq!(parse_week_start(week_start_name(day)), Ok(day));
+        }
+    }
+
+    #[test]
+    fn test_validate_meal_types() {
+        let input = vec![
+            "Breakfast".to_string(),
+            " Second   Breakfast ".to_string(),
+            "pre-workout".to_string(),
+            "breakfast".to_string(),
+        ];
+        assert_eq!(
+            validate_meal_types(&input),
+            Ok(vec![
+                "breakfast".to_string(),
+                "second breakfast".to_string(),
+                "pre-workout".to_string(),
+            ])
+        );
+
+        assert!(validate_meal_types(&[]).is_err());
+        assert!(validate_meal_types(&["  ".to_string()]).is_err());
+        assert!(validate_meal_types(&["a".repeat(MAX_MEAL_TYPE_LENGTH + 1)]).is_err());
+        let too_many: Vec<String> = (0..=MAX_MEAL_TYPES).map(|i| format!("meal {}", i)).collect();
+        assert!(validate_meal_types(&too_many).is_err());
+    }
+
+    #[test]
+    fn test_reconcile_duration_derives_from_timestamps() {
+        let start = DateTime::parse_from_rfc3339("2024-01-01T07:00:00Z").unwrap().with_timezone(&Utc);
+        let end = start + chrono::Duration::minutes(45);
+        assert_eq!(reconcile_duration_minutes(start, end, None), Ok(45));
+    }
+
+    #[test]
+    fn test_reconcile_duration_accepts_agreeing_pair() {
+        let start = DateTime::parse_from_rfc3339("2024-01-01T07:00:00Z").unwrap().with_timezone(&Utc);
+        let end = start + chrono::Duration::seconds(45 * 60 + 30);
+        assert_eq!(reconcile_duration_minutes(start, end, Some(45)), Ok(45));
+        assert_eq!(reconcile_duration_minutes(start, end, Some(46)), Ok(46));
+        assert_eq!(reconcile_duration_minutes(start, end, Some(44)), Ok(44));
+    }
+
+    #[test]
+    fn test_reconcile_duration_rejects_conflicting_pair() {
+        let start = DateTime::parse_from_rfc3339("2024-01-01T07:00:00Z").unwrap().with_timezone(&Utc);
+        let end = start + chrono::Duration::minutes(45);
+        assert!(reconcile_duration_minutes(start, end, Some(60)).is_err());
+        assert!(reconcile_duration_minutes(end, start, None).is_err());
+        assert!(reconcile_duration_minutes(start, start, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_normalize_source_variants() {
+        for raw in ["Apple Watch", "apple_watch", "applewatch", "APPLE-WATCH", " iWatch "] {
+            assert_eq!(normalize_source(raw), "apple_watch", "{raw}");
+        }
+        for raw in ["Google Fit", "googlefit", "google_fit"] {
+            assert_eq!(normalize_source(raw), "google_fit", "{raw}");
+        }
+        assert_eq!(normalize_source("HealthKit"), "apple_health");
+        assert_eq!(normalize_source("Manual"), "manual");
+        assert_eq!(normalize_source("Custom: My Scale"), "custom:My Scale");
+    }
+
+    #[test]
+    fn test_validate_source() {
+        assert_eq!(validate_source("Garmin Connect"), Ok("garmin".to_string()));
+        assert_eq!(
+            validate_source("custom:kitchen scale"),
+            Ok("custom:kitchen scale".to_string())
+        );
+        assert!(validate_source("my scale").is_err());
+        assert!(validate_source("custom:").is_err());
+        assert!(validate_source(&format!("custom:{}", "a".repeat(MAX_SOURCE_LENGTH))).is_err());
+
+        assert_eq!(resolve_source(None).unwrap(), DEFAULT_SOURCE);
+        let err = resolve_source(Some("smart fridge")).unwrap_err();
+        assert_eq!(err.field, "source");
+    }
+
     #[test]
     fn test_field_display_labels() {
         assert_eq!(get_field_display_label("date_of_birth"), "Date of Birth");
@@ -390,6 +812,60 @@ mod tests {
         assert_eq!(err.user_message(), "Height: must be at least 50 cm");
     }
 
+    #[test]
+    fn test_validate_range() {
+        assert!(validate_range(20.0, 20.0, 500.0, "weight_kg").is_ok());
+        assert!(validate_range(500.0, 20.0, 500.0, "weight_kg").is_ok());
+        assert!(validate_range(f64::NAN, 20.0, 500.0, "weight_kg").is_err());
+
+        let err = validate_range(19.9, 20.0, 500.0, "weight_kg").unwrap_err();
+        assert_eq!(err.field, "weight_kg");
+        assert_eq!(err.to_string(), "Weight (kg): must be between 20 and 500");
+    }
+
+    #[test]
+    fn test_validate_range_messages_are_uniform() {
+        let cases = [
+            (validate_range(0, 1, 299, "bpm"), "Heart Rate (BPM): must be between 1 and 299"),
+            (
+                validate_range(10001, 1, 10000, "amount_ml"),
+                "Amount (ml): must be between 1 and 10000",
+            ),
+            (
+                validate_range(120.5, 0.0, 100.0, "body_fat_percent"),
+                "Body Fat (%): must be between 0 and 100",
+            ),
+        ];
+
+        for (result, expected) in cases {
+            assert_eq!(result.unwrap_err().user_message(), expected);
+        }
+    }
+
+    #[test]
+    fn test_validation_errors_accumulate_per_field() {
+        let mut errors = ValidationErrors::new();
+        errors.add("name", "cannot be empty");
+        errors.check(validate_range(-5, 0, 50000, "calories"));
+        errors.check(validate_range(10, 0, 50000, "calories"));
+        errors.add("name", "is too short");
+
+        assert_eq!(
+            errors.get("name").unwrap(),
+            ["cannot be empty".to_string(), "is too short".to_string()]
+        );
+        assert_eq!(errors.get("calories").unwrap().len(), 1);
+        assert_eq!(
+            serde_json::to_value(&errors).unwrap(),
+            serde_json::json!({
+                "calories": ["must be between 0 and 50000"],
+                "name": ["cannot be empty", "is too short"],
+            })
+        );
+        assert!(errors.into_result().is_err());
+        assert!(ValidationErrors::new().into_result().is_ok());
+    }
+
     // Property-based tests
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(100))]